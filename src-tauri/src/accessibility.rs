@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+
+// OS accessibility settings and screen-reader announcements. The webview's
+// media queries don't follow every platform setting (WebView2 ignores the
+// Windows animation switch), so the backend reads them itself and emits
+// `accessibility-changed` when they change. Announcements go to UI Automation
+// on Windows; elsewhere they're handed to the frontend's live region via
+// `accessibility-announcement`, which VoiceOver and Orca read from the webview.
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+const MAX_ANNOUNCEMENT_CHARS: usize = 500;
+
+/// Fields are `None` where the platform setting couldn't be read.
+#[derive(Clone, Serialize, Debug, PartialEq, Default)]
+pub struct AccessibilityPrefs {
+    pub reduced_motion: Option<bool>,
+    pub high_contrast: Option<bool>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Read once the screen reader is idle.
+    #[default]
+    Polite,
+    /// Interrupts whatever is being read.
+    Assertive,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct Announcement {
+    pub message: String,
+    pub priority: Priority,
+}
+
+fn last_prefs() -> &'static Mutex<Option<AccessibilityPrefs>> {
+    static PREFS: OnceLock<Mutex<Option<AccessibilityPrefs>>> = OnceLock::new();
+    PREFS.get_or_init(|| Mutex::new(None))
+}
+
+// ── Platform settings ────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn read_prefs() -> AccessibilityPrefs {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    struct HighContrastW {
+        cb_size: u32,
+        flags: u32,
+        default_scheme: *mut u16,
+    }
+
+    extern "system" {
+        fn SystemParametersInfoW(action: u32, param: u32, value: *mut c_void, win_ini: u32) -> i32;
+    }
+    const SPI_GETHIGHCONTRAST: u32 = 0x0042;
+    const SPI_GETCLIENTAREAANIMATION: u32 = 0x1042;
+    const HCF_HIGHCONTRASTON: u32 = 0x1;
+
+    // "Show animations in Windows" in Settings > Accessibility > Visual effects
+    let mut animations: i32 = 1;
+    let animations_ptr = &mut animations as *mut i32 as *mut c_void;
+    let reduced_motion = unsafe { SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, animations_ptr, 0) != 0 }
+        .then_some(animations == 0);
+
+    let mut contrast = HighContrastW {
+        cb_size: std::mem::size_of::<HighContrastW>() as u32,
+        flags: 0,
+        default_scheme: std::ptr::null_mut(),
+    };
+    let contrast_ptr = &mut contrast as *mut HighContrastW as *mut c_void;
+    let size = contrast.cb_size;
+    let high_contrast = unsafe { SystemParametersInfoW(SPI_GETHIGHCONTRAST, size, contrast_ptr, 0) != 0 }
+        .then_some(contrast.flags & HCF_HIGHCONTRASTON != 0);
+
+    AccessibilityPrefs { reduced_motion, high_contrast }
+}
+
+/// Trimmed stdout of a settings query, None if the tool or the key is missing.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn query(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn read_prefs() -> AccessibilityPrefs {
+    // Unset keys (never toggled) read as an error, which means off
+    let flag = |key| {
+        let value = query("defaults", &["read", "com.apple.universalaccess", key]);
+        Some(value.is_some_and(|v| v == "1"))
+    };
+    AccessibilityPrefs { reduced_motion: flag("reduceMotion"), high_contrast: flag("increaseContrast") }
+}
+
+#[cfg(target_os = "linux")]
+fn read_prefs() -> AccessibilityPrefs {
+    let gsettings = |schema, key| query("gsettings", &["get", schema, key]);
+    let reduced_motion = gsettings("org.gnome.desktop.interface", "enable-animations").map(|v| v == "false");
+    // GNOME 42+ has a switch; before that, high contrast was a theme
+    let high_contrast = gsettings("org.gnome.desktop.a11y.interface", "high-contrast")
+        .map(|v| v == "true")
+        .or_else(|| gsettings("org.gnome.desktop.interface", "gtk-theme").map(|v| v.contains("HighContrast")));
+    AccessibilityPrefs { reduced_motion, high_contrast }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn read_prefs() -> AccessibilityPrefs {
+    AccessibilityPrefs::default()
+}
+
+/// Poll the settings and emit `accessibility-changed` whenever they change.
+pub fn start_watcher(app: tauri::AppHandle) {
+    if cfg!(target_os = "android") {
+        return;
+    }
+    std::thread::spawn(move || loop {
+        let prefs = read_prefs();
+        let changed = {
+            let mut last = last_prefs().lock().unwrap();
+            let changed = last.as_ref().is_some_and(|l| *l != prefs);
+            *last = Some(prefs.clone());
+            changed
+        };
+        if changed {
+            eprintln!("[accessibility] Settings changed: {prefs:?}");
+            let _ = app.emit("accessibility-changed", &prefs);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    });
+}
+
+// ── Announcements ────────────────────────────────────────────────────
+
+/// Raise a UI Automation notification on the main window, which Narrator,
+/// NVDA and JAWS speak without moving focus.
+#[cfg(target_os = "windows")]
+fn announce_platform(app: &tauri::AppHandle, announcement: &Announcement) -> bool {
+    use std::ffi::c_void;
+    use tauri::Manager;
+
+    #[link(name = "uiautomationcore")]
+    extern "system" {
+        fn UiaHostProviderFromHwnd(hwnd: isize, provider: *mut *mut c_void) -> i32;
+        fn UiaRaiseNotificationEvent(
+            provider: *mut c_void, kind: i32, processing: i32, display: *mut u16, activity: *mut u16,
+        ) -> i32;
+    }
+    #[link(name = "oleaut32")]
+    extern "system" {
+        fn SysAllocString(text: *const u16) -> *mut u16;
+        fn SysFreeString(text: *mut u16);
+    }
+    const NOTIFICATION_KIND_OTHER: i32 = 4;
+    const PROCESSING_IMPORTANT_MOST_RECENT: i32 = 1;
+    const PROCESSING_MOST_RECENT: i32 = 3;
+
+    let Some(hwnd) = app.get_webview_window("main").and_then(|w| w.hwnd().ok()) else {
+        return false;
+    };
+    let processing = match announcement.priority {
+        Priority::Polite => PROCESSING_MOST_RECENT,
+        Priority::Assertive => PROCESSING_IMPORTANT_MOST_RECENT,
+    };
+    let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+    let (message, activity) = (wide(&announcement.message), wide("SuperFlux"));
+    unsafe {
+        let mut provider: *mut c_void = std::ptr::null_mut();
+        if UiaHostProviderFromHwnd(hwnd.0 as isize, &mut provider) < 0 || provider.is_null() {
+            return false;
+        }
+        let display = SysAllocString(message.as_ptr());
+        let activity_id = SysAllocString(activity.as_ptr());
+        let hr = UiaRaiseNotificationEvent(provider, NOTIFICATION_KIND_OTHER, processing, display, activity_id);
+        SysFreeString(display);
+        SysFreeString(activity_id);
+        // IUnknown::Release, the third slot of the vtable
+        let vtable = *(provider as *const *const unsafe extern "system" fn(*mut c_void) -> u32);
+        (*vtable.add(2))(provider);
+        hr >= 0
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn announce_platform(_app: &tauri::AppHandle, _announcement: &Announcement) -> bool {
+    false
+}
+
+/// Have screen readers speak `message`, e.g. "Refresh complete, 14 new items".
+/// Returns whether the platform took it; if not, the frontend's live region gets it.
+pub fn announce_message(app: &tauri::AppHandle, message: &str, priority: Priority) -> bool {
+    let message: String = message.split_whitespace().collect::<Vec<_>>().join(" ");
+    if message.is_empty() {
+        return false;
+    }
+    let message = message.chars().take(MAX_ANNOUNCEMENT_CHARS).collect();
+    let announcement = Announcement { message, priority };
+    if announce_platform(app, &announcement) {
+        return true;
+    }
+    let _ = app.emit("accessibility-announcement", &announcement);
+    false
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Current settings, for the first render before any change event.
+#[tauri::command]
+pub fn get_accessibility_prefs() -> AccessibilityPrefs {
+    if let Some(prefs) = last_prefs().lock().unwrap().clone() {
+        return prefs;
+    }
+    read_prefs()
+}
+
+/// Returns `platform` when the OS accessibility layer took the message, or
+/// `live_region` when it was emitted for the frontend to announce.
+#[tauri::command]
+pub fn announce(message: String, priority: Option<Priority>, app: tauri::AppHandle) -> Result<String, String> {
+    if message.trim().is_empty() {
+        return Err("Nothing to announce".into());
+    }
+    let delivered = announce_message(&app, &message, priority.unwrap_or_default());
+    Ok(if delivered { "platform" } else { "live_region" }.to_string())
+}
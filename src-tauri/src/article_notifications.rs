@@ -0,0 +1,303 @@
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+use crate::db::{self, Database};
+use crate::notifications;
+
+// Native notifications for the articles a refresh stores, for the feeds that
+// opted in. A few articles are announced one by one; more are batched into a
+// summary ("12 new articles in 3 feeds"). Clicking one brings the main window
+// forward and emits `notification-clicked` with the articles it stood for.
+// Click-through uses WinRT toasts on Windows and D-Bus actions on Linux; on
+// macOS the plugin's notification is shown, which activates the app but
+// carries no article ids.
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SETTINGS_FILE: &str = "article_notifications.json";
+/// Article titles listed in a batched notification before "and N more".
+const MAX_LISTED_TITLES: usize = 4;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct ArticleNotificationSettings {
+    pub enabled: bool,
+    /// Feed ids whose new articles are announced.
+    pub feeds: BTreeSet<String>,
+    /// Up to this many new articles get a notification each; more are batched.
+    pub batch_threshold: usize,
+}
+
+impl Default for ArticleNotificationSettings {
+    fn default() -> Self {
+        ArticleNotificationSettings { enabled: true, feeds: BTreeSet::new(), batch_threshold: 3 }
+    }
+}
+
+/// Payload of `notification-clicked`. `article_id` is set when the
+/// notification was for a single article.
+#[derive(Clone, Serialize, Debug)]
+pub struct NotificationClick {
+    pub article_id: Option<String>,
+    pub article_ids: Vec<String>,
+}
+
+struct NewArticle {
+    id: String,
+    feed_id: String,
+    feed_name: String,
+    title: String,
+}
+
+struct Notice {
+    title: String,
+    body: String,
+    article_ids: Vec<String>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct ArticleNotificationStore {
+    settings: Mutex<ArticleNotificationSettings>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl ArticleNotificationStore {
+    pub fn new() -> Self {
+        ArticleNotificationStore {
+            settings: Mutex::new(ArticleNotificationSettings::default()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SETTINGS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        let Some(path) = self.file_path() else { return };
+        if !path.exists() {
+            return;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(json) => match serde_json::from_str::<ArticleNotificationSettings>(&json) {
+                Ok(settings) => *self.settings.lock().unwrap() = settings,
+                Err(e) => eprintln!("[article_notifications] Failed to parse settings: {e}"),
+            },
+            Err(e) => eprintln!("[article_notifications] Failed to read settings: {e}"),
+        }
+    }
+
+    fn save_to_disk(&self, settings: &ArticleNotificationSettings) -> Result<(), String> {
+        let Some(path) = self.file_path() else { return Ok(()) };
+        let json = serde_json::to_string_pretty(settings)
+            .map_err(|e| format!("Failed to serialize notification settings: {e}"))?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to save notification settings: {e}"))
+    }
+
+    pub fn settings(&self) -> ArticleNotificationSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    pub fn set_settings(&self, settings: ArticleNotificationSettings) -> Result<(), String> {
+        let mut current = self.settings.lock().unwrap();
+        self.save_to_disk(&settings)?;
+        *current = settings;
+        Ok(())
+    }
+
+    pub fn set_feed(&self, feed_id: &str, enabled: bool) -> Result<(), String> {
+        let mut settings = self.settings.lock().unwrap();
+        let mut updated = settings.clone();
+        if enabled {
+            updated.feeds.insert(feed_id.to_string());
+        } else {
+            updated.feeds.remove(feed_id);
+        }
+        self.save_to_disk(&updated)?;
+        *settings = updated;
+        Ok(())
+    }
+}
+
+// ── Batching ─────────────────────────────────────────────────────────
+
+fn plural(n: usize, one: &str, many: &str) -> String {
+    format!("{n} {}", if n == 1 { one } else { many })
+}
+
+/// One notice per article up to the threshold, else a single summary.
+fn notices(articles: Vec<NewArticle>, batch_threshold: usize) -> Vec<Notice> {
+    if articles.len() <= batch_threshold {
+        return articles
+            .into_iter()
+            .map(|a| Notice { title: a.feed_name, body: a.title, article_ids: vec![a.id] })
+            .collect();
+    }
+
+    let feeds: BTreeSet<&str> = articles.iter().map(|a| a.feed_id.as_str()).collect();
+    let title = if feeds.len() == 1 {
+        format!("{} in {}", plural(articles.len(), "new article", "new articles"), articles[0].feed_name)
+    } else {
+        format!(
+            "{} in {}",
+            plural(articles.len(), "new article", "new articles"),
+            plural(feeds.len(), "feed", "feeds")
+        )
+    };
+    let mut lines: Vec<String> = articles.iter().take(MAX_LISTED_TITLES).map(|a| a.title.clone()).collect();
+    if articles.len() > MAX_LISTED_TITLES {
+        lines.push(format!("and {} more", articles.len() - MAX_LISTED_TITLES));
+    }
+    vec![Notice { title, body: lines.join("\n"), article_ids: articles.into_iter().map(|a| a.id).collect() }]
+}
+
+// ── Delivery ─────────────────────────────────────────────────────────
+
+/// Bring the main window forward and tell the frontend what was clicked.
+fn open_from_notification(app: &tauri::AppHandle, article_ids: Vec<String>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let click = NotificationClick {
+        article_id: if article_ids.len() == 1 { article_ids.first().cloned() } else { None },
+        article_ids,
+    };
+    let _ = app.emit("notification-clicked", click);
+}
+
+#[cfg(windows)]
+fn show_native(app: &tauri::AppHandle, notice: Notice) -> Result<(), String> {
+    use tauri_winrt_notification::Toast;
+
+    // Toasts need the installed app's AppUserModelID; dev builds borrow PowerShell's
+    let app_id =
+        if tauri::is_dev() { Toast::POWERSHELL_APP_ID.to_string() } else { app.config().identifier.clone() };
+    let handle = app.clone();
+    Toast::new(&app_id)
+        .title(&notice.title)
+        .text1(&notice.body)
+        .on_activated(move |_| {
+            open_from_notification(&handle, notice.article_ids.clone());
+            Ok(())
+        })
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+fn show_native(app: &tauri::AppHandle, notice: Notice) -> Result<(), String> {
+    let handle = notify_rust::Notification::new()
+        .appname(app.package_info().name.as_str())
+        .summary(&notice.title)
+        .body(&notice.body)
+        .auto_icon()
+        .action("default", "Open")
+        .show()
+        .map_err(|e| e.to_string())?;
+    // Blocks until the notification is clicked or closed
+    let app = app.clone();
+    std::thread::spawn(move || {
+        handle.wait_for_action(|action| {
+            if action == "default" {
+                open_from_notification(&app, notice.article_ids);
+            }
+        })
+    });
+    Ok(())
+}
+
+#[cfg(not(any(windows, all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))))]
+fn show_native(app: &tauri::AppHandle, notice: Notice) -> Result<(), String> {
+    notifications::notify_desktop(app, &notice.title, &notice.body);
+    Ok(())
+}
+
+fn show(app: &tauri::AppHandle, notice: Notice) {
+    let (title, body) = (notice.title.clone(), notice.body.clone());
+    if let Err(e) = show_native(app, notice) {
+        eprintln!("[article_notifications] Native notification failed, using the plugin: {e}");
+        notifications::notify_desktop(app, &title, &body);
+    }
+}
+
+/// Announce newly stored articles from opted-in feeds. Articles already read
+/// (e.g. by a filter rule) and collapsed duplicates are left out.
+pub async fn notify_new_articles(app: tauri::AppHandle, ids: Vec<String>) {
+    let settings = app.state::<Arc<ArticleNotificationStore>>().settings();
+    if ids.is_empty() || !settings.enabled || settings.feeds.is_empty() || notifications::suppressed(&app) {
+        return;
+    }
+    let db = app.state::<Arc<Database>>().inner().clone();
+    let feeds = settings.feeds.clone();
+    let collapse = crate::dedup::auto_collapse();
+    let articles = db::run(&db, move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT a.id, a.feed_id, COALESCE(NULLIF(f.title, ''), a.feed_id), a.title
+             FROM articles a LEFT JOIN feeds f ON f.id = a.feed_id
+             WHERE a.id IN (SELECT value FROM json_each(?1)) AND a.is_read = 0
+               AND NOT (?2 AND a.duplicate_of IS NOT NULL)
+             ORDER BY COALESCE(a.published_at, a.fetched_at) DESC",
+        )?;
+        let ids_json = serde_json::to_string(&ids).unwrap_or_default();
+        let rows = stmt.query_map(params![ids_json, collapse], |r| {
+            Ok(NewArticle { id: r.get(0)?, feed_id: r.get(1)?, feed_name: r.get(2)?, title: r.get(3)? })
+        })?;
+        let mut articles = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+        articles.retain(|a| feeds.contains(&a.feed_id));
+        Ok(articles)
+    })
+    .await;
+    let articles = match articles {
+        Ok(articles) if !articles.is_empty() => articles,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("[article_notifications] Failed to load new articles: {e}");
+            return;
+        }
+    };
+    for notice in notices(articles, settings.batch_threshold) {
+        show(&app, notice);
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_article_notification_settings(
+    store: tauri::State<'_, Arc<ArticleNotificationStore>>,
+) -> ArticleNotificationSettings {
+    store.settings()
+}
+
+#[tauri::command]
+pub fn set_article_notification_settings(
+    settings: ArticleNotificationSettings,
+    store: tauri::State<'_, Arc<ArticleNotificationStore>>,
+) -> Result<(), String> {
+    if settings.batch_threshold == 0 {
+        return Err("Batch threshold must be at least 1".into());
+    }
+    store.set_settings(settings)
+}
+
+/// Turn new-article notifications on or off for one feed.
+#[tauri::command]
+pub fn set_feed_notifications(
+    feed_id: String,
+    enabled: bool,
+    store: tauri::State<'_, Arc<ArticleNotificationStore>>,
+) -> Result<(), String> {
+    store.set_feed(&feed_id, enabled)
+}
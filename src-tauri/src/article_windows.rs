@@ -0,0 +1,328 @@
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::Manager;
+
+use crate::db::{self, Database};
+use crate::ingest::now_millis;
+
+// Detached article windows. The manager keeps the open ones and their
+// geometry in a session file, so whatever was open when the app quit (the main
+// window going away) reopens on the next launch where it was. Each window
+// loads the app's own page and asks `get_article_window` what to show.
+
+const SESSION_FILE: &str = "article_windows.json";
+/// Labels of article windows start with this; the rest is unique per window.
+pub const LABEL_PREFIX: &str = "article-";
+#[cfg(not(target_os = "android"))]
+const DEFAULT_SIZE: (f64, f64) = (760.0, 900.0);
+#[cfg(not(target_os = "android"))]
+const MIN_SIZE: (f64, f64) = (360.0, 400.0);
+
+/// Outer position and inner size in physical pixels, as the window reports them.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Maximized on top of the size above, which is what un-maximizing returns to.
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ArticleWindow {
+    pub label: String,
+    /// Empty for a window showing `html` instead of a stored article.
+    #[serde(default)]
+    pub article_id: String,
+    /// Content to render when the article isn't in the database, e.g. a preview.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<WindowGeometry>,
+    #[serde(default)]
+    pub opened_at: u64,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct ArticleWindowStore {
+    windows: Mutex<Vec<ArticleWindow>>,
+    data_dir: Mutex<Option<PathBuf>>,
+    /// Set when the main window goes away; windows closed after that stay in the session.
+    quitting: AtomicBool,
+}
+
+impl ArticleWindowStore {
+    pub fn new() -> Self {
+        ArticleWindowStore {
+            windows: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+            quitting: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SESSION_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(windows) = serde_json::from_str::<Vec<ArticleWindow>>(&json) {
+                            eprintln!("[article_windows] {} windows in the last session", windows.len());
+                            *self.windows.lock().unwrap() = windows;
+                        }
+                    }
+                    Err(e) => eprintln!("[article_windows] Failed to read session: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let windows = self.windows.lock().unwrap();
+            match serde_json::to_string_pretty(&*windows) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[article_windows] Failed to write session: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[article_windows] Failed to serialize session: {e}"),
+            }
+        }
+    }
+
+    pub fn list(&self) -> Vec<ArticleWindow> {
+        self.windows.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, label: &str) -> Option<ArticleWindow> {
+        self.windows.lock().unwrap().iter().find(|w| w.label == label).cloned()
+    }
+
+    fn add(&self, window: ArticleWindow) {
+        self.windows.lock().unwrap().push(window);
+        self.save_to_disk();
+    }
+
+    /// Forget a window the user closed. Ignored while quitting.
+    fn remove(&self, label: &str) {
+        if self.quitting.load(Ordering::SeqCst) {
+            return;
+        }
+        let removed = {
+            let mut windows = self.windows.lock().unwrap();
+            let before = windows.len();
+            windows.retain(|w| w.label != label);
+            windows.len() != before
+        };
+        if removed {
+            self.save_to_disk();
+        }
+    }
+
+    /// Kept in memory while windows move; written when the session changes or ends.
+    fn set_geometry(&self, label: &str, geometry: WindowGeometry) {
+        if let Some(window) = self.windows.lock().unwrap().iter_mut().find(|w| w.label == label) {
+            window.geometry = Some(geometry);
+        }
+    }
+}
+
+// ── Window management ────────────────────────────────────────────────
+
+/// The window's geometry; while maximized only the flag changes, so the size
+/// to restore to is kept.
+fn geometry_of(window: &tauri::WebviewWindow, previous: Option<WindowGeometry>) -> Option<WindowGeometry> {
+    if window.is_maximized().unwrap_or(false) {
+        return previous.map(|g| WindowGeometry { maximized: true, ..g });
+    }
+    let pos = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    Some(WindowGeometry { x: pos.x, y: pos.y, width: size.width, height: size.height, maximized: false })
+}
+
+/// Follow an article window's moves and its closing.
+pub fn watch(app: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+    let store = app.state::<Arc<ArticleWindowStore>>().inner().clone();
+    let win = window.clone();
+    window.on_window_event(move |event| match event {
+        tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+            let previous = store.get(win.label()).and_then(|w| w.geometry);
+            if let Some(geometry) = geometry_of(&win, previous) {
+                store.set_geometry(win.label(), geometry);
+            }
+        }
+        tauri::WindowEvent::Destroyed => store.remove(win.label()),
+        _ => {}
+    });
+}
+
+/// Whether a window at `geometry` would show on one of the current monitors.
+#[cfg(not(target_os = "android"))]
+fn on_screen(app: &tauri::AppHandle, geometry: &WindowGeometry) -> bool {
+    // The title bar's left end must be reachable to drag the window back
+    let (x, y) = (geometry.x + 40, geometry.y + 10);
+    app.available_monitors().unwrap_or_default().iter().any(|m| {
+        let (pos, size) = (m.position(), m.size());
+        x >= pos.x && y >= pos.y && x < pos.x + size.width as i32 && y < pos.y + size.height as i32
+    })
+}
+
+/// Create the window for `entry` at its saved geometry, centered when that
+/// is missing or off-screen.
+#[cfg(not(target_os = "android"))]
+pub fn build(app: &tauri::AppHandle, entry: &ArticleWindow) -> Result<tauri::WebviewWindow, String> {
+    use tauri::{PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder};
+
+    let title = if entry.title.is_empty() { "SuperFlux" } else { &entry.title };
+    let window = WebviewWindowBuilder::new(app, &entry.label, WebviewUrl::default())
+        .title(title)
+        .inner_size(DEFAULT_SIZE.0, DEFAULT_SIZE.1)
+        .min_inner_size(MIN_SIZE.0, MIN_SIZE.1)
+        .center()
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Failed to create article window: {e}"))?;
+    if let Some(geometry) = entry.geometry.filter(|g| on_screen(app, g)) {
+        let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+        let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+        if geometry.maximized {
+            let _ = window.maximize();
+        }
+    }
+    window.show().map_err(|e| format!("Failed to show article window: {e}"))?;
+    watch(app, &window);
+    Ok(window)
+}
+
+/// Reopen the windows of the last session. Ones that fail to open are dropped.
+#[cfg(not(target_os = "android"))]
+pub fn restore_session(app: &tauri::AppHandle) {
+    let store = app.state::<Arc<ArticleWindowStore>>().inner().clone();
+    for entry in store.list() {
+        if let Err(e) = build(app, &entry) {
+            eprintln!("[article_windows] Could not restore '{}': {e}", entry.label);
+            store.remove(&entry.label);
+        }
+    }
+}
+
+/// The main window is gone and the app is quitting: save the session as it
+/// stands, then close the article windows so they don't keep the app running.
+pub fn end_session(app: &tauri::AppHandle) {
+    let store = app.state::<Arc<ArticleWindowStore>>().inner().clone();
+    store.quitting.store(true, Ordering::SeqCst);
+    store.save_to_disk();
+    for (label, window) in app.webview_windows() {
+        if label.starts_with(LABEL_PREFIX) {
+            let _ = window.close();
+        }
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_article_windows(store: tauri::State<'_, Arc<ArticleWindowStore>>) -> Vec<ArticleWindow> {
+    store.list()
+}
+
+/// What the calling window shows, or None for a window that isn't an article window.
+#[tauri::command]
+pub fn get_article_window(
+    window: tauri::WebviewWindow,
+    store: tauri::State<'_, Arc<ArticleWindowStore>>,
+) -> Option<ArticleWindow> {
+    store.get(window.label())
+}
+
+/// Open an article in a window of its own, or bring forward the one already
+/// showing it. Pass `article_id` for a stored article, or `html` (and a
+/// `title`) for content that isn't stored. Returns the window's label.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub async fn open_article_window(
+    article_id: Option<String>,
+    html: Option<String>,
+    title: Option<String>,
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<ArticleWindowStore>>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<String, String> {
+    let article_id = article_id.filter(|id| !id.is_empty());
+    if article_id.is_none() && html.is_none() {
+        return Err("An article id or HTML is required".into());
+    }
+    if let Some(id) = &article_id {
+        let open = store.list().into_iter().find(|w| &w.article_id == id);
+        if let Some(window) = open.and_then(|w| app.get_webview_window(&w.label)) {
+            let _ = window.unminimize();
+            let _ = window.set_focus();
+            return Ok(window.label().to_string());
+        }
+    }
+
+    let title = match (&article_id, title) {
+        (_, Some(title)) => title,
+        (Some(id), None) => {
+            let id = id.clone();
+            db::run(&db, move |conn| {
+                conn.query_row("SELECT title FROM articles WHERE id = ?1", [&id], |r| r.get::<_, String>(0))
+                    .optional()
+            })
+            .await?
+            .ok_or_else(|| "Article not found".to_string())?
+        }
+        (None, None) => String::new(),
+    };
+    let entry = ArticleWindow {
+        label: format!("{LABEL_PREFIX}{}", uuid::Uuid::new_v4().simple()),
+        article_id: article_id.unwrap_or_default(),
+        html,
+        title,
+        geometry: None,
+        opened_at: now_millis(),
+    };
+    // In the session first, so the window finds its entry as soon as it loads
+    store.add(entry.clone());
+    match build(&app, &entry) {
+        Ok(_) => Ok(entry.label),
+        Err(e) => {
+            store.remove(&entry.label);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub fn open_article_window() -> Result<String, String> {
+    Err("Article windows are not available on this platform".into())
+}
+
+#[tauri::command]
+pub fn close_article_window(label: String, app: tauri::AppHandle) -> Result<bool, String> {
+    if !label.starts_with(LABEL_PREFIX) {
+        return Err("Not an article window".into());
+    }
+    match app.get_webview_window(&label) {
+        Some(window) => window.close().map(|_| true).map_err(|e| format!("Failed to close window: {e}")),
+        None => Ok(false),
+    }
+}
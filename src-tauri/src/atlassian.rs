@@ -0,0 +1,310 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::ingest::IngestItem;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SOURCES_FILE: &str = "atlassian_sources.json";
+const MAX_RESULTS: u32 = 50;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AtlassianKind {
+    Confluence,
+    Jira,
+}
+
+/// A Confluence space or Jira filter polled through the REST API, since the
+/// built-in RSS endpoints are deprecated or reject token auth.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AtlassianSource {
+    pub id: String,
+    pub kind: AtlassianKind,
+    pub name: String,
+    /// Site root, e.g. `https://acme.atlassian.net/wiki` or `https://jira.acme.corp`.
+    pub base_url: String,
+    /// Account email for Atlassian Cloud (basic auth with an API token).
+    /// Leave empty for Server/Data Center personal access tokens (bearer auth).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    pub token: String,
+    /// Confluence space key, or a Jira JQL query.
+    pub query: String,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct AtlassianStore {
+    sources: Mutex<Vec<AtlassianSource>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl AtlassianStore {
+    pub fn new() -> Self {
+        AtlassianStore {
+            sources: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SOURCES_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(sources) = serde_json::from_str::<Vec<AtlassianSource>>(&json) {
+                            eprintln!("[atlassian] Loaded {} sources", sources.len());
+                            *self.sources.lock().unwrap() = sources;
+                        }
+                    }
+                    Err(e) => eprintln!("[atlassian] Failed to read sources: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let sources = self.sources.lock().unwrap();
+            match serde_json::to_string_pretty(&*sources) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[atlassian] Failed to write sources: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[atlassian] Failed to serialize sources: {e}"),
+            }
+        }
+    }
+
+    pub fn get_sources(&self) -> Vec<AtlassianSource> {
+        self.sources.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<AtlassianSource> {
+        self.sources.lock().unwrap().iter().find(|s| s.id == id).cloned()
+    }
+
+    /// Insert or replace a source. An empty id gets a fresh UUID.
+    pub fn upsert(&self, mut source: AtlassianSource) -> AtlassianSource {
+        if source.id.is_empty() {
+            source.id = uuid::Uuid::new_v4().to_string();
+        }
+        source.base_url = source.base_url.trim().trim_end_matches('/').to_string();
+        source.email = source.email.filter(|e| !e.trim().is_empty());
+        {
+            let mut sources = self.sources.lock().unwrap();
+            match sources.iter_mut().find(|s| s.id == source.id) {
+                Some(existing) => *existing = source.clone(),
+                None => sources.push(source.clone()),
+            }
+        }
+        self.save_to_disk();
+        source
+    }
+
+    pub fn delete(&self, id: &str) -> bool {
+        let mut sources = self.sources.lock().unwrap();
+        let before = sources.len();
+        sources.retain(|s| s.id != id);
+        let removed = sources.len() < before;
+        drop(sources);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+}
+
+// ── REST polling ─────────────────────────────────────────────────────
+
+fn auth_header(source: &AtlassianSource) -> String {
+    match source.email {
+        Some(ref email) => format!("Basic {}", STANDARD.encode(format!("{email}:{}", source.token))),
+        None => format!("Bearer {}", source.token),
+    }
+}
+
+async fn get_json(source: &AtlassianSource, url: &str, query: &[(&str, String)]) -> Result<Value, String> {
+    let client = crate::get_or_init_client()?;
+    let response = client
+        .get(url)
+        .query(query)
+        .header("Authorization", auth_header(source))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("{} request failed: {e}", source.name))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("HTTP {status}: {}", body.chars().take(300).collect::<String>()));
+    }
+    response.json().await.map_err(|e| format!("Invalid JSON from {}: {e}", source.name))
+}
+
+/// Jira uses `+0000` offsets, Confluence RFC 3339; both map to ms since epoch.
+fn parse_timestamp(value: &Value) -> Option<u64> {
+    let s = value.as_str()?;
+    chrono::DateTime::parse_from_rfc3339(s)
+        .or_else(|_| chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|d| d.timestamp_millis().max(0) as u64)
+}
+
+fn str_at<'a>(value: &'a Value, pointer: &str) -> &'a str {
+    value.pointer(pointer).and_then(Value::as_str).unwrap_or("")
+}
+
+async fn poll_confluence(source: &AtlassianSource) -> Result<Vec<IngestItem>, String> {
+    let cql = format!(
+        "space = \"{}\" order by lastmodified desc",
+        source.query.trim().replace('"', "")
+    );
+    let url = format!("{}/rest/api/content/search", source.base_url);
+    let json = get_json(
+        source,
+        &url,
+        &[
+            ("cql", cql),
+            ("limit", MAX_RESULTS.to_string()),
+            ("expand", "version,space".into()),
+        ],
+    )
+    .await?;
+
+    // Links are relative to `_links.base` (includes the /wiki context path on Cloud)
+    let link_base = json
+        .pointer("/_links/base")
+        .and_then(Value::as_str)
+        .unwrap_or(&source.base_url)
+        .to_string();
+    let results = json.get("results").and_then(Value::as_array).cloned().unwrap_or_default();
+
+    Ok(results
+        .iter()
+        .map(|page| {
+            let version = page.pointer("/version/number").and_then(Value::as_u64).unwrap_or(1);
+            let editor = str_at(page, "/version/by/displayName");
+            let action = if version > 1 { "Updated" } else { "Created" };
+            IngestItem {
+                // Each edit is a new feed item
+                id: format!("confluence:{}:{}:v{version}", source.id, str_at(page, "/id")),
+                title: str_at(page, "/title").to_string(),
+                url: format!("{link_base}{}", str_at(page, "/_links/webui")),
+                author: editor.to_string(),
+                summary: format!("{action} in {} by {editor} (v{version})", str_at(page, "/space/name")),
+                published_at: page.pointer("/version/when").and_then(parse_timestamp),
+                thumbnail: None,
+                ..Default::default()
+            }
+        })
+        .collect())
+}
+
+async fn poll_jira(source: &AtlassianSource) -> Result<Vec<IngestItem>, String> {
+    let url = format!("{}/rest/api/2/search", source.base_url);
+    let json = get_json(
+        source,
+        &url,
+        &[
+            ("jql", source.query.trim().to_string()),
+            ("maxResults", MAX_RESULTS.to_string()),
+            ("fields", "summary,updated,status,assignee,reporter,issuetype".into()),
+        ],
+    )
+    .await?;
+    let issues = json.get("issues").and_then(Value::as_array).cloned().unwrap_or_default();
+
+    Ok(issues
+        .iter()
+        .map(|issue| {
+            let key = str_at(issue, "/key");
+            let updated = issue.pointer("/fields/updated").and_then(parse_timestamp);
+            let assignee = match str_at(issue, "/fields/assignee/displayName") {
+                "" => "Unassigned",
+                name => name,
+            };
+            IngestItem {
+                // Keyed on the update time so each change surfaces again
+                id: format!("jira:{}:{key}:{}", source.id, updated.unwrap_or(0)),
+                title: format!("[{key}] {}", str_at(issue, "/fields/summary")),
+                url: format!("{}/browse/{key}", source.base_url),
+                author: str_at(issue, "/fields/reporter/displayName").to_string(),
+                summary: format!(
+                    "{} · {} · {assignee}",
+                    str_at(issue, "/fields/issuetype/name"),
+                    str_at(issue, "/fields/status/name"),
+                ),
+                published_at: updated,
+                thumbnail: None,
+                ..Default::default()
+            }
+        })
+        .collect())
+}
+
+pub async fn poll_source(source: &AtlassianSource) -> Result<Vec<IngestItem>, String> {
+    let items = match source.kind {
+        AtlassianKind::Confluence => poll_confluence(source).await,
+        AtlassianKind::Jira => poll_jira(source).await,
+    };
+    match items {
+        Ok(ref list) => eprintln!("[atlassian] '{}' returned {} items", source.name, list.len()),
+        Err(ref e) => eprintln!("[atlassian] '{}' failed: {e}", source.name),
+    }
+    items
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_atlassian_sources(store: tauri::State<'_, Arc<AtlassianStore>>) -> Vec<AtlassianSource> {
+    store.get_sources()
+}
+
+#[tauri::command]
+pub fn save_atlassian_source(
+    source: AtlassianSource,
+    store: tauri::State<'_, Arc<AtlassianStore>>,
+) -> Result<AtlassianSource, String> {
+    url::Url::parse(source.base_url.trim()).map_err(|e| format!("Invalid base URL: {e}"))?;
+    if source.token.trim().is_empty() {
+        return Err("API token is required".into());
+    }
+    if source.query.trim().is_empty() {
+        return Err(match source.kind {
+            AtlassianKind::Confluence => "Space key is required".into(),
+            AtlassianKind::Jira => "JQL query is required".into(),
+        });
+    }
+    Ok(store.upsert(source))
+}
+
+#[tauri::command]
+pub fn delete_atlassian_source(id: String, store: tauri::State<'_, Arc<AtlassianStore>>) -> bool {
+    store.delete(&id)
+}
+
+/// Poll a source and return its activity normalized into feed items.
+#[tauri::command]
+pub async fn fetch_atlassian_source(
+    id: String,
+    store: tauri::State<'_, Arc<AtlassianStore>>,
+) -> Result<Vec<IngestItem>, String> {
+    let source = store.get(&id).ok_or("Source not found")?;
+    poll_source(&source).await
+}
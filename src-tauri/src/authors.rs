@@ -0,0 +1,291 @@
+use rusqlite::{params, Connection, Row};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{Emitter, Manager};
+
+use crate::db::{self, Database};
+use crate::notifications::{self, PushMessage};
+use crate::search::fold;
+
+// Authors as entities. Bylines are split into names at ingest and each name is
+// keyed by its folded form, so "José Díaz" in one feed and "JOSE DIAZ" in
+// another are one author with one page. Followed authors' articles are flagged
+// in list rows and, if asked, announced as they arrive.
+
+const MAX_NAME_CHARS: usize = 80;
+/// Article titles listed in one alert; the rest are only counted.
+const MAX_ALERT_TITLES: usize = 3;
+/// What joins several names in a byline. Matched case-insensitively.
+const SEPARATORS: &[&str] = &[" and ", " & ", " und ", " et ", " y ", ";", "|", " / "];
+
+#[derive(Clone, Serialize, Debug)]
+pub struct Author {
+    /// Folded name; stable across feeds and spellings that only differ in case or accents.
+    pub id: String,
+    /// The name as first seen.
+    pub name: String,
+    pub article_count: u32,
+    pub feed_count: u32,
+    pub followed: bool,
+    /// Alert when a new article by this author arrives.
+    pub notify: bool,
+    /// Newest article (ms since epoch).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_at: Option<i64>,
+}
+
+/// Payload of `followed-author-articles`.
+#[derive(Clone, Serialize, Debug)]
+struct FollowedAuthorArticles {
+    author_id: String,
+    name: String,
+    article_ids: Vec<String>,
+}
+
+// ── Byline parsing ───────────────────────────────────────────────────
+
+/// Author key: folded, with whitespace collapsed.
+pub fn author_key(name: &str) -> String {
+    fold(name, false).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn clean_name(name: &str) -> Option<String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name = name.trim_matches(|c: char| !c.is_alphanumeric() && c != '.' && c != ')');
+    let usable = name.chars().any(char::is_alphabetic) && name.chars().count() <= MAX_NAME_CHARS;
+    usable.then(|| name.to_string())
+}
+
+/// Names in a byline such as "By Jane Doe and John Roe", "Doe, Jane" or the
+/// RSS form "jane@example.com (Jane Doe)".
+pub fn parse_byline(byline: &str) -> Vec<String> {
+    let mut byline = byline.trim();
+    // RSS puts the address first and the name in parentheses
+    if let (Some(open), true) = (byline.find('('), byline.ends_with(')')) {
+        if byline[..open].contains('@') {
+            byline = &byline[open + 1..byline.len() - 1];
+        }
+    }
+    if byline.get(..3).is_some_and(|p| p.eq_ignore_ascii_case("by ")) {
+        byline = &byline[3..];
+    }
+
+    // ASCII lowercasing keeps byte offsets, so positions carry over to the original
+    let lower = byline.to_ascii_lowercase();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while let Some((at, sep)) = SEPARATORS
+        .iter()
+        .filter_map(|sep| lower[start..].find(sep).map(|i| (start + i, sep.len())))
+        .min()
+    {
+        parts.push(&byline[start..at]);
+        start = at + sep;
+    }
+    parts.push(&byline[start..]);
+
+    let mut names = Vec::new();
+    let mut seen = HashSet::new();
+    for part in parts {
+        // "Jane Doe, John Roe" lists names; "Doe, Jane" is one name, last name first
+        let pieces: Vec<&str> = part.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+        let listed = pieces.len() > 1 && pieces.iter().all(|p| p.contains(' '));
+        let pieces = if listed { pieces } else { vec![part] };
+        for piece in pieces {
+            if piece.contains('@') && !piece.contains(' ') {
+                continue;
+            }
+            if let Some(name) = clean_name(piece) {
+                if seen.insert(author_key(&name)) {
+                    names.push(name);
+                }
+            }
+        }
+    }
+    names
+}
+
+// ── Index maintenance ────────────────────────────────────────────────
+
+/// Link the articles with these ids to the authors in their stored bylines.
+pub fn index_stored(conn: &Connection, ids: &[&str]) -> rusqlite::Result<()> {
+    let mut select = conn.prepare_cached("SELECT author FROM articles WHERE id = ?1")?;
+    let mut clear = conn.prepare_cached("DELETE FROM article_authors WHERE article_id = ?1")?;
+    let mut add_author =
+        conn.prepare_cached("INSERT INTO authors (id, name) VALUES (?1, ?2) ON CONFLICT(id) DO NOTHING")?;
+    let mut link =
+        conn.prepare_cached("INSERT OR IGNORE INTO article_authors (article_id, author_id) VALUES (?1, ?2)")?;
+    for id in ids {
+        let byline: String = select.query_row([id], |r| r.get(0))?;
+        clear.execute([id])?;
+        for name in parse_byline(&byline) {
+            let key = author_key(&name);
+            add_author.execute(params![key, name])?;
+            link.execute(params![id, key])?;
+        }
+    }
+    Ok(())
+}
+
+/// Link up to `limit` of the articles queued in `authors_backfill`, those
+/// stored before authors were tracked. Returns how many were linked.
+pub fn backfill_batch(conn: &mut Connection, limit: usize) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let ids: Vec<String> = tx
+        .prepare("SELECT article_id FROM authors_backfill LIMIT ?1")?
+        .query_map([limit as i64], |r| r.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    index_stored(&tx, &ids)?;
+    {
+        let mut done = tx.prepare_cached("DELETE FROM authors_backfill WHERE article_id = ?1")?;
+        for id in &ids {
+            done.execute([id])?;
+        }
+    }
+    tx.commit()?;
+    Ok(ids.len())
+}
+
+// ── Queries ──────────────────────────────────────────────────────────
+
+/// Authors with their counts; `{where}` filters on `au` (authors).
+const AUTHOR_SELECT: &str = "SELECT au.id, au.name, au.followed, au.notify,
+        COUNT(a.id), COUNT(DISTINCT a.feed_id), MAX(COALESCE(a.published_at, a.fetched_at))
+    FROM authors au
+    LEFT JOIN article_authors aa ON aa.author_id = au.id
+    LEFT JOIN articles a ON a.id = aa.article_id
+    WHERE {where}
+    GROUP BY au.id";
+
+fn author_from_sql(row: &Row) -> rusqlite::Result<Author> {
+    Ok(Author {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        followed: row.get(2)?,
+        notify: row.get(3)?,
+        article_count: row.get(4)?,
+        feed_count: row.get(5)?,
+        latest_at: row.get(6)?,
+    })
+}
+
+/// Followed authors first, then by article count. `search` matches anywhere
+/// in the folded name. Authors left without articles are only kept if followed.
+pub fn list_authors(
+    conn: &Connection,
+    search: Option<&str>,
+    followed_only: bool,
+    limit: u32,
+) -> rusqlite::Result<Vec<Author>> {
+    let sql = format!(
+        "{} HAVING COUNT(a.id) > 0 OR au.followed = 1
+         ORDER BY au.followed DESC, COUNT(a.id) DESC, au.name
+         LIMIT ?3",
+        AUTHOR_SELECT.replace("{where}", "(?1 IS NULL OR instr(au.id, ?1) > 0) AND (?2 = 0 OR au.followed = 1)")
+    );
+    let search = search.map(author_key).filter(|s| !s.is_empty());
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let rows = stmt.query_map(params![search, followed_only, limit], author_from_sql)?;
+    rows.collect()
+}
+
+pub fn authors_of(conn: &Connection, article_id: &str) -> rusqlite::Result<Vec<Author>> {
+    let sql = AUTHOR_SELECT
+        .replace("{where}", "au.id IN (SELECT author_id FROM article_authors WHERE article_id = ?1)");
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let rows = stmt.query_map([article_id], author_from_sql)?;
+    rows.collect()
+}
+
+/// Announce new articles by authors followed with alerts on: one
+/// `followed-author-articles` event and alert per author.
+pub async fn check_new_articles(app: tauri::AppHandle, ids: Vec<String>) {
+    if ids.is_empty() {
+        return;
+    }
+    let db = app.state::<Arc<Database>>().inner().clone();
+    let found = db::run(&db, move |conn| {
+        let mut stmt = conn.prepare_cached(
+            "SELECT au.id, au.name, a.id, a.title, a.url FROM article_authors aa
+             JOIN authors au ON au.id = aa.author_id
+             JOIN articles a ON a.id = aa.article_id
+             WHERE au.notify = 1 AND aa.article_id IN (SELECT value FROM json_each(?1))
+             ORDER BY au.id",
+        )?;
+        let ids = serde_json::to_string(&ids).unwrap_or_default();
+        let rows = stmt.query_map([ids], |r| {
+            Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?, r.get(2)?, r.get(3)?, r.get(4)?))
+        })?;
+        rows.collect::<rusqlite::Result<Vec<(String, String, String, String, String)>>>()
+    })
+    .await;
+    let rows = match found {
+        Ok(rows) => rows,
+        Err(e) => {
+            eprintln!("[authors] Checking new articles failed: {e}");
+            return;
+        }
+    };
+    for group in rows.chunk_by(|a, b| a.0 == b.0) {
+        let (author_id, name) = (group[0].0.clone(), group[0].1.clone());
+        let article_ids: Vec<String> = group.iter().map(|r| r.2.clone()).collect();
+        let mut lines: Vec<String> = group.iter().take(MAX_ALERT_TITLES).map(|r| r.3.clone()).collect();
+        if group.len() > MAX_ALERT_TITLES {
+            lines.push(format!("and {} more", group.len() - MAX_ALERT_TITLES));
+        }
+        let msg = PushMessage {
+            title: format!("New from {name}"),
+            message: lines.join("\n"),
+            click_url: (group.len() == 1).then(|| group[0].4.clone()).filter(|u| !u.is_empty()),
+        };
+        let _ = app.emit("followed-author-articles", FollowedAuthorArticles { author_id, name, article_ids });
+        notifications::raise_alert(&app, &msg).await;
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Known authors across all feeds. An author's page is `db_query_articles`
+/// with its `author_id`.
+#[tauri::command]
+pub async fn get_authors(
+    search: Option<String>,
+    followed_only: Option<bool>,
+    limit: Option<u32>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<Author>, String> {
+    let limit = limit.unwrap_or(200).min(5000);
+    db::run(&db, move |conn| list_authors(conn, search.as_deref(), followed_only.unwrap_or(false), limit)).await
+}
+
+#[tauri::command]
+pub async fn get_article_authors(
+    article_id: String,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<Author>, String> {
+    db::run(&db, move |conn| authors_of(conn, &article_id)).await
+}
+
+/// Follow or unfollow an author; `notify` (only kept while followed) alerts on new articles.
+#[tauri::command]
+pub async fn follow_author(
+    author_id: String,
+    followed: bool,
+    notify: Option<bool>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<(), String> {
+    let notify = followed && notify.unwrap_or(false);
+    let updated = db::run(&db, move |conn| {
+        conn.execute(
+            "UPDATE authors SET followed = ?2, notify = ?3 WHERE id = ?1",
+            params![author_id, followed, notify],
+        )
+    })
+    .await?;
+    match updated {
+        0 => Err("Author not found".into()),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,813 @@
+use hmac::{Hmac, Mac};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+use crate::db::Database;
+use crate::ingest::now_millis;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const CONFIG_FILE: &str = "backup_config.json";
+const LOCAL_DIR: &str = "backups";
+const RESTORE_DIR: &str = "restore_pending";
+const ARCHIVE_PREFIX: &str = "superflux-backup-";
+const DB_ENTRY: &str = "superflux.db";
+const MANIFEST_ENTRY: &str = "manifest.json";
+const CHECK_INTERVAL_SECS: u64 = 10 * 60;
+const MAX_ARCHIVE_BYTES: usize = 512 * 1024 * 1024;
+
+/// State files that go into an archive and come back on restore. Anything
+/// holding credentials (Matrix, Atlassian, push and share targets, API keys,
+/// proxy), private data (clipboard history) or security settings (grants,
+/// trusted certificates, feed hook commands, auth and header rules, the
+/// sanitizer policy) stays out, so a backup leaks no secrets and a tampered
+/// archive can't widen what the app is allowed to do.
+const DATA_FILES: &[&str] = &[
+    "article_notifications.json",
+    "clipboard_settings.json",
+    "comic_feeds.json",
+    "dedup_settings.json",
+    "feed_stats.json",
+    "feed_timezones.json",
+    "filter_rules.json",
+    "focus_sessions.json",
+    "global_shortcuts.json",
+    "keyword_counts.json",
+    "keyword_watches.json",
+    "kiosk_settings.json",
+    "media_gallery.json",
+    "naming_templates.json",
+    "rate_limits.json",
+    "response_limits.json",
+    "retention.json",
+    "retry_policy.json",
+    "rss_bridge.json",
+    "saved_searches.json",
+    "search_settings.json",
+    "sound_settings.json",
+    "speedtest.json",
+    "startup_options.json",
+    "timeouts.json",
+    "topic_mutes.json",
+    "voice_settings.json",
+];
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum BackupTarget {
+    /// A WebDAV collection (Nextcloud, ownCloud, a NAS, …).
+    Webdav {
+        url: String,
+        #[serde(default)]
+        username: String,
+        #[serde(default)]
+        password: String,
+    },
+    /// Any S3-compatible bucket (AWS, R2, B2, MinIO). Path-style addressing.
+    S3 {
+        endpoint: String,
+        region: String,
+        bucket: String,
+        #[serde(default)]
+        prefix: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BackupConfig {
+    /// Run on a schedule. Manual backups work regardless.
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u32,
+    /// Archives kept per location; older ones are deleted after each backup.
+    #[serde(default = "default_keep_last")]
+    pub keep_last: u32,
+    /// Remote target. Without one, backups are local only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<BackupTarget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_backup_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+fn default_interval_hours() -> u32 {
+    24
+}
+
+fn default_keep_last() -> u32 {
+    7
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        BackupConfig {
+            enabled: false,
+            interval_hours: default_interval_hours(),
+            keep_last: default_keep_last(),
+            target: None,
+            last_backup_at: None,
+            last_error: None,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct BackupResult {
+    pub name: String,
+    pub size: u64,
+    pub local_path: String,
+    pub uploaded: bool,
+    /// Old archives removed by retention (local and remote).
+    pub pruned: usize,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct RemoteBackup {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct RestoreResult {
+    pub name: String,
+    pub files: Vec<String>,
+    /// Restored files are applied on the next launch, before any store loads.
+    pub restart_required: bool,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct BackupStore {
+    config: Mutex<BackupConfig>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl BackupStore {
+    pub fn new() -> Self {
+        BackupStore {
+            config: Mutex::new(BackupConfig::default()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn data_dir(&self) -> Result<PathBuf, String> {
+        self.data_dir.lock().unwrap().clone().ok_or_else(|| "App data directory unavailable".to_string())
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(CONFIG_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(config) = serde_json::from_str::<BackupConfig>(&json) {
+                            *self.config.lock().unwrap() = config;
+                        }
+                    }
+                    Err(e) => eprintln!("[backup] Failed to read config: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let config = self.config.lock().unwrap();
+            match serde_json::to_string_pretty(&*config) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[backup] Failed to write config: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[backup] Failed to serialize config: {e}"),
+            }
+        }
+    }
+
+    pub fn get_config(&self) -> BackupConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Replace the user-editable settings, keeping the run history.
+    pub fn set_config(&self, config: BackupConfig) {
+        {
+            let mut current = self.config.lock().unwrap();
+            *current = BackupConfig {
+                last_backup_at: current.last_backup_at,
+                last_error: current.last_error.clone(),
+                ..config
+            };
+        }
+        self.save_to_disk();
+    }
+
+    fn record_run(&self, error: Option<String>) {
+        {
+            let mut config = self.config.lock().unwrap();
+            if error.is_none() {
+                config.last_backup_at = Some(now_millis());
+            }
+            config.last_error = error;
+        }
+        self.save_to_disk();
+    }
+
+    fn is_due(&self) -> bool {
+        let config = self.config.lock().unwrap();
+        let interval_ms = config.interval_hours.max(1) as u64 * 3_600_000;
+        config.enabled && config.last_backup_at.is_none_or(|t| now_millis().saturating_sub(t) >= interval_ms)
+    }
+}
+
+// ── Archive ──────────────────────────────────────────────────────────
+
+fn archive_name() -> String {
+    format!("{ARCHIVE_PREFIX}{}.zip", chrono::Local::now().format("%Y%m%d-%H%M%S"))
+}
+
+fn is_archive_name(name: &str) -> bool {
+    name.starts_with(ARCHIVE_PREFIX) && name.ends_with(".zip") && !name.contains(['/', '\\'])
+}
+
+/// Zip the `DATA_FILES` that exist, a consistent copy of the database and an
+/// OPML export of the subscriptions.
+fn build_archive(data_dir: &Path, db: &Database) -> Result<Vec<u8>, String> {
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let zip_err = |e: zip::result::ZipError| format!("Failed to write archive: {e}");
+    let io_err = |e: std::io::Error| format!("Failed to write archive: {e}");
+
+    let mut files: Vec<String> = Vec::new();
+    for name in DATA_FILES {
+        let path = data_dir.join(name);
+        if !path.is_file() {
+            continue;
+        }
+        let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {name}: {e}"))?;
+        zip.start_file(*name, options).map_err(zip_err)?;
+        zip.write_all(&bytes).map_err(io_err)?;
+        files.push(name.to_string());
+    }
+
+    // VACUUM INTO gives a consistent snapshot even while the app keeps writing
+    let snapshot = std::env::temp_dir().join(format!("superflux-db-{}.db", uuid::Uuid::new_v4()));
+    let snapshot_str = snapshot.to_string_lossy().to_string();
+    if db.with(|conn| conn.execute("VACUUM INTO ?1", [&snapshot_str])).is_ok() {
+        let bytes = std::fs::read(&snapshot).map_err(|e| format!("Failed to read database snapshot: {e}"));
+        let _ = std::fs::remove_file(&snapshot);
+        zip.start_file(DB_ENTRY, options).map_err(zip_err)?;
+        zip.write_all(&bytes?).map_err(io_err)?;
+        files.push(DB_ENTRY.into());
+
+        if let Ok(feeds) = db.with(|conn| crate::db::list_feeds(conn)) {
+            if !feeds.is_empty() {
+                let opml = crate::opml::write_opml("SuperFlux subscriptions", &crate::opml::tree_from_feeds(feeds))?;
+                zip.start_file("subscriptions.opml", options).map_err(zip_err)?;
+                zip.write_all(opml.as_bytes()).map_err(io_err)?;
+                files.push("subscriptions.opml".into());
+            }
+        }
+    }
+
+    let manifest = serde_json::json!({
+        "app": "SuperFlux",
+        "version": env!("CARGO_PKG_VERSION"),
+        "created_at": now_millis(),
+        "files": files,
+    });
+    zip.start_file(MANIFEST_ENTRY, options).map_err(zip_err)?;
+    zip.write_all(manifest.to_string().as_bytes()).map_err(io_err)?;
+    let cursor = zip.finish().map_err(zip_err)?;
+    Ok(cursor.into_inner())
+}
+
+/// Unpack a downloaded archive into the staging directory applied at startup.
+fn stage_restore(data_dir: &Path, bytes: &[u8]) -> Result<Vec<String>, String> {
+    let mut archive =
+        zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(|e| format!("Invalid backup archive: {e}"))?;
+    if archive.by_name(MANIFEST_ENTRY).is_err() {
+        return Err("Not a SuperFlux backup (no manifest)".into());
+    }
+    let staging = data_dir.join(RESTORE_DIR);
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging).map_err(|e| format!("Failed to create restore dir: {e}"))?;
+
+    let mut restored = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Invalid backup archive: {e}"))?;
+        // Flat files only: anything with a path component is ignored
+        let name = entry.name().to_string();
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            continue;
+        }
+        let restorable = DATA_FILES.contains(&name.as_str()) || name == DB_ENTRY;
+        if !restorable {
+            continue;
+        }
+        let mut buf = Vec::new();
+        entry.read_to_end(&mut buf).map_err(|e| format!("Failed to extract {name}: {e}"))?;
+        std::fs::write(staging.join(&name), buf).map_err(|e| format!("Failed to stage {name}: {e}"))?;
+        restored.push(name);
+    }
+    if restored.is_empty() {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err("Backup archive contains no restorable files".into());
+    }
+    Ok(restored)
+}
+
+/// Move a staged restore into place. Must run before any store reads its file.
+pub fn apply_pending_restore(data_dir: &Path) {
+    let staging = data_dir.join(RESTORE_DIR);
+    let Ok(entries) = std::fs::read_dir(&staging) else { return };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if name.to_string_lossy() == DB_ENTRY {
+            // Stale WAL pages would be replayed over the restored database
+            for suffix in ["-wal", "-shm"] {
+                let _ = std::fs::remove_file(data_dir.join(format!("{DB_ENTRY}{suffix}")));
+            }
+        }
+        if let Err(e) = std::fs::rename(entry.path(), data_dir.join(&name)) {
+            eprintln!("[backup] Failed to restore {}: {e}", name.to_string_lossy());
+        }
+    }
+    let _ = std::fs::remove_dir_all(&staging);
+    eprintln!("[backup] Applied restored backup");
+}
+
+fn prune_local(dir: &Path, keep: usize) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else { return 0 };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|n| is_archive_name(n))
+        .collect();
+    // Names embed the timestamp, so lexical order is chronological
+    names.sort();
+    let excess = names.len().saturating_sub(keep);
+    names.iter().take(excess).filter(|n| std::fs::remove_file(dir.join(n)).is_ok()).count()
+}
+
+// ── Remote targets ───────────────────────────────────────────────────
+
+fn client() -> Result<reqwest::Client, String> {
+    // Archives can be large; the shared client's 30s timeout is too short
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(600))
+        .connect_timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))
+}
+
+fn check(response: reqwest::Response, what: &str) -> Result<reqwest::Response, String> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        Err(format!("{what} failed: HTTP {}", status.as_u16()))
+    }
+}
+
+fn webdav_url(base: &str, name: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), name)
+}
+
+/// Text of every element with one of `names` (matched on the local name, since
+/// WebDAV servers and S3 clones disagree on namespace prefixes), grouped per `item`.
+fn xml_records(xml: &str, item: &[u8], names: &[&[u8]]) -> Vec<Vec<(String, String)>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut records = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+    let mut field: Option<String> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let local = e.local_name();
+                if local.as_ref() == item {
+                    current = Some(Vec::new());
+                } else if names.contains(&local.as_ref()) {
+                    field = Some(String::from_utf8_lossy(local.as_ref()).to_string());
+                }
+            }
+            Ok(Event::Text(t)) => {
+                if let (Some(rec), Some(f)) = (current.as_mut(), field.as_ref()) {
+                    if let Ok(text) = t.unescape() {
+                        rec.push((f.clone(), text.to_string()));
+                    }
+                }
+            }
+            Ok(Event::End(e)) => {
+                if e.local_name().as_ref() == item {
+                    records.extend(current.take());
+                }
+                field = None;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    records
+}
+
+fn field<'a>(rec: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    rec.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+}
+
+mod s3 {
+    use super::*;
+
+    type HmacSha256 = Hmac<Sha256>;
+
+    fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(data.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// RFC 3986 encoding as required by SigV4.
+    fn encode(s: &str, keep_slash: bool) -> String {
+        let mut out = String::new();
+        for b in s.bytes() {
+            match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+                b'/' if keep_slash => out.push('/'),
+                _ => out.push_str(&format!("%{b:02X}")),
+            }
+        }
+        out
+    }
+
+    pub struct S3<'a> {
+        pub endpoint: &'a str,
+        pub region: &'a str,
+        pub bucket: &'a str,
+        pub access_key: &'a str,
+        pub secret_key: &'a str,
+    }
+
+    impl S3<'_> {
+        /// Build a SigV4-signed request for `key` (empty for the bucket itself).
+        pub fn request(
+            &self,
+            client: &reqwest::Client,
+            method: reqwest::Method,
+            key: &str,
+            query: &[(&str, &str)],
+            body: Vec<u8>,
+        ) -> Result<reqwest::RequestBuilder, String> {
+            let endpoint = url::Url::parse(self.endpoint).map_err(|e| format!("Invalid S3 endpoint: {e}"))?;
+            let host = match endpoint.port() {
+                Some(port) => format!("{}:{port}", endpoint.host_str().unwrap_or("")),
+                None => endpoint.host_str().unwrap_or("").to_string(),
+            };
+            let path = format!("/{}/{}", self.bucket, key);
+            let canonical_uri = encode(&path, true);
+            let mut sorted: Vec<(String, String)> =
+                query.iter().map(|(k, v)| (encode(k, false), encode(v, false))).collect();
+            sorted.sort();
+            let canonical_query = sorted.iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>().join("&");
+
+            let now = chrono::Utc::now();
+            let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+            let date = now.format("%Y%m%d").to_string();
+            let payload_hash = hex(&Sha256::digest(&body));
+
+            let canonical_request = format!(
+                "{method}\n{canonical_uri}\n{canonical_query}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\nhost;x-amz-content-sha256;x-amz-date\n{payload_hash}"
+            );
+            let scope = format!("{date}/{}/s3/aws4_request", self.region);
+            let string_to_sign = format!(
+                "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+                hex(&Sha256::digest(canonical_request.as_bytes()))
+            );
+            let k_date = hmac(format!("AWS4{}", self.secret_key).as_bytes(), &date);
+            let k_region = hmac(&k_date, self.region);
+            let k_service = hmac(&k_region, "s3");
+            let k_signing = hmac(&k_service, "aws4_request");
+            let signature = hex(&hmac(&k_signing, &string_to_sign));
+            let authorization = format!(
+                "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={signature}",
+                self.access_key
+            );
+
+            let mut url = format!("{}://{host}{canonical_uri}", endpoint.scheme());
+            if !canonical_query.is_empty() {
+                url = format!("{url}?{canonical_query}");
+            }
+            Ok(client
+                .request(method, url)
+                .header("x-amz-date", amz_date)
+                .header("x-amz-content-sha256", payload_hash)
+                .header(reqwest::header::AUTHORIZATION, authorization)
+                .body(body))
+        }
+    }
+}
+
+fn s3_key(prefix: &str, name: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}/{name}")
+    }
+}
+
+async fn remote_upload(target: &BackupTarget, name: &str, bytes: Vec<u8>) -> Result<(), String> {
+    let client = client()?;
+    match target {
+        BackupTarget::Webdav { url, username, password } => {
+            // Create the collection if needed; 405 means it already exists
+            let _ = client
+                .request(reqwest::Method::from_bytes(b"MKCOL").unwrap(), url.as_str())
+                .basic_auth(username, Some(password))
+                .send()
+                .await;
+            let response = client
+                .put(webdav_url(url, name))
+                .basic_auth(username, Some(password))
+                .body(bytes)
+                .send()
+                .await
+                .map_err(|e| format!("Upload failed: {e}"))?;
+            check(response, "Upload").map(|_| ())
+        }
+        BackupTarget::S3 { endpoint, region, bucket, prefix, access_key, secret_key } => {
+            let s3 = s3::S3 { endpoint, region, bucket, access_key, secret_key };
+            let response = s3
+                .request(&client, reqwest::Method::PUT, &s3_key(prefix, name), &[], bytes)?
+                .send()
+                .await
+                .map_err(|e| format!("Upload failed: {e}"))?;
+            check(response, "Upload").map(|_| ())
+        }
+    }
+}
+
+async fn remote_list(target: &BackupTarget) -> Result<Vec<RemoteBackup>, String> {
+    let client = client()?;
+    let mut list: Vec<RemoteBackup> = match target {
+        BackupTarget::Webdav { url, username, password } => {
+            let response = client
+                .request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), url.as_str())
+                .basic_auth(username, Some(password))
+                .header("Depth", "1")
+                .send()
+                .await
+                .map_err(|e| format!("Listing failed: {e}"))?;
+            let xml = check(response, "Listing")?
+                .text()
+                .await
+                .map_err(|e| format!("Listing failed: {e}"))?;
+            xml_records(&xml, b"response", &[b"href", b"getcontentlength", b"getlastmodified"])
+                .into_iter()
+                .filter_map(|rec| {
+                    let href = field(&rec, "href")?.trim_end_matches('/');
+                    let name = href.rsplit('/').next()?.to_string();
+                    Some(RemoteBackup {
+                        name,
+                        size: field(&rec, "getcontentlength").and_then(|s| s.parse().ok()),
+                        modified: field(&rec, "getlastmodified").map(str::to_string),
+                    })
+                })
+                .collect()
+        }
+        BackupTarget::S3 { endpoint, region, bucket, prefix, access_key, secret_key } => {
+            let s3 = s3::S3 { endpoint, region, bucket, access_key, secret_key };
+            let list_prefix = s3_key(prefix, ARCHIVE_PREFIX);
+            let response = s3
+                .request(
+                    &client,
+                    reqwest::Method::GET,
+                    "",
+                    &[("list-type", "2"), ("prefix", &list_prefix)],
+                    Vec::new(),
+                )?
+                .send()
+                .await
+                .map_err(|e| format!("Listing failed: {e}"))?;
+            let xml = check(response, "Listing")?
+                .text()
+                .await
+                .map_err(|e| format!("Listing failed: {e}"))?;
+            xml_records(&xml, b"Contents", &[b"Key", b"Size", b"LastModified"])
+                .into_iter()
+                .filter_map(|rec| {
+                    Some(RemoteBackup {
+                        name: field(&rec, "Key")?.rsplit('/').next()?.to_string(),
+                        size: field(&rec, "Size").and_then(|s| s.parse().ok()),
+                        modified: field(&rec, "LastModified").map(str::to_string),
+                    })
+                })
+                .collect()
+        }
+    };
+    list.retain(|b| is_archive_name(&b.name));
+    list.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(list)
+}
+
+async fn remote_delete(target: &BackupTarget, name: &str) -> Result<(), String> {
+    let client = client()?;
+    let request = match target {
+        BackupTarget::Webdav { url, username, password } => {
+            client.delete(webdav_url(url, name)).basic_auth(username, Some(password))
+        }
+        BackupTarget::S3 { endpoint, region, bucket, prefix, access_key, secret_key } => {
+            let s3 = s3::S3 { endpoint, region, bucket, access_key, secret_key };
+            s3.request(&client, reqwest::Method::DELETE, &s3_key(prefix, name), &[], Vec::new())?
+        }
+    };
+    let response = request.send().await.map_err(|e| format!("Delete failed: {e}"))?;
+    check(response, "Delete").map(|_| ())
+}
+
+async fn remote_download(target: &BackupTarget, name: &str) -> Result<Vec<u8>, String> {
+    let client = client()?;
+    let request = match target {
+        BackupTarget::Webdav { url, username, password } => {
+            client.get(webdav_url(url, name)).basic_auth(username, Some(password))
+        }
+        BackupTarget::S3 { endpoint, region, bucket, prefix, access_key, secret_key } => {
+            let s3 = s3::S3 { endpoint, region, bucket, access_key, secret_key };
+            s3.request(&client, reqwest::Method::GET, &s3_key(prefix, name), &[], Vec::new())?
+        }
+    };
+    let response = request.send().await.map_err(|e| format!("Download failed: {e}"))?;
+    let bytes = check(response, "Download")?
+        .bytes()
+        .await
+        .map_err(|e| format!("Download failed: {e}"))?;
+    if bytes.len() > MAX_ARCHIVE_BYTES {
+        return Err("Backup archive is too large".into());
+    }
+    Ok(bytes.to_vec())
+}
+
+// ── Backup job ───────────────────────────────────────────────────────
+
+pub async fn run_backup(store: &BackupStore, db: &Arc<Database>) -> Result<BackupResult, String> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A backup is already running".into());
+    }
+    let result = run_backup_inner(store, db).await;
+    RUNNING.store(false, Ordering::SeqCst);
+    store.record_run(result.as_ref().err().cloned());
+    result
+}
+
+async fn run_backup_inner(store: &BackupStore, db: &Arc<Database>) -> Result<BackupResult, String> {
+    let data_dir = store.data_dir()?;
+    let config = store.get_config();
+    let keep = config.keep_last.max(1) as usize;
+
+    let archive = {
+        let (data_dir, db) = (data_dir.clone(), db.clone());
+        tauri::async_runtime::spawn_blocking(move || build_archive(&data_dir, &db))
+            .await
+            .map_err(|e| format!("Backup task failed: {e}"))??
+    };
+    let name = archive_name();
+    let local_dir = data_dir.join(LOCAL_DIR);
+    std::fs::create_dir_all(&local_dir).map_err(|e| format!("Failed to create backup dir: {e}"))?;
+    let local_path = local_dir.join(&name);
+    std::fs::write(&local_path, &archive).map_err(|e| format!("Failed to write backup: {e}"))?;
+    let size = archive.len() as u64;
+    let mut pruned = prune_local(&local_dir, keep);
+
+    let uploaded = match &config.target {
+        Some(target) => {
+            remote_upload(target, &name, archive).await?;
+            for old in remote_list(target).await?.iter().skip(keep) {
+                match remote_delete(target, &old.name).await {
+                    Ok(()) => pruned += 1,
+                    Err(e) => eprintln!("[backup] Failed to prune {}: {e}", old.name),
+                }
+            }
+            true
+        }
+        None => false,
+    };
+    eprintln!("[backup] Wrote {name} ({size} bytes, uploaded: {uploaded})");
+    Ok(BackupResult {
+        name,
+        size,
+        local_path: local_path.to_string_lossy().to_string(),
+        uploaded,
+        pruned,
+    })
+}
+
+/// Check periodically whether a scheduled backup is due and run it,
+/// emitting `backup-completed` or `backup-failed`.
+pub fn start_scheduler(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        let store = app.state::<Arc<BackupStore>>().inner().clone();
+        if !store.is_due() {
+            continue;
+        }
+        let db = app.state::<Arc<Database>>().inner().clone();
+        match tauri::async_runtime::block_on(run_backup(&store, &db)) {
+            Ok(result) => {
+                let _ = app.emit("backup-completed", &result);
+            }
+            Err(e) => {
+                eprintln!("[backup] Scheduled backup failed: {e}");
+                let _ = app.emit("backup-failed", &e);
+            }
+        }
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_backup_config(store: tauri::State<'_, Arc<BackupStore>>) -> BackupConfig {
+    store.get_config()
+}
+
+#[tauri::command]
+pub fn set_backup_config(config: BackupConfig, store: tauri::State<'_, Arc<BackupStore>>) -> Result<(), String> {
+    match &config.target {
+        Some(BackupTarget::Webdav { url, .. }) => {
+            url::Url::parse(url).map_err(|e| format!("Invalid WebDAV URL: {e}"))?;
+        }
+        Some(BackupTarget::S3 { endpoint, bucket, .. }) => {
+            url::Url::parse(endpoint).map_err(|e| format!("Invalid S3 endpoint: {e}"))?;
+            if bucket.trim().is_empty() {
+                return Err("Bucket is required".into());
+            }
+        }
+        None => {}
+    }
+    store.set_config(config);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn run_backup_now(
+    store: tauri::State<'_, Arc<BackupStore>>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<BackupResult, String> {
+    run_backup(&store, &db).await
+}
+
+/// Archives on the remote target, newest first.
+#[tauri::command]
+pub async fn list_remote_backups(store: tauri::State<'_, Arc<BackupStore>>) -> Result<Vec<RemoteBackup>, String> {
+    let target = store.get_config().target.ok_or("No remote backup target configured")?;
+    remote_list(&target).await
+}
+
+/// Download a remote archive and stage it; it replaces the current data on next launch.
+#[tauri::command]
+pub async fn restore_from_remote(
+    name: String,
+    store: tauri::State<'_, Arc<BackupStore>>,
+) -> Result<RestoreResult, String> {
+    if !is_archive_name(&name) {
+        return Err("Invalid backup name".into());
+    }
+    let target = store.get_config().target.ok_or("No remote backup target configured")?;
+    let bytes = remote_download(&target, &name).await?;
+    let data_dir = store.data_dir()?;
+    let files = tauri::async_runtime::spawn_blocking(move || stage_restore(&data_dir, &bytes))
+        .await
+        .map_err(|e| format!("Restore task failed: {e}"))??;
+    Ok(RestoreResult {
+        name,
+        files,
+        restart_required: true,
+    })
+}
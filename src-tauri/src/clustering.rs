@@ -0,0 +1,226 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::filters::FilterStore;
+use crate::ingest::{IngestStore, StoredItem};
+use crate::text::tokenize;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+/// Minimum centroid cosine similarity for two clusters to merge.
+const MERGE_THRESHOLD: f32 = 0.35;
+/// Candidate pairs below this are never considered (keeps the pair list sparse).
+const PAIR_THRESHOLD: f32 = 0.25;
+const MIN_CLUSTER_SIZE: usize = 2;
+/// Cap to keep the pairwise pass bounded on very busy days.
+const MAX_ITEMS: usize = 3000;
+
+#[derive(Clone, Serialize, Debug)]
+pub struct StoryCluster {
+    pub id: String,
+    /// Top terms, e.g. "openai · board · altman".
+    pub label: String,
+    pub headline: String,
+    pub headline_item_id: String,
+    pub headline_url: String,
+    pub size: usize,
+    pub feed_count: usize,
+    pub item_ids: Vec<String>,
+}
+
+type Vector = HashMap<u32, f32>;
+
+// ── TF-IDF ───────────────────────────────────────────────────────────
+
+struct Corpus {
+    vocab: Vec<String>,
+    vectors: Vec<Vector>,
+}
+
+fn build_corpus(items: &[StoredItem]) -> Corpus {
+    let mut vocab_index: HashMap<String, u32> = HashMap::new();
+    let mut vocab: Vec<String> = Vec::new();
+    let mut doc_freq: HashMap<u32, u32> = HashMap::new();
+    let mut term_counts: Vec<HashMap<u32, f32>> = Vec::with_capacity(items.len());
+
+    for item in items {
+        // Titles carry most of the topic signal, so they count double
+        let text = format!("{0} {0} {1}", item.item.title, item.item.summary);
+        let mut counts: HashMap<u32, f32> = HashMap::new();
+        for tok in tokenize(&text) {
+            let idx = *vocab_index.entry(tok.clone()).or_insert_with(|| {
+                vocab.push(tok);
+                (vocab.len() - 1) as u32
+            });
+            *counts.entry(idx).or_insert(0.0) += 1.0;
+        }
+        for idx in counts.keys() {
+            *doc_freq.entry(*idx).or_insert(0) += 1;
+        }
+        term_counts.push(counts);
+    }
+
+    let n = items.len() as f32;
+    let vectors = term_counts
+        .into_iter()
+        .map(|counts| {
+            let mut v: Vector = counts
+                .into_iter()
+                .map(|(idx, tf)| {
+                    let df = doc_freq[&idx] as f32;
+                    (idx, (1.0 + tf.ln()) * ((1.0 + n) / (1.0 + df)).ln())
+                })
+                .collect();
+            normalize(&mut v);
+            v
+        })
+        .collect();
+
+    Corpus { vocab, vectors }
+}
+
+fn normalize(v: &mut Vector) {
+    let norm = v.values().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        v.values_mut().for_each(|x| *x /= norm);
+    }
+}
+
+fn cosine(a: &Vector, b: &Vector) -> f32 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small.iter().filter_map(|(k, x)| large.get(k).map(|y| x * y)).sum()
+}
+
+// ── Agglomerative clustering ─────────────────────────────────────────
+
+/// Centroid-linkage agglomerative clustering over a sparse candidate graph:
+/// pairs are visited from most to least similar and two clusters merge only if
+/// their centroids are still close enough, which stops chains from drifting.
+fn cluster(vectors: &[Vector]) -> Vec<Vec<usize>> {
+    // Inverted index so only documents sharing a term are compared
+    let mut postings: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, v) in vectors.iter().enumerate() {
+        for idx in v.keys() {
+            postings.entry(*idx).or_default().push(i);
+        }
+    }
+    let mut pairs: Vec<(f32, usize, usize)> = Vec::new();
+    for (i, v) in vectors.iter().enumerate() {
+        let candidates: HashSet<usize> = v
+            .keys()
+            .flat_map(|idx| postings[idx].iter().copied())
+            .filter(|&j| j > i)
+            .collect();
+        for j in candidates {
+            let sim = cosine(v, &vectors[j]);
+            if sim >= PAIR_THRESHOLD {
+                pairs.push((sim, i, j));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut owner: Vec<usize> = (0..vectors.len()).collect();
+    let mut members: Vec<Vec<usize>> = (0..vectors.len()).map(|i| vec![i]).collect();
+    let mut centroids: Vec<Vector> = vectors.to_vec();
+
+    for (_, i, j) in pairs {
+        let (a, b) = (owner[i], owner[j]);
+        if a == b || cosine(&centroids[a], &centroids[b]) < MERGE_THRESHOLD {
+            continue;
+        }
+        let (keep, gone) = if members[a].len() >= members[b].len() { (a, b) } else { (b, a) };
+        let (wk, wg) = (members[keep].len() as f32, members[gone].len() as f32);
+        let gone_centroid = std::mem::take(&mut centroids[gone]);
+        let mut merged: Vector = centroids[keep].iter().map(|(k, x)| (*k, x * wk)).collect();
+        for (k, x) in gone_centroid {
+            *merged.entry(k).or_insert(0.0) += x * wg;
+        }
+        merged.values_mut().for_each(|x| *x /= wk + wg);
+        centroids[keep] = merged;
+
+        let moved = std::mem::take(&mut members[gone]);
+        for &m in &moved {
+            owner[m] = keep;
+        }
+        members[keep].extend(moved);
+    }
+
+    members.into_iter().filter(|m| m.len() >= MIN_CLUSTER_SIZE).collect()
+}
+
+pub fn story_clusters(mut items: Vec<StoredItem>) -> Vec<StoryCluster> {
+    items.sort_by_key(|i| std::cmp::Reverse(i.timestamp()));
+    items.truncate(MAX_ITEMS);
+    if items.len() < MIN_CLUSTER_SIZE {
+        return Vec::new();
+    }
+
+    let corpus = build_corpus(&items);
+    let mut clusters: Vec<StoryCluster> = cluster(&corpus.vectors)
+        .into_iter()
+        .map(|member_idx| {
+            // Centroid of the members, used for the label and the representative headline
+            let mut centroid: Vector = HashMap::new();
+            for &m in &member_idx {
+                for (k, x) in &corpus.vectors[m] {
+                    *centroid.entry(*k).or_insert(0.0) += x;
+                }
+            }
+            let mut terms: Vec<(&u32, &f32)> = centroid.iter().collect();
+            terms.sort_by(|a, b| b.1.total_cmp(a.1));
+            let label = terms
+                .iter()
+                .take(3)
+                .map(|(k, _)| corpus.vocab[**k as usize].as_str())
+                .collect::<Vec<_>>()
+                .join(" · ");
+
+            let rep = *member_idx
+                .iter()
+                .max_by(|&&a, &&b| {
+                    cosine(&corpus.vectors[a], &centroid).total_cmp(&cosine(&corpus.vectors[b], &centroid))
+                })
+                .unwrap_or(&member_idx[0]);
+            let feeds: HashSet<&str> = member_idx.iter().map(|&m| items[m].feed_url.as_str()).collect();
+            let head = &items[rep];
+
+            StoryCluster {
+                id: head.item.id.clone(),
+                label,
+                headline: head.item.title.clone(),
+                headline_item_id: head.item.id.clone(),
+                headline_url: head.item.url.clone(),
+                size: member_idx.len(),
+                feed_count: feeds.len(),
+                item_ids: member_idx.iter().map(|&m| items[m].item.id.clone()).collect(),
+            }
+        })
+        .collect();
+
+    // Stories covered by many different outlets first
+    clusters.sort_by(|a, b| b.feed_count.cmp(&a.feed_count).then(b.size.cmp(&a.size)));
+    clusters
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Group items in `range` (`today`, `24h`, `7d`, …) into story clusters.
+/// Items hidden by a topic mute are left out.
+#[tauri::command]
+pub async fn get_story_clusters(
+    range: String,
+    store: tauri::State<'_, Arc<IngestStore>>,
+    filters: tauri::State<'_, Arc<FilterStore>>,
+) -> Result<Vec<StoryCluster>, String> {
+    let since = crate::ingest::range_start_millis(&range)?;
+    let items: Vec<StoredItem> = store
+        .items_since(since)
+        .into_iter()
+        .filter(|i| !filters.is_muted(&i.item.title, &i.item.summary))
+        .collect();
+    tauri::async_runtime::spawn_blocking(move || story_clusters(items))
+        .await
+        .map_err(|e| format!("Clustering task failed: {e}"))
+}
@@ -0,0 +1,440 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::header::{HeaderValue, REFERER};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const FEEDS_FILE: &str = "comic_feeds.json";
+const INDEX_FILE: &str = "comic_index.json";
+const CACHE_DIR: &str = "comic_cache";
+const MAX_IMAGE_BYTES: u64 = 15 * 1024 * 1024;
+const MAX_INDEX_ENTRIES: usize = 2000;
+
+/// Known webcomic layouts: host suffix → CSS selector for the strip image.
+const SITE_RULES: &[(&str, &str)] = &[
+    ("xkcd.com", "#comic img"),
+    ("smbc-comics.com", "#cc-comic"),
+    ("questionablecontent.net", "#strip"),
+    ("explosm.net", "#comic img, [class*=MainComic] img"),
+    ("qwantz.com", "img.comic"),
+    ("pbfcomics.com", "#comic img"),
+    ("poorlydrawnlines.com", ".entry-content img"),
+    ("dilbert.com", "img.img-comic"),
+    ("phdcomics.com", "#comic, img#comic2"),
+    ("gocomics.com", "picture.item-comic-image img, img.lazyload.img-fluid"),
+];
+/// Generic fallbacks tried in order when no site rule or feed selector matches.
+const FALLBACK_SELECTORS: &[&str] = &[
+    "#comic img",
+    ".comic img",
+    "#cc-comic",
+    ".webcomic-image img",
+    "#comic-image",
+    "article img",
+];
+
+/// Per-feed comic mode: items open as the extracted strip instead of the article.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ComicFeed {
+    pub feed_url: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// CSS selector for the strip image, overriding the built-in site rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ComicPage {
+    pub page_url: String,
+    pub title: String,
+    pub image_url: String,
+    /// `alt` attribute of the strip.
+    #[serde(default)]
+    pub alt_text: String,
+    /// `title` attribute — the hover text on xkcd-style comics.
+    #[serde(default)]
+    pub hover_text: String,
+    /// File name of the cached image inside the comic cache directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_file: Option<String>,
+    /// `data:` URL of the cached image, filled in when returned to the frontend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_url: Option<String>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct ComicStore {
+    feeds: Mutex<Vec<ComicFeed>>,
+    /// Extracted pages keyed by page URL, so re-opening a strip needs no network.
+    index: Mutex<HashMap<String, ComicPage>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl ComicStore {
+    pub fn new() -> Self {
+        ComicStore {
+            feeds: Mutex::new(Vec::new()),
+            index: Mutex::new(HashMap::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn path(&self, name: &str) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(name))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.path(FEEDS_FILE) {
+            if let Ok(json) = std::fs::read_to_string(&path) {
+                if let Ok(feeds) = serde_json::from_str::<Vec<ComicFeed>>(&json) {
+                    eprintln!("[comics] {} feeds in comic mode", feeds.len());
+                    *self.feeds.lock().unwrap() = feeds;
+                }
+            }
+        }
+        if let Some(path) = self.path(INDEX_FILE) {
+            if let Ok(json) = std::fs::read_to_string(&path) {
+                if let Ok(index) = serde_json::from_str(&json) {
+                    *self.index.lock().unwrap() = index;
+                }
+            }
+        }
+    }
+
+    fn save_feeds(&self) {
+        if let Some(path) = self.path(FEEDS_FILE) {
+            let feeds = self.feeds.lock().unwrap();
+            match serde_json::to_string_pretty(&*feeds) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[comics] Failed to write comic feeds: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[comics] Failed to serialize comic feeds: {e}"),
+            }
+        }
+    }
+
+    fn save_index(&self) {
+        if let Some(path) = self.path(INDEX_FILE) {
+            let index = self.index.lock().unwrap();
+            match serde_json::to_string(&*index) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[comics] Failed to write comic index: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[comics] Failed to serialize comic index: {e}"),
+            }
+        }
+    }
+
+    pub fn get_feeds(&self) -> Vec<ComicFeed> {
+        self.feeds.lock().unwrap().clone()
+    }
+
+    fn feed(&self, feed_url: &str) -> Option<ComicFeed> {
+        self.feeds.lock().unwrap().iter().find(|f| f.feed_url == feed_url).cloned()
+    }
+
+    pub fn upsert_feed(&self, feed: ComicFeed) {
+        {
+            let mut feeds = self.feeds.lock().unwrap();
+            match feeds.iter_mut().find(|f| f.feed_url == feed.feed_url) {
+                Some(existing) => *existing = feed,
+                None => feeds.push(feed),
+            }
+        }
+        self.save_feeds();
+    }
+
+    pub fn remove_feed(&self, feed_url: &str) -> bool {
+        let mut feeds = self.feeds.lock().unwrap();
+        let before = feeds.len();
+        feeds.retain(|f| f.feed_url != feed_url);
+        let removed = feeds.len() < before;
+        drop(feeds);
+        if removed {
+            self.save_feeds();
+        }
+        removed
+    }
+
+    fn cached_page(&self, page_url: &str) -> Option<ComicPage> {
+        self.index.lock().unwrap().get(page_url).cloned()
+    }
+
+    fn remember(&self, page: ComicPage) {
+        {
+            let mut index = self.index.lock().unwrap();
+            if index.len() >= MAX_INDEX_ENTRIES && !index.contains_key(&page.page_url) {
+                // The index has no ordering; evicting an arbitrary entry only costs a refetch
+                if let Some(key) = index.keys().next().cloned() {
+                    if let Some(old) = index.remove(&key) {
+                        self.delete_cached_file(old.cached_file.as_deref());
+                    }
+                }
+            }
+            let new_file = page.cached_file.clone();
+            if let Some(old) = index.insert(page.page_url.clone(), page) {
+                // A re-cached strip may have a new image; drop the file it replaced
+                if old.cached_file != new_file {
+                    self.delete_cached_file(old.cached_file.as_deref());
+                }
+            }
+        }
+        self.save_index();
+    }
+
+    fn cache_dir(&self) -> Option<PathBuf> {
+        self.path(CACHE_DIR)
+    }
+
+    fn delete_cached_file(&self, name: Option<&str>) {
+        if let (Some(dir), Some(name)) = (self.cache_dir(), name) {
+            let _ = std::fs::remove_file(dir.join(name));
+        }
+    }
+}
+
+// ── Extraction ───────────────────────────────────────────────────────
+
+fn selectors_for(page: &Url, feed: Option<&ComicFeed>) -> Vec<String> {
+    let host = page.host_str().unwrap_or("");
+    let mut list: Vec<String> = Vec::new();
+    if let Some(sel) = feed.and_then(|f| f.selector.clone()).filter(|s| !s.trim().is_empty()) {
+        list.push(sel);
+    }
+    for (suffix, sel) in SITE_RULES {
+        if host == *suffix || host.ends_with(&format!(".{suffix}")) {
+            list.push(sel.to_string());
+        }
+    }
+    list.extend(FALLBACK_SELECTORS.iter().map(|s| s.to_string()));
+    list
+}
+
+/// Find the strip image plus alt/hover text. Falls back to `og:image` when no selector matches.
+fn extract(html: &str, page: &Url, feed: Option<&ComicFeed>) -> Result<ComicPage, String> {
+    let doc = Html::parse_document(html);
+    let meta = |prop: &str| {
+        Selector::parse(&format!(r#"meta[property="{prop}"], meta[name="{prop}"]"#))
+            .ok()
+            .and_then(|s| doc.select(&s).next())
+            .and_then(|m| m.value().attr("content"))
+            .map(|c| c.trim().to_string())
+    };
+    let title = meta("og:title")
+        .or_else(|| {
+            Selector::parse("title")
+                .ok()
+                .and_then(|s| doc.select(&s).next())
+                .map(|t| t.text().collect::<String>().trim().to_string())
+        })
+        .unwrap_or_default();
+
+    for raw in selectors_for(page, feed) {
+        let Ok(sel) = Selector::parse(&raw) else {
+            eprintln!("[comics] Invalid selector: {raw}");
+            continue;
+        };
+        for el in doc.select(&sel) {
+            let attrs = el.value();
+            let src = attrs
+                .attr("data-src")
+                .or_else(|| attrs.attr("src"))
+                .and_then(|s| page.join(s.trim()).ok());
+            if let Some(src) = src.filter(|u| u.scheme().starts_with("http")) {
+                return Ok(ComicPage {
+                    page_url: page.to_string(),
+                    title,
+                    image_url: src.to_string(),
+                    alt_text: attrs.attr("alt").unwrap_or("").trim().to_string(),
+                    hover_text: attrs.attr("title").unwrap_or("").trim().to_string(),
+                    cached_file: None,
+                    data_url: None,
+                });
+            }
+        }
+    }
+
+    let og = meta("og:image")
+        .and_then(|s| page.join(&s).ok())
+        .ok_or("No comic image found on the page")?;
+    Ok(ComicPage {
+        page_url: page.to_string(),
+        title,
+        image_url: og.to_string(),
+        alt_text: String::new(),
+        hover_text: String::new(),
+        cached_file: None,
+        data_url: None,
+    })
+}
+
+fn extension_for(content_type: Option<&str>, url: &str) -> &'static str {
+    match content_type.unwrap_or("") {
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        "image/svg+xml" => "svg",
+        "image/jpeg" => "jpg",
+        _ => {
+            let lower = url.to_lowercase();
+            [".png", ".gif", ".webp", ".avif", ".svg"]
+                .iter()
+                .find(|ext| lower.split('?').next().unwrap_or("").ends_with(*ext))
+                .map(|ext| &ext[1..])
+                .unwrap_or("jpg")
+        }
+    }
+}
+
+fn mime_for(file: &str) -> &'static str {
+    match file.rsplit('.').next().unwrap_or("") {
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "svg" => "image/svg+xml",
+        _ => "image/jpeg",
+    }
+}
+
+/// Download the strip into the cache (sent with the page as Referer, which
+/// hotlink-protected hosts require). Returns the cached file name.
+async fn cache_image(store: &ComicStore, page: &ComicPage) -> Result<String, String> {
+    let dir = store.cache_dir().ok_or("App data directory unavailable")?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create comic cache: {e}"))?;
+    let hash = format!("{:x}", Sha256::digest(page.image_url.as_bytes()));
+
+    let parsed = Url::parse(&page.image_url).map_err(|e| format!("Invalid image URL: {e}"))?;
+    let mut headers = crate::get_headers_for_url(&parsed);
+    if let Ok(referer) = HeaderValue::from_str(&page.page_url) {
+        headers.insert(REFERER, referer);
+    }
+    let client = crate::get_or_init_client()?;
+    let response = client
+        .get(parsed)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| format!("Image request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Image HTTP {}", response.status().as_u16()));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or("").trim().to_string());
+    let bytes = crate::response_limit::read_bytes(response, MAX_IMAGE_BYTES).await?;
+
+    let file = format!("{hash}.{}", extension_for(content_type.as_deref(), &page.image_url));
+    std::fs::write(dir.join(&file), &bytes).map_err(|e| format!("Failed to write comic image: {e}"))?;
+    Ok(file)
+}
+
+fn with_data_url(store: &ComicStore, mut page: ComicPage) -> ComicPage {
+    if let (Some(dir), Some(file)) = (store.cache_dir(), page.cached_file.as_deref()) {
+        if let Ok(bytes) = std::fs::read(dir.join(file)) {
+            page.data_url = Some(format!("data:{};base64,{}", mime_for(file), STANDARD.encode(bytes)));
+        }
+    }
+    page
+}
+
+/// Extract and cache a strip, reusing the cached copy when its image file still exists.
+pub async fn load_comic(store: &ComicStore, page_url: &str, feed_url: Option<&str>) -> Result<ComicPage, String> {
+    if let Some(page) = store.cached_page(page_url) {
+        let file_ok = match (store.cache_dir(), page.cached_file.as_deref()) {
+            (Some(dir), Some(f)) => dir.join(f).exists(),
+            _ => false,
+        };
+        if file_ok {
+            return Ok(page);
+        }
+    }
+
+    let url = Url::parse(page_url).map_err(|e| format!("Invalid page URL: {e}"))?;
+    let html = crate::fetch_text(page_url).await?;
+    let feed = feed_url.and_then(|f| store.feed(f));
+    let mut page = extract(&html, &url, feed.as_ref())?;
+    match cache_image(store, &page).await {
+        Ok(file) => page.cached_file = Some(file),
+        // Still usable: the frontend can fall back to the remote image URL
+        Err(e) => eprintln!("[comics] Could not cache {}: {e}", page.image_url),
+    }
+    store.remember(page.clone());
+    Ok(page)
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_comic_feeds(store: tauri::State<'_, Arc<ComicStore>>) -> Vec<ComicFeed> {
+    store.get_feeds()
+}
+
+#[tauri::command]
+pub fn set_comic_feed(feed: ComicFeed, store: tauri::State<'_, Arc<ComicStore>>) -> Result<(), String> {
+    if let Some(ref sel) = feed.selector {
+        if !sel.trim().is_empty() {
+            Selector::parse(sel).map_err(|e| format!("Invalid CSS selector: {e:?}"))?;
+        }
+    }
+    store.upsert_feed(feed);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_comic_feed(feed_url: String, store: tauri::State<'_, Arc<ComicStore>>) -> bool {
+    store.remove_feed(&feed_url)
+}
+
+/// The strip for an item page, with the cached image inlined as a `data:` URL.
+#[tauri::command]
+pub async fn get_comic(
+    page_url: String,
+    feed_url: Option<String>,
+    store: tauri::State<'_, Arc<ComicStore>>,
+) -> Result<ComicPage, String> {
+    let page = load_comic(&store, &page_url, feed_url.as_deref()).await?;
+    Ok(with_data_url(&store, page))
+}
+
+/// Warm the cache for upcoming strips. Returns how many were cached.
+#[tauri::command]
+pub async fn prefetch_comics(
+    page_urls: Vec<String>,
+    feed_url: Option<String>,
+    store: tauri::State<'_, Arc<ComicStore>>,
+) -> Result<usize, String> {
+    let mut cached = 0;
+    for url in &page_urls {
+        match load_comic(&store, url, feed_url.as_deref()).await {
+            Ok(page) if page.cached_file.is_some() => cached += 1,
+            Ok(_) => {}
+            Err(e) => eprintln!("[comics] Prefetch failed for {url}: {e}"),
+        }
+    }
+    Ok(cached)
+}
@@ -0,0 +1,35 @@
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::feed_hooks::FeedHookStore;
+use crate::feed_stats::FeedStatsStore;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+/// Result of a conditional fetch. `body` is absent when it hasn't changed.
+#[derive(Clone, Serialize, Debug)]
+pub struct ConditionalFetch {
+    pub not_modified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Like `fetch_url`, but reports `not_modified` instead of the body when it
+/// hasn't changed since it was last downloaded: the disk cache still holds it
+/// fresh, or the server answered its ETag / Last-Modified with a 304.
+/// `cache_clear` forces the next fetch to download in full.
+#[tauri::command]
+pub async fn fetch_url_conditional(
+    target_url: String,
+    app: tauri::AppHandle,
+    stats: tauri::State<'_, Arc<FeedStatsStore>>,
+    hooks: tauri::State<'_, Arc<FeedHookStore>>,
+) -> Result<ConditionalFetch, String> {
+    let response = crate::fetch_feed(&app, &target_url, None, Some(stats.inner().as_ref()), &hooks).await?;
+    Ok(match response.from_cache {
+        true => ConditionalFetch { not_modified: true, body: None },
+        false => ConditionalFetch { not_modified: false, body: Some(response.body) },
+    })
+}
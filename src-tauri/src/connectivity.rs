@@ -0,0 +1,108 @@
+use serde::Serialize;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use sysinfo::Networks;
+use tauri::Emitter;
+
+use crate::ingest::now_millis;
+
+// Connectivity watcher. Interface changes are picked up from the OS every few
+// seconds; whether there is a route out is checked with a TCP connect to
+// well-known anycast addresses (or to the proxy, when one is in the way).
+// Transitions are emitted as `network-online` / `network-offline` so the
+// frontend can pause refreshing instead of piling up failing requests.
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// While online with the same interfaces, the route is re-checked this often.
+const RECHECK_INTERVAL: Duration = Duration::from_secs(60);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Cloudflare and Google public resolvers, which also listen on 443.
+const PROBE_ADDRESSES: &[&str] =
+    &["1.1.1.1:443", "8.8.8.8:443", "[2606:4700:4700::1111]:443", "[2001:4860:4860::8888]:443"];
+
+#[derive(Clone, Serialize, Debug)]
+pub struct Connectivity {
+    pub online: bool,
+    /// Interfaces with a routable address.
+    pub interfaces: Vec<String>,
+    pub checked_at: u64,
+}
+
+fn state() -> &'static Mutex<Option<Connectivity>> {
+    static STATE: OnceLock<Mutex<Option<Connectivity>>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(None))
+}
+
+// ── Detection ────────────────────────────────────────────────────────
+
+fn routable(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => !v4.is_loopback() && !v4.is_link_local() && !v4.is_unspecified(),
+        // fe80::/10
+        IpAddr::V6(v6) => !v6.is_loopback() && !v6.is_unspecified() && (v6.segments()[0] & 0xffc0) != 0xfe80,
+    }
+}
+
+fn usable_interfaces(networks: &Networks) -> Vec<String> {
+    let mut names: Vec<String> = networks
+        .iter()
+        .filter(|(_, data)| data.ip_networks().iter().any(|net| routable(&net.addr)))
+        .map(|(name, _)| name.to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+fn probe_targets() -> Vec<SocketAddr> {
+    match crate::proxy::address() {
+        Some(proxy) => proxy.to_socket_addrs().map(Vec::from_iter).unwrap_or_default(),
+        None => PROBE_ADDRESSES.iter().filter_map(|a| a.parse().ok()).collect(),
+    }
+}
+
+fn route_available() -> bool {
+    probe_targets().iter().any(|addr| TcpStream::connect_timeout(addr, PROBE_TIMEOUT).is_ok())
+}
+
+/// Poll interfaces and reachability, emitting an event on every change
+/// after the first check.
+pub fn start_watcher(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut networks = Networks::new();
+        let mut last_interfaces: Option<Vec<String>> = None;
+        let mut last_probe: Option<Instant> = None;
+        loop {
+            networks.refresh_list();
+            let interfaces = usable_interfaces(&networks);
+            let online = state().lock().unwrap().as_ref().map(|s| s.online);
+            let due = online != Some(true) || last_probe.is_none_or(|t| t.elapsed() >= RECHECK_INTERVAL);
+            if due || last_interfaces.as_ref() != Some(&interfaces) {
+                // One retry, so a single dropped SYN isn't reported as an outage
+                let now_online = !interfaces.is_empty() && (route_available() || route_available());
+                last_probe = Some(Instant::now());
+                let current = Connectivity {
+                    online: now_online,
+                    interfaces: interfaces.clone(),
+                    checked_at: now_millis(),
+                };
+                *state().lock().unwrap() = Some(current.clone());
+                if online.is_some_and(|was| was != now_online) {
+                    eprintln!("[connectivity] {}", if now_online { "Online" } else { "Offline" });
+                    let event = if now_online { "network-online" } else { "network-offline" };
+                    let _ = app.emit(event, &current);
+                }
+            }
+            last_interfaces = Some(interfaces);
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// The last connectivity check, or None before the first one finishes.
+#[tauri::command]
+pub fn get_connectivity() -> Option<Connectivity> {
+    state().lock().unwrap().clone()
+}
@@ -0,0 +1,266 @@
+use ego_tree::NodeId;
+use scraper::node::Element;
+use scraper::{Html, Node};
+use url::Url;
+
+use crate::highlight::highlight_code_blocks;
+use crate::math::render_math;
+
+// Article HTML post-processing applied at ingest, so stored copies (offline
+// reading, archives, the image proxy) don't depend on the page they came from.
+
+/// Attributes holding a single URL, by element.
+const URL_ATTRIBUTES: &[(&str, &str)] = &[
+    ("a", "href"),
+    ("area", "href"),
+    ("img", "src"),
+    ("img", "longdesc"),
+    ("source", "src"),
+    ("video", "src"),
+    ("video", "poster"),
+    ("audio", "src"),
+    ("track", "src"),
+    ("iframe", "src"),
+    ("embed", "src"),
+    ("object", "data"),
+    ("input", "src"),
+    ("blockquote", "cite"),
+    ("q", "cite"),
+    ("del", "cite"),
+    ("ins", "cite"),
+];
+
+/// Attributes holding a `srcset` candidate list.
+const SRCSET_ATTRIBUTES: &[(&str, &str)] = &[("img", "srcset"), ("source", "srcset")];
+
+/// Resolve one URL against `base`. In-page anchors and unparseable values are
+/// left alone; protocol-relative `//host/x` takes the base's scheme.
+fn resolve(base: &Url, value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value.starts_with('#') {
+        return None;
+    }
+    base.join(value).ok().map(String::from)
+}
+
+/// Split a `srcset` into `(url, descriptor)` candidates, following the HTML
+/// parsing rules closely enough for URLs that contain commas.
+pub fn srcset_candidates(srcset: &str) -> Vec<(&str, &str)> {
+    let mut out = Vec::new();
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+        let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let url = &rest[..url_end];
+        rest = &rest[url_end..];
+        // A trailing comma on the URL ends the candidate with no descriptor
+        if url.ends_with(',') {
+            out.push((url.trim_end_matches(','), ""));
+            continue;
+        }
+        let descriptor_end = rest.find(',').unwrap_or(rest.len());
+        out.push((url, rest[..descriptor_end].trim()));
+        rest = &rest[descriptor_end..];
+    }
+    out
+}
+
+fn resolve_srcset(base: &Url, srcset: &str) -> String {
+    srcset_candidates(srcset)
+        .into_iter()
+        .map(|(url, descriptor)| {
+            let url = resolve(base, url).unwrap_or_else(|| url.to_string());
+            if descriptor.is_empty() {
+                url
+            } else {
+                format!("{url} {descriptor}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Rewrite relative links and media URLs in an HTML fragment to absolute ones.
+/// A `<base href>` inside the fragment takes precedence over `base_url` and is
+/// removed, since it would otherwise retarget every link in the reader view.
+pub fn absolutize_urls(html: &str, base_url: &str) -> String {
+    if !html.contains('<') {
+        return html.to_string();
+    }
+    let Ok(mut base) = Url::parse(base_url) else { return html.to_string() };
+    let mut fragment = Html::parse_fragment(html);
+
+    let mut base_tags = Vec::new();
+    let mut targets: Vec<NodeId> = Vec::new();
+    for node in fragment.tree.nodes() {
+        let Node::Element(el) = node.value() else { continue };
+        if el.name() == "base" {
+            if base_tags.is_empty() {
+                if let Some(href) = el.attr("href").and_then(|h| base.join(h.trim()).ok()) {
+                    base = href;
+                }
+            }
+            base_tags.push(node.id());
+        } else if URL_ATTRIBUTES.iter().chain(SRCSET_ATTRIBUTES).any(|(tag, _)| *tag == el.name()) {
+            targets.push(node.id());
+        }
+    }
+    if targets.is_empty() && base_tags.is_empty() {
+        return html.to_string();
+    }
+
+    for id in targets {
+        let Some(mut node) = fragment.tree.get_mut(id) else { continue };
+        let Node::Element(el) = node.value() else { continue };
+        let tag = el.name().to_string();
+        for (name, value) in el.attrs.iter_mut() {
+            let attr = &*name.local;
+            let rewritten = if URL_ATTRIBUTES.contains(&(tag.as_str(), attr)) {
+                resolve(&base, value)
+            } else if SRCSET_ATTRIBUTES.contains(&(tag.as_str(), attr)) {
+                Some(resolve_srcset(&base, value))
+            } else {
+                None
+            };
+            if let Some(rewritten) = rewritten {
+                *value = rewritten.as_str().into();
+            }
+        }
+    }
+    for id in base_tags {
+        if let Some(mut node) = fragment.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    fragment.root_element().inner_html()
+}
+
+// ── Lazy images ──────────────────────────────────────────────────────
+
+/// Width images are picked for when the reader's width isn't known (ingest).
+pub const DEFAULT_IMAGE_WIDTH: u32 = 1200;
+
+/// Where lazy-loading scripts keep the real image until it scrolls into view.
+const LAZY_SRC_ATTRIBUTES: &[&str] = &[
+    "data-src",
+    "data-lazy-src",
+    "data-original",
+    "data-lazy",
+    "data-url",
+    "data-actualsrc",
+    "data-hi-res-src",
+];
+const LAZY_SRCSET_ATTRIBUTES: &[&str] = &["data-srcset", "data-lazy-srcset"];
+
+fn attr<'a>(el: &'a Element, name: &str) -> Option<&'a str> {
+    el.attr(name).map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn set_attr(el: &mut Element, name: &str, value: &str) {
+    if let Some((_, v)) = el.attrs.iter_mut().find(|(n, _)| &*n.local == name) {
+        *v = value.into();
+        return;
+    }
+    // Attribute names can't be built directly; reuse one from the same element
+    let Some(mut qual) = el.attrs.first().map(|(n, _)| n.clone()) else { return };
+    qual.prefix = None;
+    qual.ns = "".into();
+    qual.local = name.into();
+    el.attrs.push((qual, value.into()));
+}
+
+/// Best `srcset` candidate for a display `target_width` px wide: the smallest
+/// `w` candidate at least that wide (else the widest), or the highest density up to 2x.
+pub fn pick_candidate<'a>(candidates: &[(&'a str, &str)], target_width: u32) -> Option<&'a str> {
+    let widths: Vec<(&str, u32)> = candidates
+        .iter()
+        .filter_map(|(url, d)| Some((*url, d.strip_suffix('w')?.trim().parse().ok()?)))
+        .collect();
+    if !widths.is_empty() {
+        let wide_enough = widths.iter().filter(|(_, w)| *w >= target_width).min_by_key(|(_, w)| *w);
+        return wide_enough.or_else(|| widths.iter().max_by_key(|(_, w)| *w)).map(|(url, _)| *url);
+    }
+    let density = |d: &str| d.strip_suffix('x').and_then(|x| x.trim().parse::<f32>().ok()).unwrap_or(1.0);
+    candidates
+        .iter()
+        .filter(|(_, d)| density(d) <= 2.0)
+        .max_by(|a, b| density(a.1).total_cmp(&density(b.1)))
+        .or(candidates.first())
+        .map(|(url, _)| *url)
+}
+
+/// Point `<img>` elements at a real image: lazy-loading attributes are promoted
+/// and the `srcset` candidate best suited to `target_width` becomes the `src`,
+/// so the image shows without scripts and offline copies fetch a sensible size.
+pub fn normalize_images(html: &str, target_width: u32) -> String {
+    if !html.contains("<img") && !html.contains("<source") {
+        return html.to_string();
+    }
+    let mut fragment = Html::parse_fragment(html);
+    let targets: Vec<NodeId> = fragment
+        .tree
+        .nodes()
+        .filter(|n| matches!(n.value(), Node::Element(el) if el.name() == "img" || el.name() == "source"))
+        .map(|n| n.id())
+        .collect();
+    let mut changed = false;
+    for id in targets {
+        let Some(mut node) = fragment.tree.get_mut(id) else { continue };
+        let Node::Element(el) = node.value() else { continue };
+        let lazy_srcset = LAZY_SRCSET_ATTRIBUTES.iter().find_map(|a| attr(el, a)).map(String::from);
+        let srcset = lazy_srcset.clone().or_else(|| attr(el, "srcset").map(String::from));
+
+        if el.name() == "img" {
+            let lazy_src = LAZY_SRC_ATTRIBUTES
+                .iter()
+                .find_map(|a| attr(el, a))
+                .filter(|s| !s.starts_with("data:"))
+                .map(String::from);
+            let candidates = srcset.as_deref().map(srcset_candidates).unwrap_or_default();
+            let chosen = pick_candidate(&candidates, target_width).map(String::from).or(lazy_src);
+            if let Some(src) = chosen.filter(|c| attr(el, "src") != Some(c)) {
+                set_attr(el, "src", &src);
+                changed = true;
+            }
+        }
+        if let Some(lazy_srcset) = lazy_srcset {
+            set_attr(el, "srcset", &lazy_srcset);
+            changed = true;
+        }
+        let before = el.attrs.len();
+        el.attrs.retain(|(n, _)| {
+            let name = &*n.local;
+            !LAZY_SRC_ATTRIBUTES.contains(&name) && !LAZY_SRCSET_ATTRIBUTES.contains(&name)
+        });
+        changed |= el.attrs.len() != before;
+    }
+    if !changed {
+        return html.to_string();
+    }
+    fragment.root_element().inner_html()
+}
+
+/// Ingest-time processing of feed HTML: lazy images fixed up, code blocks
+/// highlighted, LaTeX converted to MathML, then URLs made absolute.
+pub fn process_article_html(html: &str, base_url: &str) -> String {
+    let html = render_math(&highlight_code_blocks(&normalize_images(html, DEFAULT_IMAGE_WIDTH)));
+    absolutize_urls(&html, base_url)
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Make the URLs in an article's HTML absolute, relative to the article's URL.
+#[tauri::command]
+pub fn resolve_content_urls(html: String, base_url: String) -> Result<String, String> {
+    Url::parse(&base_url).map_err(|e| format!("Invalid base URL: {e}"))?;
+    Ok(absolutize_urls(&html, &base_url))
+}
+
+/// Fix lazy-loading images for a reader `target_width` px wide (default 1200).
+#[tauri::command]
+pub fn normalize_content_images(html: String, target_width: Option<u32>) -> String {
+    normalize_images(&html, target_width.unwrap_or(DEFAULT_IMAGE_WIDTH))
+}
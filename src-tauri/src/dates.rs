@@ -0,0 +1,365 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SETTINGS_FILE: &str = "feed_timezones.json";
+/// Anything earlier is a placeholder (epoch, year 1, …) rather than a real date.
+const MIN_YEAR: i32 = 1995;
+/// Dates further ahead than this are treated as wrong, so they cannot pin items to the top.
+const MAX_FUTURE_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Per-feed timezone override for feeds that publish local times.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FeedTimezone {
+    /// IANA name (`Europe/Paris`) or fixed offset (`+02:00`).
+    pub timezone: String,
+    /// Also reinterpret dates that carry an offset. For feeds that stamp local
+    /// time with `GMT` or `+0000`.
+    #[serde(default)]
+    pub ignore_stated_offset: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Zone {
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    fn parse(s: &str) -> Option<Zone> {
+        let s = s.trim();
+        if let Ok(tz) = s.parse::<Tz>() {
+            return Some(Zone::Named(tz));
+        }
+        parse_offset(s).map(Zone::Fixed)
+    }
+
+    fn to_utc(self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        // `earliest` picks a side of DST gaps and overlaps instead of failing
+        match self {
+            Zone::Named(tz) => tz.from_local_datetime(&naive).earliest().map(|d| d.with_timezone(&Utc)),
+            Zone::Fixed(off) => off.from_local_datetime(&naive).earliest().map(|d| d.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// `+02:00`, `+0200`, `-5`, `UTC`, `Z`.
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("z") || s.eq_ignore_ascii_case("utc") || s.eq_ignore_ascii_case("gmt") {
+        return FixedOffset::east_opt(0);
+    }
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let digits: String = rest.chars().filter(|c| c.is_ascii_digit()).collect();
+    let (h, m) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        3 | 4 => (digits[..digits.len() - 2].parse().ok()?, digits[digits.len() - 2..].parse().ok()?),
+        _ => return None,
+    };
+    if h > 14 || m > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (h * 3600 + m * 60))
+}
+
+// ── Lenient parsing ──────────────────────────────────────────────────
+
+/// Timezone abbreviations seen in the wild, which RFC 2822 parsers mostly reject.
+const ZONE_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("UT", "+0000"), ("UTC", "+0000"), ("GMT", "+0000"), ("Z", "+0000"),
+    ("EST", "-0500"), ("EDT", "-0400"), ("CST", "-0600"), ("CDT", "-0500"),
+    ("MST", "-0700"), ("MDT", "-0600"), ("PST", "-0800"), ("PDT", "-0700"),
+    ("AKST", "-0900"), ("AKDT", "-0800"), ("HST", "-1000"),
+    ("WET", "+0000"), ("WEST", "+0100"), ("BST", "+0100"), ("IST", "+0530"),
+    ("CET", "+0100"), ("CEST", "+0200"), ("MET", "+0100"), ("MEST", "+0200"),
+    ("EET", "+0200"), ("EEST", "+0300"), ("MSK", "+0300"),
+    ("JST", "+0900"), ("KST", "+0900"), ("HKT", "+0800"), ("SGT", "+0800"),
+    ("AEST", "+1000"), ("AEDT", "+1100"), ("ACST", "+0930"), ("AWST", "+0800"),
+    ("NZST", "+1200"), ("NZDT", "+1300"),
+];
+
+/// Month names normalized to the 3-letter English form `%b` expects.
+const MONTHS: &[(&str, &str)] = &[
+    ("january", "Jan"), ("february", "Feb"), ("march", "Mar"), ("april", "Apr"), ("may", "May"),
+    ("june", "Jun"), ("july", "Jul"), ("august", "Aug"), ("september", "Sep"), ("sept", "Sep"),
+    ("october", "Oct"), ("november", "Nov"), ("december", "Dec"),
+    // French and German, common in European feeds
+    ("janvier", "Jan"), ("février", "Feb"), ("fevrier", "Feb"), ("mars", "Mar"), ("avril", "Apr"),
+    ("mai", "May"), ("juin", "Jun"), ("juillet", "Jul"), ("août", "Aug"), ("aout", "Aug"),
+    ("septembre", "Sep"), ("octobre", "Oct"), ("novembre", "Nov"), ("décembre", "Dec"),
+    ("decembre", "Dec"), ("januar", "Jan"), ("februar", "Feb"), ("märz", "Mar"), ("juni", "Jun"),
+    ("juli", "Jul"), ("oktober", "Oct"), ("dezember", "Dec"),
+];
+
+const WEEKDAYS: &[&str] = &[
+    "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+    "mon", "tue", "tues", "wed", "thu", "thur", "thurs", "fri", "sat", "sun",
+    "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+];
+
+const ZONED_FORMATS: &[&str] = &[
+    "%d %b %Y %H:%M:%S %z",
+    "%d %b %Y %H:%M %z",
+    "%Y-%m-%d %H:%M:%S%.f %z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M%z",
+    "%b %d %Y %H:%M:%S %z",
+    "%b %d %H:%M:%S %Y %z",
+];
+
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y/%m/%d %H:%M",
+    "%d %b %Y %H:%M:%S",
+    "%d %b %Y %H:%M",
+    "%b %d %Y %H:%M:%S",
+    "%b %d %Y %H:%M",
+    "%d.%m.%Y %H:%M:%S",
+    "%d.%m.%Y %H:%M",
+];
+
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%d %b %Y", "%b %d %Y", "%d.%m.%Y", "%Y%m%d"];
+
+/// Uniform spacing, no weekday, English month abbreviations, numeric zones.
+fn clean(raw: &str) -> String {
+    let spaced = raw
+        .trim()
+        .replace([',', '\u{a0}'], " ")
+        .replace(" at ", " ");
+    let mut out: Vec<String> = Vec::new();
+    for token in spaced.split_whitespace() {
+        let lower = token.trim_end_matches('.').to_lowercase();
+        if WEEKDAYS.contains(&lower.as_str()) {
+            continue;
+        }
+        if let Some((_, abbr)) = MONTHS.iter().find(|(name, _)| *name == lower) {
+            out.push(abbr.to_string());
+            continue;
+        }
+        // "(PST)" trailing comments and ordinal suffixes ("5th")
+        let token = token.trim_matches(|c| c == '(' || c == ')');
+        if let Some((_, off)) = ZONE_ABBREVIATIONS.iter().find(|(abbr, _)| abbr.eq_ignore_ascii_case(token)) {
+            out.push(off.to_string());
+            continue;
+        }
+        let ordinal = ["st", "nd", "rd", "th"].iter().find_map(|s| {
+            token
+                .strip_suffix(s)
+                .filter(|d| !d.is_empty() && d.chars().all(|c| c.is_ascii_digit()))
+        });
+        out.push(ordinal.unwrap_or(token).to_string());
+    }
+    // A parenthesized abbreviation after a numeric offset is a duplicate
+    if out.len() >= 2 {
+        let n = out.len();
+        if parse_offset(&out[n - 1]).is_some() && parse_offset(&out[n - 2]).is_some() {
+            out.pop();
+        }
+    }
+    out.join(" ")
+}
+
+/// Rewrite a trailing `+hh:mm` as `+hhmm` so `%z` accepts it.
+fn compact_trailing_offset(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let n = bytes.len();
+    if n >= 6 && matches!(bytes[n - 6], b'+' | b'-') && bytes[n - 3] == b':' {
+        return format!("{}{}", &s[..n - 3], &s[n - 2..]);
+    }
+    s.to_string()
+}
+
+/// Parse a feed date, accepting RFC 3339, RFC 2822 and the usual broken variants
+/// (wrong weekdays, zone names, localized months, missing zones). Naive times are
+/// read in `zone`, or UTC without one.
+fn parse_with(raw: &str, zone: Option<Zone>, ignore_stated_offset: bool) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    // Unix timestamps (seconds or milliseconds)
+    if raw.chars().all(|c| c.is_ascii_digit()) && (raw.len() == 10 || raw.len() == 13) {
+        let n: i64 = raw.parse().ok()?;
+        return if raw.len() == 10 {
+            Utc.timestamp_opt(n, 0).single()
+        } else {
+            Utc.timestamp_millis_opt(n).single()
+        };
+    }
+
+    let zone_for_naive = zone.unwrap_or(Zone::Fixed(FixedOffset::east_opt(0)?));
+    let restate = |d: DateTime<FixedOffset>| -> Option<DateTime<Utc>> {
+        if ignore_stated_offset && zone.is_some() {
+            zone_for_naive.to_utc(d.naive_local())
+        } else {
+            Some(d.with_timezone(&Utc))
+        }
+    };
+
+    if let Ok(d) = DateTime::parse_from_rfc3339(raw) {
+        return restate(d);
+    }
+    if let Ok(d) = DateTime::parse_from_rfc2822(raw) {
+        return restate(d);
+    }
+
+    let mut cleaned = clean(raw);
+    // "2024-01-05T10:00Z": a `Z` glued to the time
+    if cleaned.ends_with('Z') && cleaned[..cleaned.len() - 1].ends_with(|c: char| c.is_ascii_digit()) {
+        cleaned.pop();
+        cleaned.push_str("+0000");
+    }
+    let cleaned = compact_trailing_offset(&cleaned);
+    for fmt in ZONED_FORMATS {
+        if let Ok(d) = DateTime::parse_from_str(&cleaned, fmt) {
+            return restate(d);
+        }
+    }
+    for fmt in NAIVE_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(&cleaned, fmt) {
+            return zone_for_naive.to_utc(naive);
+        }
+    }
+    for fmt in DATE_FORMATS {
+        if let Ok(date) = NaiveDate::parse_from_str(&cleaned, fmt) {
+            return zone_for_naive.to_utc(date.and_hms_opt(0, 0, 0)?);
+        }
+    }
+    None
+}
+
+/// Drop placeholder and far-future dates so they fall back to the fetch time.
+fn sane(d: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    use chrono::Datelike;
+    let max = Utc::now().timestamp_millis() + MAX_FUTURE_MS;
+    (d.year() >= MIN_YEAR && d.timestamp_millis() <= max).then_some(d)
+}
+
+/// Lenient date parser used by the feed pipeline.
+pub fn parse_date(raw: &str, timezone: Option<&FeedTimezone>) -> Option<DateTime<Utc>> {
+    let zone = timezone.and_then(|t| Zone::parse(&t.timezone));
+    let ignore = timezone.is_some_and(|t| t.ignore_stated_offset);
+    parse_with(raw, zone, ignore).and_then(sane)
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct DateStore {
+    overrides: Mutex<HashMap<String, FeedTimezone>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl DateStore {
+    pub fn new() -> Self {
+        DateStore {
+            overrides: Mutex::new(HashMap::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SETTINGS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(overrides) = serde_json::from_str(&json) {
+                            *self.overrides.lock().unwrap() = overrides;
+                        }
+                    }
+                    Err(e) => eprintln!("[dates] Failed to read timezone overrides: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let overrides = self.overrides.lock().unwrap();
+            match serde_json::to_string_pretty(&*overrides) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[dates] Failed to write timezone overrides: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[dates] Failed to serialize timezone overrides: {e}"),
+            }
+        }
+    }
+
+    pub fn timezone_for(&self, feed_url: &str) -> Option<FeedTimezone> {
+        self.overrides.lock().unwrap().get(feed_url).cloned()
+    }
+
+    pub fn get_all(&self) -> HashMap<String, FeedTimezone> {
+        self.overrides.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, feed_url: &str, timezone: Option<FeedTimezone>) {
+        {
+            let mut overrides = self.overrides.lock().unwrap();
+            match timezone {
+                Some(tz) => overrides.insert(feed_url.to_string(), tz),
+                None => overrides.remove(feed_url),
+            };
+        }
+        self.save_to_disk();
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_feed_timezones(store: tauri::State<'_, Arc<DateStore>>) -> HashMap<String, FeedTimezone> {
+    store.get_all()
+}
+
+/// Set or clear (`timezone: null`) the timezone override for a feed.
+#[tauri::command]
+pub fn set_feed_timezone(
+    feed_url: String,
+    timezone: Option<FeedTimezone>,
+    store: tauri::State<'_, Arc<DateStore>>,
+) -> Result<(), String> {
+    if let Some(tz) = &timezone {
+        if Zone::parse(&tz.timezone).is_none() {
+            return Err(format!("Unknown timezone: {}", tz.timezone));
+        }
+    }
+    store.set(&feed_url, timezone);
+    Ok(())
+}
+
+/// Parse a date string from a feed into ms since epoch, applying the feed's
+/// timezone override. Returns null for unparseable or implausible dates.
+#[tauri::command]
+pub fn parse_feed_date(
+    raw: String,
+    feed_url: Option<String>,
+    store: tauri::State<'_, Arc<DateStore>>,
+) -> Option<i64> {
+    let timezone = feed_url.and_then(|u| store.timezone_for(&u));
+    parse_date(&raw, timezone.as_ref()).map(|d| d.timestamp_millis())
+}
@@ -0,0 +1,822 @@
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::authors;
+use crate::ingest::now_millis;
+use crate::content::process_article_html;
+use crate::dedup;
+use crate::fulltext;
+use crate::gallery::{media_from, GalleryMedia};
+use crate::links;
+use crate::search;
+use crate::text::{snippet_from_html, SNIPPET_LEN};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const DB_FILE: &str = "superflux.db";
+/// Articles indexed per transaction by the background backfills.
+const BACKFILL_BATCH: usize = 500;
+const DEFAULT_PAGE_SIZE: u32 = 200;
+const MAX_PAGE_SIZE: u32 = 5000;
+
+/// Schema migrations, applied in order. `PRAGMA user_version` records how many ran.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE articles (
+        id           TEXT PRIMARY KEY,
+        feed_id      TEXT NOT NULL,
+        title        TEXT NOT NULL DEFAULT '',
+        url          TEXT NOT NULL DEFAULT '',
+        author       TEXT NOT NULL DEFAULT '',
+        summary      TEXT NOT NULL DEFAULT '',
+        content      TEXT,
+        thumbnail    TEXT,
+        published_at INTEGER,
+        fetched_at   INTEGER NOT NULL,
+        is_read      INTEGER NOT NULL DEFAULT 0,
+        read_at      INTEGER,
+        is_starred   INTEGER NOT NULL DEFAULT 0,
+        extra        TEXT
+    );
+    CREATE INDEX idx_articles_feed_date ON articles(feed_id, published_at DESC);
+    CREATE INDEX idx_articles_date ON articles(published_at DESC);
+    CREATE INDEX idx_articles_unread ON articles(feed_id) WHERE is_read = 0;
+    CREATE INDEX idx_articles_starred ON articles(is_starred) WHERE is_starred = 1;
+    CREATE TABLE feeds (
+        id              TEXT PRIMARY KEY,
+        url             TEXT NOT NULL,
+        title           TEXT NOT NULL DEFAULT '',
+        last_fetched_at INTEGER,
+        last_error      TEXT
+    );",
+    "ALTER TABLE feeds ADD COLUMN folder TEXT NOT NULL DEFAULT '';
+    ALTER TABLE feeds ADD COLUMN html_url TEXT;",
+    "ALTER TABLE articles ADD COLUMN snippet TEXT NOT NULL DEFAULT '';",
+    "ALTER TABLE articles ADD COLUMN media TEXT;",
+    // Folded text only (see `search`); search_docs gives articles stable FTS rowids
+    "CREATE TABLE search_docs (
+        rowid      INTEGER PRIMARY KEY,
+        article_id TEXT NOT NULL UNIQUE
+    );
+    CREATE VIRTUAL TABLE search_index USING fts5(
+        title, author, body,
+        content = '', contentless_delete = 1, tokenize = 'unicode61 remove_diacritics 2'
+    );
+    CREATE TRIGGER articles_search_delete AFTER DELETE ON articles BEGIN
+        DELETE FROM search_index WHERE rowid = (SELECT rowid FROM search_docs WHERE article_id = old.id);
+        DELETE FROM search_docs WHERE article_id = old.id;
+    END;",
+    // Stems get their own column so they don't break up phrases; emptying
+    // search_docs makes `search::backfill_batch` reindex everything
+    "DROP TABLE search_index;
+    DELETE FROM search_docs;
+    CREATE VIRTUAL TABLE search_index USING fts5(
+        title, author, body, stems,
+        content = '', contentless_delete = 1, tokenize = 'unicode61 remove_diacritics 2'
+    );",
+    // Link graph (see `links`); existing rows keep a NULL link_key until `links::backfill_batch`
+    "ALTER TABLE articles ADD COLUMN link_key TEXT;
+    CREATE INDEX idx_articles_link_key ON articles(link_key);
+    CREATE TABLE article_links (
+        source_id  TEXT NOT NULL,
+        target_key TEXT NOT NULL,
+        PRIMARY KEY (source_id, target_key)
+    ) WITHOUT ROWID;
+    CREATE INDEX idx_article_links_target ON article_links(target_key);
+    CREATE TRIGGER articles_links_delete AFTER DELETE ON articles BEGIN
+        DELETE FROM article_links WHERE source_id = old.id;
+    END;",
+    // Authors keyed by folded name (see `authors`)
+    "CREATE TABLE authors (
+        id       TEXT PRIMARY KEY,
+        name     TEXT NOT NULL,
+        followed INTEGER NOT NULL DEFAULT 0,
+        notify   INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE article_authors (
+        article_id TEXT NOT NULL,
+        author_id  TEXT NOT NULL,
+        PRIMARY KEY (article_id, author_id)
+    ) WITHOUT ROWID;
+    CREATE INDEX idx_article_authors_author ON article_authors(author_id);
+    CREATE TRIGGER articles_authors_delete AFTER DELETE ON articles BEGIN
+        DELETE FROM article_authors WHERE article_id = old.id;
+    END;",
+    // Set by filter rules (see `filters`); hidden articles stay stored and searchable
+    "ALTER TABLE articles ADD COLUMN is_hidden INTEGER NOT NULL DEFAULT 0;
+    CREATE TABLE article_tags (
+        article_id TEXT NOT NULL,
+        tag        TEXT NOT NULL,
+        PRIMARY KEY (article_id, tag)
+    ) WITHOUT ROWID;
+    CREATE INDEX idx_article_tags_tag ON article_tags(tag);
+    CREATE TRIGGER articles_tags_delete AFTER DELETE ON articles BEGIN
+        DELETE FROM article_tags WHERE article_id = old.id;
+    END;",
+    // Duplicate groups (see `dedup`); a NULL simhash is left for `dedup::backfill_batch`.
+    // Deleting the first copy of a story promotes the next one.
+    "ALTER TABLE articles ADD COLUMN simhash INTEGER;
+    ALTER TABLE articles ADD COLUMN duplicate_of TEXT;
+    CREATE INDEX idx_articles_duplicate_of ON articles(duplicate_of);
+    CREATE TRIGGER articles_dedup_delete AFTER DELETE ON articles BEGIN
+        UPDATE articles SET duplicate_of =
+            (SELECT id FROM articles WHERE duplicate_of = old.id ORDER BY fetched_at, id LIMIT 1)
+        WHERE duplicate_of = old.id;
+        UPDATE articles SET duplicate_of = NULL WHERE duplicate_of = id;
+    END;",
+    // Ids deleted by `retention`, so a refresh doesn't bring them back as new
+    "CREATE TABLE pruned_articles (
+        id        TEXT PRIMARY KEY,
+        feed_id   TEXT NOT NULL,
+        pruned_at INTEGER NOT NULL
+    ) WITHOUT ROWID;
+    CREATE INDEX idx_pruned_articles_feed ON pruned_articles(feed_id);",
+    // Articles whose bylines `authors::backfill_batch` still has to link
+    "CREATE TABLE authors_backfill (article_id TEXT PRIMARY KEY) WITHOUT ROWID;
+    INSERT INTO authors_backfill SELECT id FROM articles a
+        WHERE author != '' AND NOT EXISTS (SELECT 1 FROM article_authors aa WHERE aa.article_id = a.id);
+    CREATE TRIGGER articles_authors_backfill_delete AFTER DELETE ON articles BEGIN
+        DELETE FROM authors_backfill WHERE article_id = old.id;
+    END;",
+    // Article changes the tantivy index (see `fulltext`) has yet to apply, in order
+    "CREATE TABLE fulltext_pending (
+        seq        INTEGER PRIMARY KEY,
+        article_id TEXT NOT NULL
+    );
+    CREATE TRIGGER articles_fulltext_insert AFTER INSERT ON articles BEGIN
+        INSERT INTO fulltext_pending (article_id) VALUES (new.id);
+    END;
+    CREATE TRIGGER articles_fulltext_update AFTER UPDATE OF title, author, summary, content ON articles
+    WHEN old.title IS NOT new.title OR old.author IS NOT new.author
+      OR old.summary IS NOT new.summary OR old.content IS NOT new.content BEGIN
+        INSERT INTO fulltext_pending (article_id) VALUES (new.id);
+    END;
+    CREATE TRIGGER articles_fulltext_delete AFTER DELETE ON articles BEGIN
+        INSERT INTO fulltext_pending (article_id) VALUES (old.id);
+    END;",
+];
+
+/// Columns a list row needs; the bodies are only read by `get_article_content`.
+pub(crate) const ROW_COLUMNS: &str = "id, feed_id, title, url, author, snippet, thumbnail, published_at,
+    is_read, is_starred, is_hidden, extra, content IS NOT NULL AS has_content,
+    (SELECT json_group_array(tag) FROM article_tags t WHERE t.article_id = articles.id) AS tags,
+    duplicate_of, (SELECT COUNT(*) FROM articles dup WHERE dup.duplicate_of = articles.id) AS duplicates,
+    EXISTS (SELECT 1 FROM article_authors aa JOIN authors au ON au.id = aa.author_id
+            WHERE aa.article_id = articles.id AND au.followed = 1) AS by_followed_author";
+
+/// An article row. `extra` carries frontend-only fields as opaque JSON.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DbArticle {
+    pub id: String,
+    pub feed_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub summary: String,
+    /// Plain-text list preview, computed from summary/content on upsert.
+    #[serde(default)]
+    pub snippet: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+    /// ms since epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<i64>,
+    #[serde(default)]
+    pub is_read: bool,
+    #[serde(default)]
+    pub is_starred: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Value>,
+}
+
+/// What the article list shows: everything except the bodies.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ArticleRow {
+    pub id: String,
+    pub feed_id: String,
+    pub title: String,
+    pub url: String,
+    pub author: String,
+    pub snippet: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<i64>,
+    pub is_read: bool,
+    pub is_starred: bool,
+    /// Whether the feed carried full content beyond the summary.
+    pub has_content: bool,
+    /// Written by a followed author, for highlighting.
+    #[serde(default)]
+    pub by_followed_author: bool,
+    /// Hidden by a filter rule; only listed when asked for.
+    #[serde(default)]
+    pub is_hidden: bool,
+    /// Tags given by filter rules.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The first copy of this story, when this is a later one (see `dedup`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+    /// Later copies of this story from other feeds.
+    #[serde(default)]
+    pub duplicates: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Value>,
+}
+
+/// The heavy part of an article, fetched when it is opened.
+#[derive(Clone, Serialize, Debug)]
+pub struct ArticleContent {
+    pub id: String,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Images, video and audio found at ingest, for the gallery strip.
+    pub media: Vec<GalleryMedia>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct ArticleQuery {
+    #[serde(default)]
+    pub feed_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub unread_only: bool,
+    #[serde(default)]
+    pub starred_only: bool,
+    /// Inclusive lower bound on `published_at` (ms).
+    #[serde(default)]
+    pub since: Option<i64>,
+    #[serde(default)]
+    pub until: Option<i64>,
+    /// Full-text match on title, author and body. Case, accents and (with
+    /// transliteration on) script are ignored; every word matches as a prefix.
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Only articles by this author (an `authors::Author` id), across feeds.
+    #[serde(default)]
+    pub author_id: Option<String>,
+    /// Only articles a filter rule gave this tag.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Also list articles hidden by filter rules.
+    #[serde(default)]
+    pub include_hidden: bool,
+    /// List later copies of a story even when duplicates are collapsed.
+    #[serde(default)]
+    pub include_duplicates: bool,
+    #[serde(default)]
+    pub oldest_first: bool,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DbFeed {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_fetched_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Folder path, nested levels separated by `/`. Empty = top level.
+    #[serde(default)]
+    pub folder: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+}
+
+// ── Database ─────────────────────────────────────────────────────────
+
+pub struct Database {
+    conn: Mutex<Option<Connection>>,
+}
+
+impl Database {
+    pub fn new() -> Self {
+        Database { conn: Mutex::new(None) }
+    }
+
+    pub fn open(&self, dir: PathBuf) -> Result<(), String> {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create data dir: {e}"))?;
+        let conn = Connection::open(dir.join(DB_FILE)).map_err(|e| format!("Failed to open database: {e}"))?;
+        conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL; PRAGMA foreign_keys = ON;")
+            .map_err(|e| format!("Failed to configure database: {e}"))?;
+        migrate(&conn)?;
+        *self.conn.lock().unwrap() = Some(conn);
+        Ok(())
+    }
+
+    /// Run `f` with the connection. Callers go through `run` so queries stay off the async runtime.
+    pub fn with<T>(&self, f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+        let mut guard = self.conn.lock().unwrap();
+        let conn = guard.as_mut().ok_or("Database is not open")?;
+        f(conn).map_err(|e| format!("Database error: {e}"))
+    }
+}
+
+type BackfillBatch = fn(&mut Connection, usize) -> rusqlite::Result<usize>;
+
+/// Index articles stored before the search index, link graph, authors or
+/// duplicate groups existed, on a background thread. Each backfill runs in
+/// batches that release the database in between, so launch isn't held up and
+/// queries return partial results until it's done.
+pub fn start_backfills(db: Arc<Database>) {
+    let backfills: [(&str, BackfillBatch); 4] = [
+        ("search", search::backfill_batch),
+        ("links", links::backfill_batch),
+        ("authors", authors::backfill_batch),
+        ("dedup", dedup::backfill_batch),
+    ];
+    std::thread::spawn(move || {
+        for (name, batch) in backfills {
+            let mut indexed = 0;
+            loop {
+                match db.with(|conn| batch(conn, BACKFILL_BATCH)) {
+                    Ok(0) => break,
+                    Ok(n) => indexed += n,
+                    Err(e) => {
+                        eprintln!("[{name}] Backfill failed: {e}");
+                        break;
+                    }
+                }
+            }
+            if indexed > 0 {
+                eprintln!("[{name}] Backfilled {indexed} existing articles");
+            }
+        }
+    });
+}
+
+fn migrate(conn: &Connection) -> Result<(), String> {
+    let version: usize = conn
+        .query_row("PRAGMA user_version", [], |r| r.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to read schema version: {e}"))? as usize;
+    for (i, sql) in MIGRATIONS.iter().enumerate().skip(version) {
+        eprintln!("[db] Applying migration {}", i + 1);
+        let batch = format!(
+            "BEGIN; {}; PRAGMA user_version = {}; COMMIT;",
+            sql.trim().trim_end_matches(';'),
+            i + 1
+        );
+        conn.execute_batch(&batch)
+            .map_err(|e| format!("Migration {} failed: {e}", i + 1))?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn run<T: Send + 'static>(
+    db: &Arc<Database>,
+    f: impl FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+) -> Result<T, String> {
+    let db = db.clone();
+    tauri::async_runtime::spawn_blocking(move || db.with(f))
+        .await
+        .map_err(|e| format!("Database task failed: {e}"))?
+}
+
+fn article_from_row(row: &Row) -> rusqlite::Result<DbArticle> {
+    let extra: Option<String> = row.get("extra")?;
+    Ok(DbArticle {
+        id: row.get("id")?,
+        feed_id: row.get("feed_id")?,
+        title: row.get("title")?,
+        url: row.get("url")?,
+        author: row.get("author")?,
+        summary: row.get("summary")?,
+        snippet: row.get("snippet")?,
+        content: row.get("content")?,
+        thumbnail: row.get("thumbnail")?,
+        published_at: row.get("published_at")?,
+        is_read: row.get("is_read")?,
+        is_starred: row.get("is_starred")?,
+        extra: extra.and_then(|e| serde_json::from_str(&e).ok()),
+    })
+}
+
+pub(crate) fn row_from_sql(row: &Row) -> rusqlite::Result<ArticleRow> {
+    let extra: Option<String> = row.get("extra")?;
+    Ok(ArticleRow {
+        id: row.get("id")?,
+        feed_id: row.get("feed_id")?,
+        title: row.get("title")?,
+        url: row.get("url")?,
+        author: row.get("author")?,
+        snippet: row.get("snippet")?,
+        thumbnail: row.get("thumbnail")?,
+        published_at: row.get("published_at")?,
+        is_read: row.get("is_read")?,
+        is_starred: row.get("is_starred")?,
+        has_content: row.get("has_content")?,
+        by_followed_author: row.get("by_followed_author")?,
+        is_hidden: row.get("is_hidden")?,
+        tags: row
+            .get::<_, Option<String>>("tags")?
+            .and_then(|t| serde_json::from_str(&t).ok())
+            .unwrap_or_default(),
+        duplicate_of: row.get("duplicate_of")?,
+        duplicates: row.get("duplicates")?,
+        extra: extra.and_then(|e| serde_json::from_str(&e).ok()),
+    })
+}
+
+/// Insert new articles and refresh the content of known ones. Read/starred
+/// flags only ever move from false to true here, so a refresh never resets them.
+/// Articles retention already deleted are skipped. Returns the ids of the new articles.
+pub fn upsert_articles(conn: &mut Connection, articles: &[DbArticle]) -> rusqlite::Result<Vec<String>> {
+    let tx = conn.transaction()?;
+    let mut inserted = Vec::new();
+    {
+        let mut exists = tx.prepare_cached("SELECT 1 FROM articles WHERE id = ?1")?;
+        let mut pruned = tx.prepare_cached("SELECT 1 FROM pruned_articles WHERE id = ?1")?;
+        let mut upsert = tx.prepare_cached(
+            "INSERT INTO articles (id, feed_id, title, url, author, summary, content, thumbnail,
+                                   published_at, fetched_at, is_read, read_at, is_starred, extra,
+                                   snippet, media)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, CASE WHEN ?11 THEN ?10 END, ?12, ?13,
+                     ?14, ?15)
+             ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                url = excluded.url,
+                author = excluded.author,
+                summary = excluded.summary,
+                content = COALESCE(excluded.content, articles.content),
+                thumbnail = COALESCE(excluded.thumbnail, articles.thumbnail),
+                published_at = COALESCE(excluded.published_at, articles.published_at),
+                is_read = MAX(articles.is_read, excluded.is_read),
+                read_at = COALESCE(articles.read_at, excluded.read_at),
+                is_starred = MAX(articles.is_starred, excluded.is_starred),
+                extra = COALESCE(excluded.extra, articles.extra),
+                snippet = CASE WHEN excluded.snippet = '' THEN articles.snippet ELSE excluded.snippet END,
+                media = CASE WHEN excluded.content IS NULL AND articles.content IS NOT NULL
+                             THEN articles.media ELSE excluded.media END",
+        )?;
+        let now = now_millis() as i64;
+        for a in articles {
+            if a.id.is_empty() || pruned.exists([&a.id])? {
+                continue;
+            }
+            let is_new = exists.query_row([&a.id], |_| Ok(())).optional()?.is_none();
+            let source = match a.content.as_deref() {
+                Some(content) if a.summary.trim().is_empty() => content,
+                _ => &a.summary,
+            };
+            let snippet = snippet_from_html(source, SNIPPET_LEN);
+            // Stored copies are read offline, away from the page relative URLs point into
+            let summary = process_article_html(&a.summary, &a.url);
+            let content = a.content.as_deref().map(|c| process_article_html(c, &a.url));
+            // Precomputed so opening an article never re-scans its HTML
+            let body = content.as_deref().unwrap_or(&summary);
+            let media = media_from(&a.url, &[], body, a.thumbnail.as_deref());
+            upsert.execute(params![
+                a.id,
+                a.feed_id,
+                a.title,
+                a.url,
+                a.author,
+                summary,
+                content,
+                a.thumbnail,
+                a.published_at,
+                now,
+                a.is_read,
+                a.is_starred,
+                a.extra.as_ref().map(|e| e.to_string()),
+                snippet,
+                serde_json::to_string(&media).ok(),
+            ])?;
+            // Indexed from the stored row, which keeps its old content when the feed omitted it
+            search::index_stored(&tx, &[a.id.as_str()])?;
+            links::index_stored(&tx, &[a.id.as_str()])?;
+            dedup::index_stored(&tx, &[a.id.as_str()])?;
+            authors::index_stored(&tx, &[a.id.as_str()])?;
+            if is_new {
+                inserted.push(a.id.clone());
+            }
+        }
+    }
+    tx.commit()?;
+    // The triggers queued them for the tantivy index
+    fulltext::notify();
+    Ok(inserted)
+}
+
+pub fn query_articles(conn: &Connection, q: &ArticleQuery) -> rusqlite::Result<Vec<ArticleRow>> {
+    let mut clauses: Vec<String> = Vec::new();
+    let mut args: Vec<SqlValue> = Vec::new();
+
+    if let Some(ref ids) = q.feed_ids {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        clauses.push(format!("feed_id IN ({})", vec!["?"; ids.len()].join(",")));
+        args.extend(ids.iter().map(|id| SqlValue::Text(id.clone())));
+    }
+    if !q.include_hidden {
+        clauses.push("is_hidden = 0".into());
+    }
+    if !q.include_duplicates && dedup::auto_collapse() {
+        clauses.push("duplicate_of IS NULL".into());
+    }
+    if q.unread_only {
+        clauses.push("is_read = 0".into());
+    }
+    if q.starred_only {
+        clauses.push("is_starred = 1".into());
+    }
+    if let Some(since) = q.since {
+        clauses.push("published_at >= ?".into());
+        args.push(SqlValue::Integer(since));
+    }
+    if let Some(until) = q.until {
+        clauses.push("published_at <= ?".into());
+        args.push(SqlValue::Integer(until));
+    }
+    if let Some(expr) = q.search.as_deref().and_then(search::match_expression) {
+        clauses.push(
+            "id IN (SELECT d.article_id FROM search_index JOIN search_docs d ON d.rowid = search_index.rowid
+                    WHERE search_index MATCH ?)"
+                .into(),
+        );
+        args.push(SqlValue::Text(expr));
+    }
+    if let Some(ref author_id) = q.author_id {
+        clauses.push("id IN (SELECT article_id FROM article_authors WHERE author_id = ?)".into());
+        args.push(SqlValue::Text(author_id.clone()));
+    }
+    if let Some(ref tag) = q.tag {
+        clauses.push("id IN (SELECT article_id FROM article_tags WHERE tag = ?)".into());
+        args.push(SqlValue::Text(tag.clone()));
+    }
+
+    let where_sql = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+    let order = if q.oldest_first { "ASC" } else { "DESC" };
+    let sql = format!(
+        "SELECT {ROW_COLUMNS} FROM articles {where_sql}
+         ORDER BY COALESCE(published_at, fetched_at) {order}, id
+         LIMIT {} OFFSET {}",
+        q.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE),
+        q.offset.unwrap_or(0),
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(args), row_from_sql)?;
+    rows.collect()
+}
+
+pub fn article_content(conn: &Connection, id: &str) -> rusqlite::Result<Option<ArticleContent>> {
+    let sql = "SELECT id, url, summary, content, thumbnail, media FROM articles WHERE id = ?1";
+    conn.query_row(sql, [id], |row| {
+        let summary: String = row.get("summary")?;
+        let content: Option<String> = row.get("content")?;
+        let stored: Option<String> = row.get("media")?;
+        // Rows stored before media was precomputed are scanned on first open
+        let media = match stored.and_then(|m| serde_json::from_str(&m).ok()) {
+            Some(media) => media,
+            None => {
+                let url: String = row.get("url")?;
+                let thumbnail: Option<String> = row.get("thumbnail")?;
+                media_from(&url, &[], content.as_deref().unwrap_or(&summary), thumbnail.as_deref())
+            }
+        };
+        Ok(ArticleContent {
+            id: row.get("id")?,
+            summary,
+            content,
+            media,
+        })
+    })
+    .optional()
+}
+
+pub fn set_read(conn: &mut Connection, ids: &[String], read: bool) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let mut changed = 0;
+    {
+        let mut stmt = tx.prepare_cached(
+            "UPDATE articles SET is_read = ?2, read_at = CASE WHEN ?2 THEN COALESCE(read_at, ?3) END
+             WHERE id = ?1 AND is_read != ?2",
+        )?;
+        let now = now_millis() as i64;
+        for id in ids {
+            changed += stmt.execute(params![id, read, now])?;
+        }
+    }
+    tx.commit()?;
+    Ok(changed)
+}
+
+pub fn set_starred(conn: &mut Connection, ids: &[String], starred: bool) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let mut changed = 0;
+    {
+        let mut stmt = tx.prepare_cached("UPDATE articles SET is_starred = ?2 WHERE id = ?1")?;
+        for id in ids {
+            changed += stmt.execute(params![id, starred])?;
+        }
+    }
+    tx.commit()?;
+    Ok(changed)
+}
+
+pub fn set_hidden(conn: &mut Connection, ids: &[String], hidden: bool) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let mut changed = 0;
+    {
+        let mut stmt =
+            tx.prepare_cached("UPDATE articles SET is_hidden = ?2 WHERE id = ?1 AND is_hidden != ?2")?;
+        for id in ids {
+            changed += stmt.execute(params![id, hidden])?;
+        }
+    }
+    tx.commit()?;
+    Ok(changed)
+}
+
+pub fn unread_counts(conn: &Connection) -> rusqlite::Result<HashMap<String, u32>> {
+    let collapsed = if dedup::auto_collapse() { "AND duplicate_of IS NULL" } else { "" };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT feed_id, COUNT(*) FROM articles WHERE is_read = 0 AND is_hidden = 0 {collapsed} GROUP BY feed_id",
+    ))?;
+    let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?;
+    rows.collect()
+}
+
+pub fn list_feeds(conn: &Connection) -> rusqlite::Result<Vec<DbFeed>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, url, title, last_fetched_at, last_error, folder, html_url FROM feeds ORDER BY folder, title",
+    )?;
+    let rows = stmt.query_map([], |r| {
+        Ok(DbFeed {
+            id: r.get(0)?,
+            url: r.get(1)?,
+            title: r.get(2)?,
+            last_fetched_at: r.get(3)?,
+            last_error: r.get(4)?,
+            folder: r.get(5)?,
+            html_url: r.get(6)?,
+        })
+    })?;
+    rows.collect()
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Insert or update articles. Returns how many were new; those go through
+/// the filter rules, then the ones left visible are checked against the
+/// saved searches and followed authors.
+#[tauri::command]
+pub async fn db_upsert_articles(
+    articles: Vec<DbArticle>,
+    db: tauri::State<'_, Arc<Database>>,
+    app: tauri::AppHandle,
+) -> Result<usize, String> {
+    let inserted = run(&db, move |conn| upsert_articles(conn, &articles)).await?;
+    let count = inserted.len();
+    let inserted = crate::filters::apply_rules(&app, inserted).await;
+    // Alerts are delivered in the background so the refresh doesn't wait on push servers
+    tauri::async_runtime::spawn(async move {
+        crate::saved_searches::check_new_articles(app.clone(), inserted.clone()).await;
+        crate::filters::check_keywords(app.clone(), inserted.clone()).await;
+        crate::article_notifications::notify_new_articles(app.clone(), inserted.clone()).await;
+        crate::authors::check_new_articles(app, inserted).await;
+    });
+    Ok(count)
+}
+
+/// Page through articles as list rows. Bodies come from `get_article_content`.
+#[tauri::command]
+pub async fn db_query_articles(
+    query: ArticleQuery,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<ArticleRow>, String> {
+    run(&db, move |conn| query_articles(conn, &query)).await
+}
+
+/// Summary, content and media of one article, loaded when it is opened.
+#[tauri::command]
+pub async fn get_article_content(
+    id: String,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Option<ArticleContent>, String> {
+    run(&db, move |conn| article_content(conn, &id)).await
+}
+
+#[tauri::command]
+pub async fn db_get_article(id: String, db: tauri::State<'_, Arc<Database>>) -> Result<Option<DbArticle>, String> {
+    run(&db, move |conn| {
+        conn.query_row("SELECT * FROM articles WHERE id = ?1", [&id], article_from_row)
+            .optional()
+    })
+    .await
+}
+
+/// Mark articles read (or unread). Returns how many changed.
+#[tauri::command]
+pub async fn db_mark_read(
+    ids: Vec<String>,
+    read: Option<bool>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<usize, String> {
+    run(&db, move |conn| set_read(conn, &ids, read.unwrap_or(true))).await
+}
+
+/// Mark every article of a feed read, optionally only those published before `before` (ms).
+#[tauri::command]
+pub async fn db_mark_feed_read(
+    feed_id: String,
+    before: Option<i64>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<usize, String> {
+    run(&db, move |conn| {
+        conn.execute(
+            "UPDATE articles SET is_read = 1, read_at = ?3
+             WHERE feed_id = ?1 AND is_read = 0 AND (?2 IS NULL OR published_at < ?2)",
+            params![feed_id, before, now_millis() as i64],
+        )
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn db_mark_starred(
+    ids: Vec<String>,
+    starred: bool,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<usize, String> {
+    run(&db, move |conn| set_starred(conn, &ids, starred)).await
+}
+
+/// Hide articles from the list, or bring hidden ones back.
+#[tauri::command]
+pub async fn db_mark_hidden(
+    ids: Vec<String>,
+    hidden: bool,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<usize, String> {
+    run(&db, move |conn| set_hidden(conn, &ids, hidden)).await
+}
+
+/// Unread article count per feed id, hidden and collapsed duplicates left out.
+#[tauri::command]
+pub async fn db_get_unread_counts(db: tauri::State<'_, Arc<Database>>) -> Result<HashMap<String, u32>, String> {
+    run(&db, |conn| unread_counts(conn)).await
+}
+
+#[tauri::command]
+pub async fn db_upsert_feed(feed: DbFeed, db: tauri::State<'_, Arc<Database>>) -> Result<(), String> {
+    run(&db, move |conn| {
+        conn.execute(
+            "INSERT INTO feeds (id, url, title, last_fetched_at, last_error, folder, html_url)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(id) DO UPDATE SET url = excluded.url, title = excluded.title,
+                last_fetched_at = COALESCE(excluded.last_fetched_at, feeds.last_fetched_at),
+                last_error = excluded.last_error, folder = excluded.folder,
+                html_url = COALESCE(excluded.html_url, feeds.html_url)",
+            params![
+                feed.id,
+                feed.url,
+                feed.title,
+                feed.last_fetched_at,
+                feed.last_error,
+                feed.folder.trim_matches('/'),
+                feed.html_url
+            ],
+        )
+        .map(|_| ())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn db_get_feeds(db: tauri::State<'_, Arc<Database>>) -> Result<Vec<DbFeed>, String> {
+    run(&db, |conn| list_feeds(conn)).await
+}
+
+/// Remove a feed and all of its articles.
+#[tauri::command]
+pub async fn db_delete_feed(feed_id: String, db: tauri::State<'_, Arc<Database>>) -> Result<usize, String> {
+    run(&db, move |conn| {
+        let tx = conn.transaction()?;
+        let removed = tx.execute("DELETE FROM articles WHERE feed_id = ?1", [&feed_id])?;
+        tx.execute("DELETE FROM pruned_articles WHERE feed_id = ?1", [&feed_id])?;
+        tx.execute("DELETE FROM feeds WHERE id = ?1", [&feed_id])?;
+        tx.commit()?;
+        Ok(removed)
+    })
+    .await
+}
@@ -0,0 +1,244 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::db::{self, ArticleRow, Database};
+use crate::search::fold;
+use crate::text::html_to_text;
+
+// Duplicate detection across feeds. When an article is stored it is compared
+// with earlier ones: the same page (by `links::link_key`) or near-identical
+// text (SimHash of word shingles, within a few bits) makes it a duplicate of
+// the earliest copy, recorded in `articles.duplicate_of`. With auto-collapse
+// on, the article list only shows the first copy and counts the others.
+// Articles from before deduplication existed are hashed in the background.
+
+const SETTINGS_FILE: &str = "dedup_settings.json";
+/// Articles shorter than this (in words) are only matched by URL; SimHash
+/// is too noisy on a headline and a sentence.
+const MIN_WORDS: usize = 25;
+/// Words of the body that go into the hash, so a full article and its
+/// teaser-plus-more copy still compare on the same opening.
+const MAX_WORDS: usize = 300;
+/// Words per shingle; pairs keep a small edit from moving many bits.
+const SHINGLE: usize = 2;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct DedupSettings {
+    /// Hide later copies of a story from the article list and unread counts.
+    pub auto_collapse: bool,
+    /// Differing SimHash bits (of 64) still counted as the same text.
+    pub max_distance: u32,
+    /// Only articles published this close to each other are compared by text.
+    pub window_hours: u32,
+}
+
+impl Default for DedupSettings {
+    fn default() -> Self {
+        DedupSettings { auto_collapse: false, max_distance: 8, window_hours: 72 }
+    }
+}
+
+static SETTINGS: OnceLock<Mutex<DedupSettings>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn settings_lock() -> &'static Mutex<DedupSettings> {
+    SETTINGS.get_or_init(|| Mutex::new(DedupSettings::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(SETTINGS_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(settings) = serde_json::from_str::<DedupSettings>(&json) {
+            *settings_lock().lock().unwrap() = settings;
+        }
+    }
+}
+
+pub fn settings() -> DedupSettings {
+    settings_lock().lock().unwrap().clone()
+}
+
+pub fn auto_collapse() -> bool {
+    settings_lock().lock().unwrap().auto_collapse
+}
+
+// ── SimHash ──────────────────────────────────────────────────────────
+
+/// FNV-1a; stable across builds, unlike std's hasher, as hashes are stored.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ u64::from(*b)).wrapping_mul(0x100_0000_01b3))
+}
+
+/// 64-bit SimHash of the title and opening of the body, or 0 when the text
+/// is too short to compare.
+pub fn simhash(title: &str, body_html: &str) -> u64 {
+    let text = format!("{title} {}", html_to_text(body_html));
+    let folded = fold(&text, false);
+    let words: Vec<&str> =
+        folded.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).take(MAX_WORDS).collect();
+    if words.len() < MIN_WORDS {
+        return 0;
+    }
+    let mut weights = [0i32; 64];
+    for shingle in words.windows(SHINGLE) {
+        let hash = fnv1a(shingle.join(" ").as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            *weight += if hash >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    }
+    // 0 means "not comparable", so a text hashing to it is nudged off
+    weights.iter().enumerate().filter(|(_, w)| **w > 0).fold(0, |hash, (bit, _)| hash | 1 << bit).max(1)
+}
+
+// ── Index maintenance ────────────────────────────────────────────────
+
+/// The copy an article duplicates: an earlier-stored article with the same
+/// link key, or else with a close SimHash. Returns the first copy of its group.
+fn find_original(
+    conn: &Connection,
+    id: &str,
+    hash: u64,
+    settings: &DedupSettings,
+) -> rusqlite::Result<Option<String>> {
+    let (feed_id, link_key, fetched_at, published_at): (String, Option<String>, i64, Option<i64>) = conn
+        .prepare_cached("SELECT feed_id, link_key, fetched_at, published_at FROM articles WHERE id = ?1")?
+        .query_row([id], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))?;
+
+    if let Some(key) = link_key.filter(|k| !k.is_empty()) {
+        let same_page = conn
+            .prepare_cached(
+                "SELECT COALESCE(duplicate_of, id) FROM articles
+                 WHERE link_key = ?1 AND id != ?2 AND (fetched_at, id) < (?3, ?2)
+                 ORDER BY fetched_at, id LIMIT 1",
+            )?
+            .query_row(params![key, id, fetched_at], |r| r.get(0))
+            .optional()?;
+        if same_page.is_some() {
+            return Ok(same_page);
+        }
+    }
+    if hash == 0 {
+        return Ok(None);
+    }
+
+    // Similar text within one feed is usually a template (episode notes,
+    // changelogs), not the same story, so only other feeds are compared
+    let at = published_at.unwrap_or(fetched_at);
+    let window = i64::from(settings.window_hours) * 3_600_000;
+    let mut stmt = conn.prepare_cached(
+        "SELECT COALESCE(duplicate_of, id), simhash FROM articles
+         WHERE simhash IS NOT NULL AND simhash != 0 AND id != ?1 AND (fetched_at, id) < (?2, ?1)
+           AND COALESCE(published_at, fetched_at) BETWEEN ?3 AND ?4 AND feed_id != ?5",
+    )?;
+    let candidates = stmt.query_map(params![id, fetched_at, at - window, at + window, feed_id], |r| {
+        Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)? as u64))
+    })?;
+    let mut best: Option<(u32, String)> = None;
+    for candidate in candidates {
+        let (original, other) = candidate?;
+        let distance = (hash ^ other).count_ones();
+        if distance <= settings.max_distance && best.as_ref().is_none_or(|(d, _)| distance < *d) {
+            best = Some((distance, original));
+        }
+    }
+    Ok(best.map(|(_, original)| original))
+}
+
+/// Hash the articles with these ids from what is stored for them, and link
+/// each to its original if it is a duplicate. Expects `links::index_stored`
+/// to have set their link keys. Articles that already are an original, or
+/// already point to one, keep their place.
+pub fn index_stored(conn: &Connection, ids: &[&str]) -> rusqlite::Result<()> {
+    let settings = settings();
+    let mut select = conn.prepare_cached(
+        "SELECT title, COALESCE(content, summary), duplicate_of IS NOT NULL
+                OR EXISTS (SELECT 1 FROM articles d WHERE d.duplicate_of = articles.id)
+         FROM articles WHERE id = ?1",
+    )?;
+    let mut set_hash = conn.prepare_cached("UPDATE articles SET simhash = ?2 WHERE id = ?1")?;
+    let mut set_original = conn.prepare_cached("UPDATE articles SET duplicate_of = ?2 WHERE id = ?1")?;
+    for id in ids {
+        let (title, body, grouped): (String, String, bool) =
+            select.query_row([id], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?;
+        let hash = simhash(&title, &body);
+        set_hash.execute(params![id, hash as i64])?;
+        if grouped {
+            continue;
+        }
+        if let Some(original) = find_original(conn, id, hash, &settings)? {
+            set_original.execute(params![id, original])?;
+        }
+    }
+    Ok(())
+}
+
+/// Hash and group up to `limit` articles stored before deduplication existed,
+/// oldest first so the earliest copy becomes the original. Returns how many
+/// were checked.
+pub fn backfill_batch(conn: &mut Connection, limit: usize) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let ids: Vec<String> = tx
+        .prepare("SELECT id FROM articles WHERE simhash IS NULL ORDER BY fetched_at, id LIMIT ?1")?
+        .query_map([limit as i64], |r| r.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    index_stored(&tx, &ids)?;
+    tx.commit()?;
+    Ok(ids.len())
+}
+
+// ── Queries ──────────────────────────────────────────────────────────
+
+/// The other copies of an article's story, first copy first.
+pub fn duplicates(conn: &Connection, id: &str) -> rusqlite::Result<Vec<ArticleRow>> {
+    let sql = format!(
+        "SELECT {} FROM articles
+         WHERE COALESCE(duplicate_of, id) = (SELECT COALESCE(duplicate_of, id) FROM articles WHERE id = ?1)
+           AND id != ?1
+         ORDER BY fetched_at, id",
+        db::ROW_COLUMNS
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let rows = stmt.query_map([id], db::row_from_sql)?;
+    rows.collect()
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Stored articles carrying the same story as `article_id`, from other feeds
+/// or reposts. Empty when it has no duplicates.
+#[tauri::command]
+pub async fn duplicates_of(
+    article_id: String,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<ArticleRow>, String> {
+    db::run(&db, move |conn| duplicates(conn, &article_id)).await
+}
+
+#[tauri::command]
+pub fn get_dedup_settings() -> DedupSettings {
+    settings()
+}
+
+/// Save the settings. Matching thresholds apply to articles stored from now on.
+#[tauri::command]
+pub fn set_dedup_settings(settings: DedupSettings) -> Result<(), String> {
+    if !(1..=16).contains(&settings.max_distance) {
+        return Err("Distance must be between 1 and 16 bits".into());
+    }
+    if settings.window_hours == 0 {
+        return Err("Window must be at least an hour".into());
+    }
+    if let Some(dir) = DATA_DIR.get() {
+        let json = serde_json::to_string_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize dedup settings: {e}"))?;
+        std::fs::write(dir.join(SETTINGS_FILE), json)
+            .map_err(|e| format!("Failed to save dedup settings: {e}"))?;
+    }
+    *settings_lock().lock().unwrap() = settings;
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use scraper::{Html, Selector};
+use serde::Serialize;
+use std::collections::HashSet;
+use url::Url;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+/// Paths tried when a page advertises no feed.
+const COMMON_PATHS: &[&str] = &[
+    "/feed",
+    "/rss",
+    "/feed.xml",
+    "/rss.xml",
+    "/atom.xml",
+    "/index.xml",
+    "/feed.json",
+    "/?feed=rss2",
+];
+
+#[derive(Clone, Serialize, Debug)]
+pub struct FeedCandidate {
+    pub url: String,
+    pub title: String,
+    /// `rss`, `atom` or `json`, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// How it was found: `direct` (the URL is a feed), `link`, `probe` or `youtube`.
+    pub source: String,
+}
+
+// ── Discovery ────────────────────────────────────────────────────────
+
+fn kind_from_mime(mime: &str) -> Option<&'static str> {
+    let mime = mime.split(';').next().unwrap_or("").trim().to_lowercase();
+    match mime.as_str() {
+        "application/rss+xml" | "application/rdf+xml" => Some("rss"),
+        "application/atom+xml" => Some("atom"),
+        // Plain application/json is also WordPress' REST API link, so it is not enough
+        "application/feed+json" => Some("json"),
+        _ => None,
+    }
+}
+
+/// Cheap sniff before handing the body to the full parser.
+fn looks_like_feed(body: &str) -> bool {
+    let head: String = body.trim_start().chars().take(512).collect::<String>().to_lowercase();
+    (head.starts_with("<?xml") && !head.contains("<html"))
+        || head.starts_with("<rss")
+        || head.starts_with("<feed")
+        || head.starts_with("<rdf")
+        || (head.starts_with('{') && head.contains("jsonfeed.org"))
+}
+
+/// `<link rel="alternate">` feeds declared by an HTML page.
+fn links_from_html(html: &str, page_url: &Url) -> Vec<FeedCandidate> {
+    let doc = Html::parse_document(html);
+    let base = Selector::parse("base[href]")
+        .ok()
+        .and_then(|s| doc.select(&s).next().and_then(|b| b.value().attr("href")).map(str::to_string))
+        .and_then(|href| page_url.join(&href).ok())
+        .unwrap_or_else(|| page_url.clone());
+    let page_title = Selector::parse("title")
+        .ok()
+        .and_then(|s| doc.select(&s).next().map(|t| t.text().collect::<String>().trim().to_string()))
+        .filter(|t| !t.is_empty());
+    let Ok(link_sel) = Selector::parse("link[href]") else {
+        return Vec::new();
+    };
+
+    doc.select(&link_sel)
+        .filter_map(|link| {
+            let el = link.value();
+            let rel = el.attr("rel").unwrap_or("").to_lowercase();
+            if !rel.split_whitespace().any(|r| r == "alternate" || r == "feed") {
+                return None;
+            }
+            let mime = el.attr("type").unwrap_or("");
+            let kind = kind_from_mime(mime);
+            // rel="alternate" is also used for translations; only typed links are feeds
+            if kind.is_none() && !rel.split_whitespace().any(|r| r == "feed") {
+                return None;
+            }
+            let url = base.join(el.attr("href")?.trim()).ok()?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return None;
+            }
+            let title = el
+                .attr("title")
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .or_else(|| page_title.clone())
+                .unwrap_or_else(|| url.to_string());
+            Some(FeedCandidate {
+                url: url.to_string(),
+                title,
+                kind: kind.map(str::to_string),
+                source: "link".into(),
+            })
+        })
+        .collect()
+}
+
+fn kind_of(body: &str) -> &'static str {
+    let head = body.trim_start();
+    if head.starts_with('{') {
+        "json"
+    } else if head.chars().take(1024).collect::<String>().contains("<feed") {
+        "atom"
+    } else {
+        "rss"
+    }
+}
+
+/// Fetch a URL and return it as a candidate if it parses as a feed.
+async fn probe(url: String) -> Option<FeedCandidate> {
+    let response = crate::send_get(&url, HeaderMap::new()).await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let final_url = response.url().to_string();
+    let body = crate::read_body(&url, response).await.ok()?;
+    if !looks_like_feed(&body) {
+        return None;
+    }
+    let parsed = crate::feed_parser::parse_body(&body, &final_url, None).ok()?;
+    Some(FeedCandidate {
+        title: if parsed.title.is_empty() { final_url.clone() } else { parsed.title },
+        kind: Some(kind_of(&body).into()),
+        url: final_url,
+        source: "probe".into(),
+    })
+}
+
+pub async fn discover(input: &str) -> Result<Vec<FeedCandidate>, String> {
+    let input = input.trim();
+    let with_scheme = if input.contains("://") { input.to_string() } else { format!("https://{input}") };
+    let url = Url::parse(&with_scheme).map_err(|e| format!("Invalid URL: {e}"))?;
+
+    // YouTube pages advertise no feed except on channel pages; work it out from the page
+    if url.host_str().is_some_and(crate::youtube::is_youtube_host) {
+        let feed = crate::youtube::resolve(url.as_str()).await?;
+        return Ok(vec![FeedCandidate {
+            title: feed.title.unwrap_or_else(|| feed.url.clone()),
+            url: feed.url,
+            kind: Some("atom".into()),
+            source: "youtube".into(),
+        }]);
+    }
+
+    let response = crate::send_get(url.as_str(), HeaderMap::new()).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    let page_url = response.url().clone();
+    let mime = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    let body = crate::read_body(page_url.as_str(), response).await?;
+
+    // The URL may already be a feed
+    if kind_from_mime(&mime).is_some() || mime.contains("xml") || looks_like_feed(&body) {
+        if let Ok(parsed) = crate::feed_parser::parse_body(&body, page_url.as_str(), None) {
+            return Ok(vec![FeedCandidate {
+                url: page_url.to_string(),
+                title: if parsed.title.is_empty() { page_url.to_string() } else { parsed.title },
+                kind: Some(kind_of(&body).into()),
+                source: "direct".into(),
+            }]);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut candidates: Vec<FeedCandidate> = links_from_html(&body, &page_url)
+        .into_iter()
+        .filter(|c| seen.insert(c.url.clone()))
+        .collect();
+    if !candidates.is_empty() {
+        return Ok(candidates);
+    }
+
+    // Nothing advertised: try the usual locations on the site root concurrently
+    let probes: Vec<_> = COMMON_PATHS
+        .iter()
+        .filter_map(|p| page_url.join(p).ok())
+        .map(|u| tauri::async_runtime::spawn(probe(u.to_string())))
+        .collect();
+    for handle in probes {
+        if let Ok(Some(c)) = handle.await {
+            if seen.insert(c.url.clone()) {
+                candidates.push(c);
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Find the feeds behind a web page: the page itself if it is a feed, its
+/// `<link rel="alternate">` tags, or else common feed paths on the site.
+#[tauri::command]
+pub async fn discover_feeds(url: String) -> Result<Vec<FeedCandidate>, String> {
+    discover(&url).await
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use tauri::{PhysicalPosition, PhysicalSize};
+
+use crate::{AppState, COLLAPSED_HEIGHT};
+
+// Docked ticker mode: the collapsed bar snapped to the top or bottom edge of
+// its monitor, spanning the full width. On Windows the bar can also reserve
+// its strip as an AppBar, like the taskbar does, so maximized windows stop
+// short of it instead of covering it. `expand_window` undocks.
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DockEdge {
+    Top,
+    Bottom,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Docked {
+    pub edge: DockEdge,
+    /// Registered as an AppBar, to be removed on undock.
+    pub reserved: bool,
+}
+
+#[cfg(target_os = "windows")]
+mod appbar {
+    use super::DockEdge;
+
+    #[repr(C)]
+    struct Rect {
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    }
+
+    #[repr(C)]
+    struct AppBarData {
+        cb_size: u32,
+        hwnd: isize,
+        callback_message: u32,
+        edge: u32,
+        rc: Rect,
+        lparam: isize,
+    }
+
+    #[link(name = "shell32")]
+    extern "system" {
+        fn SHAppBarMessage(message: u32, data: *mut AppBarData) -> usize;
+    }
+
+    const ABM_NEW: u32 = 0;
+    const ABM_REMOVE: u32 = 1;
+    const ABM_QUERYPOS: u32 = 2;
+    const ABM_SETPOS: u32 = 3;
+    const ABE_TOP: u32 = 1;
+    const ABE_BOTTOM: u32 = 3;
+    /// WM_APP + 0x51; the shell's position notices are posted with it and ignored.
+    const CALLBACK_MESSAGE: u32 = 0x8051;
+
+    fn data(hwnd: isize) -> AppBarData {
+        AppBarData {
+            cb_size: std::mem::size_of::<AppBarData>() as u32,
+            hwnd,
+            callback_message: CALLBACK_MESSAGE,
+            edge: 0,
+            rc: Rect { left: 0, top: 0, right: 0, bottom: 0 },
+            lparam: 0,
+        }
+    }
+
+    /// Register the window as an AppBar on `edge` of the monitor spanning
+    /// `left..right` and `top..bottom`, `height` tall. Returns the strip the
+    /// shell granted as (x, y, width, height), below or above other AppBars.
+    pub fn reserve(
+        hwnd: isize,
+        edge: DockEdge,
+        (left, top, right, bottom): (i32, i32, i32, i32),
+        height: i32,
+    ) -> Result<(i32, i32, u32, u32), String> {
+        let mut abd = data(hwnd);
+        // Registering twice fails; an earlier registration is fine to reuse
+        unsafe { SHAppBarMessage(ABM_NEW, &mut abd) };
+        abd.edge = if edge == DockEdge::Top { ABE_TOP } else { ABE_BOTTOM };
+        abd.rc = Rect { left, top, right, bottom };
+        unsafe { SHAppBarMessage(ABM_QUERYPOS, &mut abd) };
+        // The shell moved the proposed edge past other bars; size the strip from it
+        match edge {
+            DockEdge::Top => abd.rc.bottom = abd.rc.top + height,
+            DockEdge::Bottom => abd.rc.top = abd.rc.bottom - height,
+        }
+        if unsafe { SHAppBarMessage(ABM_SETPOS, &mut abd) } == 0 {
+            release(hwnd);
+            return Err("The shell refused the AppBar position".into());
+        }
+        let rc = &abd.rc;
+        Ok((rc.left, rc.top, (rc.right - rc.left).max(0) as u32, (rc.bottom - rc.top).max(0) as u32))
+    }
+
+    pub fn release(hwnd: isize) {
+        let mut abd = data(hwnd);
+        unsafe { SHAppBarMessage(ABM_REMOVE, &mut abd) };
+    }
+}
+
+/// Give back the reserved strip, if any, and forget the dock. The window
+/// keeps its geometry; `expand_window` restores it.
+pub fn undock(window: &tauri::WebviewWindow, state: &AppState) {
+    let Some(docked) = state.docked.lock().unwrap().take() else { return };
+    #[cfg(target_os = "windows")]
+    if docked.reserved {
+        if let Ok(hwnd) = window.hwnd() {
+            appbar::release(hwnd.0 as isize);
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = window;
+    eprintln!("[dock] undocked from {:?}", docked.edge);
+}
+
+/// Collapse the window if needed, then stretch the bar along `edge` of its
+/// current monitor. With `reserve` (Windows only) the strip becomes an AppBar.
+pub fn dock(
+    window: &tauri::WebviewWindow,
+    state: &AppState,
+    edge: DockEdge,
+    reserve: bool,
+) -> Result<(), String> {
+    if state.saved.lock().unwrap().is_none() {
+        crate::collapse(window, state)?;
+    }
+    undock(window, state);
+    let monitor = window
+        .current_monitor()
+        .map_err(|e| format!("current_monitor: {e}"))?
+        .ok_or_else(|| "The window is not on any monitor".to_string())?;
+    let height = (COLLAPSED_HEIGHT * monitor.scale_factor()).round() as u32;
+
+    let area = monitor.work_area();
+    let (x, width) = (area.position.x, area.size.width);
+    let y = match edge {
+        DockEdge::Top => area.position.y,
+        DockEdge::Bottom => area.position.y + area.size.height as i32 - height as i32,
+    };
+    let rect = (x, y, width, height);
+
+    #[cfg(target_os = "windows")]
+    let rect = if reserve {
+        let hwnd = window.hwnd().map_err(|e| format!("hwnd: {e}"))?.0 as isize;
+        let (origin, size) = (monitor.position(), monitor.size());
+        let bounds = (origin.x, origin.y, origin.x + size.width as i32, origin.y + size.height as i32);
+        appbar::reserve(hwnd, edge, bounds, height as i32)?
+    } else {
+        rect
+    };
+    #[cfg(not(target_os = "windows"))]
+    if reserve {
+        eprintln!("[dock] Reserving screen space is only supported on Windows");
+    }
+
+    let (x, y, width, height) = rect;
+    window
+        .set_size(PhysicalSize::new(width, height))
+        .map_err(|e| format!("set_size: {e}"))?;
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|e| format!("set_position: {e}"))?;
+    *state.docked.lock().unwrap() = Some(Docked { edge, reserved: reserve && cfg!(target_os = "windows") });
+    eprintln!("[dock] docked to {edge:?} at ({x}, {y}), {width}x{height}");
+    Ok(())
+}
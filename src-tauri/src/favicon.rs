@@ -0,0 +1,206 @@
+use base64::Engine;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use scraper::{Html, Selector};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+// Site icons for the feed list, fetched natively because the webview can't
+// read cross-origin images. Tried in order: /favicon.ico, the page's
+// <link rel="icon"> tags, then Google's favicon service. Icons are
+// normalized to a small PNG and cached on disk per host.
+
+/// Icons are downscaled to fit this many pixels (2x the feed list's 16px).
+const ICON_SIZE: u32 = 32;
+/// Cached icons are fetched again after this long.
+const ICON_TTL: Duration = Duration::from_secs(30 * 24 * 3600);
+/// Hosts without an icon are not asked again before this.
+const MISS_TTL: Duration = Duration::from_secs(24 * 3600);
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn init(dir: PathBuf) {
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("[favicon] Failed to create cache dir: {e}");
+        return;
+    }
+    let _ = CACHE_DIR.set(dir);
+}
+
+fn cache_dir() -> Result<&'static PathBuf, String> {
+    CACHE_DIR.get().ok_or_else(|| "Favicon cache is not initialized".to_string())
+}
+
+/// File name stem for a host, e.g. `example.com_8080`.
+fn key_for(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let key = match url.port() {
+        Some(port) => format!("{host}_{port}"),
+        None => host,
+    };
+    key.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect()
+}
+
+fn age(path: &Path) -> Option<Duration> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+fn data_url(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png))
+}
+
+/// `example.com`, `https://example.com/blog` → `https://example.com/`.
+fn site_root(site_url: &str) -> Result<Url, String> {
+    let trimmed = site_url.trim();
+    let with_scheme = if trimmed.contains("://") { trimmed.to_string() } else { format!("https://{trimmed}") };
+    let mut url = Url::parse(&with_scheme).map_err(|e| format!("Invalid site URL: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err("Only http(s) sites have favicons".into());
+    }
+    url.set_path("/");
+    url.set_query(None);
+    url.set_fragment(None);
+    Ok(url)
+}
+
+// ── Fetching ─────────────────────────────────────────────────────────
+
+/// Decode any supported format and re-encode as a PNG of at most ICON_SIZE.
+/// ICO files decode to their largest entry.
+fn normalize(bytes: &[u8]) -> Option<Vec<u8>> {
+    let decoded = image::load_from_memory(bytes).ok()?;
+    if decoded.width() == 0 || decoded.height() == 0 {
+        return None;
+    }
+    let icon = if decoded.width() > ICON_SIZE || decoded.height() > ICON_SIZE {
+        decoded.resize(ICON_SIZE, ICON_SIZE, FilterType::Lanczos3)
+    } else {
+        decoded
+    };
+    let mut out = Vec::new();
+    icon.write_with_encoder(PngEncoder::new(&mut out)).ok()?;
+    Some(out)
+}
+
+async fn fetch_icon(url: &str) -> Option<Vec<u8>> {
+    let (bytes, _) = crate::image_cache::download(url, None).await.ok()?;
+    tauri::async_runtime::spawn_blocking(move || normalize(&bytes)).await.ok()?
+}
+
+/// Icons declared by the page, best first: the smallest one at least
+/// ICON_SIZE wide, then smaller ones. SVG is skipped as it can't be decoded.
+fn icon_links(html: &str, page_url: &Url) -> Vec<String> {
+    let doc = Html::parse_document(html);
+    let base = Selector::parse("base[href]")
+        .ok()
+        .and_then(|s| doc.select(&s).next().and_then(|b| b.value().attr("href")).map(str::to_string))
+        .and_then(|href| page_url.join(&href).ok())
+        .unwrap_or_else(|| page_url.clone());
+    let Ok(link_sel) = Selector::parse("link[rel][href]") else {
+        return Vec::new();
+    };
+
+    let mut icons: Vec<(u32, String)> = doc
+        .select(&link_sel)
+        .filter_map(|link| {
+            let el = link.value();
+            let rel = el.attr("rel").unwrap_or_default().to_ascii_lowercase();
+            let touch = rel.contains("apple-touch-icon");
+            if !touch && !rel.split_whitespace().any(|r| r == "icon") {
+                return None;
+            }
+            let url = base.join(el.attr("href")?.trim()).ok()?;
+            let svg = el.attr("type").is_some_and(|t| t.contains("svg")) || url.path().ends_with(".svg");
+            if svg || !matches!(url.scheme(), "http" | "https") {
+                return None;
+            }
+            // "16x16 32x32" → 32; touch icons are 180 unless stated
+            let size = el
+                .attr("sizes")
+                .into_iter()
+                .flat_map(str::split_whitespace)
+                .filter_map(|s| s.to_ascii_lowercase().split_once('x')?.0.parse::<u32>().ok())
+                .max()
+                .unwrap_or(if touch { 180 } else { 0 });
+            Some((size, url.to_string()))
+        })
+        .collect();
+    icons.sort_by_key(|(size, _)| (*size < ICON_SIZE, size.abs_diff(ICON_SIZE)));
+    icons.dedup_by(|a, b| a.1 == b.1);
+    icons.into_iter().map(|(_, url)| url).collect()
+}
+
+async fn from_link_tags(root: &Url) -> Option<Vec<u8>> {
+    let page = crate::fetch_document(root.as_str()).await.ok()?;
+    let page_url = Url::parse(&page.url).unwrap_or_else(|_| root.clone());
+    for link in icon_links(&page.body, &page_url) {
+        if let Some(icon) = fetch_icon(&link).await {
+            return Some(icon);
+        }
+    }
+    None
+}
+
+async fn from_google(root: &Url) -> Option<Vec<u8>> {
+    let host = root.host_str()?;
+    // Intranet and local hosts are not worth telling Google about
+    if !host.contains('.') || host.parse::<std::net::IpAddr>().is_ok() || host.ends_with(".local") {
+        return None;
+    }
+    fetch_icon(&format!("https://www.google.com/s2/favicons?domain={host}&sz=64")).await
+}
+
+async fn find_icon(root: &Url) -> Option<Vec<u8>> {
+    if let Some(icon) = fetch_icon(root.join("/favicon.ico").ok()?.as_str()).await {
+        return Some(icon);
+    }
+    if let Some(icon) = from_link_tags(root).await {
+        return Some(icon);
+    }
+    from_google(root).await
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// The site's icon as a PNG data URL, at most 32px. Fails when the site has
+/// none; that answer is also cached for a day.
+#[tauri::command]
+pub async fn get_favicon(site_url: String) -> Result<String, String> {
+    let root = site_root(&site_url)?;
+    let dir = cache_dir()?;
+    let key = key_for(&root);
+    let icon_path = dir.join(format!("{key}.png"));
+    let miss_path = dir.join(format!("{key}.miss"));
+
+    let cached = std::fs::read(&icon_path).ok();
+    if let Some(png) = cached.as_ref().filter(|_| age(&icon_path).is_some_and(|a| a < ICON_TTL)) {
+        return Ok(data_url(png));
+    }
+    if cached.is_none() && age(&miss_path).is_some_and(|a| a < MISS_TTL) {
+        return Err("No favicon found".into());
+    }
+
+    match find_icon(&root).await {
+        Some(png) => {
+            if let Err(e) = std::fs::write(&icon_path, &png) {
+                eprintln!("[favicon] Failed to cache icon for {key}: {e}");
+            }
+            let _ = std::fs::remove_file(&miss_path);
+            Ok(data_url(&png))
+        }
+        // A stale icon beats none when the refresh fails; rewriting it waits out another TTL
+        None => match cached {
+            Some(png) => {
+                let _ = std::fs::write(&icon_path, &png);
+                Ok(data_url(&png))
+            }
+            None => {
+                let _ = std::fs::write(&miss_path, b"");
+                Err("No favicon found".into())
+            }
+        },
+    }
+}
@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+use crate::permissions::{host_permission, PermissionStore, Principal, PrincipalKind};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const HOOKS_FILE: &str = "feed_hooks.json";
+const HOOKS_WORKDIR: &str = "hook_runs";
+const MAX_TIMEOUT_SECS: u64 = 300;
+const MAX_OUTPUT_KB: usize = 16 * 1024;
+const STDERR_CAP: usize = 4096;
+
+/// An external command whose stdout replaces the HTTP fetch for a feed
+/// (e.g. an RSS-Bridge script or a custom scraper).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FeedHook {
+    pub feed_url: String,
+    /// Executable path. Run directly, never through a shell.
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_timeout")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_max_output")]
+    pub max_output_kb: usize,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_timeout() -> u64 {
+    30
+}
+
+fn default_max_output() -> usize {
+    4096
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct FeedHookStore {
+    hooks: Mutex<Vec<FeedHook>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl FeedHookStore {
+    pub fn new() -> Self {
+        FeedHookStore {
+            hooks: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(HOOKS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(hooks) = serde_json::from_str::<Vec<FeedHook>>(&json) {
+                            eprintln!("[feed_hooks] Loaded {} hooks", hooks.len());
+                            *self.hooks.lock().unwrap() = hooks;
+                        }
+                    }
+                    Err(e) => eprintln!("[feed_hooks] Failed to read hooks: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let hooks = self.hooks.lock().unwrap();
+            match serde_json::to_string_pretty(&*hooks) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[feed_hooks] Failed to write hooks: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[feed_hooks] Failed to serialize hooks: {e}"),
+            }
+        }
+    }
+
+    pub fn get_hooks(&self) -> Vec<FeedHook> {
+        self.hooks.lock().unwrap().clone()
+    }
+
+    /// The enabled hook for a feed, if any.
+    pub fn hook_for(&self, feed_url: &str) -> Option<FeedHook> {
+        self.hooks
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|h| h.enabled && h.feed_url == feed_url)
+            .cloned()
+    }
+
+    /// Insert or replace the hook for `hook.feed_url`.
+    pub fn upsert(&self, mut hook: FeedHook) -> FeedHook {
+        hook.timeout_secs = hook.timeout_secs.clamp(1, MAX_TIMEOUT_SECS);
+        hook.max_output_kb = hook.max_output_kb.clamp(1, MAX_OUTPUT_KB);
+        {
+            let mut hooks = self.hooks.lock().unwrap();
+            match hooks.iter_mut().find(|h| h.feed_url == hook.feed_url) {
+                Some(existing) => *existing = hook.clone(),
+                None => hooks.push(hook.clone()),
+            }
+        }
+        self.save_to_disk();
+        hook
+    }
+
+    pub fn delete(&self, feed_url: &str) -> bool {
+        let mut hooks = self.hooks.lock().unwrap();
+        let before = hooks.len();
+        hooks.retain(|h| h.feed_url != feed_url);
+        let removed = hooks.len() < before;
+        drop(hooks);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+
+    fn work_dir(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(HOOKS_WORKDIR))
+    }
+}
+
+// ── Process runner ───────────────────────────────────────────────────
+
+fn read_capped(mut pipe: impl Read, cap: usize) -> (Vec<u8>, bool) {
+    let mut buf = Vec::new();
+    let _ = pipe.by_ref().take(cap as u64 + 1).read_to_end(&mut buf);
+    let overflow = buf.len() > cap;
+    buf.truncate(cap);
+    (buf, overflow)
+}
+
+/// Run a hook with a cleared environment, no stdin, a private working
+/// directory, a hard timeout and capped output. The feed URL is passed as
+/// `SUPERFLUX_FEED_URL`.
+pub fn run_hook(hook: &FeedHook, work_dir: Option<PathBuf>) -> Result<String, String> {
+    let mut cmd = Command::new(&hook.program);
+    cmd.args(&hook.args)
+        .env_clear()
+        .env("SUPERFLUX_FEED_URL", &hook.feed_url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // Keep only what executables need to start
+    for var in ["PATH", "SYSTEMROOT", "TEMP", "TMP", "LANG"] {
+        if let Ok(value) = std::env::var(var) {
+            cmd.env(var, value);
+        }
+    }
+    if let Some(dir) = work_dir {
+        let _ = std::fs::create_dir_all(&dir);
+        cmd.current_dir(dir);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start '{}': {e}", hook.program))?;
+
+    let cap = hook.max_output_kb * 1024;
+    let stdout = child.stdout.take().ok_or("No stdout pipe")?;
+    let stderr = child.stderr.take().ok_or("No stderr pipe")?;
+    let out_reader = std::thread::spawn(move || read_capped(stdout, cap));
+    let err_reader = std::thread::spawn(move || read_capped(stderr, STDERR_CAP));
+
+    let deadline = Instant::now() + Duration::from_secs(hook.timeout_secs);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Hook timed out after {}s", hook.timeout_secs));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("Failed to wait for hook: {e}")),
+        }
+    };
+
+    let (stdout, overflow) = out_reader.join().unwrap_or_default();
+    let (stderr, _) = err_reader.join().unwrap_or_default();
+    if overflow {
+        return Err(format!("Hook output exceeded {} KB", hook.max_output_kb));
+    }
+    if !status.success() {
+        return Err(format!(
+            "Hook exited with {status}: {}",
+            String::from_utf8_lossy(&stderr).trim()
+        ));
+    }
+    String::from_utf8(stdout).map_err(|_| "Hook output is not valid UTF-8".to_string())
+}
+
+/// Run the hook on a blocking thread so the async runtime stays free. The
+/// program must be granted the feed's host, prompting on first use.
+pub async fn run_for_feed(app: &tauri::AppHandle, store: &FeedHookStore, hook: FeedHook) -> Result<String, String> {
+    let principal = Principal {
+        kind: PrincipalKind::Script,
+        id: hook.program.clone(),
+    };
+    let required = vec![host_permission(&hook.feed_url)?];
+    let perms = app.state::<Arc<PermissionStore>>();
+    crate::permissions::require(app, perms.inner(), &principal, Some(&hook.program), required).await?;
+    eprintln!("[feed_hooks] Running '{}' for {}", hook.program, hook.feed_url);
+    let work_dir = store.work_dir();
+    tauri::async_runtime::spawn_blocking(move || run_hook(&hook, work_dir))
+        .await
+        .map_err(|e| format!("Hook task failed: {e}"))?
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_feed_hooks(store: tauri::State<'_, Arc<FeedHookStore>>) -> Vec<FeedHook> {
+    store.get_hooks()
+}
+
+#[tauri::command]
+pub fn save_feed_hook(
+    hook: FeedHook,
+    store: tauri::State<'_, Arc<FeedHookStore>>,
+) -> Result<FeedHook, String> {
+    if hook.feed_url.trim().is_empty() {
+        return Err("Feed URL is required".into());
+    }
+    if hook.program.trim().is_empty() {
+        return Err("Program is required".into());
+    }
+    Ok(store.upsert(hook))
+}
+
+#[tauri::command]
+pub fn delete_feed_hook(feed_url: String, store: tauri::State<'_, Arc<FeedHookStore>>) -> bool {
+    store.delete(&feed_url)
+}
+
+/// Dry-run a hook configuration and return its output without saving it.
+#[tauri::command]
+pub async fn test_feed_hook(
+    hook: FeedHook,
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<FeedHookStore>>,
+) -> Result<String, String> {
+    let mut hook = hook;
+    hook.timeout_secs = hook.timeout_secs.clamp(1, MAX_TIMEOUT_SECS);
+    hook.max_output_kb = hook.max_output_kb.clamp(1, MAX_OUTPUT_KB);
+    run_for_feed(&app, &store, hook).await
+}
@@ -0,0 +1,219 @@
+use feed_rs::model::{Entry, Feed, Text};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::content::process_article_html;
+use crate::dates::{parse_date, DateStore, FeedTimezone};
+use crate::feed_hooks::FeedHookStore;
+use crate::feed_stats::FeedStatsStore;
+use crate::text::{snippet_from_html, SNIPPET_LEN};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+/// Feed normalized across RSS 0.9x/1.0/2.0, Atom and JSON Feed.
+/// Dates are ms since epoch.
+#[derive(Clone, Serialize, Debug)]
+pub struct ParsedFeed {
+    pub title: String,
+    pub link: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    /// New address of a feed that moved permanently (301/308).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permanent_url: Option<String>,
+    pub items: Vec<ParsedItem>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct ParsedItem {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub author: String,
+    /// Short description as provided by the feed (may contain HTML).
+    pub summary: String,
+    /// Plain-text list preview of the summary, or of the content without one.
+    pub snippet: String,
+    /// Full content when the feed carries it (may contain HTML).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    pub categories: Vec<String>,
+    pub enclosures: Vec<Enclosure>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct Enclosure {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
+    /// Duration in seconds (podcasts, video).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+}
+
+// ── Normalization ────────────────────────────────────────────────────
+
+fn text(t: &Option<Text>) -> String {
+    t.as_ref().map(|t| t.content.trim().to_string()).unwrap_or_default()
+}
+
+/// The entry's main link: `alternate` (or unlabeled) first, anything else as fallback.
+fn primary_link(links: &[feed_rs::model::Link]) -> String {
+    links
+        .iter()
+        .find(|l| matches!(l.rel.as_deref(), None | Some("alternate")))
+        .or_else(|| links.iter().find(|l| l.rel.as_deref() != Some("enclosure")))
+        .map(|l| l.href.clone())
+        .unwrap_or_default()
+}
+
+fn enclosures(entry: &Entry) -> Vec<Enclosure> {
+    let mut out: Vec<Enclosure> = Vec::new();
+    // RSS <enclosure> and Media RSS <media:content> both land in `media`
+    for media in &entry.media {
+        for c in &media.content {
+            if let Some(ref url) = c.url {
+                out.push(Enclosure {
+                    url: url.to_string(),
+                    mime_type: c.content_type.as_ref().map(|m| m.to_string()),
+                    length: c.size,
+                    duration: c.duration.or(media.duration).map(|d| d.as_secs()),
+                });
+            }
+        }
+    }
+    // Atom <link rel="enclosure">
+    for link in entry.links.iter().filter(|l| l.rel.as_deref() == Some("enclosure")) {
+        out.push(Enclosure {
+            url: link.href.clone(),
+            mime_type: link.media_type.clone(),
+            length: link.length,
+            duration: None,
+        });
+    }
+    out.dedup_by(|a, b| a.url == b.url);
+    out
+}
+
+fn thumbnail(entry: &Entry, enclosures: &[Enclosure]) -> Option<String> {
+    entry
+        .media
+        .iter()
+        .flat_map(|m| m.thumbnails.iter())
+        .map(|t| t.image.uri.clone())
+        .next()
+        .or_else(|| {
+            enclosures
+                .iter()
+                .find(|e| e.mime_type.as_deref().is_some_and(|m| m.starts_with("image/")))
+                .map(|e| e.url.clone())
+        })
+}
+
+fn normalize_entry(entry: &Entry, base_url: &str) -> ParsedItem {
+    let enclosures = enclosures(entry);
+    let url = primary_link(&entry.links);
+    // Relative URLs in the HTML point into the article page, else the feed itself
+    let html_base = if url::Url::parse(&url).is_ok() { url.as_str() } else { base_url };
+    let summary = process_article_html(&text(&entry.summary), html_base);
+    let content = entry
+        .content
+        .as_ref()
+        .and_then(|c| c.body.as_deref())
+        .map(|c| process_article_html(c, html_base));
+    let snippet = match (summary.is_empty(), &content) {
+        (true, Some(c)) => snippet_from_html(c, SNIPPET_LEN),
+        _ => snippet_from_html(&summary, SNIPPET_LEN),
+    };
+    ParsedItem {
+        // feed-rs generates a stable hash id when the feed has none
+        id: entry.id.clone(),
+        title: text(&entry.title),
+        url,
+        author: entry.authors.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", "),
+        summary,
+        snippet,
+        content,
+        published_at: entry.published.or(entry.updated).map(|d| d.timestamp_millis()),
+        updated_at: entry.updated.map(|d| d.timestamp_millis()),
+        categories: entry
+            .categories
+            .iter()
+            .map(|c| c.label.clone().unwrap_or_else(|| c.term.clone()))
+            .collect(),
+        thumbnail: thumbnail(entry, &enclosures),
+        enclosures,
+    }
+}
+
+fn normalize(feed: Feed, base_url: &str) -> ParsedFeed {
+    ParsedFeed {
+        title: text(&feed.title),
+        link: primary_link(&feed.links),
+        description: text(&feed.description),
+        icon: feed.icon.or(feed.logo).map(|i| i.uri),
+        updated_at: feed.updated.map(|d| d.timestamp_millis()),
+        permanent_url: None,
+        items: feed.entries.iter().map(|e| normalize_entry(e, base_url)).collect(),
+    }
+}
+
+/// Parse raw feed text. `base_url` resolves relative links; dates go through the
+/// lenient parser, reading zone-less times in `timezone` when the feed has an override.
+pub fn parse_body(body: &str, base_url: &str, timezone: Option<FeedTimezone>) -> Result<ParsedFeed, String> {
+    let feed = feed_rs::parser::Builder::new()
+        .base_uri(Some(base_url))
+        .timestamp_parser(move |raw| parse_date(raw, timezone.as_ref()))
+        .build()
+        .parse(body.as_bytes())
+        .map_err(|e| format!("Failed to parse feed: {e}"))?;
+    Ok(normalize(feed, base_url))
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Fetch and parse a feed natively, returning a normalized structure. A
+/// subreddit whose RSS fetch fails is read through `reddit` instead.
+#[tauri::command]
+pub async fn parse_feed(
+    url: String,
+    app: tauri::AppHandle,
+    stats: tauri::State<'_, Arc<FeedStatsStore>>,
+    hooks: tauri::State<'_, Arc<FeedHookStore>>,
+    dates: tauri::State<'_, Arc<DateStore>>,
+) -> Result<ParsedFeed, String> {
+    let response = match crate::fetch_feed(&app, &url, None, Some(stats.inner().as_ref()), &hooks).await {
+        Ok(response) => response,
+        // Reddit often refuses its RSS endpoints; the JSON listing carries the same posts
+        Err(e) => match crate::reddit::rss_target(&url) {
+            Some((subreddit, sort, period)) => {
+                eprintln!("[feed_parser] {url} failed ({e}), trying Reddit's JSON API");
+                return crate::reddit::fetch_subreddit(&subreddit, Some(&sort), period.as_deref(), None)
+                    .await
+                    .map_err(|fallback| format!("{e}; JSON fallback: {fallback}"));
+            }
+            None => return Err(e),
+        },
+    };
+    let timezone = dates.timezone_for(&url);
+    // Relative links resolve against where the feed actually lives
+    let base_url = response.url;
+    let body = response.body;
+    let mut parsed = tauri::async_runtime::spawn_blocking(move || parse_body(&body, &base_url, timezone))
+        .await
+        .map_err(|e| format!("Parse task failed: {e}"))??;
+    parsed.permanent_url = response.permanent_url;
+    eprintln!("[feed_parser] Parsed '{}' ({} items)", parsed.title, parsed.items.len());
+    Ok(parsed)
+}
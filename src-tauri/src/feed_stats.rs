@@ -0,0 +1,347 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const STATS_FILE: &str = "feed_stats.json";
+const MAX_ERRORS_PER_FEED: usize = 50;
+const MAX_DAYS_KEPT: i64 = 365;
+const DATE_FMT: &str = "%Y-%m-%d";
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct DayStats {
+    #[serde(default)]
+    pub fetches: u32,
+    #[serde(default)]
+    pub errors: u32,
+    #[serde(default)]
+    pub new_items: u32,
+    #[serde(default)]
+    pub reads: u32,
+    /// Reads that happened during a focus session.
+    #[serde(default)]
+    pub focus_reads: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FetchError {
+    pub timestamp: u64,
+    pub message: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct FeedHistory {
+    #[serde(default)]
+    pub feed_name: String,
+    /// Keyed by local date (`YYYY-MM-DD`).
+    #[serde(default)]
+    pub daily: BTreeMap<String, DayStats>,
+    #[serde(default)]
+    pub errors: Vec<FetchError>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct FeedStatsReport {
+    pub feed_url: String,
+    pub feed_name: String,
+    pub new_items: u32,
+    pub posts_per_day: f64,
+    pub reads: u32,
+    pub focus_reads: u32,
+    pub fetches: u32,
+    pub errors: u32,
+    pub daily: BTreeMap<String, DayStats>,
+    pub error_history: Vec<FetchError>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+/// Per-feed counters keyed by feed URL. Writes are batched by a flush timer
+/// because every feed refresh records into it.
+pub struct FeedStatsStore {
+    feeds: Mutex<HashMap<String, FeedHistory>>,
+    data_dir: Mutex<Option<PathBuf>>,
+    dirty: AtomicBool,
+}
+
+impl FeedStatsStore {
+    pub fn new() -> Self {
+        FeedStatsStore {
+            feeds: Mutex::new(HashMap::new()),
+            data_dir: Mutex::new(None),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(STATS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(feeds) = serde_json::from_str::<HashMap<String, FeedHistory>>(&json) {
+                            eprintln!("[feed_stats] Loaded stats for {} feeds", feeds.len());
+                            *self.feeds.lock().unwrap() = feeds;
+                        }
+                    }
+                    Err(e) => eprintln!("[feed_stats] Failed to read stats file: {e}"),
+                }
+            }
+        }
+    }
+
+    pub fn flush(&self) {
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        self.prune_old_days();
+        if let Some(path) = self.file_path() {
+            let feeds = self.feeds.lock().unwrap();
+            match serde_json::to_string(&*feeds) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[feed_stats] Failed to write stats file: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[feed_stats] Failed to serialize stats: {e}"),
+            }
+        }
+    }
+
+    /// Drop days and errors older than `MAX_DAYS_KEPT`, then feeds left with neither.
+    fn prune_old_days(&self) {
+        let cutoff_date = Local::now().date_naive() - chrono::Duration::days(MAX_DAYS_KEPT);
+        let cutoff = cutoff_date.format(DATE_FMT).to_string();
+        let cutoff_ms = local_millis(cutoff_date, 0, 0, 0).unwrap_or(0);
+        let mut feeds = self.feeds.lock().unwrap();
+        for history in feeds.values_mut() {
+            history.daily.retain(|day, _| *day >= cutoff);
+            history.errors.retain(|e| e.timestamp >= cutoff_ms);
+        }
+        feeds.retain(|_, history| !history.daily.is_empty() || !history.errors.is_empty());
+    }
+
+    fn update_today(&self, feed_url: &str, f: impl FnOnce(&mut FeedHistory, &mut DayStats)) {
+        let today = Local::now().format(DATE_FMT).to_string();
+        let mut feeds = self.feeds.lock().unwrap();
+        let history = feeds.entry(feed_url.to_string()).or_default();
+        let mut day = history.daily.remove(&today).unwrap_or_default();
+        f(history, &mut day);
+        history.daily.insert(today, day);
+        self.dirty.store(true, Ordering::SeqCst);
+    }
+
+    pub fn record_fetch(&self, feed_url: &str, error: Option<&str>) {
+        self.update_today(feed_url, |history, day| {
+            day.fetches += 1;
+            if let Some(msg) = error {
+                day.errors += 1;
+                history.errors.push(FetchError {
+                    timestamp: now_millis(),
+                    message: msg.to_string(),
+                });
+                if history.errors.len() > MAX_ERRORS_PER_FEED {
+                    let excess = history.errors.len() - MAX_ERRORS_PER_FEED;
+                    history.errors.drain(..excess);
+                }
+            }
+        });
+    }
+
+    pub fn record_new_items(&self, feed_url: &str, feed_name: &str, count: u32) {
+        self.update_today(feed_url, |history, day| {
+            if !feed_name.is_empty() {
+                history.feed_name = feed_name.to_string();
+            }
+            day.new_items += count;
+        });
+    }
+
+    pub fn record_read(&self, feed_url: &str, focused: bool) {
+        self.update_today(feed_url, |_, day| {
+            day.reads += 1;
+            if focused {
+                day.focus_reads += 1;
+            }
+        });
+    }
+
+    /// Aggregate every feed over the inclusive `[from, to]` date range.
+    pub fn report(&self, from: NaiveDate, to: NaiveDate) -> Vec<FeedStatsReport> {
+        let from_key = from.format(DATE_FMT).to_string();
+        let to_key = to.format(DATE_FMT).to_string();
+        // Local dates, like the day keys
+        let from_ms = local_millis(from, 0, 0, 0).unwrap_or(0);
+        let to_ms = local_millis(to, 23, 59, 59).map(|ms| ms + 999).unwrap_or(u64::MAX);
+        let span_days = ((to - from).num_days() + 1).max(1) as f64;
+
+        let feeds = self.feeds.lock().unwrap();
+        let mut reports: Vec<FeedStatsReport> = feeds
+            .iter()
+            .map(|(url, history)| {
+                let daily: BTreeMap<String, DayStats> = history
+                    .daily
+                    .range(from_key.clone()..=to_key.clone())
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                let sum = |f: fn(&DayStats) -> u32| daily.values().map(f).sum::<u32>();
+                let new_items = sum(|d| d.new_items);
+                FeedStatsReport {
+                    feed_url: url.clone(),
+                    feed_name: history.feed_name.clone(),
+                    new_items,
+                    posts_per_day: (new_items as f64 / span_days * 100.0).round() / 100.0,
+                    reads: sum(|d| d.reads),
+                    focus_reads: sum(|d| d.focus_reads),
+                    fetches: sum(|d| d.fetches),
+                    errors: sum(|d| d.errors),
+                    error_history: history
+                        .errors
+                        .iter()
+                        .filter(|e| e.timestamp >= from_ms && e.timestamp <= to_ms)
+                        .cloned()
+                        .collect(),
+                    daily,
+                }
+            })
+            .filter(|r| !r.daily.is_empty() || !r.error_history.is_empty())
+            .collect();
+        reports.sort_by(|a, b| b.new_items.cmp(&a.new_items).then_with(|| a.feed_url.cmp(&b.feed_url)));
+        reports
+    }
+}
+
+/// Epoch milliseconds of a local date and time.
+fn local_millis(date: NaiveDate, hour: u32, min: u32, sec: u32) -> Option<u64> {
+    let local = date.and_hms_opt(hour, min, sec)?.and_local_timezone(Local).earliest()?;
+    Some(local.timestamp_millis().max(0) as u64)
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+pub fn start_flush_timer(store: Arc<FeedStatsStore>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(30));
+        store.flush();
+    });
+}
+
+// ── Export ───────────────────────────────────────────────────────────
+
+fn parse_range(from: &str, to: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let from = NaiveDate::parse_from_str(from, DATE_FMT).map_err(|e| format!("Invalid 'from' date: {e}"))?;
+    let to = NaiveDate::parse_from_str(to, DATE_FMT).map_err(|e| format!("Invalid 'to' date: {e}"))?;
+    if from > to {
+        return Err("'from' must not be after 'to'".into());
+    }
+    Ok((from, to))
+}
+
+/// One row per feed per day, which graphs directly in a spreadsheet.
+fn to_csv(reports: &[FeedStatsReport]) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(Vec::new());
+    wtr.write_record(["date", "feed_url", "feed_name", "new_items", "reads", "focus_reads", "fetches", "errors"])
+        .map_err(|e| format!("CSV error: {e}"))?;
+    for r in reports {
+        for (date, day) in &r.daily {
+            wtr.write_record([
+                date.as_str(),
+                r.feed_url.as_str(),
+                r.feed_name.as_str(),
+                &day.new_items.to_string(),
+                &day.reads.to_string(),
+                &day.focus_reads.to_string(),
+                &day.fetches.to_string(),
+                &day.errors.to_string(),
+            ])
+            .map_err(|e| format!("CSV error: {e}"))?;
+        }
+    }
+    let bytes = wtr.into_inner().map_err(|e| format!("CSV error: {e}"))?;
+    String::from_utf8(bytes).map_err(|e| format!("CSV encoding error: {e}"))
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn stats_record_new_items(
+    feed_url: String,
+    feed_name: String,
+    count: u32,
+    store: tauri::State<'_, Arc<FeedStatsStore>>,
+) {
+    store.record_new_items(&feed_url, &feed_name, count);
+}
+
+#[tauri::command]
+pub fn stats_record_read(
+    feed_url: String,
+    store: tauri::State<'_, Arc<FeedStatsStore>>,
+    focus: tauri::State<'_, Arc<crate::focus::FocusStore>>,
+) {
+    store.record_read(&feed_url, focus.record_read());
+}
+
+#[tauri::command]
+pub fn get_feed_stats(
+    from: String,
+    to: String,
+    store: tauri::State<'_, Arc<FeedStatsStore>>,
+) -> Result<Vec<FeedStatsReport>, String> {
+    let (from, to) = parse_range(&from, &to)?;
+    Ok(store.report(from, to))
+}
+
+/// Export stats for `[from, to]` (`YYYY-MM-DD`) as `csv` or `json` through a save dialog.
+/// Returns `false` if the user cancelled.
+#[tauri::command]
+pub async fn export_feed_stats(
+    format: String,
+    from: String,
+    to: String,
+    store: tauri::State<'_, Arc<FeedStatsStore>>,
+) -> Result<bool, String> {
+    let (from_date, to_date) = parse_range(&from, &to)?;
+    let reports = store.report(from_date, to_date);
+    let (content, ext, label) = match format.as_str() {
+        "csv" => (to_csv(&reports)?, "csv", "CSV"),
+        "json" => (
+            serde_json::to_string_pretty(&reports).map_err(|e| format!("Serialize error: {e}"))?,
+            "json",
+            "JSON",
+        ),
+        other => return Err(format!("Unsupported format: {other}")),
+    };
+
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("superflux-stats-{from}-{to}.{ext}"))
+        .add_filter(label, &[ext])
+        .save_file()
+        .await;
+
+    match dialog {
+        Some(handle) => {
+            std::fs::write(handle.path(), content.as_bytes())
+                .map_err(|e| format!("Failed to write file: {e}"))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,949 @@
+use regex::{Regex, RegexBuilder};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+use crate::db::{self, ArticleRow, Database};
+use crate::ingest::now_millis;
+use crate::notifications::{self, PushMessage};
+use crate::text::html_to_text;
+
+// Topic mutes hide items for a while; filter rules act on every new article
+// as it is stored: conditions on title, content, author, feed and URL, and
+// actions that mark read, star, hide, tag or notify. Rules run in order and
+// all matching ones apply, unless one says to stop. Watched keywords raise a
+// desktop notification and a `keyword-alert` event for each new article
+// mentioning them.
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const MUTES_FILE: &str = "topic_mutes.json";
+const RULES_FILE: &str = "filter_rules.json";
+const KEYWORDS_FILE: &str = "keyword_watches.json";
+/// More keyword matches than this in one refresh share a single notification.
+const MAX_KEYWORD_NOTIFICATIONS: usize = 3;
+/// Characters kept either side of a keyword match in alert excerpts.
+const EXCERPT_CONTEXT: usize = 60;
+/// Stored articles a rule preview looks through, newest first.
+const PREVIEW_SCAN: u32 = 1000;
+const MAX_ALERT_TITLES: usize = 5;
+
+/// Hide items mentioning any of `keywords` until `until` (ms since epoch).
+/// Muted items are only hidden, never deleted.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TopicMute {
+    pub id: String,
+    pub keywords: Vec<String>,
+    pub until: u64,
+    pub created_at: u64,
+}
+
+/// Minimal view of an item for matching.
+#[derive(Clone, Deserialize, Debug)]
+pub struct FilterCandidate {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub summary: String,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleField {
+    Title,
+    /// Article body as text, or the summary when there is no full content.
+    Content,
+    Author,
+    /// The feed id, compared exactly.
+    Feed,
+    Url,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RuleCondition {
+    pub field: RuleField,
+    /// Case-insensitive regex, except for `feed`.
+    pub pattern: String,
+    /// Match articles the pattern does *not* match.
+    #[serde(default)]
+    pub negate: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    MarkRead,
+    Star,
+    Hide,
+    Tag { tag: String },
+    /// Desktop notification and push targets, one alert per refresh.
+    Notify,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FilterRule {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Every condition must match (default), or any one of them.
+    #[serde(default = "default_true")]
+    pub match_all: bool,
+    pub conditions: Vec<RuleCondition>,
+    pub actions: Vec<RuleAction>,
+    /// Skip the rules after this one for articles it matched.
+    #[serde(default)]
+    pub stop: bool,
+    /// Articles matched so far.
+    #[serde(default)]
+    pub hits: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_hit_at: Option<u64>,
+}
+
+/// A word or phrase to be alerted about.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct KeywordWatch {
+    pub id: String,
+    /// Matched case-insensitively on whole words; spacing inside a phrase is loose.
+    pub keyword: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub created_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_match_at: Option<u64>,
+}
+
+/// Payload of `keyword-alert`, emitted once per new article matching a watch.
+#[derive(Clone, Serialize, Debug)]
+pub struct KeywordAlert {
+    pub article_id: String,
+    pub feed_id: String,
+    pub feed_name: String,
+    pub title: String,
+    pub url: String,
+    /// Watched keywords the article mentions.
+    pub keywords: Vec<String>,
+    /// The first match as written in the article.
+    pub matched: String,
+    /// Text around that match.
+    pub excerpt: String,
+}
+
+struct CompiledMute {
+    mute: TopicMute,
+    patterns: Vec<Regex>,
+}
+
+fn compile_mute(mute: TopicMute) -> Result<CompiledMute, String> {
+    let patterns = mute
+        .keywords
+        .iter()
+        .map(|k| {
+            Regex::new(&format!(r"(?i)(?:^|\W){}(?:\W|$)", regex::escape(k.trim())))
+                .map_err(|e| format!("Invalid keyword '{k}': {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CompiledMute { mute, patterns })
+}
+
+#[derive(Clone)]
+struct CompiledKeyword {
+    watch: KeywordWatch,
+    pattern: Regex,
+}
+
+fn compile_keyword(watch: KeywordWatch) -> Result<CompiledKeyword, String> {
+    let words: Vec<String> = watch.keyword.split_whitespace().map(regex::escape).collect();
+    // `\b` never matches next to keywords like `C++` or `.NET`; group 1 is the keyword itself
+    let pattern = Regex::new(&format!(r"(?i)(?:^|\W)({})(?:\W|$)", words.join(r"\s+")))
+        .map_err(|e| format!("Invalid keyword '{}': {e}", watch.keyword))?;
+    Ok(CompiledKeyword { watch, pattern })
+}
+
+#[derive(Clone)]
+struct CompiledCondition {
+    field: RuleField,
+    /// None for `feed`, which compares the pattern itself.
+    regex: Option<Regex>,
+    pattern: String,
+    negate: bool,
+}
+
+#[derive(Clone)]
+struct CompiledRule {
+    rule: FilterRule,
+    conditions: Vec<CompiledCondition>,
+}
+
+fn compile_rule(rule: FilterRule) -> Result<CompiledRule, String> {
+    let conditions = rule
+        .conditions
+        .iter()
+        .map(|c| {
+            let regex = match c.field {
+                RuleField::Feed => None,
+                _ => Some(
+                    RegexBuilder::new(&c.pattern)
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|e| format!("Invalid pattern '{}': {e}", c.pattern))?,
+                ),
+            };
+            Ok(CompiledCondition { field: c.field, regex, pattern: c.pattern.clone(), negate: c.negate })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(CompiledRule { rule, conditions })
+}
+
+fn validate_rule(rule: &FilterRule) -> Result<(), String> {
+    if rule.name.trim().is_empty() {
+        return Err("Rule name is required".into());
+    }
+    if rule.conditions.is_empty() {
+        return Err("At least one condition is required".into());
+    }
+    if rule.conditions.iter().any(|c| c.pattern.trim().is_empty()) {
+        return Err("Conditions need a pattern".into());
+    }
+    if rule.actions.is_empty() {
+        return Err("At least one action is required".into());
+    }
+    if rule.actions.iter().any(|a| matches!(a, RuleAction::Tag { tag } if tag.trim().is_empty())) {
+        return Err("Tag actions need a tag".into());
+    }
+    Ok(())
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct FilterStore {
+    mutes: Mutex<Vec<CompiledMute>>,
+    rules: Mutex<Vec<CompiledRule>>,
+    keywords: Mutex<Vec<CompiledKeyword>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl FilterStore {
+    pub fn new() -> Self {
+        FilterStore {
+            mutes: Mutex::new(Vec::new()),
+            rules: Mutex::new(Vec::new()),
+            keywords: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+        self.load_rules();
+        self.load_keywords();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(MUTES_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(mutes) = serde_json::from_str::<Vec<TopicMute>>(&json) {
+                            *self.mutes.lock().unwrap() =
+                                mutes.into_iter().filter_map(|m| compile_mute(m).ok()).collect();
+                        }
+                    }
+                    Err(e) => eprintln!("[filters] Failed to read mutes: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let mutes = self.mutes.lock().unwrap();
+            let list: Vec<&TopicMute> = mutes.iter().map(|m| &m.mute).collect();
+            match serde_json::to_string_pretty(&list) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[filters] Failed to write mutes: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[filters] Failed to serialize mutes: {e}"),
+            }
+        }
+    }
+
+    /// Drop expired mutes. Returns true if anything was removed.
+    fn expire(&self) -> bool {
+        let now = now_millis();
+        let mut mutes = self.mutes.lock().unwrap();
+        let before = mutes.len();
+        mutes.retain(|m| m.mute.until > now);
+        let changed = mutes.len() < before;
+        drop(mutes);
+        if changed {
+            self.save_to_disk();
+        }
+        changed
+    }
+
+    pub fn add_mute(&self, keywords: Vec<String>, until: u64) -> Result<TopicMute, String> {
+        let keywords: Vec<String> = keywords
+            .into_iter()
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty())
+            .collect();
+        if keywords.is_empty() {
+            return Err("At least one keyword is required".into());
+        }
+        if until <= now_millis() {
+            return Err("Mute end date must be in the future".into());
+        }
+        let compiled = compile_mute(TopicMute {
+            id: uuid::Uuid::new_v4().to_string(),
+            keywords,
+            until,
+            created_at: now_millis(),
+        })?;
+        let mute = compiled.mute.clone();
+        self.mutes.lock().unwrap().push(compiled);
+        self.save_to_disk();
+        Ok(mute)
+    }
+
+    pub fn list_mutes(&self) -> Vec<TopicMute> {
+        self.expire();
+        self.mutes.lock().unwrap().iter().map(|m| m.mute.clone()).collect()
+    }
+
+    pub fn lift_mute(&self, id: &str) -> bool {
+        let mut mutes = self.mutes.lock().unwrap();
+        let before = mutes.len();
+        mutes.retain(|m| m.mute.id != id);
+        let removed = mutes.len() < before;
+        drop(mutes);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+
+    fn load_rules(&self) {
+        let Some(path) = self.data_dir.lock().unwrap().as_ref().map(|d| d.join(RULES_FILE)) else {
+            return;
+        };
+        if !path.exists() {
+            return;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(json) => {
+                if let Ok(rules) = serde_json::from_str::<Vec<FilterRule>>(&json) {
+                    let compiled = rules.into_iter().filter_map(|r| match compile_rule(r) {
+                        Ok(rule) => Some(rule),
+                        Err(e) => {
+                            eprintln!("[filters] Skipping rule: {e}");
+                            None
+                        }
+                    });
+                    *self.rules.lock().unwrap() = compiled.collect();
+                }
+            }
+            Err(e) => eprintln!("[filters] Failed to read rules: {e}"),
+        }
+    }
+
+    fn save_rules(&self) {
+        let Some(path) = self.data_dir.lock().unwrap().as_ref().map(|d| d.join(RULES_FILE)) else {
+            return;
+        };
+        let rules = self.rules.lock().unwrap();
+        let list: Vec<&FilterRule> = rules.iter().map(|r| &r.rule).collect();
+        match serde_json::to_string_pretty(&list) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    eprintln!("[filters] Failed to write rules: {e}");
+                }
+            }
+            Err(e) => eprintln!("[filters] Failed to serialize rules: {e}"),
+        }
+    }
+
+    pub fn list_rules(&self) -> Vec<FilterRule> {
+        self.rules.lock().unwrap().iter().map(|r| r.rule.clone()).collect()
+    }
+
+    fn enabled_rules(&self) -> Vec<CompiledRule> {
+        self.rules.lock().unwrap().iter().filter(|r| r.rule.enabled).cloned().collect()
+    }
+
+    /// Add a rule, or replace the one with the same id (keeping its place and counters).
+    pub fn save_rule(&self, mut rule: FilterRule) -> Result<FilterRule, String> {
+        validate_rule(&rule)?;
+        rule.name = rule.name.trim().to_string();
+        for action in &mut rule.actions {
+            if let RuleAction::Tag { tag } = action {
+                *tag = tag.trim().to_string();
+            }
+        }
+        if rule.id.is_empty() {
+            rule.id = uuid::Uuid::new_v4().to_string();
+        }
+        let mut rules = self.rules.lock().unwrap();
+        match rules.iter_mut().find(|r| r.rule.id == rule.id) {
+            Some(existing) => {
+                rule.hits = existing.rule.hits;
+                rule.last_hit_at = existing.rule.last_hit_at;
+                *existing = compile_rule(rule.clone())?;
+            }
+            None => {
+                rule.hits = 0;
+                rule.last_hit_at = None;
+                rules.push(compile_rule(rule.clone())?);
+            }
+        }
+        drop(rules);
+        self.save_rules();
+        Ok(rule)
+    }
+
+    pub fn delete_rule(&self, id: &str) -> bool {
+        let mut rules = self.rules.lock().unwrap();
+        let before = rules.len();
+        rules.retain(|r| r.rule.id != id);
+        let removed = rules.len() < before;
+        drop(rules);
+        if removed {
+            self.save_rules();
+        }
+        removed
+    }
+
+    /// Reorder rules to follow `ids`; rules not listed keep their order at the end.
+    pub fn reorder_rules(&self, ids: &[String]) {
+        let mut rules = self.rules.lock().unwrap();
+        rules.sort_by_key(|r| ids.iter().position(|id| *id == r.rule.id).unwrap_or(usize::MAX));
+        drop(rules);
+        self.save_rules();
+    }
+
+    fn record_hits(&self, counts: &HashMap<String, u64>) {
+        let now = now_millis();
+        for rule in self.rules.lock().unwrap().iter_mut() {
+            if let Some(count) = counts.get(&rule.rule.id) {
+                rule.rule.hits += count;
+                rule.rule.last_hit_at = Some(now);
+            }
+        }
+        self.save_rules();
+    }
+
+    fn load_keywords(&self) {
+        let Some(path) = self.data_dir.lock().unwrap().as_ref().map(|d| d.join(KEYWORDS_FILE)) else {
+            return;
+        };
+        if !path.exists() {
+            return;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(json) => {
+                if let Ok(watches) = serde_json::from_str::<Vec<KeywordWatch>>(&json) {
+                    *self.keywords.lock().unwrap() =
+                        watches.into_iter().filter_map(|w| compile_keyword(w).ok()).collect();
+                }
+            }
+            Err(e) => eprintln!("[filters] Failed to read keyword watches: {e}"),
+        }
+    }
+
+    fn save_keywords(&self) {
+        let Some(path) = self.data_dir.lock().unwrap().as_ref().map(|d| d.join(KEYWORDS_FILE)) else {
+            return;
+        };
+        let keywords = self.keywords.lock().unwrap();
+        let list: Vec<&KeywordWatch> = keywords.iter().map(|k| &k.watch).collect();
+        match serde_json::to_string_pretty(&list) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    eprintln!("[filters] Failed to write keyword watches: {e}");
+                }
+            }
+            Err(e) => eprintln!("[filters] Failed to serialize keyword watches: {e}"),
+        }
+    }
+
+    pub fn list_keywords(&self) -> Vec<KeywordWatch> {
+        self.keywords.lock().unwrap().iter().map(|k| k.watch.clone()).collect()
+    }
+
+    pub fn add_keyword(&self, keyword: &str) -> Result<KeywordWatch, String> {
+        let keyword = keyword.split_whitespace().collect::<Vec<_>>().join(" ");
+        if keyword.is_empty() {
+            return Err("Keyword is required".into());
+        }
+        let mut keywords = self.keywords.lock().unwrap();
+        if keywords.iter().any(|k| k.watch.keyword.eq_ignore_ascii_case(&keyword)) {
+            return Err(format!("'{keyword}' is already watched"));
+        }
+        let compiled = compile_keyword(KeywordWatch {
+            id: uuid::Uuid::new_v4().to_string(),
+            keyword,
+            enabled: true,
+            created_at: now_millis(),
+            last_match_at: None,
+        })?;
+        let watch = compiled.watch.clone();
+        keywords.push(compiled);
+        drop(keywords);
+        self.save_keywords();
+        Ok(watch)
+    }
+
+    pub fn set_keyword_enabled(&self, id: &str, enabled: bool) -> Option<KeywordWatch> {
+        let mut keywords = self.keywords.lock().unwrap();
+        let watch = keywords.iter_mut().find(|k| k.watch.id == id).map(|k| {
+            k.watch.enabled = enabled;
+            k.watch.clone()
+        });
+        drop(keywords);
+        if watch.is_some() {
+            self.save_keywords();
+        }
+        watch
+    }
+
+    pub fn remove_keyword(&self, id: &str) -> bool {
+        let mut keywords = self.keywords.lock().unwrap();
+        let before = keywords.len();
+        keywords.retain(|k| k.watch.id != id);
+        let removed = keywords.len() < before;
+        drop(keywords);
+        if removed {
+            self.save_keywords();
+        }
+        removed
+    }
+
+    fn enabled_keywords(&self) -> Vec<CompiledKeyword> {
+        self.keywords.lock().unwrap().iter().filter(|k| k.watch.enabled).cloned().collect()
+    }
+
+    fn record_keyword_matches(&self, alerts: &[KeywordAlert]) {
+        let now = now_millis();
+        for keyword in self.keywords.lock().unwrap().iter_mut() {
+            if alerts.iter().any(|a| a.keywords.contains(&keyword.watch.keyword)) {
+                keyword.watch.last_match_at = Some(now);
+            }
+        }
+        self.save_keywords();
+    }
+
+    /// True if an active mute matches the title or summary.
+    pub fn is_muted(&self, title: &str, summary: &str) -> bool {
+        let now = now_millis();
+        self.mutes.lock().unwrap().iter().any(|m| {
+            m.mute.until > now
+                && m.patterns.iter().any(|p| p.is_match(title) || p.is_match(summary))
+        })
+    }
+}
+
+// ── Rules ────────────────────────────────────────────────────────────
+
+/// The parts of a stored article rules look at.
+struct RuleArticle {
+    id: String,
+    feed_id: String,
+    title: String,
+    url: String,
+    author: String,
+    content: String,
+}
+
+impl CompiledCondition {
+    fn matches(&self, article: &RuleArticle) -> bool {
+        let text = match self.field {
+            RuleField::Title => &article.title,
+            RuleField::Content => &article.content,
+            RuleField::Author => &article.author,
+            RuleField::Url => &article.url,
+            RuleField::Feed => return (article.feed_id == self.pattern.trim()) != self.negate,
+        };
+        self.regex.as_ref().is_some_and(|r| r.is_match(text)) != self.negate
+    }
+}
+
+impl CompiledRule {
+    fn matches(&self, article: &RuleArticle) -> bool {
+        if self.rule.match_all {
+            self.conditions.iter().all(|c| c.matches(article))
+        } else {
+            self.conditions.iter().any(|c| c.matches(article))
+        }
+    }
+}
+
+fn load_articles(conn: &Connection, ids: &[String]) -> rusqlite::Result<Vec<RuleArticle>> {
+    let ids = serde_json::to_string(ids).unwrap_or_default();
+    let mut stmt = conn.prepare_cached(
+        "SELECT id, feed_id, title, url, author, COALESCE(content, summary) FROM articles
+         WHERE id IN (SELECT value FROM json_each(?1))",
+    )?;
+    let rows = stmt.query_map([ids], |r| {
+        let body: String = r.get(5)?;
+        Ok(RuleArticle {
+            id: r.get(0)?,
+            feed_id: r.get(1)?,
+            title: r.get(2)?,
+            url: r.get(3)?,
+            author: r.get(4)?,
+            content: html_to_text(&body),
+        })
+    })?;
+    rows.collect()
+}
+
+/// Matches of one batch: per rule, the articles it matched (id, title, url).
+type RuleMatches = Vec<(CompiledRule, Vec<(String, String, String)>)>;
+
+/// Match `ids` against `rules` and apply the database actions. Returns the
+/// matches and the ids that ended up hidden.
+fn run_rules(
+    conn: &mut Connection,
+    rules: Vec<CompiledRule>,
+    ids: &[String],
+) -> rusqlite::Result<(RuleMatches, HashSet<String>)> {
+    let articles = load_articles(conn, ids)?;
+    let mut matches: RuleMatches = rules.into_iter().map(|r| (r, Vec::new())).collect();
+    for article in &articles {
+        for (rule, matched) in matches.iter_mut() {
+            if rule.matches(article) {
+                matched.push((article.id.clone(), article.title.clone(), article.url.clone()));
+                if rule.rule.stop {
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut hidden = HashSet::new();
+    let tx = conn.transaction()?;
+    {
+        let mut read = tx.prepare_cached(
+            "UPDATE articles SET is_read = 1, read_at = COALESCE(read_at, ?2) WHERE id = ?1",
+        )?;
+        let mut star = tx.prepare_cached("UPDATE articles SET is_starred = 1 WHERE id = ?1")?;
+        let mut hide = tx.prepare_cached("UPDATE articles SET is_hidden = 1 WHERE id = ?1")?;
+        let mut tag =
+            tx.prepare_cached("INSERT OR IGNORE INTO article_tags (article_id, tag) VALUES (?1, ?2)")?;
+        let now = now_millis() as i64;
+        for (rule, matched) in &matches {
+            for (id, _, _) in matched {
+                for action in &rule.rule.actions {
+                    match action {
+                        RuleAction::MarkRead => read.execute(params![id, now])?,
+                        RuleAction::Star => star.execute([id])?,
+                        RuleAction::Hide => {
+                            hidden.insert(id.clone());
+                            hide.execute([id])?
+                        }
+                        RuleAction::Tag { tag: name } => tag.execute(params![id, name])?,
+                        RuleAction::Notify => 0,
+                    };
+                }
+            }
+        }
+    }
+    tx.commit()?;
+    matches.retain(|(_, matched)| !matched.is_empty());
+    Ok((matches, hidden))
+}
+
+fn alert_message(rule: &FilterRule, matched: &[(String, String, String)]) -> PushMessage {
+    let title = match matched.len() {
+        1 => format!("{}: 1 new article", rule.name),
+        n => format!("{}: {n} new articles", rule.name),
+    };
+    let mut lines: Vec<String> = matched.iter().take(MAX_ALERT_TITLES).map(|(_, t, _)| t.clone()).collect();
+    if matched.len() > MAX_ALERT_TITLES {
+        lines.push(format!("and {} more", matched.len() - MAX_ALERT_TITLES));
+    }
+    let click_url = match matched {
+        [(_, _, url)] if !url.is_empty() => Some(url.clone()),
+        _ => None,
+    };
+    PushMessage { title, message: lines.join("\n"), click_url }
+}
+
+/// Run the enabled rules over newly stored articles. Returns the ids that are
+/// still visible; alerts for `notify` rules go out in the background.
+pub async fn apply_rules(app: &tauri::AppHandle, ids: Vec<String>) -> Vec<String> {
+    let store = app.state::<Arc<FilterStore>>().inner().clone();
+    let rules = store.enabled_rules();
+    if ids.is_empty() || rules.is_empty() {
+        return ids;
+    }
+    let db = app.state::<Arc<Database>>().inner().clone();
+    let batch = ids.clone();
+    let (matches, hidden) = match db::run(&db, move |conn| run_rules(conn, rules, &batch)).await {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("[filters] Applying rules failed: {e}");
+            return ids;
+        }
+    };
+    if matches.is_empty() {
+        return ids;
+    }
+    store.record_hits(&matches.iter().map(|(r, m)| (r.rule.id.clone(), m.len() as u64)).collect());
+    let alerts: Vec<PushMessage> = matches
+        .iter()
+        .filter(|(rule, _)| rule.rule.actions.contains(&RuleAction::Notify))
+        .map(|(rule, matched)| alert_message(&rule.rule, matched))
+        .collect();
+    if !alerts.is_empty() {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            for alert in alerts {
+                notifications::raise_alert(&app, &alert).await;
+            }
+        });
+    }
+    ids.into_iter().filter(|id| !hidden.contains(id)).collect()
+}
+
+// ── Keyword alerts ───────────────────────────────────────────────────
+
+/// `text[start..end]` with up to EXCERPT_CONTEXT characters either side.
+fn excerpt(text: &str, start: usize, end: usize) -> String {
+    let from = text[..start].char_indices().rev().nth(EXCERPT_CONTEXT - 1).map_or(0, |(i, _)| i);
+    let to = text[end..].char_indices().nth(EXCERPT_CONTEXT).map_or(text.len(), |(i, _)| end + i);
+    let mut out = text[from..to].split_whitespace().collect::<Vec<_>>().join(" ");
+    if from > 0 {
+        out.insert(0, '…');
+    }
+    if to < text.len() {
+        out.push('…');
+    }
+    out
+}
+
+fn keyword_alert(
+    article: &RuleArticle,
+    keywords: &[CompiledKeyword],
+    feed_names: &HashMap<String, String>,
+) -> Option<KeywordAlert> {
+    let mut matched_keywords = Vec::new();
+    let mut first = None;
+    for keyword in keywords {
+        let found = [&article.title, &article.content]
+            .into_iter()
+            .find_map(|text| keyword.pattern.captures(text).and_then(|c| c.get(1)).map(|m| (text, m)));
+        if let Some((text, m)) = found {
+            matched_keywords.push(keyword.watch.keyword.clone());
+            first.get_or_insert_with(|| (m.as_str().to_string(), excerpt(text, m.start(), m.end())));
+        }
+    }
+    let (matched, excerpt) = first?;
+    let feed_name = feed_names
+        .get(&article.feed_id)
+        .filter(|name| !name.is_empty())
+        .cloned()
+        .or_else(|| url::Url::parse(&article.url).ok()?.host_str().map(str::to_string))
+        .unwrap_or_default();
+    Some(KeywordAlert {
+        article_id: article.id.clone(),
+        feed_id: article.feed_id.clone(),
+        feed_name,
+        title: article.title.clone(),
+        url: article.url.clone(),
+        keywords: matched_keywords,
+        matched,
+        excerpt,
+    })
+}
+
+/// Check newly stored articles against the watched keywords: every match
+/// emits `keyword-alert` and shows a desktop notification; large batches get
+/// one notification for all of them.
+pub async fn check_keywords(app: tauri::AppHandle, ids: Vec<String>) {
+    let store = app.state::<Arc<FilterStore>>().inner().clone();
+    let keywords = store.enabled_keywords();
+    if ids.is_empty() || keywords.is_empty() {
+        return;
+    }
+    let db = app.state::<Arc<Database>>().inner().clone();
+    let alerts = db::run(&db, move |conn| {
+        let feed_names: HashMap<String, String> =
+            db::list_feeds(conn)?.into_iter().map(|f| (f.id, f.title)).collect();
+        let articles = load_articles(conn, &ids)?;
+        Ok(articles.iter().filter_map(|a| keyword_alert(a, &keywords, &feed_names)).collect::<Vec<_>>())
+    })
+    .await;
+    let alerts = match alerts {
+        Ok(alerts) if !alerts.is_empty() => alerts,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("[filters] Keyword check failed: {e}");
+            return;
+        }
+    };
+    store.record_keyword_matches(&alerts);
+    for alert in &alerts {
+        let _ = app.emit("keyword-alert", alert);
+    }
+    if alerts.len() <= MAX_KEYWORD_NOTIFICATIONS {
+        for alert in &alerts {
+            let title = format!("\u{201c}{}\u{201d} in {}", alert.matched, alert.feed_name);
+            notifications::notify_desktop(&app, &title, &alert.title);
+        }
+    } else {
+        let title = format!("{} new articles match your keywords", alerts.len());
+        let mut lines: Vec<String> = alerts.iter().take(MAX_ALERT_TITLES).map(|a| a.title.clone()).collect();
+        if alerts.len() > MAX_ALERT_TITLES {
+            lines.push(format!("and {} more", alerts.len() - MAX_ALERT_TITLES));
+        }
+        notifications::notify_desktop(&app, &title, &lines.join("\n"));
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Mute `keywords` until `until` (ms since epoch).
+#[tauri::command]
+pub fn mute_topic(
+    keywords: Vec<String>,
+    until: u64,
+    store: tauri::State<'_, Arc<FilterStore>>,
+) -> Result<TopicMute, String> {
+    store.add_mute(keywords, until)
+}
+
+/// Active mutes (expired ones are pruned).
+#[tauri::command]
+pub fn list_topic_mutes(store: tauri::State<'_, Arc<FilterStore>>) -> Vec<TopicMute> {
+    store.list_mutes()
+}
+
+#[tauri::command]
+pub fn lift_topic_mute(id: String, store: tauri::State<'_, Arc<FilterStore>>) -> bool {
+    store.lift_mute(&id)
+}
+
+/// Return the ids of the given items that are currently hidden by a mute.
+#[tauri::command]
+pub fn get_muted_item_ids(
+    items: Vec<FilterCandidate>,
+    store: tauri::State<'_, Arc<FilterStore>>,
+) -> Vec<String> {
+    items
+        .into_iter()
+        .filter(|i| store.is_muted(&i.title, &i.summary))
+        .map(|i| i.id)
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_filter_rules(store: tauri::State<'_, Arc<FilterStore>>) -> Vec<FilterRule> {
+    store.list_rules()
+}
+
+/// Create or update a rule. Rules apply to articles stored from then on.
+#[tauri::command]
+pub fn save_filter_rule(
+    rule: FilterRule,
+    store: tauri::State<'_, Arc<FilterStore>>,
+) -> Result<FilterRule, String> {
+    store.save_rule(rule)
+}
+
+#[tauri::command]
+pub fn delete_filter_rule(id: String, store: tauri::State<'_, Arc<FilterStore>>) -> bool {
+    store.delete_rule(&id)
+}
+
+/// Set the order rules run in.
+#[tauri::command]
+pub fn reorder_filter_rules(ids: Vec<String>, store: tauri::State<'_, Arc<FilterStore>>) -> Vec<FilterRule> {
+    store.reorder_rules(&ids);
+    store.list_rules()
+}
+
+/// Recent stored articles `rule` would match, without applying it, so a rule
+/// can be checked while it is being written.
+#[tauri::command]
+pub async fn preview_filter_rule(
+    rule: FilterRule,
+    limit: Option<u32>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<ArticleRow>, String> {
+    let compiled = compile_rule(rule)?;
+    let limit = limit.unwrap_or(50) as usize;
+    db::run(&db, move |conn| {
+        let sql = format!(
+            "SELECT {}, COALESCE(content, summary) AS body FROM articles
+             ORDER BY COALESCE(published_at, fetched_at) DESC, id LIMIT ?1",
+            db::ROW_COLUMNS
+        );
+        let mut stmt = conn.prepare_cached(&sql)?;
+        let mut rows = stmt.query([PREVIEW_SCAN])?;
+        let mut found = Vec::new();
+        while let Some(row) = rows.next()? {
+            let article = db::row_from_sql(row)?;
+            let candidate = RuleArticle {
+                id: article.id.clone(),
+                feed_id: article.feed_id.clone(),
+                title: article.title.clone(),
+                url: article.url.clone(),
+                author: article.author.clone(),
+                content: html_to_text(&row.get::<_, String>("body")?),
+            };
+            if compiled.matches(&candidate) {
+                found.push(article);
+                if found.len() >= limit {
+                    break;
+                }
+            }
+        }
+        Ok(found)
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn get_keyword_watches(store: tauri::State<'_, Arc<FilterStore>>) -> Vec<KeywordWatch> {
+    store.list_keywords()
+}
+
+/// Watch a word or phrase: new articles mentioning it raise an alert.
+#[tauri::command]
+pub fn add_keyword_watch(
+    keyword: String,
+    store: tauri::State<'_, Arc<FilterStore>>,
+) -> Result<KeywordWatch, String> {
+    store.add_keyword(&keyword)
+}
+
+#[tauri::command]
+pub fn set_keyword_watch_enabled(
+    id: String,
+    enabled: bool,
+    store: tauri::State<'_, Arc<FilterStore>>,
+) -> Result<KeywordWatch, String> {
+    store.set_keyword_enabled(&id, enabled).ok_or_else(|| "Keyword watch not found".to_string())
+}
+
+#[tauri::command]
+pub fn remove_keyword_watch(id: String, store: tauri::State<'_, Arc<FilterStore>>) -> bool {
+    store.remove_keyword(&id)
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::Emitter;
+
+use crate::ingest::now_millis;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const HISTORY_FILE: &str = "focus_sessions.json";
+const MAX_HISTORY: usize = 500;
+const MAX_DURATION_MINUTES: u32 = 8 * 60;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FocusSession {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// ms since epoch
+    pub started_at: u64,
+    pub ends_at: u64,
+    /// Push notifications and notification sounds are held back while active.
+    pub mute_notifications: bool,
+    /// Reads during the session are counted as focus reads in the feed stats.
+    pub track_reads: bool,
+    #[serde(default)]
+    pub reads: u32,
+    /// Set once the session is over.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<u64>,
+    /// True if the timer ran out, false if the session was stopped early.
+    #[serde(default)]
+    pub completed: bool,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct FocusStatus {
+    #[serde(flatten)]
+    pub session: FocusSession,
+    pub remaining_ms: u64,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+/// The running session lives in memory only; finished sessions are kept as history.
+pub struct FocusStore {
+    current: Mutex<Option<FocusSession>>,
+    history: Mutex<Vec<FocusSession>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl FocusStore {
+    pub fn new() -> Self {
+        FocusStore {
+            current: Mutex::new(None),
+            history: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(HISTORY_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(history) = serde_json::from_str::<Vec<FocusSession>>(&json) {
+                            *self.history.lock().unwrap() = history;
+                        }
+                    }
+                    Err(e) => eprintln!("[focus] Failed to read session history: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let history = self.history.lock().unwrap();
+            match serde_json::to_string_pretty(&*history) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[focus] Failed to write session history: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[focus] Failed to serialize session history: {e}"),
+            }
+        }
+    }
+
+    /// The running session, if its timer has not run out yet.
+    pub fn active(&self) -> Option<FocusSession> {
+        let now = now_millis();
+        self.current.lock().unwrap().clone().filter(|s| s.ends_at > now)
+    }
+
+    pub fn notifications_suppressed(&self) -> bool {
+        self.active().is_some_and(|s| s.mute_notifications)
+    }
+
+    /// Count a read against the active session. Returns true if it should be
+    /// recorded as a focus read.
+    pub fn record_read(&self) -> bool {
+        let now = now_millis();
+        let mut current = self.current.lock().unwrap();
+        match current.as_mut() {
+            Some(s) if s.ends_at > now => {
+                s.reads += 1;
+                s.track_reads
+            }
+            _ => false,
+        }
+    }
+
+    /// Start a session, replacing (and ending) any running one.
+    pub fn start(&self, session: FocusSession) {
+        let _ = self.finish(None, false);
+        *self.current.lock().unwrap() = Some(session);
+    }
+
+    /// End the running session (only if it matches `id`, when given) and move
+    /// it to the history.
+    pub fn finish(&self, id: Option<&str>, completed: bool) -> Option<FocusSession> {
+        let mut session = {
+            let mut current = self.current.lock().unwrap();
+            if id.is_some_and(|id| current.as_ref().is_none_or(|s| s.id != id)) {
+                return None;
+            }
+            current.take()?
+        };
+        session.ended_at = Some(now_millis().min(session.ends_at));
+        session.completed = completed;
+        {
+            let mut history = self.history.lock().unwrap();
+            history.push(session.clone());
+            if history.len() > MAX_HISTORY {
+                let excess = history.len() - MAX_HISTORY;
+                history.drain(..excess);
+            }
+        }
+        self.save_to_disk();
+        Some(session)
+    }
+
+    /// Finished sessions, newest first.
+    pub fn history(&self, limit: usize) -> Vec<FocusSession> {
+        self.history.lock().unwrap().iter().rev().take(limit).cloned().collect()
+    }
+}
+
+/// Poll until the session runs out, then move it to the history and emit
+/// `focus-session-completed`. Exits quietly if the session was stopped or replaced.
+fn spawn_timer(app: tauri::AppHandle, store: Arc<FocusStore>, id: String) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        let Some(current) = store.current.lock().unwrap().clone() else { return };
+        if current.id != id {
+            return;
+        }
+        if now_millis() >= current.ends_at {
+            if let Some(session) = store.finish(Some(&id), true) {
+                let _ = app.emit("focus-session-completed", &session);
+            }
+            return;
+        }
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Start a timed focus session. Notifications are muted and reads tracked by default.
+#[tauri::command]
+pub fn start_focus_session(
+    app: tauri::AppHandle,
+    duration_minutes: u32,
+    label: Option<String>,
+    mute_notifications: Option<bool>,
+    track_reads: Option<bool>,
+    store: tauri::State<'_, Arc<FocusStore>>,
+) -> Result<FocusSession, String> {
+    if duration_minutes == 0 || duration_minutes > MAX_DURATION_MINUTES {
+        return Err(format!("Duration must be between 1 and {MAX_DURATION_MINUTES} minutes"));
+    }
+    let now = now_millis();
+    let session = FocusSession {
+        id: uuid::Uuid::new_v4().to_string(),
+        label: label.filter(|l| !l.trim().is_empty()),
+        started_at: now,
+        ends_at: now + duration_minutes as u64 * 60_000,
+        mute_notifications: mute_notifications.unwrap_or(true),
+        track_reads: track_reads.unwrap_or(true),
+        reads: 0,
+        ended_at: None,
+        completed: false,
+    };
+    store.start(session.clone());
+    spawn_timer(app, store.inner().clone(), session.id.clone());
+    Ok(session)
+}
+
+/// Stop the running session early. Returns the ended session, if any.
+#[tauri::command]
+pub fn stop_focus_session(store: tauri::State<'_, Arc<FocusStore>>) -> Option<FocusSession> {
+    store.finish(None, false)
+}
+
+#[tauri::command]
+pub fn get_focus_session(store: tauri::State<'_, Arc<FocusStore>>) -> Option<FocusStatus> {
+    store.active().map(|session| FocusStatus {
+        remaining_ms: session.ends_at.saturating_sub(now_millis()),
+        session,
+    })
+}
+
+#[tauri::command]
+pub fn get_focus_history(limit: Option<usize>, store: tauri::State<'_, Arc<FocusStore>>) -> Vec<FocusSession> {
+    store.history(limit.unwrap_or(50))
+}
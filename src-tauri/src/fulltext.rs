@@ -0,0 +1,370 @@
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::time::Duration;
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, STORED, STRING};
+use tantivy::tokenizer::{AsciiFoldingFilter, LowerCaser, RemoveLongFilter, SimpleTokenizer, TextAnalyzer};
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+
+use crate::db::{self, ArticleRow, Database};
+use crate::text::html_to_text;
+
+// Tantivy index behind `search_query`: article titles, authors and text, with
+// phrase queries, `title:` / `author:` / `body:` filters and bm25 ranking.
+// Article writes are queued in `fulltext_pending` by triggers, in the same
+// transaction that stores them, and a background worker applies the queue to
+// the index. Read state, folders and the like stay in SQLite: the scored hits
+// are filtered, sorted and paged there, so they never need reindexing.
+
+const INDEX_DIR: &str = "fulltext_index";
+const TOKENIZER: &str = "folded";
+/// Memory the index writer buffers before flushing a segment.
+const WRITER_HEAP_BYTES: usize = 50 * 1024 * 1024;
+/// Queued changes applied per index commit.
+const BATCH: usize = 500;
+/// How often the worker checks the queue when nobody wakes it, e.g. after deletions.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Title and author matches count this many times a body match.
+const TITLE_BOOST: f32 = 10.0;
+const AUTHOR_BOOST: f32 = 5.0;
+/// Hits handed to SQL for filtering; the best ones when a query matches more.
+const MAX_CANDIDATES: usize = 10_000;
+const DEFAULT_RESULTS: u32 = 100;
+const MAX_RESULTS: u32 = 1000;
+
+#[derive(Clone, Copy)]
+struct Fields {
+    id: Field,
+    title: Field,
+    author: Field,
+    body: Field,
+}
+
+struct FullText {
+    index: Index,
+    reader: IndexReader,
+    /// Held for a whole batch, so batches apply the queue in order.
+    writer: Mutex<IndexWriter>,
+    fields: Fields,
+}
+
+static INDEX: OnceLock<FullText> = OnceLock::new();
+/// Set by `notify` when new articles are queued.
+static WAKE: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchSort {
+    /// Best matches first; titles count most, then authors, then bodies.
+    #[default]
+    Relevance,
+    Newest,
+    Oldest,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct SearchFilters {
+    pub feed_ids: Option<Vec<String>>,
+    pub folder: Option<String>,
+    pub unread_only: bool,
+    pub starred_only: bool,
+    /// Inclusive bounds on `published_at` (ms).
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// Only articles by this author (an `authors::Author` id).
+    pub author_id: Option<String>,
+    pub sort: SearchSort,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub article: ArticleRow,
+    /// bm25 relevance; higher is better, only comparable within one query.
+    pub score: f64,
+}
+
+// ── Index ────────────────────────────────────────────────────────────
+
+fn schema() -> (Schema, Fields) {
+    let mut builder = Schema::builder();
+    let text = TextOptions::default().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer(TOKENIZER)
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+    );
+    let fields = Fields {
+        id: builder.add_text_field("id", STRING | STORED),
+        title: builder.add_text_field("title", text.clone()),
+        author: builder.add_text_field("author", text.clone()),
+        body: builder.add_text_field("body", text),
+    };
+    (builder.build(), fields)
+}
+
+/// Open the index in `path`, creating it when missing. The flag is true when
+/// it was created, and so holds no articles yet.
+fn open(path: &Path) -> tantivy::Result<(FullText, bool)> {
+    std::fs::create_dir_all(path)?;
+    let directory = MmapDirectory::open(path)?;
+    let created = !Index::exists(&directory)?;
+    let (schema, fields) = schema();
+    let index = Index::open_or_create(directory, schema)?;
+    // Lowercased with accents stripped, so "zurich" finds "Zürich"
+    index.tokenizers().register(
+        TOKENIZER,
+        TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveLongFilter::limit(40))
+            .filter(LowerCaser)
+            .filter(AsciiFoldingFilter)
+            .build(),
+    );
+    let reader = index.reader_builder().reload_policy(ReloadPolicy::Manual).try_into()?;
+    let writer = index.writer(WRITER_HEAP_BYTES)?;
+    Ok((FullText { index, reader, writer: Mutex::new(writer), fields }, created))
+}
+
+/// Open the index, rebuilding it from scratch when it can't be read.
+fn open_or_rebuild(path: &Path) -> tantivy::Result<(FullText, bool)> {
+    match open(path) {
+        Ok(opened) => Ok(opened),
+        Err(e) => {
+            eprintln!("[fulltext] Index unreadable ({e}), rebuilding it");
+            let _ = std::fs::remove_dir_all(path);
+            open(path)
+        }
+    }
+}
+
+/// Open the index under the app data dir on a background thread, then keep
+/// applying queued article changes to it.
+pub fn start(dir: PathBuf, db: Arc<Database>) {
+    std::thread::spawn(move || {
+        let (fulltext, created) = match open_or_rebuild(&dir.join(INDEX_DIR)) {
+            Ok(opened) => opened,
+            Err(e) => {
+                eprintln!("[fulltext] Failed to open search index: {e}");
+                return;
+            }
+        };
+        // A new index starts empty: queue every stored article for it
+        if created {
+            let queued = db.with(|conn| {
+                conn.execute("INSERT INTO fulltext_pending (article_id) SELECT id FROM articles", [])
+            });
+            if let Err(e) = queued {
+                eprintln!("[fulltext] Failed to queue articles: {e}");
+            }
+        }
+        let _ = INDEX.set(fulltext);
+        loop {
+            if let Err(e) = drain(&db) {
+                eprintln!("[fulltext] Indexing failed: {e}");
+            }
+            let (woken, wake) = &WAKE;
+            let guard = woken.lock().unwrap();
+            let (mut guard, _) = wake.wait_timeout_while(guard, POLL_INTERVAL, |woken| !*woken).unwrap();
+            *guard = false;
+        }
+    });
+}
+
+/// Wake the worker after queueing articles.
+pub fn notify() {
+    let (woken, wake) = &WAKE;
+    *woken.lock().unwrap() = true;
+    wake.notify_one();
+}
+
+/// A queued change: its seq, the article id, and the title, author and
+/// HTML body if the article is still stored.
+type PendingChange = (i64, String, Option<(String, String, String)>);
+
+/// Apply up to `BATCH` queued changes, oldest first. Returns how many were applied.
+fn apply_batch(fulltext: &FullText, db: &Database) -> Result<usize, String> {
+    let mut writer = fulltext.writer.lock().unwrap();
+    let changes: Vec<PendingChange> = db.with(|conn| {
+        let mut stmt = conn.prepare_cached(
+            "SELECT p.seq, p.article_id, a.id IS NOT NULL, a.title, a.author, COALESCE(a.content, a.summary)
+             FROM fulltext_pending p LEFT JOIN articles a ON a.id = p.article_id
+             ORDER BY p.seq LIMIT ?1",
+        )?;
+        let rows = stmt.query_map([BATCH as i64], |r| {
+            let stored: bool = r.get(2)?;
+            let article = match stored {
+                true => Some((r.get(3)?, r.get(4)?, r.get(5)?)),
+                false => None,
+            };
+            Ok((r.get(0)?, r.get(1)?, article))
+        })?;
+        rows.collect()
+    })?;
+    let Some(&(last, _, _)) = changes.last() else { return Ok(0) };
+
+    let f = fulltext.fields;
+    for (_, id, article) in &changes {
+        writer.delete_term(Term::from_field_text(f.id, id));
+        // Deleted articles only leave the delete behind
+        if let Some((title, author, body_html)) = article {
+            let body = html_to_text(body_html);
+            let document = doc!(
+                f.id => id.as_str(),
+                f.title => title.as_str(),
+                f.author => author.as_str(),
+                f.body => body,
+            );
+            writer.add_document(document).map_err(|e| format!("Failed to index {id}: {e}"))?;
+        }
+    }
+    writer.commit().map_err(|e| format!("Failed to commit search index: {e}"))?;
+    fulltext.reader.reload().map_err(|e| format!("Failed to reload search index: {e}"))?;
+    // Changes queued while this batch ran have a later seq and stay queued
+    db.with(|conn| conn.execute("DELETE FROM fulltext_pending WHERE seq <= ?1", [last]))?;
+    Ok(changes.len())
+}
+
+/// Apply the whole queue. Does nothing until the index is open.
+fn drain(db: &Database) -> Result<usize, String> {
+    let Some(fulltext) = INDEX.get() else { return Ok(0) };
+    let mut applied = 0;
+    loop {
+        match apply_batch(fulltext, db)? {
+            0 => return Ok(applied),
+            n => applied += n,
+        }
+    }
+}
+
+/// Article ids matching `query`, best first, with their scores.
+fn matches(query: &str) -> Result<Vec<(String, f32)>, String> {
+    let fulltext = INDEX.get().ok_or("Search index is still opening")?;
+    let f = fulltext.fields;
+    let mut parser = QueryParser::for_index(&fulltext.index, vec![f.title, f.author, f.body]);
+    parser.set_conjunction_by_default();
+    parser.set_field_boost(f.title, TITLE_BOOST);
+    parser.set_field_boost(f.author, AUTHOR_BOOST);
+    // Unknown fields and stray quotes are dropped rather than failing the search
+    let (query, _) = parser.parse_query_lenient(query);
+
+    let searcher = fulltext.reader.searcher();
+    let limit = (searcher.num_docs() as usize).clamp(1, MAX_CANDIDATES);
+    let top = searcher
+        .search(&query, &TopDocs::with_limit(limit))
+        .map_err(|e| format!("Search failed: {e}"))?;
+    Ok(top
+        .into_iter()
+        .filter_map(|(score, address)| {
+            let doc: TantivyDocument = searcher.doc(address).ok()?;
+            Some((doc.get_first(f.id)?.as_str()?.to_string(), score))
+        })
+        .collect())
+}
+
+// ── Ranked search ────────────────────────────────────────────────────
+
+fn ranked(
+    conn: &rusqlite::Connection,
+    hits: &[(String, f32)],
+    filters: &SearchFilters,
+) -> rusqlite::Result<Vec<SearchHit>> {
+    let order = match filters.sort {
+        SearchSort::Relevance => "hits.score DESC, COALESCE(published_at, fetched_at) DESC",
+        SearchSort::Newest => "COALESCE(published_at, fetched_at) DESC",
+        SearchSort::Oldest => "COALESCE(published_at, fetched_at) ASC",
+    };
+    // Hits of articles deleted since they were indexed drop out in the join
+    let sql = format!(
+        "WITH hits AS (
+            SELECT json_extract(value, '$[0]') AS article_id, json_extract(value, '$[1]') AS score
+            FROM json_each(?1)
+         )
+         SELECT {}, hits.score FROM articles JOIN hits ON hits.article_id = articles.id
+         WHERE (?2 IS NULL OR feed_id IN (SELECT value FROM json_each(?2)))
+           AND (?3 IS NULL OR feed_id IN (SELECT id FROM feeds WHERE folder = ?3))
+           AND (NOT ?4 OR is_read = 0)
+           AND (NOT ?5 OR is_starred = 1)
+           AND (?6 IS NULL OR published_at >= ?6)
+           AND (?7 IS NULL OR published_at <= ?7)
+           AND (?8 IS NULL OR id IN (SELECT article_id FROM article_authors WHERE author_id = ?8))
+         ORDER BY {order}, id
+         LIMIT ?9 OFFSET ?10",
+        db::ROW_COLUMNS
+    );
+    let hits = serde_json::to_string(hits).unwrap_or_default();
+    let feeds = filters.feed_ids.as_ref().map(|ids| serde_json::to_string(ids).unwrap_or_default());
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let rows = stmt.query_map(
+        params![
+            hits,
+            feeds,
+            filters.folder,
+            filters.unread_only,
+            filters.starred_only,
+            filters.since,
+            filters.until,
+            filters.author_id,
+            filters.limit.unwrap_or(DEFAULT_RESULTS).min(MAX_RESULTS),
+            filters.offset.unwrap_or(0),
+        ],
+        |row| Ok(SearchHit { article: db::row_from_sql(row)?, score: row.get("score")? }),
+    )?;
+    rows.collect()
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// (Re)index stored articles now, e.g. after their full text was fetched.
+/// Ids that aren't stored are skipped. Returns how many were queued.
+#[tauri::command]
+pub async fn search_index_add(ids: Vec<String>, db: tauri::State<'_, Arc<Database>>) -> Result<usize, String> {
+    let db = db.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let queued = db.with(|conn| {
+            let tx = conn.transaction()?;
+            let mut queued = 0;
+            {
+                let mut queue = tx.prepare_cached(
+                    "INSERT INTO fulltext_pending (article_id) SELECT id FROM articles WHERE id = ?1",
+                )?;
+                for id in &ids {
+                    queued += queue.execute([id])?;
+                }
+            }
+            tx.commit()?;
+            Ok(queued)
+        })?;
+        drain(&db)?;
+        Ok(queued)
+    })
+    .await
+    .map_err(|e| format!("Index task failed: {e}"))?
+}
+
+/// Search stored articles. `q` takes plain words (all must match, accents
+/// ignored), `"exact phrases"`, `title:` / `author:` / `body:` prefixes,
+/// `-word` to exclude and `OR`.
+#[tauri::command]
+pub async fn search_query(
+    q: String,
+    filters: Option<SearchFilters>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<SearchHit>, String> {
+    if q.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let hits = tauri::async_runtime::spawn_blocking(move || matches(&q))
+        .await
+        .map_err(|e| format!("Search task failed: {e}"))??;
+    if hits.is_empty() {
+        return Ok(Vec::new());
+    }
+    let filters = filters.unwrap_or_default();
+    db::run(&db, move |conn| ranked(conn, &hits, &filters)).await
+}
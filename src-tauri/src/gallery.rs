@@ -0,0 +1,327 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use url::Url;
+
+use crate::ingest::{now_millis, MediaInput, StoredItem};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const GALLERY_FILE: &str = "media_gallery.json";
+/// Same rolling window as the ingest store.
+const MAX_AGE_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+const MAX_MEDIA_PER_ITEM: usize = 50;
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Image,
+    Video,
+    Audio,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GalleryMedia {
+    pub url: String,
+    pub kind: MediaKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Alt text or media title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+}
+
+/// All media of one item, denormalized so gallery queries need no join.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GalleryEntry {
+    pub item_id: String,
+    pub title: String,
+    pub url: String,
+    pub feed_url: String,
+    pub feed_name: String,
+    /// Item timestamp, ms since epoch.
+    pub timestamp: u64,
+    pub media: Vec<GalleryMedia>,
+}
+
+// ── Extraction ───────────────────────────────────────────────────────
+
+fn img_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?is)<(img|video|audio|source)\b[^>]*>"#).unwrap())
+}
+
+fn attr_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?is)([a-z-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap())
+}
+
+fn kind_for(mime: Option<&str>, url: &str) -> Option<MediaKind> {
+    if let Some(m) = mime {
+        if m.starts_with("image/") {
+            return Some(MediaKind::Image);
+        }
+        if m.starts_with("video/") {
+            return Some(MediaKind::Video);
+        }
+        if m.starts_with("audio/") {
+            return Some(MediaKind::Audio);
+        }
+    }
+    let path = url.split(['?', '#']).next().unwrap_or("").to_lowercase();
+    let ext = path.rsplit('.').next().unwrap_or("");
+    match ext {
+        "jpg" | "jpeg" | "png" | "gif" | "webp" | "avif" | "svg" | "bmp" => Some(MediaKind::Image),
+        "mp4" | "webm" | "mov" | "m4v" => Some(MediaKind::Video),
+        "mp3" | "m4a" | "ogg" | "opus" | "wav" | "aac" => Some(MediaKind::Audio),
+        // media:content without a type is almost always an image
+        _ if mime.is_none() => Some(MediaKind::Image),
+        _ => None,
+    }
+}
+
+fn resolve(base: Option<&Url>, src: &str) -> Option<String> {
+    let src = src.trim();
+    if src.is_empty() || src.starts_with("data:") {
+        return None;
+    }
+    match base {
+        Some(b) => b.join(src).ok().map(|u| u.to_string()),
+        None => Url::parse(src).ok().map(|u| u.to_string()),
+    }
+}
+
+/// Media from the feed's media:content/enclosures, the thumbnail and inline
+/// `<img>`/`<video>`/`<audio>` tags, deduplicated by URL. Tracking pixels are skipped.
+pub fn extract_media(item: &StoredItem) -> Vec<GalleryMedia> {
+    media_from(&item.item.url, &item.item.media, &item.item.content, item.item.thumbnail.as_deref())
+}
+
+/// `extract_media` for an item given as parts; relative URLs resolve against `page_url`.
+pub fn media_from(
+    page_url: &str,
+    inputs: &[MediaInput],
+    html: &str,
+    thumbnail: Option<&str>,
+) -> Vec<GalleryMedia> {
+    let base = Url::parse(page_url).ok();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut media: Vec<GalleryMedia> = Vec::new();
+    let mut push = |m: GalleryMedia| {
+        let tiny = m.width.is_some_and(|w| w <= 2) || m.height.is_some_and(|h| h <= 2);
+        if !tiny && media.len() < MAX_MEDIA_PER_ITEM && seen.insert(m.url.clone()) {
+            media.push(m);
+        }
+    };
+
+    for input in inputs {
+        let Some(url) = resolve(base.as_ref(), &input.url) else { continue };
+        let Some(kind) = kind_for(input.mime_type.as_deref(), &url) else { continue };
+        push(GalleryMedia {
+            url,
+            kind,
+            mime_type: input.mime_type.clone(),
+            width: input.width,
+            height: input.height,
+            caption: input.title.clone().filter(|t| !t.trim().is_empty()),
+        });
+    }
+
+    for tag in img_regex().captures_iter(html) {
+        let attrs: HashMap<String, String> = attr_regex()
+            .captures_iter(&tag[0])
+            .map(|c| {
+                let value = c.get(2).or(c.get(3)).map(|m| m.as_str()).unwrap_or("");
+                (c[1].to_lowercase(), value.to_string())
+            })
+            .collect();
+        // Lazy-loaded images keep the real URL in data-src
+        let src = attrs.get("data-src").or(attrs.get("src"));
+        let Some(url) = src.and_then(|s| resolve(base.as_ref(), s)) else { continue };
+        let kind = match tag[1].to_lowercase().as_str() {
+            "img" => Some(MediaKind::Image),
+            "video" => Some(MediaKind::Video),
+            "audio" => Some(MediaKind::Audio),
+            _ => kind_for(attrs.get("type").map(|s| s.as_str()), &url),
+        };
+        let Some(kind) = kind else { continue };
+        let dim = |k: &str| attrs.get(k).and_then(|v| v.trim_end_matches("px").parse().ok());
+        push(GalleryMedia {
+            url,
+            kind,
+            mime_type: attrs.get("type").cloned(),
+            width: dim("width"),
+            height: dim("height"),
+            caption: attrs
+                .get("alt")
+                .or(attrs.get("title"))
+                .filter(|t| !t.trim().is_empty())
+                .cloned(),
+        });
+    }
+
+    // The thumbnail is usually a resized copy of one of the above, so it comes last
+    if let Some(url) = thumbnail.and_then(|t| resolve(base.as_ref(), t)) {
+        push(GalleryMedia {
+            url,
+            kind: MediaKind::Image,
+            mime_type: None,
+            width: None,
+            height: None,
+            caption: None,
+        });
+    }
+    media
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct GalleryStore {
+    entries: Mutex<HashMap<String, GalleryEntry>>,
+    data_dir: Mutex<Option<PathBuf>>,
+    dirty: AtomicBool,
+}
+
+impl GalleryStore {
+    pub fn new() -> Self {
+        GalleryStore {
+            entries: Mutex::new(HashMap::new()),
+            data_dir: Mutex::new(None),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(GALLERY_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(entries) = serde_json::from_str::<Vec<GalleryEntry>>(&json) {
+                            *self.entries.lock().unwrap() =
+                                entries.into_iter().map(|e| (e.item_id.clone(), e)).collect();
+                        }
+                    }
+                    Err(e) => eprintln!("[gallery] Failed to read gallery: {e}"),
+                }
+            }
+        }
+    }
+
+    pub fn flush(&self) {
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        let cutoff = now_millis().saturating_sub(MAX_AGE_MS);
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, e| e.timestamp >= cutoff);
+        if let Some(path) = self.file_path() {
+            let list: Vec<&GalleryEntry> = entries.values().collect();
+            match serde_json::to_string(&list) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[gallery] Failed to write gallery: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[gallery] Failed to serialize gallery: {e}"),
+            }
+        }
+    }
+
+    /// Extract and store media for freshly ingested items.
+    pub fn record(&self, items: &[StoredItem]) {
+        let mut entries = self.entries.lock().unwrap();
+        for item in items {
+            let media = extract_media(item);
+            if media.is_empty() {
+                continue;
+            }
+            entries.insert(
+                item.item.id.clone(),
+                GalleryEntry {
+                    item_id: item.item.id.clone(),
+                    title: item.item.title.clone(),
+                    url: item.item.url.clone(),
+                    feed_url: item.feed_url.clone(),
+                    feed_name: item.feed_name.clone(),
+                    timestamp: item.timestamp(),
+                    media,
+                },
+            );
+            self.dirty.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Newest first, optionally restricted to one feed, a start time and a media kind.
+    pub fn query(
+        &self,
+        feed_url: Option<&str>,
+        since: u64,
+        kind: Option<MediaKind>,
+        offset: usize,
+        limit: usize,
+    ) -> Vec<GalleryEntry> {
+        let entries = self.entries.lock().unwrap();
+        let mut list: Vec<GalleryEntry> = entries
+            .values()
+            .filter(|e| e.timestamp >= since && feed_url.is_none_or(|f| e.feed_url == f))
+            .filter_map(|e| match kind {
+                Some(k) => {
+                    let media: Vec<GalleryMedia> = e.media.iter().filter(|m| m.kind == k).cloned().collect();
+                    (!media.is_empty()).then(|| GalleryEntry { media, ..e.clone() })
+                }
+                None => Some(e.clone()),
+            })
+            .collect();
+        list.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        list.into_iter().skip(offset).take(limit).collect()
+    }
+}
+
+pub fn start_flush_timer(store: Arc<GalleryStore>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(30));
+        store.flush();
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Gallery entries (items with their media), newest first.
+#[tauri::command]
+pub fn get_media_gallery(
+    feed_url: Option<String>,
+    range: Option<String>,
+    kind: Option<MediaKind>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    store: tauri::State<'_, Arc<GalleryStore>>,
+) -> Result<Vec<GalleryEntry>, String> {
+    let since = match range {
+        Some(r) => crate::ingest::range_start_millis(&r)?,
+        None => 0,
+    };
+    Ok(store.query(
+        feed_url.as_deref(),
+        since,
+        kind,
+        offset.unwrap_or(0),
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+    ))
+}
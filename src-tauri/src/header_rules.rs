@@ -0,0 +1,129 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+// User-defined request headers per domain, for hosts that turn away the
+// defaults (Medium, Substack mirrors, ...). A rule's headers are applied on
+// top of the built-in ones from `get_headers_for_url`, replacing any with the
+// same name.
+
+const RULES_FILE: &str = "header_rules.json";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HeaderRule {
+    /// e.g. `medium.com`. Subdomains inherit; the most specific rule wins.
+    pub domain: String,
+    /// Header name to value, e.g. `User-Agent`, `Accept`, `Referer`, `Cookie`.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+static RULES: OnceLock<Mutex<Vec<HeaderRule>>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn rules_lock() -> &'static Mutex<Vec<HeaderRule>> {
+    RULES.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(RULES_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(rules) = serde_json::from_str::<Vec<HeaderRule>>(&json) {
+            eprintln!("[header_rules] Loaded {} header rules", rules.len());
+            *rules_lock().lock().unwrap() = rules;
+        }
+    }
+}
+
+fn save_rules(rules: &[HeaderRule]) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json =
+        serde_json::to_string_pretty(rules).map_err(|e| format!("Failed to serialize header rules: {e}"))?;
+    std::fs::write(dir.join(RULES_FILE), json).map_err(|e| format!("Failed to save header rules: {e}"))
+}
+
+/// `*.Example.com` and `www.example.com` are both stored as `example.com`.
+fn normalize_domain(domain: &str) -> String {
+    let domain = domain.trim().trim_start_matches("*.").trim_start_matches('.').to_ascii_lowercase();
+    domain.strip_prefix("www.").map(String::from).unwrap_or(domain)
+}
+
+fn parse_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue), String> {
+    let header_name =
+        HeaderName::try_from(name.trim()).map_err(|e| format!("Invalid header name '{name}': {e}"))?;
+    let header_value =
+        HeaderValue::from_str(value.trim()).map_err(|e| format!("Invalid header value for '{name}': {e}"))?;
+    Ok((header_name, header_value))
+}
+
+// ── Request headers ──────────────────────────────────────────────────
+
+/// Add the headers of the enabled rule matching `host` most specifically.
+pub fn apply(host: &str, headers: &mut HeaderMap) {
+    let host = normalize_domain(host);
+    let rules = rules_lock().lock().unwrap();
+    let mut domain = host.as_str();
+    let rule = loop {
+        if let Some(rule) = rules.iter().find(|r| r.enabled && r.domain == domain) {
+            break rule;
+        }
+        match domain.split_once('.') {
+            Some((_, parent)) if parent.contains('.') => domain = parent,
+            _ => return,
+        }
+    };
+    for (name, value) in &rule.headers {
+        // Validated when saved; a hand-edited file may still hold a bad one
+        if let Ok((name, value)) = parse_header(name, value) {
+            headers.insert(name, value);
+        }
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_header_rules() -> Vec<HeaderRule> {
+    rules_lock().lock().unwrap().clone()
+}
+
+/// Add a rule, or replace the one for the same domain.
+#[tauri::command]
+pub fn set_header_rule(mut rule: HeaderRule) -> Result<HeaderRule, String> {
+    rule.domain = normalize_domain(&rule.domain);
+    if rule.domain.is_empty() {
+        return Err("Domain is required".into());
+    }
+    for (name, value) in &rule.headers {
+        parse_header(name, value)?;
+    }
+    let mut rules = rules_lock().lock().unwrap();
+    rules.retain(|r| r.domain != rule.domain);
+    rules.push(rule.clone());
+    rules.sort_by(|a, b| a.domain.cmp(&b.domain));
+    save_rules(&rules)?;
+    eprintln!("[header_rules] Saved {} headers for {}", rule.headers.len(), rule.domain);
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn delete_header_rule(domain: String) -> Result<bool, String> {
+    let domain = normalize_domain(&domain);
+    let mut rules = rules_lock().lock().unwrap();
+    let before = rules.len();
+    rules.retain(|r| r.domain != domain);
+    let removed = rules.len() != before;
+    if removed {
+        save_rules(&rules)?;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,150 @@
+use ego_tree::NodeId;
+use scraper::node::Text;
+use scraper::{ElementRef, Html, Node};
+use std::sync::OnceLock;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+// Syntax highlighting of <pre><code> blocks at ingest. Output uses prefixed
+// classes rather than inline colors, so the reader's theme picks the palette
+// (see `highlight_theme_css`).
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "sf-hl-" };
+/// Larger blocks are left as plain text; highlighting them would stall ingest.
+const MAX_BLOCK_BYTES: usize = 64 * 1024;
+/// Marks highlighted blocks in the output; never appears in real content.
+const PLACEHOLDER: char = '\u{E000}';
+
+/// Markers that identify a language when the markup doesn't say, scored by count.
+const LANGUAGE_HINTS: &[(&str, &[&str])] = &[
+    ("rs", &["fn ", "let mut ", "impl ", "pub fn", "::", "-> ", "&self", "use std"]),
+    ("py", &["def ", "import ", "self.", "elif ", "print(", "None", "):\n"]),
+    ("js", &["function ", "const ", "=> ", "console.log", "let ", "require(", "===", "async "]),
+    ("go", &["package ", "func ", ":= ", "fmt.", "err != nil"]),
+    ("cpp", &["#include", "std::", "int main", "nullptr", "template<"]),
+    ("java", &["public class", "public static", "System.out", "private ", "@Override"]),
+    ("sql", &["SELECT ", "FROM ", "WHERE ", "INSERT INTO", "CREATE TABLE", "JOIN "]),
+    ("sh", &["#!/bin/", "$ ", "sudo ", "apt ", "echo ", "export ", "| grep"]),
+    ("html", &["<div", "</", "<span", "<a href", "<!DOCTYPE"]),
+    ("css", &["{\n", "px;", "color:", "margin:", "display:"]),
+    ("json", &["\": ", "{\"", "[{", "\": {"]),
+    ("yaml", &["---\n", ": |", "- name:"]),
+];
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Language named by the block's classes: `language-x`, `lang-x`, `brush: x`,
+/// `highlight-x` or a bare known token.
+fn language_from_classes<'a>(set: &'a SyntaxSet, classes: &[&str]) -> Option<&'a SyntaxReference> {
+    let tokens = classes.iter().map(|c| {
+        let c = c.trim_end_matches(';');
+        ["language-", "lang-", "highlight-source-", "highlight-", "brush:"]
+            .iter()
+            .find_map(|p| c.strip_prefix(p))
+            .unwrap_or(c)
+    });
+    tokens
+        .filter(|t| !t.is_empty() && !matches!(*t, "hljs" | "code" | "highlight" | "sourceCode" | "brush"))
+        .find_map(|t| set.find_syntax_by_token(&t.to_ascii_lowercase()))
+}
+
+fn guess_language<'a>(set: &'a SyntaxSet, code: &str) -> Option<&'a SyntaxReference> {
+    if let Some(syntax) = code.lines().next().and_then(|l| set.find_syntax_by_first_line(l)) {
+        return Some(syntax);
+    }
+    let (token, score) = LANGUAGE_HINTS
+        .iter()
+        .map(|(token, markers)| (*token, markers.iter().map(|m| code.matches(m).count()).sum::<usize>()))
+        .max_by_key(|(_, score)| *score)?;
+    // A single stray marker is too weak to be worth a wrong colouring
+    if score < 2 {
+        return None;
+    }
+    set.find_syntax_by_token(token)
+}
+
+fn highlight_block(set: &SyntaxSet, syntax: &SyntaxReference, code: &str) -> Option<String> {
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, set, CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        generator.parse_html_for_line_which_includes_newline(line).ok()?;
+    }
+    Some(generator.finalize())
+}
+
+/// Highlight every `<pre>` code block whose language is declared or can be
+/// guessed. Each block's contents become `<code class="sf-hl language-x">` with
+/// `sf-hl-*` token spans; blocks already highlighted by the feed are left alone.
+pub fn highlight_code_blocks(html: &str) -> String {
+    if !html.contains("<pre") {
+        return html.to_string();
+    }
+    let set = syntax_set();
+    let mut fragment = Html::parse_fragment(html);
+
+    let mut blocks: Vec<(NodeId, String)> = Vec::new();
+    for pre in fragment.tree.nodes().filter_map(ElementRef::wrap).filter(|e| e.value().name() == "pre") {
+        let code = pre
+            .children()
+            .filter_map(ElementRef::wrap)
+            .find(|c| c.value().name() == "code")
+            .unwrap_or(pre);
+        // Existing markup means the feed already highlighted it
+        if code.children().any(|c| c.value().is_element()) {
+            continue;
+        }
+        let text: String = code.text().collect();
+        if text.trim().is_empty() || text.len() > MAX_BLOCK_BYTES || text.contains(PLACEHOLDER) {
+            continue;
+        }
+        let classes: Vec<&str> = code.value().classes().chain(pre.value().classes()).collect();
+        let Some(syntax) = language_from_classes(set, &classes).or_else(|| guess_language(set, &text)) else {
+            continue;
+        };
+        let Some(spans) = highlight_block(set, syntax, &text) else { continue };
+        let token = syntax.file_extensions.first().map_or(syntax.name.to_ascii_lowercase(), |e| e.to_string());
+        blocks.push((pre.id(), format!(r#"<code class="sf-hl language-{token}">{spans}</code>"#)));
+    }
+    if blocks.is_empty() {
+        return html.to_string();
+    }
+
+    // Swap each block's children for a placeholder, then splice the markup in
+    for (i, (id, _)) in blocks.iter().enumerate() {
+        let Some(mut pre) = fragment.tree.get_mut(*id) else { continue };
+        while let Some(mut child) = pre.first_child() {
+            child.detach();
+        }
+        pre.append(Node::Text(Text { text: format!("{PLACEHOLDER}{i}{PLACEHOLDER}").as_str().into() }));
+    }
+    let mut out = fragment.root_element().inner_html();
+    for (i, (_, markup)) in blocks.iter().enumerate() {
+        out = out.replacen(&format!("{PLACEHOLDER}{i}{PLACEHOLDER}"), markup, 1);
+    }
+    out
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Stylesheet for the `sf-hl-*` classes in one of the bundled themes
+/// (e.g. "base16-ocean.dark", "InspiredGitHub", "Solarized (light)").
+#[tauri::command]
+pub fn highlight_theme_css(theme: Option<String>) -> Result<String, String> {
+    let themes = ThemeSet::load_defaults();
+    let name = theme.unwrap_or_else(|| "base16-ocean.dark".into());
+    let Some(theme) = themes.themes.get(&name) else {
+        let known: Vec<&str> = themes.themes.keys().map(String::as_str).collect();
+        return Err(format!("Unknown theme '{name}'. Available: {}", known.join(", ")));
+    };
+    css_for_theme_with_class_style(theme, CLASS_STYLE).map_err(|e| format!("Failed to build theme CSS: {e}"))
+}
+
+/// Highlight the code blocks in an HTML fragment.
+#[tauri::command]
+pub fn highlight_html(html: String) -> String {
+    highlight_code_blocks(&html)
+}
@@ -0,0 +1,270 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use md5::Md5;
+use reqwest::header::{HeaderMap, HeaderValue, WWW_AUTHENTICATE};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use url::Url;
+
+// Per-host HTTP Basic / Digest credentials for private feeds (FreshRSS, intranets).
+// Usernames are kept in a JSON file; passwords only in the OS keyring.
+
+const HOSTS_FILE: &str = "http_auth_hosts.json";
+const KEYRING_SERVICE: &str = "SuperFlux HTTP auth";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AuthHost {
+    pub host: String,
+    pub username: String,
+    /// Scheme the server last asked for (`basic` or `digest`). Basic credentials
+    /// are then sent up front instead of waiting for a 401.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+}
+
+static HOSTS: OnceLock<Mutex<Vec<AuthHost>>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Passwords read from the keyring this session, by host.
+static PASSWORDS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+fn hosts_lock() -> &'static Mutex<Vec<AuthHost>> {
+    HOSTS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn passwords_lock() -> &'static Mutex<HashMap<String, String>> {
+    PASSWORDS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(HOSTS_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(hosts) = serde_json::from_str::<Vec<AuthHost>>(&json) {
+            eprintln!("[http_auth] Credentials stored for {} hosts", hosts.len());
+            *hosts_lock().lock().unwrap() = hosts;
+        }
+    }
+}
+
+fn save_hosts(hosts: &[AuthHost]) {
+    if let Some(dir) = DATA_DIR.get() {
+        if let Ok(json) = serde_json::to_string_pretty(hosts) {
+            if let Err(e) = std::fs::write(dir.join(HOSTS_FILE), json) {
+                eprintln!("[http_auth] Failed to save hosts: {e}");
+            }
+        }
+    }
+}
+
+fn keyring_entry(host: &str, username: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, &format!("{username}@{host}")).map_err(|e| format!("Keyring error: {e}"))
+}
+
+// ── Credential storage ───────────────────────────────────────────────
+
+/// Save credentials for a host, replacing any previous ones.
+pub fn store(host: &str, credentials: &Credentials) -> Result<(), String> {
+    let host = host.to_lowercase();
+    let previous = lookup_host(&host);
+    keyring_entry(&host, &credentials.username)?
+        .set_password(&credentials.password)
+        .map_err(|e| format!("Failed to store password in the keyring: {e}"))?;
+    if let Some(prev) = previous.filter(|p| p.username != credentials.username) {
+        if let Ok(entry) = keyring_entry(&host, &prev.username) {
+            let _ = entry.delete_credential();
+        }
+    }
+    passwords_lock().lock().unwrap().insert(host.clone(), credentials.password.clone());
+    let mut hosts = hosts_lock().lock().unwrap();
+    hosts.retain(|h| h.host != host);
+    hosts.push(AuthHost {
+        host,
+        username: credentials.username.clone(),
+        scheme: None,
+    });
+    hosts.sort_by(|a, b| a.host.cmp(&b.host));
+    save_hosts(&hosts);
+    Ok(())
+}
+
+fn lookup_host(host: &str) -> Option<AuthHost> {
+    hosts_lock().lock().unwrap().iter().find(|h| h.host == host).cloned()
+}
+
+fn credentials_for(host: &str) -> Option<(AuthHost, String)> {
+    let host = host.to_lowercase();
+    let record = lookup_host(&host)?;
+    if let Some(password) = passwords_lock().lock().unwrap().get(&host) {
+        return Some((record, password.clone()));
+    }
+    let password = match keyring_entry(&host, &record.username).and_then(|e| {
+        e.get_password().map_err(|e| format!("Keyring error: {e}"))
+    }) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("[http_auth] No password for {host}: {e}");
+            return None;
+        }
+    };
+    passwords_lock().lock().unwrap().insert(host, password.clone());
+    Some((record, password))
+}
+
+fn remember_scheme(host: &str, scheme: &str) {
+    let mut hosts = hosts_lock().lock().unwrap();
+    if let Some(h) = hosts.iter_mut().find(|h| h.host == host) {
+        if h.scheme.as_deref() != Some(scheme) {
+            h.scheme = Some(scheme.to_string());
+            save_hosts(&hosts);
+        }
+    }
+}
+
+// ── Authorization headers ────────────────────────────────────────────
+
+fn basic(username: &str, password: &str) -> String {
+    format!("Basic {}", STANDARD.encode(format!("{username}:{password}")))
+}
+
+/// `Authorization` to send before any challenge, for hosts known to use Basic.
+pub fn preemptive(url: &Url) -> Option<HeaderValue> {
+    let (record, password) = credentials_for(url.host_str()?)?;
+    if record.scheme.as_deref() != Some("basic") {
+        return None;
+    }
+    HeaderValue::from_str(&basic(&record.username, &password)).ok()
+}
+
+/// Parse `key=value, key="quoted, value"` challenge parameters.
+fn challenge_params(params: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    let mut rest = params.trim();
+    while !rest.is_empty() {
+        let Some((key, after)) = rest.split_once('=') else { break };
+        let key = key.trim().trim_start_matches(',').trim().to_lowercase();
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                (quoted[..end].to_string(), quoted.get(end + 1..).unwrap_or(""))
+            }
+            None => {
+                let end = after.find(',').unwrap_or(after.len());
+                (after[..end].trim().to_string(), &after[end..])
+            }
+        };
+        out.insert(key, value);
+        rest = remaining.trim_start().trim_start_matches(',').trim_start();
+    }
+    out
+}
+
+/// RFC 7616 Digest response for a GET of `url`.
+fn digest(url: &Url, username: &str, password: &str, params: &HashMap<String, String>) -> Option<String> {
+    let realm = params.get("realm").map(String::as_str).unwrap_or("");
+    let nonce = params.get("nonce")?;
+    let algorithm = params.get("algorithm").map(|a| a.to_uppercase()).unwrap_or_else(|| "MD5".into());
+    let hash = |s: String| -> String {
+        if algorithm.starts_with("SHA-256") {
+            format!("{:x}", Sha256::digest(s.as_bytes()))
+        } else {
+            format!("{:x}", Md5::digest(s.as_bytes()))
+        }
+    };
+    if !matches!(algorithm.as_str(), "MD5" | "MD5-SESS" | "SHA-256" | "SHA-256-SESS") {
+        return None;
+    }
+    let uri = match url.query() {
+        Some(q) => format!("{}?{q}", url.path()),
+        None => url.path().to_string(),
+    };
+    let cnonce = format!("{:016x}", rand::random::<u64>());
+    let nc = "00000001";
+    let mut ha1 = hash(format!("{username}:{realm}:{password}"));
+    if algorithm.ends_with("-SESS") {
+        ha1 = hash(format!("{ha1}:{nonce}:{cnonce}"));
+    }
+    let ha2 = hash(format!("GET:{uri}"));
+    let qop_auth = params
+        .get("qop")
+        .is_some_and(|q| q.split(',').any(|v| v.trim().eq_ignore_ascii_case("auth")));
+
+    let mut header = format!(r#"Digest username="{username}", realm="{realm}", nonce="{nonce}", uri="{uri}""#);
+    let response = if qop_auth {
+        header.push_str(&format!(r#", qop=auth, nc={nc}, cnonce="{cnonce}""#));
+        hash(format!("{ha1}:{nonce}:{nc}:{cnonce}:auth:{ha2}"))
+    } else {
+        hash(format!("{ha1}:{nonce}:{ha2}"))
+    };
+    header.push_str(&format!(r#", response="{response}", algorithm={algorithm}"#));
+    if let Some(opaque) = params.get("opaque") {
+        header.push_str(&format!(r#", opaque="{opaque}""#));
+    }
+    Some(header)
+}
+
+/// Answer a 401 with stored credentials, preferring Digest over Basic.
+pub fn authorization(url: &Url, headers: &HeaderMap) -> Option<HeaderValue> {
+    let host = url.host_str()?.to_lowercase();
+    let (record, password) = credentials_for(&host)?;
+    let challenges: Vec<&str> = headers
+        .get_all(WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect();
+    let find = |scheme: &str| {
+        challenges.iter().find_map(|c| {
+            let (name, params) = c.trim().split_once(' ').unwrap_or((c.trim(), ""));
+            name.eq_ignore_ascii_case(scheme).then_some(params)
+        })
+    };
+    let value = if let Some(params) = find("digest") {
+        remember_scheme(&host, "digest");
+        digest(url, &record.username, &password, &challenge_params(params))?
+    } else if find("basic").is_some() {
+        remember_scheme(&host, "basic");
+        basic(&record.username, &password)
+    } else {
+        return None;
+    };
+    HeaderValue::from_str(&value).ok()
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Hosts with stored credentials. Passwords are never returned.
+#[tauri::command]
+pub fn get_http_auth_hosts() -> Vec<AuthHost> {
+    hosts_lock().lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_http_auth(host: String, credentials: Credentials) -> Result<(), String> {
+    let host = host.trim().to_lowercase();
+    if host.is_empty() || credentials.username.is_empty() {
+        return Err("Host and username are required".into());
+    }
+    store(&host, &credentials)
+}
+
+#[tauri::command]
+pub fn delete_http_auth(host: String) -> bool {
+    let host = host.trim().to_lowercase();
+    let Some(record) = lookup_host(&host) else { return false };
+    if let Ok(entry) = keyring_entry(&host, &record.username) {
+        let _ = entry.delete_credential();
+    }
+    passwords_lock().lock().unwrap().remove(&host);
+    let mut hosts = hosts_lock().lock().unwrap();
+    hosts.retain(|h| h.host != host);
+    save_hosts(&hosts);
+    true
+}
@@ -0,0 +1,250 @@
+use reqwest::header::{
+    HeaderMap, HeaderValue, AGE, CACHE_CONTROL, CONTENT_TYPE, ETAG, EXPIRES, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED, PRAGMA, VARY,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::ingest::now_millis;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+/// Bodies larger than this are never cached.
+const MAX_ENTRY_BYTES: usize = 20 * 1024 * 1024;
+/// Oldest entries are evicted at startup beyond this total.
+const MAX_CACHE_BYTES: u64 = 300 * 1024 * 1024;
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Metadata stored next to each cached body (`<hash>.json` + `<hash>.bin`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CacheMeta {
+    pub url: String,
+    pub stored_at: u64,
+    /// Fresh until this time (ms since epoch); after that it must be revalidated.
+    pub expires_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Final URL after redirects.
+    pub final_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    pub size: u64,
+}
+
+pub struct CachedResponse {
+    pub meta: CacheMeta,
+    pub body: Vec<u8>,
+}
+
+impl CachedResponse {
+    pub fn is_fresh(&self) -> bool {
+        now_millis() < self.meta.expires_at
+    }
+
+    /// `If-None-Match` / `If-Modified-Since` headers to revalidate a stale entry.
+    pub fn validators(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(v) = self.meta.etag.as_deref().and_then(|e| HeaderValue::from_str(e).ok()) {
+            headers.insert(IF_NONE_MATCH, v);
+        }
+        if let Some(v) = self.meta.last_modified.as_deref().and_then(|l| HeaderValue::from_str(l).ok()) {
+            headers.insert(IF_MODIFIED_SINCE, v);
+        }
+        headers
+    }
+}
+
+// ── Cache-Control ────────────────────────────────────────────────────
+
+/// Freshness lifetime in ms from `Cache-Control`, falling back to `Expires`.
+/// `None` means the response must not be stored at all.
+fn freshness_ms(headers: &HeaderMap) -> Option<u64> {
+    let header = |name| headers.get(name).and_then(|v: &HeaderValue| v.to_str().ok());
+    if header(VARY).is_some_and(|v| v.trim() == "*") {
+        return None;
+    }
+    let age_ms = header(AGE).and_then(|a| a.trim().parse::<u64>().ok()).unwrap_or(0) * 1000;
+
+    let mut max_age = None;
+    let mut must_revalidate = header(PRAGMA).is_some_and(|p| p.to_ascii_lowercase().contains("no-cache"));
+    for directive in headers.get_all(CACHE_CONTROL).iter().filter_map(|v| v.to_str().ok()) {
+        for part in directive.split(',') {
+            let (name, value) = part.split_once('=').unwrap_or((part, ""));
+            match name.trim().to_ascii_lowercase().as_str() {
+                "no-store" => return None,
+                "no-cache" => must_revalidate = true,
+                // s-maxage is for shared caches, but feed CDNs often only set that
+                "max-age" | "s-maxage" => {
+                    if let Ok(secs) = value.trim().trim_matches('"').parse::<u64>() {
+                        max_age = Some(max_age.map_or(secs, |m: u64| m.max(secs)));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    if must_revalidate {
+        return Some(0);
+    }
+    if let Some(secs) = max_age {
+        return Some((secs * 1000).saturating_sub(age_ms));
+    }
+    let expires = header(EXPIRES)
+        .and_then(|e| chrono::DateTime::parse_from_rfc2822(e.trim()).ok())
+        .map(|d| d.timestamp_millis().max(0) as u64);
+    Some(expires.map_or(0, |e| e.saturating_sub(now_millis())))
+}
+
+// ── Storage ──────────────────────────────────────────────────────────
+
+/// Set the cache directory and evict old entries in the background.
+pub fn init(dir: PathBuf) {
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("[http_cache] Failed to create cache dir: {e}");
+        return;
+    }
+    if CACHE_DIR.set(dir.clone()).is_ok() {
+        std::thread::spawn(move || prune(&dir, MAX_CACHE_BYTES));
+    }
+}
+
+fn paths_for(url: &str) -> Option<(PathBuf, PathBuf)> {
+    let dir = CACHE_DIR.get()?;
+    let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+    Some((dir.join(format!("{key}.json")), dir.join(format!("{key}.bin"))))
+}
+
+pub fn get(url: &str) -> Option<CachedResponse> {
+    let (meta_path, body_path) = paths_for(url)?;
+    let meta: CacheMeta = serde_json::from_str(&std::fs::read_to_string(meta_path).ok()?).ok()?;
+    if meta.url != url {
+        return None;
+    }
+    let body = std::fs::read(body_path).ok()?;
+    Some(CachedResponse { meta, body })
+}
+
+/// Store a successful response, unless its headers forbid it. Entries that are
+/// immediately stale are only kept when they can be revalidated.
+pub fn put(url: &str, final_url: &str, headers: &HeaderMap, body: &[u8]) {
+    let Some((meta_path, body_path)) = paths_for(url) else { return };
+    if body.len() > MAX_ENTRY_BYTES {
+        return;
+    }
+    let Some(fresh_ms) = freshness_ms(headers) else {
+        remove(url);
+        return;
+    };
+    let header = |name| headers.get(name).and_then(|v: &HeaderValue| v.to_str().ok()).map(String::from);
+    let meta = CacheMeta {
+        url: url.to_string(),
+        stored_at: now_millis(),
+        expires_at: now_millis() + fresh_ms,
+        content_type: header(CONTENT_TYPE),
+        final_url: final_url.to_string(),
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+        size: body.len() as u64,
+    };
+    if fresh_ms == 0 && meta.etag.is_none() && meta.last_modified.is_none() {
+        remove(url);
+        return;
+    }
+    // Body first: a meta file without its body is never read
+    let result = std::fs::write(&body_path, body).and_then(|_| {
+        let json = serde_json::to_string(&meta).map_err(std::io::Error::other)?;
+        std::fs::write(&meta_path, json)
+    });
+    if let Err(e) = result {
+        eprintln!("[http_cache] Failed to store {url}: {e}");
+    }
+}
+
+/// After a 304, extend the entry's lifetime using the new headers and keep
+/// any new ETag / Last-Modified they carry for the next revalidation.
+pub fn refresh(url: &str, headers: &HeaderMap) {
+    let Some((meta_path, _)) = paths_for(url) else { return };
+    let Some(mut cached) = get(url) else { return };
+    let Some(fresh_ms) = freshness_ms(headers) else {
+        remove(url);
+        return;
+    };
+    let header = |name| headers.get(name).and_then(|v: &HeaderValue| v.to_str().ok()).map(String::from);
+    cached.meta.stored_at = now_millis();
+    cached.meta.expires_at = now_millis() + fresh_ms;
+    if let Some(etag) = header(ETAG) {
+        cached.meta.etag = Some(etag);
+    }
+    if let Some(last_modified) = header(LAST_MODIFIED) {
+        cached.meta.last_modified = Some(last_modified);
+    }
+    if let Ok(json) = serde_json::to_string(&cached.meta) {
+        let _ = std::fs::write(meta_path, json);
+    }
+}
+
+fn remove(url: &str) {
+    if let Some((meta_path, body_path)) = paths_for(url) {
+        let _ = std::fs::remove_file(meta_path);
+        let _ = std::fs::remove_file(body_path);
+    }
+}
+
+/// Delete entries, oldest first, until the cache holds at most `max_bytes`.
+fn prune(dir: &Path, max_bytes: u64) {
+    let Ok(read) = std::fs::read_dir(dir) else { return };
+    let mut entries: Vec<(u64, u64, PathBuf)> = read
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+        .filter_map(|e| {
+            let meta: CacheMeta = serde_json::from_str(&std::fs::read_to_string(e.path()).ok()?).ok()?;
+            Some((meta.stored_at, meta.size, e.path()))
+        })
+        .collect();
+    let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    entries.sort_by_key(|(stored_at, _, _)| *stored_at);
+    for (_, size, meta_path) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        let _ = std::fs::remove_file(meta_path.with_extension("bin"));
+        let _ = std::fs::remove_file(&meta_path);
+        total -= size;
+    }
+}
+
+fn clear_all(dir: &Path) -> u64 {
+    let Ok(read) = std::fs::read_dir(dir) else { return 0 };
+    let mut freed = 0;
+    for entry in read.flatten() {
+        let size = entry.metadata().map_or(0, |m| m.len());
+        if std::fs::remove_file(entry.path()).is_ok() {
+            freed += size;
+        }
+    }
+    freed
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Drop the cached response for `url`, or the whole cache, along with the
+/// validators `fetch_url_conditional` revalidates with. Returns bytes freed.
+#[tauri::command]
+pub async fn cache_clear(url: Option<String>) -> Result<u64, String> {
+    let dir = CACHE_DIR.get().ok_or("HTTP cache is not initialized")?.clone();
+    tauri::async_runtime::spawn_blocking(move || match url {
+        Some(url) => {
+            let size = get(&url).map_or(0, |c| c.meta.size);
+            remove(&url);
+            size
+        }
+        None => clear_all(&dir),
+    })
+    .await
+    .map_err(|e| format!("Cache clear task failed: {e}"))
+}
@@ -0,0 +1,302 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE, REFERER};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+use tauri::http::{header, Request, Response, StatusCode};
+
+// Images downloaded once and served to the webview from disk through the
+// `sfimage:` URI scheme. This gets around mixed content and hotlink blocking
+// (the download can carry the article as Referer), keeps scrolling off the
+// network and lets saved articles show their images offline. Thumbnails are
+// downscaled and re-encoded; full-size copies are stored as downloaded.
+
+pub const SCHEME: &str = "sfimage";
+/// Downloads above this are refused.
+const MAX_DOWNLOAD_BYTES: u64 = 25 * 1024 * 1024;
+/// Oldest files are evicted at startup beyond this total.
+const MAX_CACHE_BYTES: u64 = 500 * 1024 * 1024;
+const MIN_WIDTH: u32 = 16;
+const MAX_WIDTH: u32 = 4096;
+const JPEG_QUALITY: u8 = 82;
+/// Extensions a cached file can have, so a lookup by URL finds it without an index.
+const EXTENSIONS: &[&str] = &["jpg", "png", "gif", "webp", "avif", "svg", "bmp", "ico"];
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Clone, Serialize, Debug)]
+pub struct CachedImage {
+    /// `sfimage:` URL for `<img src>`.
+    pub url: String,
+    pub path: String,
+    pub content_type: String,
+    pub size: u64,
+    /// Pixel size, when the image could be decoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+}
+
+/// Set the cache directory and evict old files in the background.
+pub fn init(dir: PathBuf) {
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("[image_cache] Failed to create cache dir: {e}");
+        return;
+    }
+    if CACHE_DIR.set(dir.clone()).is_ok() {
+        std::thread::spawn(move || prune(&dir, MAX_CACHE_BYTES));
+    }
+}
+
+fn cache_dir() -> Result<&'static PathBuf, String> {
+    CACHE_DIR.get().ok_or_else(|| "Image cache is not initialized".to_string())
+}
+
+/// One file per source URL and size.
+fn key_for(url: &str, max_width: Option<u32>) -> String {
+    let variant = max_width.map_or_else(|| "full".to_string(), |w| format!("w{w}"));
+    format!("{:x}", Sha256::digest(format!("{variant}|{url}").as_bytes()))
+}
+
+fn mime_for(ext: &str) -> &'static str {
+    match ext {
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        _ => "image/jpeg",
+    }
+}
+
+fn extension_for(content_type: Option<&str>, bytes: &[u8]) -> &'static str {
+    let from_header = match content_type.unwrap_or_default() {
+        "image/png" => Some("png"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "image/avif" => Some("avif"),
+        "image/svg+xml" => Some("svg"),
+        "image/bmp" => Some("bmp"),
+        "image/x-icon" | "image/vnd.microsoft.icon" => Some("ico"),
+        "image/jpeg" | "image/jpg" | "image/pjpeg" => Some("jpg"),
+        _ => None,
+    };
+    // Servers often send images as application/octet-stream
+    from_header
+        .or_else(|| match image::guess_format(bytes).ok()? {
+            ImageFormat::Png => Some("png"),
+            ImageFormat::Gif => Some("gif"),
+            ImageFormat::WebP => Some("webp"),
+            ImageFormat::Avif => Some("avif"),
+            ImageFormat::Bmp => Some("bmp"),
+            ImageFormat::Ico => Some("ico"),
+            _ => None,
+        })
+        .unwrap_or("jpg")
+}
+
+fn asset_url(file: &str) -> String {
+    // WebView2 and Android only load custom schemes as http://<scheme>.localhost
+    if cfg!(any(target_os = "windows", target_os = "android")) {
+        format!("http://{SCHEME}.localhost/{file}")
+    } else {
+        format!("{SCHEME}://localhost/{file}")
+    }
+}
+
+fn describe(path: &Path, dimensions: Option<(u32, u32)>) -> Option<CachedImage> {
+    let file = path.file_name()?.to_str()?;
+    let ext = path.extension()?.to_str()?;
+    let size = std::fs::metadata(path).ok()?.len();
+    Some(CachedImage {
+        url: asset_url(file),
+        path: path.to_string_lossy().to_string(),
+        content_type: mime_for(ext).to_string(),
+        size,
+        width: dimensions.map(|(w, _)| w),
+        height: dimensions.map(|(_, h)| h),
+    })
+}
+
+fn find_cached(dir: &Path, key: &str) -> Option<PathBuf> {
+    EXTENSIONS.iter().map(|ext| dir.join(format!("{key}.{ext}"))).find(|p| p.is_file())
+}
+
+// ── Processing ───────────────────────────────────────────────────────
+
+/// Image bytes, their extension and pixel size (None if not decoded).
+type Processed = (Vec<u8>, &'static str, Option<(u32, u32)>);
+
+/// Downscale to `max_width` and re-encode: PNG when the image has transparency,
+/// JPEG otherwise. Animated GIFs, SVG and formats that can't be decoded are kept
+/// as they are.
+fn process(bytes: Vec<u8>, ext: &'static str, max_width: Option<u32>) -> Processed {
+    if matches!(ext, "svg" | "gif") {
+        return (bytes, ext, None);
+    }
+    let Ok(decoded) = image::load_from_memory(&bytes) else { return (bytes, ext, None) };
+    let original = (decoded.width(), decoded.height());
+    let Some(max_width) = max_width.filter(|w| *w < decoded.width()) else {
+        return (bytes, ext, Some(original));
+    };
+
+    let resized = decoded.resize(max_width, u32::MAX, FilterType::Triangle);
+    let dimensions = (resized.width(), resized.height());
+    let mut out = Vec::new();
+    let encoded = if resized.color().has_alpha() {
+        resized.write_with_encoder(PngEncoder::new(&mut out)).map(|_| "png")
+    } else {
+        let rgb = DynamicImage::ImageRgb8(resized.to_rgb8());
+        rgb.write_with_encoder(JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY)).map(|_| "jpg")
+    };
+    match encoded {
+        Ok(ext) => (out, ext, Some(dimensions)),
+        Err(e) => {
+            eprintln!("[image_cache] Re-encoding failed: {e}");
+            (bytes, ext, Some(original))
+        }
+    }
+}
+
+pub async fn download(url: &str, referer: Option<&str>) -> Result<(Vec<u8>, Option<String>), String> {
+    let mut extra = HeaderMap::new();
+    extra.insert(ACCEPT, HeaderValue::from_static("image/avif,image/webp,image/png,image/jpeg,image/*;q=0.8"));
+    if let Some(value) = referer.and_then(|r| HeaderValue::from_str(r).ok()) {
+        extra.insert(REFERER, value);
+    }
+    let response = crate::send_get(url, extra).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or_default().trim().to_ascii_lowercase());
+    if content_type.as_deref().is_some_and(|t| t.starts_with("text/") || t.contains("html")) {
+        return Err(format!("Not an image: {}", content_type.unwrap_or_default()));
+    }
+    let bytes = crate::response_limit::read_bytes(response, MAX_DOWNLOAD_BYTES)
+        .await
+        .map_err(|e| format!("Image not cached: {e}"))?;
+    Ok((bytes, content_type))
+}
+
+/// The cached copy of `url` at `max_width`, downloading it first if needed.
+pub async fn cache(url: &str, max_width: Option<u32>, referer: Option<&str>) -> Result<CachedImage, String> {
+    let dir = cache_dir()?;
+    let key = key_for(url, max_width);
+    if let Some(path) = find_cached(dir, &key) {
+        if let Some(cached) = describe(&path, None) {
+            return Ok(cached);
+        }
+    }
+    let (bytes, content_type) = download(url, referer).await?;
+    let dir = dir.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let ext = extension_for(content_type.as_deref(), &bytes);
+        let (bytes, ext, dimensions) = process(bytes, ext, max_width);
+        let path = dir.join(format!("{key}.{ext}"));
+        // Written under a temp name so a half-written file is never served
+        let partial = path.with_extension("part");
+        std::fs::write(&partial, &bytes).map_err(|e| format!("Failed to write cached image: {e}"))?;
+        std::fs::rename(&partial, &path).map_err(|e| format!("Failed to write cached image: {e}"))?;
+        describe(&path, dimensions).ok_or_else(|| "Cached image disappeared".to_string())
+    })
+    .await
+    .map_err(|e| format!("Image task failed: {e}"))?
+}
+
+// ── Storage ──────────────────────────────────────────────────────────
+
+fn prune(dir: &Path, max_bytes: u64) {
+    let Ok(read) = std::fs::read_dir(dir) else { return };
+    let mut entries: Vec<(SystemTime, u64, PathBuf)> = read
+        .flatten()
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some((meta.modified().unwrap_or(SystemTime::UNIX_EPOCH), meta.len(), e.path()))
+        })
+        .collect();
+    let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    entries.sort_by_key(|(modified, _, _)| *modified);
+    for (_, size, path) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total -= size;
+        }
+    }
+}
+
+/// Serve a cached file for the `sfimage:` scheme. Only bare file names from
+/// the cache directory are answered.
+pub fn serve(request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let file = request.uri().path().trim_start_matches('/');
+    let valid = file.split_once('.').is_some_and(|(key, ext)| {
+        key.len() == 64 && key.chars().all(|c| c.is_ascii_hexdigit()) && EXTENSIONS.contains(&ext)
+    });
+    let body = CACHE_DIR.get().filter(|_| valid).and_then(|dir| std::fs::read(dir.join(file)).ok());
+    let builder = Response::builder().header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+    let response = match body {
+        Some(body) => {
+            let ext = file.rsplit('.').next().unwrap_or_default();
+            builder
+                .header(header::CONTENT_TYPE, mime_for(ext))
+                // The name is a hash of the source, so the content never changes
+                .header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+                .body(body)
+        }
+        None => builder.status(StatusCode::NOT_FOUND).body(Vec::new()),
+    };
+    response.unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Download `url` into the image cache and return a local URL for it.
+/// `max_width` gives a downscaled, re-encoded thumbnail; `referer` is sent
+/// to hosts that block hotlinking.
+#[tauri::command]
+pub async fn cache_image(
+    url: String,
+    max_width: Option<u32>,
+    referer: Option<String>,
+) -> Result<CachedImage, String> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("Only http(s) images can be cached".into());
+    }
+    if max_width.is_some_and(|w| !(MIN_WIDTH..=MAX_WIDTH).contains(&w)) {
+        return Err(format!("max_width must be between {MIN_WIDTH} and {MAX_WIDTH}"));
+    }
+    cache(&url, max_width, referer.as_deref()).await
+}
+
+/// Delete every cached image. Returns bytes freed.
+#[tauri::command]
+pub async fn clear_image_cache() -> Result<u64, String> {
+    let dir = cache_dir()?.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Ok(read) = std::fs::read_dir(&dir) else { return 0 };
+        let mut freed = 0;
+        for entry in read.flatten() {
+            let size = entry.metadata().map_or(0, |m| m.len());
+            if std::fs::remove_file(entry.path()).is_ok() {
+                freed += size;
+            }
+        }
+        freed
+    })
+    .await
+    .map_err(|e| format!("Image cache clear failed: {e}"))
+}
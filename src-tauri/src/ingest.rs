@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::feed_stats::FeedStatsStore;
+use crate::gallery::GalleryStore;
+use crate::text::{snippet_from_html, SNIPPET_LEN};
+use crate::trending::TrendingStore;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const ITEMS_FILE: &str = "recent_items.json";
+/// Rolling window kept for analysis (clustering, trending, …).
+const MAX_ITEM_AGE_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+const MAX_ITEMS: usize = 20_000;
+
+/// An item as parsed by the frontend, pushed to the backend after each refresh.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct IngestItem {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub author: String,
+    /// Plain-text excerpt. HTML is reduced to a snippet at ingest.
+    #[serde(default)]
+    pub summary: String,
+    /// Publication time in ms since epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+    /// Full HTML content, scanned for inline images at ingest. Not persisted here.
+    #[serde(default, skip_serializing)]
+    pub content: String,
+    /// media:content / enclosures as parsed by the frontend. Not persisted here.
+    #[serde(default, skip_serializing)]
+    pub media: Vec<MediaInput>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MediaInput {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StoredItem {
+    #[serde(flatten)]
+    pub item: IngestItem,
+    pub feed_url: String,
+    #[serde(default)]
+    pub feed_name: String,
+    pub ingested_at: u64,
+}
+
+impl StoredItem {
+    /// Publication time, falling back to ingest time for missing or future dates.
+    pub fn timestamp(&self) -> u64 {
+        match self.item.published_at {
+            Some(ts) if ts > 0 && ts <= self.ingested_at + 3_600_000 => ts,
+            _ => self.ingested_at,
+        }
+    }
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct IngestStore {
+    items: Mutex<HashMap<String, StoredItem>>,
+    data_dir: Mutex<Option<PathBuf>>,
+    dirty: AtomicBool,
+}
+
+impl IngestStore {
+    pub fn new() -> Self {
+        IngestStore {
+            items: Mutex::new(HashMap::new()),
+            data_dir: Mutex::new(None),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(ITEMS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(items) = serde_json::from_str::<Vec<StoredItem>>(&json) {
+                            eprintln!("[ingest] Loaded {} recent items", items.len());
+                            *self.items.lock().unwrap() =
+                                items.into_iter().map(|i| (i.item.id.clone(), i)).collect();
+                        }
+                    }
+                    Err(e) => eprintln!("[ingest] Failed to read items file: {e}"),
+                }
+            }
+        }
+    }
+
+    pub fn flush(&self) {
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        self.prune();
+        if let Some(path) = self.file_path() {
+            let items = self.items.lock().unwrap();
+            let list: Vec<&StoredItem> = items.values().collect();
+            match serde_json::to_string(&list) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[ingest] Failed to write items file: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[ingest] Failed to serialize items: {e}"),
+            }
+        }
+    }
+
+    fn prune(&self) {
+        let cutoff = now_millis().saturating_sub(MAX_ITEM_AGE_MS);
+        let mut items = self.items.lock().unwrap();
+        items.retain(|_, i| i.ingested_at >= cutoff);
+        if items.len() > MAX_ITEMS {
+            let mut by_age: Vec<(u64, String)> =
+                items.values().map(|i| (i.ingested_at, i.item.id.clone())).collect();
+            by_age.sort();
+            let excess = items.len() - MAX_ITEMS;
+            for (_, id) in by_age.into_iter().take(excess) {
+                items.remove(&id);
+            }
+        }
+    }
+
+    /// Store items, returning only those not seen before.
+    pub fn ingest(&self, feed_url: &str, feed_name: &str, items: Vec<IngestItem>) -> Vec<StoredItem> {
+        let now = now_millis();
+        let mut store = self.items.lock().unwrap();
+        let mut fresh = Vec::new();
+        for mut item in items {
+            if item.id.is_empty() || store.contains_key(&item.id) {
+                continue;
+            }
+            let source = if item.summary.trim().is_empty() { &item.content } else { &item.summary };
+            item.summary = snippet_from_html(source, SNIPPET_LEN);
+            let stored = StoredItem {
+                item,
+                feed_url: feed_url.to_string(),
+                feed_name: feed_name.to_string(),
+                ingested_at: now,
+            };
+            // Content and media are only needed by the ingest hooks, not kept in memory
+            let mut kept = stored.clone();
+            kept.item.content = String::new();
+            kept.item.media = Vec::new();
+            store.insert(kept.item.id.clone(), kept);
+            fresh.push(stored);
+        }
+        if !fresh.is_empty() {
+            self.dirty.store(true, Ordering::SeqCst);
+        }
+        fresh
+    }
+
+    /// Items whose timestamp is at or after `since_ms`.
+    pub fn items_since(&self, since_ms: u64) -> Vec<StoredItem> {
+        self.items
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| i.timestamp() >= since_ms)
+            .cloned()
+            .collect()
+    }
+}
+
+pub fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Resolve a range like `today`, `24h`, `7d` into a start timestamp (ms).
+pub fn range_start_millis(range: &str) -> Result<u64, String> {
+    let range = range.trim().to_lowercase();
+    if range == "today" {
+        let midnight = chrono::Local::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .and_then(|d| d.and_local_timezone(chrono::Local).earliest())
+            .ok_or("Cannot resolve local midnight")?;
+        return Ok(midnight.timestamp_millis().max(0) as u64);
+    }
+    let (num, unit_ms) = if let Some(num) = range.strip_suffix('h') {
+        (num, 3_600_000u64)
+    } else if let Some(num) = range.strip_suffix('d') {
+        (num, 86_400_000)
+    } else {
+        return Err(format!("Invalid range: {range}"));
+    };
+    let n: u64 = num.parse().map_err(|_| format!("Invalid range: {range}"))?;
+    let span = n.checked_mul(unit_ms).ok_or_else(|| format!("Range too large: {range}"))?;
+    Ok(now_millis().saturating_sub(span))
+}
+
+pub fn start_flush_timer(store: Arc<IngestStore>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(30));
+        store.flush();
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Push freshly parsed items for a feed. Returns how many were new.
+#[tauri::command]
+pub fn ingest_items(
+    feed_url: String,
+    feed_name: String,
+    items: Vec<IngestItem>,
+    store: tauri::State<'_, Arc<IngestStore>>,
+    stats: tauri::State<'_, Arc<FeedStatsStore>>,
+    trending: tauri::State<'_, Arc<TrendingStore>>,
+    gallery: tauri::State<'_, Arc<GalleryStore>>,
+) -> usize {
+    let fresh = store.ingest(&feed_url, &feed_name, items);
+    if !fresh.is_empty() {
+        stats.record_new_items(&feed_url, &feed_name, fresh.len() as u32);
+        trending.record(&fresh);
+        gallery.record(&fresh);
+    }
+    fresh.len()
+}
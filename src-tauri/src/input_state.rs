@@ -0,0 +1,87 @@
+use serde::Serialize;
+use std::time::Duration;
+use tauri::Emitter;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Lock keys and keyboard layout for the collapsed bar's status strip.
+/// Fields are `None` where the platform does not expose them.
+#[derive(Clone, Serialize, Debug, PartialEq, Default)]
+pub struct InputState {
+    pub caps_lock: Option<bool>,
+    pub num_lock: Option<bool>,
+    pub scroll_lock: Option<bool>,
+    /// BCP 47 tag of the foreground window's layout, e.g. `fr-FR`.
+    pub keyboard_layout: Option<String>,
+}
+
+// ── Platform ─────────────────────────────────────────────────────────
+
+#[cfg(target_os = "windows")]
+fn read_state() -> InputState {
+    extern "system" {
+        fn GetKeyState(vk: i32) -> i16;
+        fn GetForegroundWindow() -> isize;
+        fn GetWindowThreadProcessId(hwnd: isize, pid: *mut u32) -> u32;
+        fn GetKeyboardLayout(thread_id: u32) -> isize;
+        fn LCIDToLocaleName(locale: u32, name: *mut u16, len: i32, flags: u32) -> i32;
+    }
+    const VK_CAPITAL: i32 = 0x14;
+    const VK_NUMLOCK: i32 = 0x90;
+    const VK_SCROLL: i32 = 0x91;
+    const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+    // The low bit of GetKeyState is the toggle state
+    let toggled = |vk| unsafe { GetKeyState(vk) & 1 == 1 };
+
+    // The layout is per thread, so read the one of whatever window has focus
+    let keyboard_layout = unsafe {
+        let thread = GetWindowThreadProcessId(GetForegroundWindow(), std::ptr::null_mut());
+        let lang_id = (GetKeyboardLayout(thread) as usize & 0xFFFF) as u32;
+        let mut buf = [0u16; LOCALE_NAME_MAX_LENGTH];
+        let len = LCIDToLocaleName(lang_id, buf.as_mut_ptr(), buf.len() as i32, 0);
+        // len includes the terminating NUL
+        (len > 1).then(|| String::from_utf16_lossy(&buf[..len as usize - 1]))
+    };
+
+    InputState {
+        caps_lock: Some(toggled(VK_CAPITAL)),
+        num_lock: Some(toggled(VK_NUMLOCK)),
+        scroll_lock: Some(toggled(VK_SCROLL)),
+        keyboard_layout,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_state() -> InputState {
+    InputState::default()
+}
+
+/// Poll the input state and emit `input-state-changed` whenever it changes,
+/// alongside the CPU/memory/network stats the bar already shows.
+pub fn start_poller(app: tauri::AppHandle) {
+    if !cfg!(target_os = "windows") {
+        return;
+    }
+    std::thread::spawn(move || {
+        let mut last: Option<InputState> = None;
+        loop {
+            let state = read_state();
+            if last.as_ref() != Some(&state) {
+                let _ = app.emit("input-state-changed", &state);
+                last = Some(state);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Current state, for the initial render before the first change event.
+#[tauri::command]
+pub fn get_input_state() -> InputState {
+    read_state()
+}
@@ -0,0 +1,337 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::header::{HeaderMap, AUTHORIZATION, WWW_AUTHENTICATE};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use url::Url;
+
+// Windows integrated authentication (NTLM / Kerberos via "Negotiate") for intranet
+// SharePoint/Confluence feeds. Opt-in per host: the current user's logon session is
+// sent to whichever server asks, so it must never be used for arbitrary hosts.
+// GSSAPI (macOS/Linux) is not wired up; those platforms get a clear error instead.
+
+const HOSTS_FILE: &str = "integrated_auth_hosts.json";
+const MAX_LEGS: usize = 3;
+
+static ENABLED_HOSTS: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn hosts_lock() -> &'static Mutex<Vec<String>> {
+    ENABLED_HOSTS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(HOSTS_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(hosts) = serde_json::from_str::<Vec<String>>(&json) {
+            eprintln!("[integrated_auth] {} hosts enabled", hosts.len());
+            *hosts_lock().lock().unwrap() = hosts;
+        }
+    }
+}
+
+fn save_hosts(hosts: &[String]) {
+    if let Some(dir) = DATA_DIR.get() {
+        if let Ok(json) = serde_json::to_string_pretty(hosts) {
+            if let Err(e) = std::fs::write(dir.join(HOSTS_FILE), json) {
+                eprintln!("[integrated_auth] Failed to save hosts: {e}");
+            }
+        }
+    }
+}
+
+fn host_enabled(host: &str) -> bool {
+    let host = host.to_lowercase();
+    hosts_lock()
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|h| host == *h || host.ends_with(&format!(".{h}")))
+}
+
+/// If the host is opted in and the 401 offers Negotiate or NTLM, return the scheme to use.
+pub fn challenge_scheme(url: &Url, headers: &HeaderMap) -> Option<&'static str> {
+    if !host_enabled(url.host_str()?) {
+        return None;
+    }
+    let offered: Vec<String> = headers
+        .get_all(WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .map(|v| v.trim().to_lowercase())
+        .collect();
+    if offered.iter().any(|v| v.starts_with("negotiate")) {
+        Some("Negotiate")
+    } else if offered.iter().any(|v| v.starts_with("ntlm")) {
+        Some("NTLM")
+    } else {
+        None
+    }
+}
+
+fn server_token(headers: &HeaderMap, scheme: &str) -> Option<Vec<u8>> {
+    headers
+        .get_all(WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|v| {
+            let (name, token) = v.trim().split_once(' ')?;
+            if name.eq_ignore_ascii_case(scheme) {
+                STANDARD.decode(token.trim()).ok()
+            } else {
+                None
+            }
+        })
+}
+
+/// Replay a GET with the SSPI handshake. NTLM needs the same connection across legs,
+/// which the pooled client gives us for sequential keep-alive requests.
+pub async fn negotiate(
+    client: &reqwest::Client,
+    url: &Url,
+    headers: HeaderMap,
+    scheme: &str,
+) -> Result<reqwest::Response, String> {
+    let host = url.host_str().ok_or("URL has no host")?;
+    let mut ctx = sspi::SecurityContext::new(scheme, &format!("HTTP/{host}"))?;
+    let mut input: Option<Vec<u8>> = None;
+
+    for leg in 0..MAX_LEGS {
+        let token = ctx.step(input.as_deref())?;
+        eprintln!("[integrated_auth] {scheme} leg {} for {host}", leg + 1);
+        let response = client
+            .get(url.as_str())
+            .headers(headers.clone())
+            .header(AUTHORIZATION, format!("{scheme} {}", STANDARD.encode(&token)))
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {e}"))?;
+
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        match server_token(response.headers(), scheme) {
+            Some(challenge) if !ctx.is_complete() => input = Some(challenge),
+            _ => return Err(format!("{scheme} authentication rejected by {host}")),
+        }
+    }
+    Err(format!("{scheme} handshake with {host} did not complete"))
+}
+
+#[cfg(target_os = "windows")]
+mod sspi {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    struct SecHandle {
+        lower: usize,
+        upper: usize,
+    }
+
+    #[repr(C)]
+    struct SecBuffer {
+        cb_buffer: u32,
+        buffer_type: u32,
+        pv_buffer: *mut c_void,
+    }
+
+    #[repr(C)]
+    struct SecBufferDesc {
+        version: u32,
+        c_buffers: u32,
+        p_buffers: *mut SecBuffer,
+    }
+
+    #[link(name = "secur32")]
+    extern "system" {
+        fn AcquireCredentialsHandleW(
+            principal: *const u16, package: *const u16, cred_use: u32,
+            logon_id: *mut c_void, auth_data: *mut c_void,
+            get_key_fn: *mut c_void, get_key_arg: *mut c_void,
+            credential: *mut SecHandle, expiry: *mut i64,
+        ) -> i32;
+        fn InitializeSecurityContextW(
+            credential: *mut SecHandle, context: *mut SecHandle, target_name: *const u16,
+            context_req: u32, reserved1: u32, target_data_rep: u32,
+            input: *mut SecBufferDesc, reserved2: u32, new_context: *mut SecHandle,
+            output: *mut SecBufferDesc, context_attr: *mut u32, expiry: *mut i64,
+        ) -> i32;
+        fn FreeContextBuffer(buffer: *mut c_void) -> i32;
+        fn FreeCredentialsHandle(credential: *mut SecHandle) -> i32;
+        fn DeleteSecurityContext(context: *mut SecHandle) -> i32;
+    }
+
+    const SECPKG_CRED_OUTBOUND: u32 = 2;
+    const ISC_REQ_MUTUAL_AUTH: u32 = 0x2;
+    const ISC_REQ_ALLOCATE_MEMORY: u32 = 0x100;
+    const ISC_REQ_CONNECTION: u32 = 0x800;
+    const SECURITY_NATIVE_DREP: u32 = 0x10;
+    const SECBUFFER_VERSION: u32 = 0;
+    const SECBUFFER_TOKEN: u32 = 2;
+    const SEC_E_OK: i32 = 0;
+    const SEC_I_CONTINUE_NEEDED: i32 = 0x0009_0312;
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    /// Client-side SSPI context using the logged-in user's credentials.
+    pub struct SecurityContext {
+        cred: SecHandle,
+        ctx: Option<SecHandle>,
+        target: Vec<u16>,
+        complete: bool,
+    }
+
+    impl SecurityContext {
+        pub fn new(package: &str, spn: &str) -> Result<Self, String> {
+            let mut cred = SecHandle::default();
+            let mut expiry = 0i64;
+            let package = wide(package);
+            let status = unsafe {
+                AcquireCredentialsHandleW(
+                    std::ptr::null(), package.as_ptr(), SECPKG_CRED_OUTBOUND,
+                    std::ptr::null_mut(), std::ptr::null_mut(),
+                    std::ptr::null_mut(), std::ptr::null_mut(),
+                    &mut cred, &mut expiry,
+                )
+            };
+            if status != SEC_E_OK {
+                return Err(format!("AcquireCredentialsHandle failed: 0x{status:08X}"));
+            }
+            Ok(SecurityContext {
+                cred,
+                ctx: None,
+                target: wide(spn),
+                complete: false,
+            })
+        }
+
+        pub fn is_complete(&self) -> bool {
+            self.complete
+        }
+
+        /// Feed the server's token (if any) and produce the next client token.
+        pub fn step(&mut self, input: Option<&[u8]>) -> Result<Vec<u8>, String> {
+            let mut input_copy = input.map(|b| b.to_vec()).unwrap_or_default();
+            let mut in_buf = SecBuffer {
+                cb_buffer: input_copy.len() as u32,
+                buffer_type: SECBUFFER_TOKEN,
+                pv_buffer: input_copy.as_mut_ptr() as *mut c_void,
+            };
+            let mut in_desc = SecBufferDesc {
+                version: SECBUFFER_VERSION,
+                c_buffers: 1,
+                p_buffers: &mut in_buf,
+            };
+            let mut out_buf = SecBuffer {
+                cb_buffer: 0,
+                buffer_type: SECBUFFER_TOKEN,
+                pv_buffer: std::ptr::null_mut(),
+            };
+            let mut out_desc = SecBufferDesc {
+                version: SECBUFFER_VERSION,
+                c_buffers: 1,
+                p_buffers: &mut out_buf,
+            };
+            let mut new_ctx = self.ctx.unwrap_or_default();
+            let mut attrs = 0u32;
+            let mut expiry = 0i64;
+
+            let status = unsafe {
+                InitializeSecurityContextW(
+                    &mut self.cred,
+                    match self.ctx.as_mut() {
+                        Some(c) => c as *mut SecHandle,
+                        None => std::ptr::null_mut(),
+                    },
+                    self.target.as_ptr(),
+                    ISC_REQ_ALLOCATE_MEMORY | ISC_REQ_CONNECTION | ISC_REQ_MUTUAL_AUTH,
+                    0,
+                    SECURITY_NATIVE_DREP,
+                    if input.is_some() { &mut in_desc } else { std::ptr::null_mut() },
+                    0,
+                    &mut new_ctx,
+                    &mut out_desc,
+                    &mut attrs,
+                    &mut expiry,
+                )
+            };
+            self.ctx = Some(new_ctx);
+
+            let token = if out_buf.pv_buffer.is_null() {
+                Vec::new()
+            } else {
+                let bytes = unsafe {
+                    std::slice::from_raw_parts(out_buf.pv_buffer as *const u8, out_buf.cb_buffer as usize)
+                        .to_vec()
+                };
+                unsafe { FreeContextBuffer(out_buf.pv_buffer) };
+                bytes
+            };
+
+            match status {
+                SEC_E_OK => self.complete = true,
+                SEC_I_CONTINUE_NEEDED => {}
+                other => return Err(format!("InitializeSecurityContext failed: 0x{other:08X}")),
+            }
+            if token.is_empty() {
+                return Err("SSPI produced an empty token".into());
+            }
+            Ok(token)
+        }
+    }
+
+    impl Drop for SecurityContext {
+        fn drop(&mut self) {
+            unsafe {
+                if let Some(ref mut c) = self.ctx {
+                    DeleteSecurityContext(c);
+                }
+                FreeCredentialsHandle(&mut self.cred);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod sspi {
+    pub struct SecurityContext;
+
+    impl SecurityContext {
+        pub fn new(_package: &str, _spn: &str) -> Result<Self, String> {
+            Err("Integrated Windows authentication is only available on Windows".into())
+        }
+
+        pub fn is_complete(&self) -> bool {
+            true
+        }
+
+        pub fn step(&mut self, _input: Option<&[u8]>) -> Result<Vec<u8>, String> {
+            Err("Integrated Windows authentication is only available on Windows".into())
+        }
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_integrated_auth_hosts() -> Vec<String> {
+    hosts_lock().lock().unwrap().clone()
+}
+
+/// Hosts (and their subdomains) allowed to receive the user's Windows logon via NTLM/Negotiate.
+#[tauri::command]
+pub fn set_integrated_auth_hosts(hosts: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = hosts
+        .into_iter()
+        .map(|h| h.trim().trim_start_matches("*.").to_lowercase())
+        .filter(|h| !h.is_empty())
+        .collect();
+    cleaned.sort();
+    cleaned.dedup();
+    *hosts_lock().lock().unwrap() = cleaned.clone();
+    save_hosts(&cleaned);
+    cleaned
+}
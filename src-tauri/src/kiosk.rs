@@ -0,0 +1,672 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{Datelike, NaiveTime, Timelike};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Emitter;
+
+use crate::db::{self, ArticleQuery, Database};
+use crate::http_cache;
+use crate::ingest::now_millis;
+
+// Kiosk mode: the main window goes fullscreen and the backend rotates through
+// the latest headlines, for a wall or lobby screen nobody touches. Each step is
+// a `kiosk-slide` event with the image already downloaded (as a data URL), so
+// transitions never wait on the network. Outside the active hours the screen is
+// blanked, and static parts drift and periodically go black to avoid burn-in.
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SETTINGS_FILE: &str = "kiosk_settings.json";
+const TICK: Duration = Duration::from_millis(500);
+const MIN_INTERVAL_SECS: u32 = 5;
+const MAX_INTERVAL_SECS: u32 = 60 * 60;
+const MAX_SLIDES: u32 = 200;
+/// The slide list is rebuilt from the database this often, picking up new articles.
+const RELOAD_INTERVAL_MS: u64 = 5 * 60_000;
+/// Images above this are shown as headlines only.
+const MAX_IMAGE_BYTES: usize = 8 * 1024 * 1024;
+/// Largest drift of the slide content from its place, in CSS pixels.
+const MAX_SHIFT_PX: f64 = 12.0;
+/// Layout variants the frontend alternates between (image left, right, full-bleed).
+const LAYOUTS: u32 = 3;
+/// How often the screen goes black for `PIXEL_REFRESH_MS`, with burn-in protection on.
+const PIXEL_REFRESH_EVERY_MS: u64 = 60 * 60_000;
+const PIXEL_REFRESH_MS: u64 = 10_000;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ActiveHours {
+    /// Local time, `HH:MM`. An end before the start spans midnight.
+    pub start: String,
+    pub end: String,
+    /// ISO weekdays (1 = Monday). Empty = every day.
+    #[serde(default)]
+    pub days: Vec<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct KioskSettings {
+    /// Seconds each headline stays on screen.
+    pub interval_secs: u32,
+    /// Folder paths to draw from, subfolders included. Empty = every feed.
+    pub folders: Vec<String>,
+    pub unread_only: bool,
+    /// Only articles published within this many hours.
+    pub max_age_hours: u32,
+    pub max_slides: u32,
+    /// Skip articles without a thumbnail.
+    pub images_only: bool,
+    /// The screen is blanked outside these hours. None = always on.
+    pub active_hours: Option<ActiveHours>,
+    /// Drift, alternate layouts and a periodic black screen, for OLED and plasma panels.
+    pub burn_in_protection: bool,
+    pub hide_cursor: bool,
+}
+
+impl Default for KioskSettings {
+    fn default() -> Self {
+        KioskSettings {
+            interval_secs: 15,
+            folders: Vec::new(),
+            unread_only: false,
+            max_age_hours: 48,
+            max_slides: 40,
+            images_only: false,
+            active_hours: None,
+            burn_in_protection: true,
+            hide_cursor: true,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct KioskSlide {
+    pub article_id: String,
+    pub feed_id: String,
+    pub feed_title: String,
+    pub title: String,
+    pub snippet: String,
+    pub author: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    /// The downloaded image as a `data:` URL, set shortly before the slide is shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+#[derive(Clone, Copy, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Transition {
+    Fade,
+    Slide,
+    Zoom,
+}
+
+/// Payload of `kiosk-slide`.
+#[derive(Clone, Serialize, Debug)]
+pub struct SlideEvent {
+    pub index: usize,
+    pub total: usize,
+    pub slide: KioskSlide,
+    pub transition: Transition,
+    /// Index into the frontend's layout variants.
+    pub layout: u32,
+    /// Offset to apply to the whole slide, in CSS pixels.
+    pub shift_x: i32,
+    pub shift_y: i32,
+    /// When the next slide is due (ms since epoch), for a progress bar.
+    pub next_at: u64,
+}
+
+#[derive(Clone, Copy, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KioskPhase {
+    Showing,
+    /// Outside the active hours; the frontend shows a black screen.
+    Asleep,
+    /// A short black screen to let the panel recover.
+    PixelRefresh,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct KioskStatus {
+    pub phase: KioskPhase,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    pub total: usize,
+    pub started_at: u64,
+    pub settings: KioskSettings,
+}
+
+/// A running rotation. Replaced as a whole by `start_kiosk`, so the loop of an
+/// older one notices and exits.
+struct Rotation {
+    id: String,
+    settings: KioskSettings,
+    slides: Vec<KioskSlide>,
+    index: Option<usize>,
+    /// Direction of the next step, set by `kiosk_next` / `kiosk_previous`.
+    step: isize,
+    /// Slides shown so far, which drives the burn-in drift.
+    shown: u32,
+    next_at: u64,
+    loaded_at: Option<u64>,
+    phase: KioskPhase,
+    /// While in `PixelRefresh`, when it ends.
+    refresh_until: u64,
+    last_refresh: u64,
+    started_at: u64,
+    was_fullscreen: bool,
+}
+
+/// What the rotation loop should do after a tick.
+struct Tick {
+    phase_changed: bool,
+    due: bool,
+    reload: bool,
+}
+
+impl Rotation {
+    fn status(&self) -> KioskStatus {
+        KioskStatus {
+            phase: self.phase,
+            index: self.index,
+            total: self.slides.len(),
+            started_at: self.started_at,
+            settings: self.settings.clone(),
+        }
+    }
+
+    fn tick(&mut self, now: u64, awake: bool) -> Tick {
+        let before = self.phase;
+        self.phase = match self.phase {
+            _ if !awake => KioskPhase::Asleep,
+            KioskPhase::PixelRefresh if now < self.refresh_until => KioskPhase::PixelRefresh,
+            _ if self.settings.burn_in_protection
+                && now.saturating_sub(self.last_refresh) >= PIXEL_REFRESH_EVERY_MS =>
+            {
+                self.refresh_until = now + PIXEL_REFRESH_MS;
+                self.last_refresh = now;
+                KioskPhase::PixelRefresh
+            }
+            _ => KioskPhase::Showing,
+        };
+        // Coming back to the screen, show a slide right away
+        if before != KioskPhase::Showing && self.phase == KioskPhase::Showing {
+            self.next_at = now;
+        }
+        Tick {
+            phase_changed: before != self.phase,
+            due: self.phase == KioskPhase::Showing && now >= self.next_at,
+            reload: self.loaded_at.is_none_or(|at| now.saturating_sub(at) >= RELOAD_INTERVAL_MS),
+        }
+    }
+
+    /// Swap in a fresh slide list, staying on the current article if it's still there.
+    fn set_slides(&mut self, slides: Vec<KioskSlide>, now: u64) {
+        let current = self.index.and_then(|i| self.slides.get(i)).map(|s| s.article_id.clone());
+        self.index = current.and_then(|id| slides.iter().position(|s| s.article_id == id));
+        self.slides = slides;
+        self.loaded_at = Some(now);
+    }
+
+    /// Move to the next slide. Returns its index, or None when there are no slides.
+    fn advance(&mut self, now: u64) -> Option<usize> {
+        self.next_at = now + self.settings.interval_secs as u64 * 1000;
+        let total = self.slides.len();
+        if total == 0 {
+            self.index = None;
+            return None;
+        }
+        let index = match self.index {
+            Some(i) => (i as isize + self.step).rem_euclid(total as isize) as usize,
+            None => 0,
+        };
+        self.index = Some(index);
+        self.step = 1;
+        self.shown = self.shown.wrapping_add(1);
+        Some(index)
+    }
+
+    /// Drop downloaded images except for the slides at `keep`, so memory stays flat.
+    fn keep_images(&mut self, keep: &[usize]) {
+        for (i, slide) in self.slides.iter_mut().enumerate() {
+            if !keep.contains(&i) {
+                slide.image = None;
+            }
+        }
+    }
+
+    fn event(&self, index: usize) -> SlideEvent {
+        let (layout, shift_x, shift_y) = if self.settings.burn_in_protection {
+            // Walk a spiral by the golden angle: consecutive offsets are far apart
+            // and no spot repeats for a long time
+            let n = self.shown as f64;
+            let angle = n * 2.399_963;
+            let radius = MAX_SHIFT_PX * ((n % 7.0) + 1.0) / 7.0;
+            (self.shown % LAYOUTS, (radius * angle.cos()).round() as i32, (radius * angle.sin()).round() as i32)
+        } else {
+            (0, 0, 0)
+        };
+        let transition = match self.shown % 3 {
+            0 => Transition::Fade,
+            1 => Transition::Slide,
+            _ => Transition::Zoom,
+        };
+        SlideEvent {
+            index,
+            total: self.slides.len(),
+            slide: self.slides[index].clone(),
+            transition,
+            layout,
+            shift_x,
+            shift_y,
+            next_at: self.next_at,
+        }
+    }
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+/// Settings are kept on disk; a rotation lives in memory and ends with the app.
+pub struct KioskStore {
+    settings: Mutex<KioskSettings>,
+    rotation: Mutex<Option<Rotation>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl KioskStore {
+    pub fn new() -> Self {
+        KioskStore {
+            settings: Mutex::new(KioskSettings::default()),
+            rotation: Mutex::new(None),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SETTINGS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(settings) = serde_json::from_str::<KioskSettings>(&json) {
+                            *self.settings.lock().unwrap() = settings;
+                        }
+                    }
+                    Err(e) => eprintln!("[kiosk] Failed to read settings: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let settings = self.settings.lock().unwrap();
+            match serde_json::to_string_pretty(&*settings) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[kiosk] Failed to write settings: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[kiosk] Failed to serialize settings: {e}"),
+            }
+        }
+    }
+
+    pub fn settings(&self) -> KioskSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    fn set_settings(&self, settings: KioskSettings) {
+        *self.settings.lock().unwrap() = settings;
+        self.save_to_disk();
+    }
+
+    /// Run `f` on the rotation `id`. None once it was stopped or replaced.
+    fn with_rotation<T>(&self, id: &str, f: impl FnOnce(&mut Rotation) -> T) -> Option<T> {
+        let mut rotation = self.rotation.lock().unwrap();
+        rotation.as_mut().filter(|r| r.id == id).map(f)
+    }
+
+    pub fn status(&self) -> Option<KioskStatus> {
+        self.rotation.lock().unwrap().as_ref().map(Rotation::status)
+    }
+}
+
+fn validate(settings: &KioskSettings) -> Result<(), String> {
+    if !(MIN_INTERVAL_SECS..=MAX_INTERVAL_SECS).contains(&settings.interval_secs) {
+        return Err(format!("Interval must be between {MIN_INTERVAL_SECS} and {MAX_INTERVAL_SECS} seconds"));
+    }
+    if settings.max_slides == 0 || settings.max_slides > MAX_SLIDES {
+        return Err(format!("Slides must be between 1 and {MAX_SLIDES}"));
+    }
+    if let Some(hours) = &settings.active_hours {
+        parse_time(&hours.start)?;
+        parse_time(&hours.end)?;
+        if let Some(day) = hours.days.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(format!("Invalid weekday {day}, expected 1 (Monday) to 7"));
+        }
+    }
+    Ok(())
+}
+
+// ── Scheduling ───────────────────────────────────────────────────────
+
+fn parse_time(text: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time '{text}', expected HH:MM"))
+}
+
+/// Whether `now` falls inside the active hours. A span past midnight belongs to
+/// the day it started on.
+fn is_active(hours: Option<&ActiveHours>, now: chrono::DateTime<chrono::Local>) -> bool {
+    let Some(hours) = hours else { return true };
+    let (Ok(start), Ok(end)) = (parse_time(&hours.start), parse_time(&hours.end)) else { return true };
+    let time = NaiveTime::from_hms_opt(now.hour(), now.minute(), now.second()).unwrap_or_default();
+    let weekday = now.weekday().number_from_monday();
+    let day_ok = |day: u32| hours.days.is_empty() || hours.days.contains(&day);
+    if start <= end {
+        day_ok(weekday) && time >= start && time < end
+    } else {
+        let yesterday = if weekday == 1 { 7 } else { weekday - 1 };
+        (day_ok(weekday) && time >= start) || (day_ok(yesterday) && time < end)
+    }
+}
+
+// ── Slides and images ────────────────────────────────────────────────
+
+/// Recent articles from the chosen folders, newest first.
+async fn load_slides(db: &Arc<Database>, settings: &KioskSettings) -> Result<Vec<KioskSlide>, String> {
+    let settings = settings.clone();
+    db::run(db, move |conn| {
+        let feeds = db::list_feeds(conn)?;
+        let in_folders = |folder: &str| {
+            settings.folders.is_empty()
+                || settings.folders.iter().any(|f| {
+                    let f = f.trim_matches('/');
+                    folder == f || folder.strip_prefix(f).is_some_and(|rest| rest.starts_with('/'))
+                })
+        };
+        let titles: HashMap<String, String> =
+            feeds.iter().filter(|f| in_folders(&f.folder)).map(|f| (f.id.clone(), f.title.clone())).collect();
+        let query = ArticleQuery {
+            feed_ids: (!settings.folders.is_empty()).then(|| titles.keys().cloned().collect()),
+            unread_only: settings.unread_only,
+            since: Some(now_millis() as i64 - settings.max_age_hours as i64 * 3_600_000),
+            // Leave room for the ones dropped for lacking an image
+            limit: Some(if settings.images_only { settings.max_slides * 4 } else { settings.max_slides }),
+            ..Default::default()
+        };
+        let slides = db::query_articles(conn, &query)?
+            .into_iter()
+            .filter(|a| !a.title.trim().is_empty())
+            .map(|a| KioskSlide {
+                feed_title: titles.get(&a.feed_id).cloned().unwrap_or_default(),
+                image_url: a.thumbnail.filter(|t| t.starts_with("http://") || t.starts_with("https://")),
+                article_id: a.id,
+                feed_id: a.feed_id,
+                title: a.title,
+                snippet: a.snippet,
+                author: a.author,
+                url: a.url,
+                published_at: a.published_at,
+                image: None,
+            })
+            .filter(|s| !settings.images_only || s.image_url.is_some())
+            .take(settings.max_slides as usize)
+            .collect();
+        Ok(slides)
+    })
+    .await
+}
+
+fn data_url(content_type: Option<&str>, body: &[u8]) -> String {
+    let mime = content_type.and_then(|t| t.split(';').next()).map(str::trim).unwrap_or("image/jpeg");
+    format!("data:{mime};base64,{}", STANDARD.encode(body))
+}
+
+/// Download an image through the disk cache, as a data URL.
+async fn fetch_image(url: &str) -> Result<String, String> {
+    let cached = http_cache::get(url);
+    if let Some(c) = cached.as_ref().filter(|c| c.is_fresh()) {
+        return Ok(data_url(c.meta.content_type.as_deref(), &c.body));
+    }
+    let mut extra = cached.as_ref().map(|c| c.validators()).unwrap_or_default();
+    extra.insert(ACCEPT, HeaderValue::from_static("image/avif,image/webp,image/*;q=0.8"));
+    let response = crate::send_get(url, extra).await?;
+    let status = response.status();
+    if let (reqwest::StatusCode::NOT_MODIFIED, Some(c)) = (status, &cached) {
+        http_cache::refresh(url, response.headers());
+        return Ok(data_url(c.meta.content_type.as_deref(), &c.body));
+    }
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    let headers: HeaderMap = response.headers().clone();
+    let content_type = headers.get(reqwest::header::CONTENT_TYPE).and_then(|v| v.to_str().ok());
+    if content_type.is_some_and(|t| !t.starts_with("image/")) {
+        return Err(format!("Not an image: {}", content_type.unwrap_or_default()));
+    }
+    if response.content_length().is_some_and(|len| len > MAX_IMAGE_BYTES as u64) {
+        return Err("Image too large".into());
+    }
+    let final_url = response.url().to_string();
+    let bytes = response.bytes().await.map_err(|e| format!("Failed to read image: {e}"))?;
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err("Image too large".into());
+    }
+    http_cache::put(url, &final_url, &headers, &bytes);
+    Ok(data_url(content_type, &bytes))
+}
+
+/// Make sure slide `index` has its image downloaded. A failed download leaves
+/// the slide as a headline only.
+async fn ensure_image(store: &KioskStore, id: &str, index: usize) {
+    let url = store.with_rotation(id, |r| {
+        r.slides.get(index).filter(|s| s.image.is_none()).and_then(|s| s.image_url.clone())
+    });
+    let Some(Some(url)) = url else { return };
+    let image = match fetch_image(&url).await {
+        Ok(image) => Some(image),
+        Err(e) => {
+            eprintln!("[kiosk] Image failed for {url}: {e}");
+            None
+        }
+    };
+    store.with_rotation(id, |r| {
+        if let Some(slide) = r.slides.get_mut(index).filter(|s| s.image_url.as_deref() == Some(&url)) {
+            if image.is_none() {
+                slide.image_url = None;
+            }
+            slide.image = image;
+        }
+    });
+}
+
+// ── Rotation ─────────────────────────────────────────────────────────
+
+/// Drive rotation `id` until it is stopped or replaced: reload the slides now
+/// and then, emit `kiosk-slide` when one is due and prefetch the one after it.
+fn spawn_rotation(app: tauri::AppHandle, store: Arc<KioskStore>, db: Arc<Database>, id: String) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let now = now_millis();
+            let Some(settings) = store.with_rotation(&id, |r| r.settings.clone()) else { return };
+            let awake = is_active(settings.active_hours.as_ref(), chrono::Local::now());
+            let Some(tick) = store.with_rotation(&id, |r| r.tick(now, awake)) else { return };
+            if tick.phase_changed {
+                if let Some(status) = store.with_rotation(&id, |r| r.status()) {
+                    eprintln!("[kiosk] {:?}", status.phase);
+                    let _ = app.emit("kiosk-state", &status);
+                }
+            }
+            if tick.due {
+                if tick.reload {
+                    match load_slides(&db, &settings).await {
+                        Ok(slides) => {
+                            store.with_rotation(&id, |r| r.set_slides(slides, now));
+                        }
+                        Err(e) => eprintln!("[kiosk] Failed to load slides: {e}"),
+                    }
+                }
+                match store.with_rotation(&id, |r| r.advance(now)) {
+                    None => return,
+                    Some(None) => {
+                        if let Some(status) = store.with_rotation(&id, |r| r.status()) {
+                            let _ = app.emit("kiosk-state", &status);
+                        }
+                    }
+                    Some(Some(index)) => {
+                        // Normally prefetched during the previous slide
+                        ensure_image(&store, &id, index).await;
+                        let Some((event, next)) = store.with_rotation(&id, |r| {
+                            let next = (index + 1) % r.slides.len();
+                            r.keep_images(&[index, next]);
+                            (r.event(index), next)
+                        }) else {
+                            return;
+                        };
+                        let _ = app.emit("kiosk-slide", &event);
+                        ensure_image(&store, &id, next).await;
+                    }
+                }
+            }
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+/// Fullscreen and cursor for the main window. Returns whether it was fullscreen before.
+#[cfg(not(target_os = "android"))]
+fn present(app: &tauri::AppHandle, on: bool, settings: &KioskSettings, restore_fullscreen: bool) -> bool {
+    use tauri::Manager;
+
+    let Some(window) = app.get_webview_window("main") else { return false };
+    let was_fullscreen = window.is_fullscreen().unwrap_or(false);
+    if on {
+        let _ = window.show();
+        let _ = window.set_focus();
+        if let Err(e) = window.set_fullscreen(true) {
+            eprintln!("[kiosk] set_fullscreen: {e}");
+        }
+        let _ = window.set_cursor_visible(!settings.hide_cursor);
+    } else {
+        let _ = window.set_fullscreen(restore_fullscreen);
+        let _ = window.set_cursor_visible(true);
+    }
+    was_fullscreen
+}
+
+#[cfg(target_os = "android")]
+fn present(_app: &tauri::AppHandle, _on: bool, _settings: &KioskSettings, _restore_fullscreen: bool) -> bool {
+    true
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_kiosk_settings(store: tauri::State<'_, Arc<KioskStore>>) -> KioskSettings {
+    store.settings()
+}
+
+#[tauri::command]
+pub fn set_kiosk_settings(
+    settings: KioskSettings,
+    store: tauri::State<'_, Arc<KioskStore>>,
+) -> Result<(), String> {
+    validate(&settings)?;
+    store.set_settings(settings);
+    Ok(())
+}
+
+/// Go fullscreen and start rotating headlines, with `settings` (saved) or the saved ones.
+/// Slides follow as `kiosk-slide` events; phase changes as `kiosk-state`.
+#[tauri::command]
+pub fn start_kiosk(
+    app: tauri::AppHandle,
+    settings: Option<KioskSettings>,
+    store: tauri::State<'_, Arc<KioskStore>>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<KioskStatus, String> {
+    if let Some(settings) = settings {
+        validate(&settings)?;
+        store.set_settings(settings);
+    }
+    let settings = store.settings();
+    let previous = store.rotation.lock().unwrap().take();
+    let fullscreen_before = present(&app, true, &settings, false);
+    // A restart with new settings restores what was there before the first start
+    let was_fullscreen = previous.map_or(fullscreen_before, |r| r.was_fullscreen);
+    eprintln!("[kiosk] Started, {}s per slide", settings.interval_secs);
+    let now = now_millis();
+    let rotation = Rotation {
+        id: uuid::Uuid::new_v4().to_string(),
+        settings,
+        slides: Vec::new(),
+        index: None,
+        step: 1,
+        shown: 0,
+        next_at: now,
+        loaded_at: None,
+        phase: KioskPhase::Showing,
+        refresh_until: 0,
+        last_refresh: now,
+        started_at: now,
+        was_fullscreen,
+    };
+    let status = rotation.status();
+    let id = rotation.id.clone();
+    *store.rotation.lock().unwrap() = Some(rotation);
+    spawn_rotation(app, store.inner().clone(), db.inner().clone(), id);
+    Ok(status)
+}
+
+/// Leave kiosk mode and restore the window. Returns false if it wasn't running.
+#[tauri::command]
+pub fn stop_kiosk(app: tauri::AppHandle, store: tauri::State<'_, Arc<KioskStore>>) -> bool {
+    let Some(rotation) = store.rotation.lock().unwrap().take() else { return false };
+    present(&app, false, &rotation.settings, rotation.was_fullscreen);
+    eprintln!("[kiosk] Stopped after {} slides", rotation.shown);
+    let _ = app.emit("kiosk-stopped", ());
+    true
+}
+
+#[tauri::command]
+pub fn get_kiosk_status(store: tauri::State<'_, Arc<KioskStore>>) -> Option<KioskStatus> {
+    store.status()
+}
+
+/// Show the next slide now, e.g. from a remote or keyboard.
+#[tauri::command]
+pub fn kiosk_next(store: tauri::State<'_, Arc<KioskStore>>) -> Result<(), String> {
+    skip(&store, 1)
+}
+
+#[tauri::command]
+pub fn kiosk_previous(store: tauri::State<'_, Arc<KioskStore>>) -> Result<(), String> {
+    skip(&store, -1)
+}
+
+fn skip(store: &KioskStore, step: isize) -> Result<(), String> {
+    let mut rotation = store.rotation.lock().unwrap();
+    let rotation = rotation.as_mut().ok_or("Kiosk mode is not running")?;
+    rotation.step = step;
+    rotation.next_at = 0;
+    Ok(())
+}
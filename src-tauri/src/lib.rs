@@ -1,14 +1,97 @@
 use base64::{Engine, engine::general_purpose::STANDARD};
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, USER_AGENT};
-use serde::Serialize;
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE,
+    LAST_MODIFIED, RANGE, USER_AGENT,
+};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
 
+mod accessibility;
+mod article_notifications;
+mod article_windows;
+mod atlassian;
+mod authors;
+mod backup;
 mod clipboard;
 mod clipboard_history;
+mod clustering;
+mod comics;
+mod conditional_get;
+mod connectivity;
+mod content;
+mod dates;
+mod db;
+mod dedup;
+mod discovery;
+#[cfg(not(target_os = "android"))]
+mod dock;
+mod favicon;
+mod feed_hooks;
+mod feed_parser;
+mod feed_stats;
+mod filters;
+mod focus;
+mod fulltext;
+mod gallery;
+mod header_rules;
+mod highlight;
+mod http_auth;
+mod http_cache;
+mod image_cache;
+mod ingest;
+mod input_state;
+mod integrated_auth;
+mod kiosk;
+mod links;
+#[cfg(target_os = "linux")]
+mod linux_effects;
+mod locale;
 mod markdown_vault;
+mod math;
+mod matrix;
+mod miniplayer;
+mod naming;
+mod netsim;
+mod network_identity;
+mod notifications;
+mod opml;
 mod password_vault;
+mod permissions;
+mod power;
+mod private_hosts;
+mod proxy;
+mod rate_limit;
+mod readability;
+mod reddit;
+mod redirects;
+mod research;
+mod response_limit;
+mod retention;
+mod retry;
+mod rss_bridge;
+mod sanitize;
+mod saved_searches;
+mod search;
+mod share;
+mod shortcuts;
 mod snippets;
+mod sounds;
+mod speedtest;
+mod sse;
+mod startup;
+mod sync_folder;
+mod taskbar;
+mod tasks;
+mod text;
+mod timeouts;
+mod tls;
+mod trending;
+mod voice;
+#[cfg(not(target_os = "android"))]
+mod window_state;
+mod ws;
+mod youtube;
 #[cfg(not(target_os = "android"))]
 use tauri::{LogicalSize, PhysicalPosition, PhysicalSize};
 #[cfg(not(target_os = "android"))]
@@ -46,6 +129,77 @@ fn force_dwm_repaint(window: &tauri::WebviewWindow) {
     }
 }
 
+/// Minimum time between DWM repaints while a window is being dragged or resized.
+#[cfg(target_os = "windows")]
+const DWM_REPAINT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// `force_dwm_repaint` for move/resize events, which come in storms while
+/// dragging: at most one repaint per interval, plus one trailing repaint for
+/// the events skipped so the final geometry is always covered.
+#[cfg(target_os = "windows")]
+fn throttled_dwm_repaint(window: &tauri::WebviewWindow) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Instant;
+
+    static LAST: Mutex<Option<Instant>> = Mutex::new(None);
+    static TRAILING: AtomicBool = AtomicBool::new(false);
+
+    let since = LAST.lock().unwrap().map(|t| t.elapsed());
+    match since {
+        Some(elapsed) if elapsed < DWM_REPAINT_INTERVAL => {
+            if TRAILING.swap(true, Ordering::Relaxed) {
+                return;
+            }
+            let win = window.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(DWM_REPAINT_INTERVAL - elapsed);
+                TRAILING.store(false, Ordering::Relaxed);
+                let target = win.clone();
+                let _ = win.run_on_main_thread(move || {
+                    *LAST.lock().unwrap() = Some(Instant::now());
+                    force_dwm_repaint(&target);
+                });
+            });
+        }
+        _ => {
+            *LAST.lock().unwrap() = Some(Instant::now());
+            force_dwm_repaint(window);
+        }
+    }
+}
+
+/// Repaint once more when the user lets go of a move/resize drag
+/// (WM_EXITSIZEMOVE), which Tauri doesn't report as an event.
+#[cfg(target_os = "windows")]
+fn repaint_on_drag_end(window: &tauri::WebviewWindow) {
+    type SubclassProc = unsafe extern "system" fn(isize, u32, usize, isize, usize, usize) -> isize;
+    #[link(name = "comctl32")]
+    extern "system" {
+        fn SetWindowSubclass(hwnd: isize, proc_: SubclassProc, id: usize, data: usize) -> i32;
+        fn DefSubclassProc(hwnd: isize, msg: u32, wparam: usize, lparam: isize) -> isize;
+    }
+    const WM_EXITSIZEMOVE: u32 = 0x0232;
+    /// Arbitrary, identifies this subclass among the window's others.
+    const SUBCLASS_ID: usize = 0x5346;
+
+    // `data` carries the leaked window handle, which lives as long as the app
+    unsafe extern "system" fn on_message(
+        hwnd: isize, msg: u32, wparam: usize, lparam: isize, _id: usize, data: usize,
+    ) -> isize {
+        if msg == WM_EXITSIZEMOVE && EFFECT_ACTIVE.load(std::sync::atomic::Ordering::Relaxed) {
+            force_dwm_repaint(&*(data as *const tauri::WebviewWindow));
+        }
+        DefSubclassProc(hwnd, msg, wparam, lparam)
+    }
+
+    let Ok(hwnd) = window.hwnd() else { return };
+    let data = Box::into_raw(Box::new(window.clone())) as usize;
+    if unsafe { SetWindowSubclass(hwnd.0 as isize, on_message, SUBCLASS_ID, data) } == 0 {
+        drop(unsafe { Box::from_raw(data as *mut tauri::WebviewWindow) });
+        eprintln!("[window] Failed to watch for the end of drags");
+    }
+}
+
 /// Track whether a window effect is active so we know to repaint on move.
 #[cfg(not(target_os = "android"))]
 static EFFECT_ACTIVE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
@@ -54,33 +208,90 @@ const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) Appl
 const COLLAPSED_HEIGHT: f64 = 52.0;
 
 #[cfg(not(target_os = "android"))]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 struct SavedGeometry {
     size: PhysicalSize<u32>,
     pos: PhysicalPosition<i32>,
 }
 
+/// What `set_reading_mode` changed, to put back when reading mode ends.
+#[cfg(not(target_os = "android"))]
+struct ReadingMode {
+    geometry: SavedGeometry,
+    maximized: bool,
+    decorated: bool,
+    dimmed: bool,
+}
+
 struct AppState {
     #[cfg(not(target_os = "android"))]
     saved: Mutex<Option<SavedGeometry>>,
+    /// The main window's placement, written to disk when it closes.
+    #[cfg(not(target_os = "android"))]
+    window_state: Mutex<Option<window_state::WindowState>>,
+    /// Set while the collapsed bar is docked to a screen edge.
+    #[cfg(not(target_os = "android"))]
+    docked: Mutex<Option<dock::Docked>>,
+    /// The effect and color last chosen with `set_window_effect`.
+    #[cfg(not(target_os = "android"))]
+    window_effect: Mutex<Option<(String, [u8; 4])>>,
+    /// Set while in reading mode.
+    #[cfg(not(target_os = "android"))]
+    reading: Mutex<Option<ReadingMode>>,
 }
 
-// Shared HTTP client — created once, reused for all requests (connection pooling)
-static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+// Shared HTTP client — created once, reused for all requests (connection pooling).
+// Dropped by `reset_client` when network settings change, then rebuilt on next use.
+static HTTP_CLIENT: Mutex<Option<reqwest::Client>> = Mutex::new(None);
+// Same settings, refusing private addresses (see `private_hosts`), for URLs from feed content.
+static GUARDED_CLIENT: Mutex<Option<reqwest::Client>> = Mutex::new(None);
+// Guarded, without certificate verification, only for hosts opted in via `tls`.
+static INSECURE_CLIENT: Mutex<Option<reqwest::Client>> = Mutex::new(None);
+
+fn build_client(accept_invalid_certs: bool, guarded: bool) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .redirect(redirects::policy(guarded))
+        .danger_accept_invalid_certs(accept_invalid_certs);
+    if guarded {
+        builder = builder.dns_resolver(Arc::new(private_hosts::GuardedResolver));
+    }
+    let builder = timeouts::apply(builder);
+    proxy::apply(tls::apply(builder))?
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))
+}
 
-fn get_or_init_client() -> Result<&'static reqwest::Client, String> {
-    if let Some(c) = HTTP_CLIENT.get() {
-        return Ok(c);
+fn get_or_init_client() -> Result<reqwest::Client, String> {
+    let mut slot = HTTP_CLIENT.lock().unwrap();
+    if let Some(c) = slot.as_ref() {
+        return Ok(c.clone());
     }
     eprintln!("[http] Initializing shared HTTP client...");
-    let client = reqwest::Client::builder()
-        .redirect(reqwest::redirect::Policy::limited(10))
-        .timeout(std::time::Duration::from_secs(30))
-        .connect_timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    let client = build_client(false, false)?;
     eprintln!("[http] Shared HTTP client initialized OK");
-    let _ = HTTP_CLIENT.set(client);
-    Ok(HTTP_CLIENT.get().unwrap())
+    *slot = Some(client.clone());
+    Ok(client)
+}
+
+/// Client for a URL taken from feed content: guarded against private
+/// addresses, and unverified if `url`'s host is opted in.
+fn client_for(url: &Url) -> Result<reqwest::Client, String> {
+    private_hosts::check_url(url)?;
+    let insecure = url.host_str().is_some_and(tls::accepts_invalid_certs);
+    let mut slot = if insecure { INSECURE_CLIENT.lock().unwrap() } else { GUARDED_CLIENT.lock().unwrap() };
+    if let Some(c) = slot.as_ref() {
+        return Ok(c.clone());
+    }
+    let client = build_client(insecure, true)?;
+    *slot = Some(client.clone());
+    Ok(client)
+}
+
+/// Drop the shared clients so the next request builds them with current settings.
+fn reset_client() {
+    *HTTP_CLIENT.lock().unwrap() = None;
+    *GUARDED_CLIENT.lock().unwrap() = None;
+    *INSECURE_CLIENT.lock().unwrap() = None;
 }
 
 fn get_headers_for_url(url: &Url) -> HeaderMap {
@@ -120,9 +331,14 @@ fn get_headers_for_url(url: &Url) -> HeaderMap {
         );
     }
 
+    // User-defined rules override the defaults above
+    header_rules::apply(host, &mut headers);
     headers
 }
 
+/// Connectivity checks should answer quickly whatever the configured timeouts.
+const NETWORK_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// Quick network connectivity check — returns diagnostic info
 #[tauri::command]
 async fn check_network() -> Result<String, String> {
@@ -132,7 +348,7 @@ async fn check_network() -> Result<String, String> {
 
     // Test 1: simple HTTPS GET
     let test_url = "https://httpbin.org/get";
-    match client.get(test_url).send().await {
+    match client.get(test_url).timeout(NETWORK_CHECK_TIMEOUT).send().await {
         Ok(resp) => {
             let status = resp.status();
             eprintln!("[check_network] {test_url} → {status}");
@@ -155,23 +371,292 @@ async fn check_network() -> Result<String, String> {
     }
 }
 
+/// Fetch a feed. `credentials`, when given, are saved to the keyring for the
+/// feed's host and used for this and every later fetch from that host.
+/// A `request_id` lets `cancel_request` abort the fetch; `timeout_secs`
+/// overrides the configured request timeout for this call. Only fetches made
+/// with `feed` set (refreshes, not article pages) count in the feed stats.
+#[tauri::command]
+async fn fetch_url(
+    target_url: String,
+    credentials: Option<http_auth::Credentials>,
+    request_id: Option<String>,
+    timeout_secs: Option<u64>,
+    feed: Option<bool>,
+    app: tauri::AppHandle,
+    stats: tauri::State<'_, Arc<feed_stats::FeedStatsStore>>,
+    hooks: tauri::State<'_, Arc<feed_hooks::FeedHookStore>>,
+) -> Result<FetchResponse, String> {
+    if let Some(credentials) = credentials {
+        let parsed = Url::parse(&target_url).map_err(|e| format!("Invalid URL: {e}"))?;
+        http_auth::store(parsed.host_str().ok_or("URL has no host")?, &credentials)?;
+    }
+    let stats = feed.unwrap_or(false).then(|| stats.inner().as_ref());
+    let fetch = fetch_feed(&app, &target_url, request_id.as_deref(), stats, &hooks);
+    timeouts::scoped(timeouts::from_secs(timeout_secs), fetch).await
+}
+
+/// Response headers passed back to the frontend with a fetched document.
+const FORWARDED_HEADERS: &[&str] = &["content-type", "etag", "last-modified", "cache-control", "expires"];
+
+#[derive(Serialize)]
+struct FetchResponse {
+    body: String,
+    status: u16,
+    /// Final URL after redirects.
+    url: String,
+    redirects: Vec<redirects::RedirectHop>,
+    /// The final URL when every redirect was permanent; the feed's new address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permanent_url: Option<String>,
+    headers: HashMap<String, String>,
+    /// Body came from the disk cache (fresh, or revalidated with a 304).
+    from_cache: bool,
+}
+
+impl FetchResponse {
+    /// A body that wasn't fetched over HTTP (command hooks).
+    fn local(url: &str, body: String) -> Self {
+        FetchResponse {
+            body,
+            status: 200,
+            url: url.to_string(),
+            redirects: Vec::new(),
+            permanent_url: None,
+            headers: HashMap::new(),
+            from_cache: false,
+        }
+    }
+
+    fn cached(c: &http_cache::CachedResponse) -> Self {
+        let headers = [
+            ("content-type", &c.meta.content_type),
+            ("etag", &c.meta.etag),
+            ("last-modified", &c.meta.last_modified),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.clone()?)))
+        .collect();
+        FetchResponse {
+            body: String::from_utf8_lossy(&c.body).into_owned(),
+            status: 200,
+            url: c.meta.final_url.clone(),
+            redirects: Vec::new(),
+            permanent_url: None,
+            headers,
+            from_cache: true,
+        }
+    }
+}
+
+const DEFAULT_FETCH_CONCURRENCY: usize = 8;
+const MAX_FETCH_CONCURRENCY: usize = 32;
+
+#[derive(Serialize)]
+struct FetchResult {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permanent_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    elapsed_ms: u64,
+}
+
+/// Fetch many feeds in one invoke, at most `concurrency` (default 8) at a time.
+/// Results come back in the order of `urls`. Cancelling `request_id` stops the
+/// whole batch; feeds already fetched are still returned. `timeout_secs`
+/// overrides the request timeout for every feed in the batch.
 #[tauri::command]
-async fn fetch_url(target_url: String) -> Result<String, String> {
+async fn fetch_urls(
+    urls: Vec<String>,
+    concurrency: Option<usize>,
+    request_id: Option<String>,
+    timeout_secs: Option<u64>,
+    app: tauri::AppHandle,
+    stats: tauri::State<'_, Arc<feed_stats::FeedStatsStore>>,
+    hooks: tauri::State<'_, Arc<feed_hooks::FeedHookStore>>,
+) -> Result<Vec<FetchResult>, String> {
+    let workers = concurrency
+        .unwrap_or(DEFAULT_FETCH_CONCURRENCY)
+        .clamp(1, MAX_FETCH_CONCURRENCY)
+        .min(urls.len());
+    let total = urls.len();
+    let timeout = timeouts::from_secs(timeout_secs);
+    let label = format!("Refreshing {total} feeds");
+    tasks::supervise_request(request_id.as_deref(), "fetch", &label, FETCH_STALL_TIMEOUT, |task| async move {
+        // Reversed so `pop` hands out URLs in their original order
+        let queue = Arc::new(Mutex::new(urls.iter().cloned().enumerate().rev().collect::<Vec<_>>()));
+        let finished = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let queue = queue.clone();
+                let finished = finished.clone();
+                let task = task.clone();
+                let app = app.clone();
+                let stats = stats.inner().clone();
+                let hooks = hooks.inner().clone();
+                tauri::async_runtime::spawn(async move {
+                    let mut done = Vec::new();
+                    // Each worker pulls the next URL until the queue is drained or the batch is cancelled
+                    while !task.is_cancelled() {
+                        let next = queue.lock().unwrap().pop();
+                        let Some((index, url)) = next else { break };
+                        let start = std::time::Instant::now();
+                        let result = timeouts::scoped(timeout, fetch_feed(&app, &url, None, Some(&*stats), &hooks)).await;
+                        let elapsed_ms = start.elapsed().as_millis() as u64;
+                        let (body, permanent_url, error) = match result {
+                            Ok(r) => (Some(r.body), r.permanent_url, None),
+                            Err(e) => (None, None, Some(e)),
+                        };
+                        done.push((index, FetchResult { url, body, permanent_url, error, elapsed_ms }));
+                        let count = finished.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                        task.progress(Some(count as f32 / total as f32));
+                    }
+                    done
+                })
+            })
+            .collect();
+
+        let mut results: Vec<Option<FetchResult>> = urls.iter().map(|_| None).collect();
+        for handle in handles {
+            let done = handle.await.map_err(|e| format!("Fetch task failed: {e}"))?;
+            for (index, result) in done {
+                results[index] = Some(result);
+            }
+        }
+        Ok(results.into_iter().flatten().collect())
+    })
+    .await
+}
+
+/// Covers retries and rate-limit waits (a single attempt times out per `timeouts`);
+/// a batch refresh restarts the clock with every finished feed.
+const FETCH_STALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(360);
+
+/// Fetch a feed body, honouring per-feed command hooks and recording fetch
+/// stats when given the store.
+async fn fetch_feed(
+    app: &tauri::AppHandle,
+    target_url: &str,
+    request_id: Option<&str>,
+    stats: Option<&feed_stats::FeedStatsStore>,
+    hooks: &feed_hooks::FeedHookStore,
+) -> Result<FetchResponse, String> {
+    let result = tasks::supervise_request(request_id, "fetch", target_url, FETCH_STALL_TIMEOUT, |_| async {
+        // A configured external command replaces the HTTP fetch for this feed
+        match hooks.hook_for(target_url) {
+            Some(hook) => feed_hooks::run_for_feed(app, hooks, hook)
+                .await
+                .map(|body| FetchResponse::local(target_url, body)),
+            None => fetch_document(target_url).await,
+        }
+    })
+    .await;
+    if let Some(stats) = stats {
+        stats.record_fetch(target_url, result.as_ref().err().map(|e| e.as_str()));
+    }
+    result
+}
+
+/// GET a text body through the disk cache: fresh entries skip the network,
+/// stale ones are revalidated with their ETag / Last-Modified.
+async fn fetch_document(target_url: &str) -> Result<FetchResponse, String> {
+    let cached = http_cache::get(target_url);
+    if let Some(c) = cached.as_ref().filter(|c| c.is_fresh() && !netsim::is_simulated(target_url)) {
+        return Ok(FetchResponse::cached(c));
+    }
+    let extra = cached.as_ref().map(|c| c.validators()).unwrap_or_default();
+    let (response, redirects) = redirects::track(send_get(target_url, extra)).await;
+    let response = response?;
+    let status = response.status();
+    if let (reqwest::StatusCode::NOT_MODIFIED, Some(c)) = (status, &cached) {
+        http_cache::refresh(target_url, response.headers());
+        return Ok(FetchResponse::cached(c));
+    }
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    let headers = response.headers().clone();
+    let final_url = response.url().to_string();
+    let body = read_body(target_url, response).await?;
+    http_cache::put(target_url, &final_url, &headers, body.as_bytes());
+    if !redirects.is_empty() {
+        eprintln!("[fetch_url] {target_url} redirected {} times to {final_url}", redirects.len());
+    }
+    Ok(FetchResponse {
+        body,
+        status: status.as_u16(),
+        permanent_url: redirects::is_permanent(&redirects).then(|| final_url.clone()),
+        url: final_url,
+        redirects,
+        headers: FORWARDED_HEADERS
+            .iter()
+            .filter_map(|name| Some((name.to_string(), headers.get(*name)?.to_str().ok()?.to_string())))
+            .collect(),
+        from_cache: false,
+    })
+}
+
+/// `fetch_document` for callers that only need the body.
+async fn fetch_text(target_url: &str) -> Result<String, String> {
+    fetch_document(target_url).await.map(|r| r.body)
+}
+
+/// GET with the per-host default headers plus `extra`, handling integrated auth challenges.
+/// Non-2xx statuses are returned as-is for the caller to interpret.
+async fn send_get(target_url: &str, extra: HeaderMap) -> Result<reqwest::Response, String> {
     eprintln!("[fetch_url] Fetching: {target_url}");
 
-    let parsed = Url::parse(&target_url).map_err(|e| {
+    let parsed = Url::parse(target_url).map_err(|e| {
         eprintln!("[fetch_url] Invalid URL: {e}");
         format!("Invalid URL: {e}")
     })?;
-    let headers = get_headers_for_url(&parsed);
-
-    let client = get_or_init_client()?;
+    let mut headers = get_headers_for_url(&parsed);
+    headers.extend(extra);
+    if let Some(auth) = http_auth::preemptive(&parsed) {
+        headers.insert(AUTHORIZATION, auth);
+    }
 
-    let response = client
-        .get(&target_url)
-        .headers(headers)
-        .send()
-        .await
+    let client = client_for(&parsed)?;
+
+    // Transient failures (5xx, timeouts, refused connections) are retried with backoff
+    let policy = retry::policy();
+    let mut attempt = 1;
+    let host = parsed.host_str().unwrap_or_default().to_string();
+    let result = loop {
+        rate_limit::acquire(&host).await;
+        let request = client.get(target_url).headers(headers.clone()).timeout(timeouts::for_host(&host));
+        // Development builds may answer with a simulated failure or fixture instead
+        let result = match netsim::intercept(target_url, timeouts::for_host(&host)).await? {
+            Some(simulated) => Ok(simulated),
+            None => request.send().await,
+        };
+        // A host asking us to slow down gets no requests at all until then
+        if let Ok(r) = &result {
+            if matches!(r.status().as_u16(), 429 | 503) {
+                if let Some(after) = retry::retry_after(r.headers()) {
+                    rate_limit::defer(&host, after);
+                }
+            }
+        }
+        // Some(retry_after) when the failure is worth another try
+        let transient = match &result {
+            Ok(r) if retry::is_transient_status(r.status()) => Some(retry::retry_after(r.headers())),
+            Err(e) if retry::is_transient_error(e) => Some(None),
+            _ => None,
+        };
+        match transient.and_then(|after| policy.delay(attempt, after)) {
+            Some(delay) => {
+                eprintln!("[fetch_url] Attempt {attempt} failed for {target_url}, retrying in {delay:?}");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            None => break result,
+        }
+    };
+    let response = result
         .map_err(|e| {
             let detail = if e.is_connect() {
                 format!("Connection failed: {e}")
@@ -186,20 +671,241 @@ async fn fetch_url(target_url: String) -> Result<String, String> {
             detail
         })?;
 
-    let status = response.status();
-    eprintln!("[fetch_url] Response status: {status} for {target_url}");
+    // Intranet feeds behind Windows integrated auth (opt-in hosts only),
+    // then Basic/Digest with credentials saved for the host
+    let response = match response.status() {
+        reqwest::StatusCode::UNAUTHORIZED => {
+            match integrated_auth::challenge_scheme(&parsed, response.headers()) {
+                Some(scheme) => integrated_auth::negotiate(&client, &parsed, headers, scheme).await?,
+                None => match http_auth::authorization(&parsed, response.headers()) {
+                    Some(auth) => client
+                        .get(target_url)
+                        .headers(headers)
+                        .header(AUTHORIZATION, auth)
+                        .send()
+                        .await
+                        .map_err(|e| format!("Request failed: {e}"))?,
+                    None => response,
+                },
+            }
+        }
+        _ => response,
+    };
+
+    eprintln!("[fetch_url] Response status: {} for {target_url}", response.status());
+    Ok(response)
+}
+
+async fn read_body(target_url: &str, response: reqwest::Response) -> Result<String, String> {
+    response_limit::read_text(response, response_limit::max_body_bytes())
+        .await
+        .inspect_err(|e| eprintln!("[fetch_url] Failed to read body for {target_url}: {e}"))
+}
 
+#[derive(Serialize)]
+struct ProbeResult {
+    /// A 206 from the ranged fallback is reported as 200.
+    status: u16,
+    /// Final URL after redirects.
+    url: String,
+    redirects: Vec<redirects::RedirectHop>,
+    /// "HEAD", or "GET" when the server wouldn't answer a HEAD.
+    method: &'static str,
+    content_type: Option<String>,
+    /// Full size of the resource, when the server reports it.
+    content_length: Option<u64>,
+    last_modified: Option<String>,
+}
+
+impl ProbeResult {
+    fn from_response(
+        response: &reqwest::Response,
+        method: &'static str,
+        redirects: Vec<redirects::RedirectHop>,
+    ) -> Self {
+        let headers = response.headers();
+        let header = |name: HeaderName| {
+            headers.get(name).and_then(|v| v.to_str().ok()).map(|s| s.trim().to_string())
+        };
+        // "bytes 0-0/12345" carries the full size of a ranged response
+        let content_length = match response.status() {
+            reqwest::StatusCode::PARTIAL_CONTENT => header(CONTENT_RANGE)
+                .and_then(|range| range.rsplit_once('/').and_then(|(_, total)| total.parse().ok())),
+            _ => header(CONTENT_LENGTH).and_then(|len| len.parse().ok()),
+        };
+        let status = match response.status() {
+            reqwest::StatusCode::PARTIAL_CONTENT => 200,
+            other => other.as_u16(),
+        };
+        ProbeResult {
+            status,
+            url: response.url().to_string(),
+            redirects,
+            method,
+            content_type: header(CONTENT_TYPE),
+            content_length,
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+}
+
+/// Check a URL without downloading it: a HEAD, or a one-byte ranged GET for
+/// servers that refuse HEAD. Non-2xx statuses are returned, not errors.
+#[tauri::command]
+async fn probe_url(url: String) -> Result<ProbeResult, String> {
+    let parsed = Url::parse(&url).map_err(|e| format!("Invalid URL: {e}"))?;
+    let mut headers = get_headers_for_url(&parsed);
+    if let Some(auth) = http_auth::preemptive(&parsed) {
+        headers.insert(AUTHORIZATION, auth);
+    }
+    let client = client_for(&parsed)?;
+    let host = parsed.host_str().unwrap_or_default();
+    rate_limit::acquire(host).await;
+    let head = client.head(url.as_str()).headers(headers).timeout(timeouts::for_host(host));
+    let (head, hops) = redirects::track(head.send()).await;
+    // Some servers and CDNs reject HEAD outright instead of treating it like GET
+    match head {
+        Ok(r) if !matches!(r.status().as_u16(), 403 | 405 | 501) => {
+            return Ok(ProbeResult::from_response(&r, "HEAD", hops));
+        }
+        Ok(r) => eprintln!("[probe_url] HEAD → {} for {url}, retrying with GET", r.status()),
+        Err(e) => eprintln!("[probe_url] HEAD failed for {url}: {e}, retrying with GET"),
+    }
+    let mut range = HeaderMap::new();
+    range.insert(RANGE, HeaderValue::from_static("bytes=0-0"));
+    let (response, hops) = redirects::track(send_get(&url, range)).await;
+    // Dropped unread, so at most the first byte is transferred
+    Ok(ProbeResult::from_response(&response?, "GET", hops))
+}
+
+/// Bodies above this are only delivered through a temp file.
+const MAX_INLINE_BINARY_BYTES: u64 = 50 * 1024 * 1024;
+
+#[derive(Serialize)]
+struct BinaryResponse {
+    content_type: Option<String>,
+    size: u64,
+    /// Final URL after redirects.
+    url: String,
+    /// Base64 body, when not written to a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<String>,
+    /// Temp file holding the body, when `to_file` was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+}
+
+/// Fetch raw bytes (enclosures, images, PDFs) with the same per-host headers as
+/// `fetch_url`. Returned as base64, or streamed to a temp file with `to_file`.
+/// Inline responses go through the disk cache. A `request_id` lets
+/// `cancel_request` abort the download; `timeout_secs` overrides the request timeout.
+#[tauri::command]
+async fn fetch_binary(
+    url: String,
+    to_file: Option<bool>,
+    request_id: Option<String>,
+    timeout_secs: Option<u64>,
+) -> Result<BinaryResponse, String> {
+    let to_file = to_file.unwrap_or(false);
+    let target = url.as_str();
+    let job = tasks::supervise_request(request_id.as_deref(), "download", target, FETCH_STALL_TIMEOUT, |task| {
+        async move { download(target, to_file, &task).await }
+    });
+    timeouts::scoped(timeouts::from_secs(timeout_secs), job).await
+}
+
+/// Removes a temp file unless disarmed, so aborted downloads leave nothing behind.
+struct PartialFile(Option<std::path::PathBuf>);
+
+impl Drop for PartialFile {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+async fn download(url: &str, to_file: bool, task: &tasks::TaskHandle) -> Result<BinaryResponse, String> {
+    let url = url.to_string();
+    let cached = if to_file { None } else { http_cache::get(&url) };
+    let cached_response = |c: &http_cache::CachedResponse| BinaryResponse {
+        content_type: c.meta.content_type.clone(),
+        size: c.body.len() as u64,
+        url: c.meta.final_url.clone(),
+        data: Some(STANDARD.encode(&c.body)),
+        path: None,
+    };
+    if let Some(c) = cached.as_ref().filter(|c| c.is_fresh()) {
+        return Ok(cached_response(c));
+    }
+    let extra = cached.as_ref().map(|c| c.validators()).unwrap_or_default();
+    let mut response = send_get(&url, extra).await?;
+    let status = response.status();
+    if let (reqwest::StatusCode::NOT_MODIFIED, Some(c)) = (status, &cached) {
+        http_cache::refresh(&url, response.headers());
+        return Ok(cached_response(c));
+    }
     if !status.is_success() {
         return Err(format!("HTTP {}", status.as_u16()));
     }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    let final_url = response.url().to_string();
+
+    if !to_file {
+        if response.content_length().is_some_and(|len| len > MAX_INLINE_BINARY_BYTES) {
+            return Err("Response too large to return inline; use to_file".into());
+        }
+        let headers = response.headers().clone();
+        let bytes = response_limit::read_bytes(response, MAX_INLINE_BINARY_BYTES).await?;
+        http_cache::put(&url, &final_url, &headers, &bytes);
+        return Ok(BinaryResponse {
+            content_type,
+            size: bytes.len() as u64,
+            url: final_url,
+            data: Some(STANDARD.encode(&bytes)),
+            path: None,
+        });
+    }
 
-    response
-        .text()
-        .await
-        .map_err(|e| {
-            eprintln!("[fetch_url] Failed to read body for {target_url}: {e}");
-            format!("Failed to read response body: {e}")
+    let ext = Url::parse(&final_url)
+        .ok()
+        .and_then(|u| {
+            let name = u.path_segments()?.next_back()?.to_string();
+            let (_, ext) = name.rsplit_once('.')?;
+            (ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric())).then(|| ext.to_lowercase())
         })
+        .unwrap_or_else(|| "bin".into());
+    let dir = std::env::temp_dir().join("superflux_downloads");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp dir: {e}"))?;
+    let path = dir.join(format!("{}.{ext}", uuid::Uuid::new_v4()));
+
+    use std::io::Write;
+    let mut file = std::fs::File::create(&path).map_err(|e| format!("Failed to create temp file: {e}"))?;
+    let mut partial = PartialFile(Some(path.clone()));
+    let total = response.content_length();
+    let mut size = 0u64;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response body: {e}"))?
+    {
+        size += chunk.len() as u64;
+        file.write_all(&chunk).map_err(|e| format!("Failed to write temp file: {e}"))?;
+        // Each chunk counts as progress, so large downloads aren't taken for stuck ones
+        task.progress(total.map(|t| size as f32 / t.max(1) as f32));
+    }
+    partial.0 = None;
+    Ok(BinaryResponse {
+        content_type,
+        size,
+        url: final_url,
+        data: None,
+        path: Some(path.to_string_lossy().to_string()),
+    })
 }
 
 #[derive(Serialize)]
@@ -209,14 +915,132 @@ struct HttpResponse {
     headers: HashMap<String, String>,
 }
 
+/// Files attached from disk are read into memory; anything bigger should be streamed another way.
+const MAX_UPLOAD_FILE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Files the user picked with `pick_upload_file`, by token. A form can only
+/// attach a file through its token, never by path.
+static UPLOAD_TOKENS: Mutex<Option<HashMap<String, std::path::PathBuf>>> = Mutex::new(None);
+
+#[derive(Serialize)]
+struct UploadFile {
+    token: String,
+    name: String,
+    size: u64,
+}
+
+/// Let the user pick a file to attach to a multipart `http_request`. Returns
+/// `None` if the dialog was cancelled.
+#[tauri::command]
+async fn pick_upload_file() -> Result<Option<UploadFile>, String> {
+    let Some(handle) = rfd::AsyncFileDialog::new().pick_file().await else {
+        return Ok(None);
+    };
+    let path = handle.path().to_path_buf();
+    let size = std::fs::metadata(&path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?.len();
+    let token = uuid::Uuid::new_v4().to_string();
+    UPLOAD_TOKENS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(token.clone(), path);
+    Ok(Some(UploadFile { token, name: handle.file_name(), size }))
+}
+
+/// The picked file for `token`. Each token attaches its file once.
+fn take_upload_token(token: &str) -> Option<std::path::PathBuf> {
+    UPLOAD_TOKENS.lock().unwrap().as_mut()?.remove(token)
+}
+
+/// An `http_request` body: a plain string, or `{ "multipart": [...] }` for form uploads.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RequestBody {
+    Text(String),
+    Multipart { multipart: Vec<FormPart> },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum FormPart {
+    Text {
+        name: String,
+        value: String,
+    },
+    Base64 {
+        name: String,
+        data: String,
+        filename: Option<String>,
+        content_type: Option<String>,
+    },
+    /// Local file, by the token `pick_upload_file` returned for it.
+    File {
+        name: String,
+        token: String,
+        filename: Option<String>,
+        content_type: Option<String>,
+    },
+}
+
+fn file_part(
+    bytes: Vec<u8>,
+    filename: Option<String>,
+    content_type: Option<String>,
+) -> Result<reqwest::multipart::Part, String> {
+    let mut part = reqwest::multipart::Part::bytes(bytes);
+    if let Some(filename) = filename {
+        part = part.file_name(filename);
+    }
+    match content_type {
+        Some(mime) => part.mime_str(&mime).map_err(|e| format!("Invalid content type '{mime}': {e}")),
+        None => Ok(part),
+    }
+}
+
+fn build_form(parts: Vec<FormPart>) -> Result<reqwest::multipart::Form, String> {
+    let mut form = reqwest::multipart::Form::new();
+    for part in parts {
+        form = match part {
+            FormPart::Text { name, value } => form.text(name, value),
+            FormPart::Base64 { name, data, filename, content_type } => {
+                let bytes =
+                    STANDARD.decode(data.trim()).map_err(|e| format!("Invalid base64 in part '{name}': {e}"))?;
+                form.part(name, file_part(bytes, filename, content_type)?)
+            }
+            FormPart::File { name, token, filename, content_type } => {
+                let path = take_upload_token(&token)
+                    .ok_or_else(|| format!("Part '{name}' does not reference a picked file"))?;
+                let shown = path.display();
+                let size = std::fs::metadata(&path).map_err(|e| format!("Cannot read {shown}: {e}"))?.len();
+                if size > MAX_UPLOAD_FILE_BYTES {
+                    return Err(format!("{shown} is larger than {} MB", MAX_UPLOAD_FILE_BYTES / 1024 / 1024));
+                }
+                let bytes = std::fs::read(&path).map_err(|e| format!("Cannot read {shown}: {e}"))?;
+                // Default to the file's own name, as a browser would
+                let filename = filename.or_else(|| path.file_name().map(|n| n.to_string_lossy().into_owned()));
+                form.part(name, file_part(bytes, filename, content_type)?)
+            }
+        };
+    }
+    Ok(form)
+}
+
+/// Generic HTTP request. Plugin and user-script windows must hold a host
+/// permission for the target; the app's own windows go unchecked. The body is
+/// capped at the configured response limit. `body` is a string, or a multipart
+/// form of text, base64 and local file parts; files must have been picked with
+/// `pick_upload_file`.
 #[tauri::command]
 async fn http_request(
     method: String,
     url: String,
     headers: HashMap<String, String>,
-    body: Option<String>,
+    body: Option<RequestBody>,
+    window: tauri::WebviewWindow,
 ) -> Result<HttpResponse, String> {
-    let client = get_or_init_client()?;
+    permissions::require_host(&window, &url).await?;
+    let parsed = Url::parse(&url).map_err(|e| format!("Invalid URL: {e}"))?;
+    let client = client_for(&parsed)?;
 
     let mut req = match method.to_uppercase().as_str() {
         "GET" => client.get(&url),
@@ -227,8 +1051,14 @@ async fn http_request(
         other => return Err(format!("Unsupported HTTP method: {other}")),
     };
 
+    let is_multipart = matches!(body, Some(RequestBody::Multipart { .. }));
+
     // Apply custom headers
     for (key, value) in &headers {
+        // The form sets its own Content-Type carrying the boundary
+        if is_multipart && key.eq_ignore_ascii_case("content-type") {
+            continue;
+        }
         let header_name = HeaderName::try_from(key.as_str())
             .map_err(|e| format!("Invalid header name '{key}': {e}"))?;
         let header_value = HeaderValue::from_str(value)
@@ -236,14 +1066,23 @@ async fn http_request(
         req = req.header(header_name, header_value);
     }
 
+    req = req.timeout(timeouts::for_host(parsed.host_str().unwrap_or_default()));
+
     // Set User-Agent if not already provided
     if !headers.keys().any(|k| k.eq_ignore_ascii_case("user-agent")) {
         req = req.header(USER_AGENT, BROWSER_USER_AGENT);
     }
 
     // Set body if provided
-    if let Some(body_str) = body {
-        req = req.body(body_str);
+    match body {
+        Some(RequestBody::Text(body_str)) => req = req.body(body_str),
+        Some(RequestBody::Multipart { multipart }) => {
+            let form = tauri::async_runtime::spawn_blocking(move || build_form(multipart))
+                .await
+                .map_err(|e| format!("Form task failed: {e}"))??;
+            req = req.multipart(form);
+        }
+        None => {}
     }
 
     let response = req
@@ -257,10 +1096,7 @@ async fn http_request(
         .iter()
         .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or("").to_string()))
         .collect();
-    let resp_body = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response body: {e}"))?;
+    let resp_body = response_limit::read_text(response, response_limit::max_body_bytes()).await?;
 
     Ok(HttpResponse {
         status,
@@ -359,6 +1195,12 @@ fn open_external(url: String) -> Result<(), String> {
 #[cfg(not(target_os = "android"))]
 #[tauri::command]
 fn collapse_window(window: tauri::WebviewWindow, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    collapse(&window, &state)
+}
+
+/// Shrink the window to the widget bar, remembering its geometry for `expand_window`.
+#[cfg(not(target_os = "android"))]
+fn collapse(window: &tauri::WebviewWindow, state: &AppState) -> Result<(), String> {
     let size = window.outer_size().map_err(|e| format!("outer_size: {e}"))?;
     let pos = window.outer_position().map_err(|e| format!("outer_position: {e}"))?;
     let factor = window.scale_factor().map_err(|e| format!("scale_factor: {e}"))?;
@@ -385,10 +1227,24 @@ fn collapse_window(window: tauri::WebviewWindow, state: tauri::State<'_, AppStat
     Ok(())
 }
 
+/// Collapse to the widget bar and snap it to the top or bottom of the current
+/// monitor, full width. `reserve` keeps other windows out of its strip (Windows).
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+fn dock_window(
+    window: tauri::WebviewWindow,
+    edge: dock::DockEdge,
+    reserve: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    dock::dock(&window, &state, edge, reserve.unwrap_or(false))
+}
+
 #[cfg(not(target_os = "android"))]
 #[tauri::command]
 fn expand_window(window: tauri::WebviewWindow, state: tauri::State<'_, AppState>) -> Result<(), String> {
     eprintln!("[expand] restoring window");
+    dock::undock(&window, &state);
 
     let saved = state.saved.lock().unwrap().take();
     if let Some(geo) = saved {
@@ -424,12 +1280,95 @@ fn hide_to_tray(window: tauri::WebviewWindow) -> Result<(), String> {
     Ok(())
 }
 
+/// Keep the window above other apps, e.g. so the collapsed ticker bar stays visible.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+fn set_always_on_top(window: tauri::WebviewWindow, on_top: bool) -> Result<(), String> {
+    window.set_always_on_top(on_top).map_err(|e| format!("set_always_on_top: {e}"))?;
+    eprintln!("[window] always on top: {on_top}");
+    Ok(())
+}
+
+/// Tint behind the window in dimmed reading mode.
+#[cfg(not(target_os = "android"))]
+const READING_DIM: [u8; 4] = [0, 0, 0, 200];
+
+/// Distraction-free reading: fullscreen without decorations, optionally over a
+/// dimmed backdrop. Turning it off restores the window as it was, like
+/// `expand_window` does after a collapse. The collapsed bar is expanded first.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+fn set_reading_mode(
+    window: tauri::WebviewWindow,
+    enabled: bool,
+    dim: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    if enabled {
+        if state.reading.lock().unwrap().is_some() {
+            return Ok(());
+        }
+        if state.saved.lock().unwrap().is_some() {
+            expand_window(window.clone(), state.clone())?;
+        }
+        let maximized = window.is_maximized().unwrap_or(false);
+        let decorated = window.is_decorated().unwrap_or(false);
+        let size = window.outer_size().map_err(|e| format!("outer_size: {e}"))?;
+        let pos = window.outer_position().map_err(|e| format!("outer_position: {e}"))?;
+
+        window.set_decorations(false).map_err(|e| format!("set_decorations: {e}"))?;
+        window.set_fullscreen(true).map_err(|e| format!("set_fullscreen: {e}"))?;
+        let dimmed = dim.unwrap_or(false);
+        if dimmed {
+            if let Err(e) = apply_window_effect(&window, "acrylic", READING_DIM) {
+                eprintln!("[reading] Failed to dim: {e}");
+            }
+        }
+        *state.reading.lock().unwrap() =
+            Some(ReadingMode { geometry: SavedGeometry { size, pos }, maximized, decorated, dimmed });
+        eprintln!("[reading] on (dimmed: {dimmed})");
+        return Ok(());
+    }
+
+    let Some(reading) = state.reading.lock().unwrap().take() else { return Ok(()) };
+    window.set_fullscreen(false).map_err(|e| format!("set_fullscreen: {e}"))?;
+    window
+        .set_decorations(reading.decorated)
+        .map_err(|e| format!("set_decorations: {e}"))?;
+    if reading.maximized {
+        window.maximize().map_err(|e| format!("maximize: {e}"))?;
+    } else {
+        let geo = reading.geometry;
+        window.set_size(geo.size).map_err(|e| format!("set_size: {e}"))?;
+        window.set_position(geo.pos).map_err(|e| format!("set_position: {e}"))?;
+    }
+    if reading.dimmed {
+        let previous = state.window_effect.lock().unwrap().clone();
+        let (effect, color) = previous.unwrap_or_else(|| ("none".to_string(), [0; 4]));
+        apply_window_effect(&window, &effect, color)?;
+    }
+    eprintln!("[reading] off");
+    Ok(())
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+fn set_reading_mode() -> Result<(), String> {
+    Ok(())
+}
+
 #[cfg(target_os = "android")]
 #[tauri::command]
 fn collapse_window() -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(target_os = "android")]
+#[tauri::command]
+fn dock_window() -> Result<(), String> {
+    Ok(())
+}
+
 #[cfg(target_os = "android")]
 #[tauri::command]
 fn expand_window() -> Result<(), String> {
@@ -442,6 +1381,62 @@ fn hide_to_tray() -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(target_os = "android")]
+#[tauri::command]
+fn set_always_on_top() -> Result<(), String> {
+    Ok(())
+}
+
+/// macOS NSVisualEffectView materials, by the names `set_window_effect` accepts.
+#[cfg(not(target_os = "android"))]
+const MACOS_MATERIALS: &[(&str, Effect)] = &[
+    ("titlebar", Effect::Titlebar),
+    ("selection", Effect::Selection),
+    ("menu", Effect::Menu),
+    ("popover", Effect::Popover),
+    ("sidebar", Effect::Sidebar),
+    ("header-view", Effect::HeaderView),
+    ("sheet", Effect::Sheet),
+    ("window-background", Effect::WindowBackground),
+    ("hud", Effect::HudWindow),
+    ("fullscreen-ui", Effect::FullScreenUI),
+    ("tooltip", Effect::Tooltip),
+    ("content-background", Effect::ContentBackground),
+    ("under-window", Effect::UnderWindowBackground),
+    ("under-page", Effect::UnderPageBackground),
+];
+
+/// The effect for a name from the UI. On macOS the Windows backdrops map to
+/// the closest vibrancy material, so one theme setting works on both.
+#[cfg(not(target_os = "android"))]
+fn effect_for(name: &str) -> Result<Effect, String> {
+    if let Some((_, material)) = MACOS_MATERIALS.iter().find(|(n, _)| *n == name) {
+        return if cfg!(target_os = "macos") {
+            Ok(*material)
+        } else {
+            Err(format!("'{name}' is a macOS material"))
+        };
+    }
+    let eff = match name {
+        "mica" => Effect::Mica,
+        "mica-dark" => Effect::MicaDark,
+        "mica-light" => Effect::MicaLight,
+        "acrylic" => Effect::Acrylic,
+        "tabbed" => Effect::Tabbed,
+        "tabbed-dark" => Effect::TabbedDark,
+        "tabbed-light" => Effect::TabbedLight,
+        "blur" => Effect::Blur,
+        other => return Err(format!("Unknown effect: {other}")),
+    };
+    if cfg!(target_os = "macos") {
+        return Ok(match eff {
+            Effect::Acrylic | Effect::Blur => Effect::HudWindow,
+            _ => Effect::UnderWindowBackground,
+        });
+    }
+    Ok(eff)
+}
+
 #[cfg(not(target_os = "android"))]
 #[tauri::command]
 fn set_window_effect(
@@ -451,24 +1446,40 @@ fn set_window_effect(
     g: u8,
     b: u8,
     a: u8,
+    state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
     eprintln!("[set_window_effect] effect={effect}, color=({r},{g},{b},{a})");
+    apply_window_effect(&window, &effect, [r, g, b, a])?;
+    *state.window_effect.lock().unwrap() = Some((effect, [r, g, b, a]));
+    Ok(())
+}
 
+#[cfg(not(target_os = "android"))]
+fn apply_window_effect(
+    window: &tauri::WebviewWindow,
+    effect: &str,
+    [r, g, b, a]: [u8; 4],
+) -> Result<(), String> {
     if effect == "none" {
         EFFECT_ACTIVE.store(false, std::sync::atomic::Ordering::Relaxed);
+        #[cfg(target_os = "linux")]
+        linux_effects::apply(window, false)?;
+        #[cfg(not(target_os = "linux"))]
         window
             .set_effects(EffectsBuilder::new().build())
             .map_err(|e| format!("clear effects: {e}"))?;
     } else {
-        let eff = match effect.as_str() {
-            "mica" => Effect::Mica,
-            "mica-dark" => Effect::MicaDark,
-            "mica-light" => Effect::MicaLight,
-            "acrylic" => Effect::Acrylic,
-            "tabbed" => Effect::Tabbed,
-            "blur" => Effect::Blur,
-            other => return Err(format!("Unknown effect: {other}")),
-        };
+        let eff = effect_for(effect)?;
+        #[cfg(target_os = "linux")]
+        {
+            // Tauri has no effects here; blur where KWin offers it, else stay transparent
+            let _ = eff;
+            let backdrop = linux_effects::apply(window, true)?;
+            EFFECT_ACTIVE.store(true, std::sync::atomic::Ordering::Relaxed);
+            eprintln!("[set_window_effect] Effect {effect} applied as {}", backdrop.as_str());
+            return Ok(());
+        }
+        #[cfg(not(target_os = "linux"))]
         window
             .set_effects(
                 EffectsBuilder::new()
@@ -482,7 +1493,7 @@ fn set_window_effect(
     }
 
     #[cfg(target_os = "windows")]
-    force_dwm_repaint(&window);
+    force_dwm_repaint(window);
 
     eprintln!("[set_window_effect] Effect {effect} applied OK");
     Ok(())
@@ -500,6 +1511,123 @@ fn set_window_effect(
     Ok(())
 }
 
+/// Lowest opacity `set_window_opacity` goes to, so the window can't vanish.
+#[cfg(not(target_os = "android"))]
+const MIN_OPACITY: f64 = 0.1;
+
+/// Whole-window opacity from 0.0 to 1.0, content included, e.g. for a
+/// semi-transparent collapsed ticker. Works on top of any window effect.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+fn set_window_opacity(window: tauri::WebviewWindow, level: f64) -> Result<(), String> {
+    if !level.is_finite() {
+        return Err(format!("Invalid opacity: {level}"));
+    }
+    let level = level.clamp(MIN_OPACITY, 1.0);
+
+    #[cfg(target_os = "windows")]
+    {
+        extern "system" {
+            fn GetWindowLongPtrW(hwnd: isize, index: i32) -> isize;
+            fn SetWindowLongPtrW(hwnd: isize, index: i32, value: isize) -> isize;
+            fn SetLayeredWindowAttributes(hwnd: isize, key: u32, alpha: u8, flags: u32) -> i32;
+        }
+        const GWL_EXSTYLE: i32 = -20;
+        const WS_EX_LAYERED: isize = 0x0008_0000;
+        const LWA_ALPHA: u32 = 0x2;
+
+        let hwnd = window.hwnd().map_err(|e| format!("hwnd: {e}"))?.0 as isize;
+        unsafe {
+            let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+            if level >= 1.0 {
+                // Fully opaque windows draw faster without the layered style
+                SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style & !WS_EX_LAYERED);
+            } else {
+                SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED);
+                if SetLayeredWindowAttributes(hwnd, 0, (level * 255.0).round() as u8, LWA_ALPHA) == 0 {
+                    return Err("SetLayeredWindowAttributes failed".into());
+                }
+            }
+        }
+        force_dwm_repaint(&window);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::ffi::{c_char, c_void};
+
+        #[link(name = "objc")]
+        extern "C" {
+            fn sel_registerName(name: *const c_char) -> *const c_void;
+            fn objc_msgSend();
+        }
+
+        let ns_window = window.ns_window().map_err(|e| format!("ns_window: {e}"))? as usize;
+        // AppKit must be used from the main thread
+        window
+            .run_on_main_thread(move || unsafe {
+                let send: unsafe extern "C" fn(*mut c_void, *const c_void, f64) =
+                    std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+                send(ns_window as *mut c_void, sel_registerName(c"setAlphaValue:".as_ptr()), level);
+            })
+            .map_err(|e| format!("run_on_main_thread: {e}"))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    linux_effects::set_opacity(&window, level)?;
+
+    eprintln!("[set_window_opacity] {} opacity set to {level:.2}", window.label());
+    Ok(())
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+fn set_window_opacity(_level: f64) -> Result<(), String> {
+    Ok(())
+}
+
+/// What `set_window_effect` can do on this system, so the UI can adjust its
+/// theming, e.g. a more opaque background where nothing gets blurred.
+#[derive(serde::Serialize)]
+struct EffectSupport {
+    /// Names `set_window_effect` accepts besides `none`.
+    effects: Vec<&'static str>,
+    /// What an effect gives: `blur`, `transparent` (no blur behind) or `opaque`.
+    backdrop: &'static str,
+    /// XDG_CURRENT_DESKTOP on Linux, empty elsewhere.
+    desktop: String,
+}
+
+#[cfg(not(target_os = "android"))]
+const WINDOWS_EFFECTS: &[&str] =
+    &["mica", "mica-dark", "mica-light", "acrylic", "tabbed", "tabbed-dark", "tabbed-light", "blur"];
+
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+fn get_window_effect_support(window: tauri::WebviewWindow) -> Result<EffectSupport, String> {
+    let mut effects = WINDOWS_EFFECTS.to_vec();
+    if cfg!(target_os = "macos") {
+        effects.extend(MACOS_MATERIALS.iter().map(|(name, _)| *name));
+    }
+    #[cfg(target_os = "linux")]
+    return Ok(EffectSupport {
+        effects,
+        backdrop: linux_effects::backdrop(&window)?.as_str(),
+        desktop: linux_effects::desktop(),
+    });
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = window;
+        Ok(EffectSupport { effects, backdrop: "blur", desktop: String::new() })
+    }
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+fn get_window_effect_support() -> Result<EffectSupport, String> {
+    Ok(EffectSupport { effects: Vec::new(), backdrop: "opaque", desktop: String::new() })
+}
+
 // ── TTS (native) ──────────────────────────────────────────────────────
 
 #[cfg(not(target_os = "android"))]
@@ -510,6 +1638,59 @@ fn get_tts_lock() -> &'static Mutex<Option<tts::Tts>> {
     TTS_INSTANCE.get_or_init(|| Mutex::new(None))
 }
 
+/// What native TTS is reading, so `tts_pause` can stop and `tts_resume` pick
+/// up again. The `tts` crate doesn't expose the backends' own pause, so pausing
+/// is a stop everywhere. Where the backend reports finished utterances the text
+/// is queued sentence by sentence and resuming starts at the sentence that was
+/// cut off; elsewhere it starts the text over.
+#[cfg(not(target_os = "android"))]
+struct TtsReading {
+    sentences: Vec<String>,
+    /// Index of the first sentence not finished yet.
+    next: usize,
+    paused: bool,
+}
+
+#[cfg(not(target_os = "android"))]
+static TTS_READING: Mutex<Option<TtsReading>> = Mutex::new(None);
+
+/// Split after sentence punctuation followed by whitespace, and at line breaks.
+#[cfg(not(target_os = "android"))]
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let at_break = chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        let ends_sentence = c == '\n' || (matches!(c, '.' | '!' | '?' | '…') && at_break);
+        if ends_sentence {
+            let end = i + c.len_utf8();
+            sentences.push(text[start..end].trim().to_string());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim().to_string());
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+/// Utterances end in the order they were queued, so each one moves the reading on a sentence.
+#[cfg(not(target_os = "android"))]
+fn on_sentence_end(_id: tts::UtteranceId) {
+    if let Some(reading) = TTS_READING.lock().unwrap().as_mut() {
+        reading.next = (reading.next + 1).min(reading.sentences.len());
+    }
+}
+
+/// Queue `sentences` from `from` on, cutting off whatever is being spoken.
+#[cfg(not(target_os = "android"))]
+fn speak_sentences(tts: &mut tts::Tts, sentences: &[String], from: usize) -> Result<(), String> {
+    for (n, sentence) in sentences[from..].iter().enumerate() {
+        tts.speak(sentence.as_str(), n == 0).map_err(|e| format!("TTS speak: {e}"))?;
+    }
+    Ok(())
+}
+
 #[cfg(not(target_os = "android"))]
 #[tauri::command]
 fn tts_speak(text: String, rate: Option<f32>) -> Result<(), String> {
@@ -518,6 +1699,9 @@ fn tts_speak(text: String, rate: Option<f32>) -> Result<(), String> {
         Some(t) => t,
         None => {
             let instance = tts::Tts::default().map_err(|e| format!("TTS init: {e}"))?;
+            if instance.supported_features().utterance_callbacks {
+                let _ = instance.on_utterance_end(Some(Box::new(on_sentence_end)));
+            }
             *guard = Some(instance);
             guard.as_mut().unwrap()
         }
@@ -536,7 +1720,11 @@ fn tts_speak(text: String, rate: Option<f32>) -> Result<(), String> {
         };
         tts.set_rate(mapped).map_err(|e| format!("TTS rate: {e}"))?;
     }
-    tts.speak(text, true).map_err(|e| format!("TTS speak: {e}"))?;
+    let sentences =
+        if tts.supported_features().utterance_callbacks { split_sentences(&text) } else { vec![text] };
+    *TTS_READING.lock().unwrap() = None;
+    speak_sentences(tts, &sentences, 0)?;
+    *TTS_READING.lock().unwrap() = Some(TtsReading { sentences, next: 0, paused: false });
     Ok(())
 }
 
@@ -544,12 +1732,61 @@ fn tts_speak(text: String, rate: Option<f32>) -> Result<(), String> {
 #[tauri::command]
 fn tts_stop() -> Result<(), String> {
     let mut guard = get_tts_lock().lock().map_err(|e| format!("TTS lock: {e}"))?;
+    *TTS_READING.lock().unwrap() = None;
     if let Some(tts) = guard.as_mut() {
         tts.stop().map_err(|e| format!("TTS stop: {e}"))?;
     }
     Ok(())
 }
 
+/// Pause native TTS where it is. Returns false when nothing was being read.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+fn tts_pause() -> Result<bool, String> {
+    let mut guard = get_tts_lock().lock().map_err(|e| format!("TTS lock: {e}"))?;
+    let Some(tts) = guard.as_mut() else { return Ok(false) };
+    // Backends that can't tell count as speaking; the sentence count still has a say
+    let speaking = tts.is_speaking().unwrap_or(true);
+    let unfinished =
+        TTS_READING.lock().unwrap().as_ref().is_some_and(|r| !r.paused && r.next < r.sentences.len());
+    if !speaking || !unfinished {
+        return Ok(false);
+    }
+    // Not while holding TTS_READING: the backend may be delivering an end to it
+    tts.stop().map_err(|e| format!("TTS stop: {e}"))?;
+    if let Some(reading) = TTS_READING.lock().unwrap().as_mut() {
+        reading.paused = true;
+        eprintln!("[tts] paused at sentence {}/{}", reading.next + 1, reading.sentences.len());
+    }
+    Ok(true)
+}
+
+/// Carry on after `tts_pause`. Returns false when nothing was paused.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+fn tts_resume() -> Result<bool, String> {
+    let mut guard = get_tts_lock().lock().map_err(|e| format!("TTS lock: {e}"))?;
+    let Some(tts) = guard.as_mut() else { return Ok(false) };
+    let paused =
+        TTS_READING.lock().unwrap().as_ref().filter(|r| r.paused).map(|r| (r.sentences.clone(), r.next));
+    let Some((sentences, next)) = paused else { return Ok(false) };
+    if next >= sentences.len() {
+        *TTS_READING.lock().unwrap() = None;
+        return Ok(false);
+    }
+    speak_sentences(tts, &sentences, next)?;
+    if let Some(reading) = TTS_READING.lock().unwrap().as_mut() {
+        reading.paused = false;
+    }
+    Ok(true)
+}
+
+/// Whether native TTS is paused mid-text.
+#[cfg(not(target_os = "android"))]
+fn tts_paused() -> bool {
+    TTS_READING.lock().unwrap().as_ref().is_some_and(|r| r.paused)
+}
+
 #[cfg(target_os = "android")]
 #[tauri::command]
 fn tts_speak(_text: String, _rate: Option<f32>) -> Result<(), String> {
@@ -562,6 +1799,23 @@ fn tts_stop() -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(target_os = "android")]
+#[tauri::command]
+fn tts_pause() -> Result<bool, String> {
+    Ok(false)
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+fn tts_resume() -> Result<bool, String> {
+    Ok(false)
+}
+
+#[cfg(target_os = "android")]
+fn tts_paused() -> bool {
+    false
+}
+
 #[tauri::command]
 async fn tts_speak_elevenlabs(
     text: String,
@@ -602,8 +1856,7 @@ async fn tts_speak_elevenlabs(
         return Err(format!("ElevenLabs HTTP {status}: {err_body}"));
     }
 
-    let bytes = response
-        .bytes()
+    let bytes = response_limit::read_bytes(response, MAX_INLINE_BINARY_BYTES)
         .await
         .map_err(|e| format!("ElevenLabs read body: {e}"))?;
 
@@ -651,7 +1904,19 @@ async fn open_auth_window(_url: String) -> Result<(), String> {
 // ── Save file dialog (export) ─────────────────────────────────────────
 
 #[tauri::command]
-async fn save_file_dialog(content: String, default_name: String) -> Result<bool, String> {
+async fn save_file_dialog(
+    content: String,
+    default_name: String,
+    naming: Option<naming::NamingContext>,
+) -> Result<bool, String> {
+    // With a naming context, the export template names the file; `default_name` keeps its extension
+    let default_name = match naming {
+        Some(context) => {
+            let ext = std::path::Path::new(&default_name).extension().and_then(|e| e.to_str()).unwrap_or("");
+            naming::file_name(naming::TemplateKind::ExportFile, &context, ext)?
+        }
+        None => default_name,
+    };
     let dialog = rfd::AsyncFileDialog::new()
         .set_file_name(&default_name)
         .add_filter("JSON", &["json"])
@@ -685,8 +1950,11 @@ fn pandoc_check() -> Result<String, String> {
     Ok(first_line.to_string())
 }
 
+/// pandoc reports no progress, so this bounds a whole conversion.
+const PANDOC_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
 #[tauri::command]
-fn pandoc_import(base64_data: String, filename: String) -> Result<String, String> {
+async fn pandoc_import(base64_data: String, filename: String) -> Result<String, String> {
     let bytes = STANDARD.decode(&base64_data)
         .map_err(|e| format!("base64 decode error: {e}"))?;
 
@@ -698,15 +1966,19 @@ fn pandoc_import(base64_data: String, filename: String) -> Result<String, String
     std::fs::write(&input_path, &bytes)
         .map_err(|e| format!("Failed to write temp file: {e}"))?;
 
-    let output = std::process::Command::new("pandoc")
-        .arg(input_path.to_str().unwrap())
+    let mut cmd = std::process::Command::new("pandoc");
+    cmd.arg(input_path.to_str().unwrap())
         .arg("-t").arg("html")
-        .arg("--wrap=none")
-        .output()
-        .map_err(|e| format!("pandoc execution failed: {e}"))?;
+        .arg("--wrap=none");
+    let label = format!("Import {filename}");
+    let output = tasks::supervise("pandoc", &label, PANDOC_TIMEOUT, |task| async move {
+        tasks::run_process(&task, cmd).await.map_err(|e| format!("pandoc execution failed: {e}"))
+    })
+    .await;
 
     // Clean up temp file
     let _ = std::fs::remove_file(&input_path);
+    let output = output?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -717,7 +1989,7 @@ fn pandoc_import(base64_data: String, filename: String) -> Result<String, String
 }
 
 #[tauri::command]
-fn pandoc_export(html_content: String, format: String) -> Result<String, String> {
+async fn pandoc_export(html_content: String, format: String) -> Result<String, String> {
     let tmp_dir = std::env::temp_dir().join("superflux_pandoc");
     std::fs::create_dir_all(&tmp_dir)
         .map_err(|e| format!("Failed to create temp dir: {e}"))?;
@@ -733,15 +2005,21 @@ fn pandoc_export(html_content: String, format: String) -> Result<String, String>
     std::fs::write(&input_path, &html_content)
         .map_err(|e| format!("Failed to write temp file: {e}"))?;
 
-    let output = std::process::Command::new("pandoc")
-        .arg(input_path.to_str().unwrap())
+    let mut cmd = std::process::Command::new("pandoc");
+    cmd.arg(input_path.to_str().unwrap())
         .arg("-f").arg("html")
         .arg("-t").arg(&format)
-        .arg("-o").arg(output_path.to_str().unwrap())
-        .output()
-        .map_err(|e| format!("pandoc execution failed: {e}"))?;
+        .arg("-o").arg(output_path.to_str().unwrap());
+    let label = format!("Export to {format}");
+    let output = tasks::supervise("pandoc", &label, PANDOC_TIMEOUT, |task| async move {
+        tasks::run_process(&task, cmd).await.map_err(|e| format!("pandoc execution failed: {e}"))
+    })
+    .await;
 
     let _ = std::fs::remove_file(&input_path);
+    let output = output.inspect_err(|_| {
+        let _ = std::fs::remove_file(&output_path);
+    })?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -764,9 +2042,26 @@ pub fn run() {
         .manage(AppState {
             #[cfg(not(target_os = "android"))]
             saved: Mutex::new(None),
+            #[cfg(not(target_os = "android"))]
+            window_state: Mutex::new(None),
+            #[cfg(not(target_os = "android"))]
+            docked: Mutex::new(None),
+            #[cfg(not(target_os = "android"))]
+            window_effect: Mutex::new(None),
+            #[cfg(not(target_os = "android"))]
+            reading: Mutex::new(None),
         })
-        .invoke_handler(tauri::generate_handler![fetch_url, http_request, open_external, get_cpu_usage, get_memory_usage, get_net_speed, collapse_window, expand_window, hide_to_tray, check_network, set_window_effect, tts_speak, tts_stop, tts_speak_elevenlabs, open_auth_window, save_file_dialog, pandoc_check, pandoc_import, pandoc_export, snippets::sync_snippets, snippets::set_snippet_shortcut, clipboard_history::get_clipboard_history, clipboard_history::delete_clip_entry, clipboard_history::clear_clipboard_history, clipboard_history::toggle_pin_clip_entry, clipboard_history::paste_clip_entry, clipboard_history::set_clip_shortcut, clipboard_history::get_clipboard_settings, clipboard_history::set_clipboard_settings, password_vault::pw_vault_exists, password_vault::pw_create_vault, password_vault::pw_unlock_vault, password_vault::pw_lock_vault, password_vault::pw_is_unlocked, password_vault::pw_get_entries, password_vault::pw_add_entry, password_vault::pw_update_entry, password_vault::pw_delete_entry, password_vault::pw_get_folders, password_vault::pw_add_folder, password_vault::pw_update_folder, password_vault::pw_delete_folder, password_vault::pw_generate_password, password_vault::pw_get_totp, password_vault::pw_copy_to_clipboard, password_vault::pw_change_master, password_vault::pw_audit_passwords, password_vault::pw_get_settings, password_vault::pw_update_settings, password_vault::pw_export_csv, password_vault::pw_import_csv, password_vault::pw_get_vault_blob, password_vault::pw_import_vault_blob, password_vault::pw_get_vault_meta, password_vault::pw_import_vault_meta, markdown_vault::md_pick_folder, markdown_vault::md_list_vault_files, markdown_vault::md_read_file, markdown_vault::md_write_file, markdown_vault::md_create_file, markdown_vault::md_create_folder, markdown_vault::md_delete_entry, markdown_vault::md_rename_entry, markdown_vault::md_resolve_wikilink, markdown_vault::md_list_md_files, markdown_vault::md_scan_vault_links, markdown_vault::md_get_backlinks, markdown_vault::md_search_in_vault, markdown_vault::md_replace_in_file, markdown_vault::md_git_repo_info, markdown_vault::md_git_status, markdown_vault::md_git_init, markdown_vault::md_git_stage, markdown_vault::md_git_unstage, markdown_vault::md_git_commit, markdown_vault::md_git_log, markdown_vault::md_git_diff, markdown_vault::md_git_diff_contents, markdown_vault::md_git_discard_changes, markdown_vault::md_git_list_branches, markdown_vault::md_git_checkout_branch, markdown_vault::md_git_create_branch, markdown_vault::md_git_push, markdown_vault::md_git_pull, markdown_vault::md_git_parse_conflicts, markdown_vault::md_git_resolve_conflict, markdown_vault::md_git_sync, markdown_vault::md_parse_file_metadata, markdown_vault::md_scan_vault_metadata, markdown_vault::md_get_vault_tags, ])
+        .register_asynchronous_uri_scheme_protocol(image_cache::SCHEME, |_ctx, request, responder| {
+            // Reads a file from disk, so kept off the event loop
+            std::thread::spawn(move || responder.respond(image_cache::serve(&request)));
+        })
+        .invoke_handler(tauri::generate_handler![fetch_url, fetch_urls, fetch_binary, http_request, open_external, get_cpu_usage, get_memory_usage, get_net_speed, collapse_window, dock_window, expand_window, hide_to_tray, set_always_on_top, set_reading_mode, check_network, set_window_effect, set_window_opacity, get_window_effect_support, tts_speak, tts_stop, tts_pause, tts_resume, tts_speak_elevenlabs, open_auth_window, save_file_dialog, pick_upload_file, pandoc_check, pandoc_import, pandoc_export, snippets::sync_snippets, snippets::set_snippet_shortcut, clipboard_history::get_clipboard_history, clipboard_history::delete_clip_entry, clipboard_history::clear_clipboard_history, clipboard_history::toggle_pin_clip_entry, clipboard_history::paste_clip_entry, clipboard_history::set_clip_shortcut, clipboard_history::get_clipboard_settings, clipboard_history::set_clipboard_settings, password_vault::pw_vault_exists, password_vault::pw_create_vault, password_vault::pw_unlock_vault, password_vault::pw_lock_vault, password_vault::pw_is_unlocked, password_vault::pw_get_entries, password_vault::pw_add_entry, password_vault::pw_update_entry, password_vault::pw_delete_entry, password_vault::pw_get_folders, password_vault::pw_add_folder, password_vault::pw_update_folder, password_vault::pw_delete_folder, password_vault::pw_generate_password, password_vault::pw_get_totp, password_vault::pw_copy_to_clipboard, password_vault::pw_change_master, password_vault::pw_audit_passwords, password_vault::pw_get_settings, password_vault::pw_update_settings, password_vault::pw_export_csv, password_vault::pw_import_csv, password_vault::pw_get_vault_blob, password_vault::pw_import_vault_blob, password_vault::pw_get_vault_meta, password_vault::pw_import_vault_meta, markdown_vault::md_pick_folder, markdown_vault::md_list_vault_files, markdown_vault::md_read_file, markdown_vault::md_write_file, markdown_vault::md_create_file, markdown_vault::md_create_folder, markdown_vault::md_delete_entry, markdown_vault::md_rename_entry, markdown_vault::md_resolve_wikilink, markdown_vault::md_list_md_files, markdown_vault::md_scan_vault_links, markdown_vault::md_get_backlinks, markdown_vault::md_search_in_vault, markdown_vault::md_replace_in_file, markdown_vault::md_git_repo_info, markdown_vault::md_git_status, markdown_vault::md_git_init, markdown_vault::md_git_stage, markdown_vault::md_git_unstage, markdown_vault::md_git_commit, markdown_vault::md_git_log, markdown_vault::md_git_diff, markdown_vault::md_git_diff_contents, markdown_vault::md_git_discard_changes, markdown_vault::md_git_list_branches, markdown_vault::md_git_checkout_branch, markdown_vault::md_git_create_branch, markdown_vault::md_git_push, markdown_vault::md_git_pull, markdown_vault::md_git_parse_conflicts, markdown_vault::md_git_resolve_conflict, markdown_vault::md_git_sync, markdown_vault::md_parse_file_metadata, markdown_vault::md_scan_vault_metadata, markdown_vault::md_get_vault_tags, notifications::get_push_targets, notifications::save_push_target, notifications::delete_push_target, notifications::test_push_target, notifications::send_push_notification, share::get_share_targets, share::save_share_target, share::delete_share_target, share::preview_share_message, share::share_article, matrix::matrix_get_config, matrix::matrix_set_config, matrix::matrix_list_joined_rooms, matrix::matrix_send_article, feed_stats::stats_record_new_items, feed_stats::stats_record_read, feed_stats::get_feed_stats, feed_stats::export_feed_stats, ingest::ingest_items, clustering::get_story_clusters, trending::get_trending, filters::mute_topic, filters::list_topic_mutes, filters::lift_topic_mute, filters::get_muted_item_ids, integrated_auth::get_integrated_auth_hosts, integrated_auth::set_integrated_auth_hosts, atlassian::get_atlassian_sources, atlassian::save_atlassian_source, atlassian::delete_atlassian_source, atlassian::fetch_atlassian_source, feed_hooks::get_feed_hooks, feed_hooks::save_feed_hook, feed_hooks::delete_feed_hook, feed_hooks::test_feed_hook, feed_parser::parse_feed, rss_bridge::rss_bridge_get_config, rss_bridge::rss_bridge_set_instance, rss_bridge::rss_bridge_list_bridges, rss_bridge::rss_bridge_build_url, rss_bridge::rss_bridge_subscribe, rss_bridge::rss_bridge_unsubscribe, conditional_get::fetch_url_conditional, gallery::get_media_gallery, comics::get_comic_feeds, comics::set_comic_feed, comics::remove_comic_feed, comics::get_comic, comics::prefetch_comics, db::db_upsert_articles, db::db_query_articles, db::db_get_article, db::db_mark_read, db::db_mark_feed_read, db::db_mark_starred, db::db_get_unread_counts, db::db_upsert_feed, db::db_get_feeds, db::db_delete_feed, sounds::get_sound_settings, sounds::save_sound_settings, sounds::list_sounds, sounds::import_sound, sounds::delete_sound, sounds::play_sound, sounds::play_notification_sound, focus::start_focus_session, focus::stop_focus_session, focus::get_focus_session, focus::get_focus_history, opml::opml_import, input_state::get_input_state, opml::opml_export, discovery::discover_feeds, network_identity::get_network_identity, speedtest::get_speedtest_config, speedtest::set_speedtest_config, speedtest::run_speedtest, backup::get_backup_config, backup::set_backup_config, backup::run_backup_now, backup::list_remote_backups, backup::restore_from_remote, sync_folder::get_sync_folder, sync_folder::set_sync_folder, sync_folder::sync_record_changes, sync_folder::sync_folder_now, http_cache::cache_clear, permissions::get_permission_grants, permissions::set_permission_grant, permissions::revoke_permissions, permissions::respond_permission_request, permissions::request_permissions, http_auth::get_http_auth_hosts, http_auth::set_http_auth, http_auth::delete_http_auth, text::html_to_snippet, dates::get_feed_timezones, dates::set_feed_timezone, dates::parse_feed_date, proxy::get_proxy, proxy::set_proxy, content::resolve_content_urls, tls::get_tls_trust, tls::add_root_certificate, tls::remove_root_certificate, tls::set_accept_invalid_certs, content::normalize_content_images, highlight::highlight_theme_css, highlight::highlight_html, retry::get_retry_policy, retry::set_retry_policy, math::tex_to_mathml, math::render_math_html, rate_limit::get_rate_limits, rate_limit::set_rate_limits, response_limit::get_response_limits, response_limit::set_response_limits, research::get_research_sources, research::save_research_source, research::delete_research_source, research::fetch_research_source, db::get_article_content, tasks::get_background_tasks, tasks::cancel_background_task, tasks::cancel_request, startup::get_startup_state, startup::save_startup_snapshot, timeouts::get_timeouts, timeouts::set_timeouts, startup::get_startup_options, startup::set_startup_options, startup::set_last_view, startup::get_startup_plan, probe_url, locale::get_locale_info, locale::format_date_localized, locale::format_number_localized, locale::get_plural_category, search::get_search_settings, search::set_search_settings, search::rebuild_search_index, sse::sse_subscribe, sse::sse_close, ws::ws_connect, ws::ws_send, ws::ws_close, saved_searches::get_saved_searches, saved_searches::save_saved_search, saved_searches::delete_saved_search, saved_searches::get_saved_search_articles, saved_searches::get_saved_search_counts, links::get_backlinks, links::get_outlinks, links::get_backlink_counts, connectivity::get_connectivity, authors::get_authors, authors::get_article_authors, authors::follow_author, private_hosts::get_private_host_allowlist, private_hosts::set_private_host_allowed, header_rules::get_header_rules, header_rules::set_header_rule, header_rules::delete_header_rule, article_windows::get_article_windows, article_windows::get_article_window, article_windows::close_article_window, reddit::fetch_reddit, naming::get_naming_templates, naming::set_naming_templates, naming::preview_naming_template, naming::render_name, accessibility::get_accessibility_prefs, accessibility::announce, youtube::resolve_youtube_feed, readability::extract_article, voice::voice_available, voice::get_voice_settings, voice::set_voice_settings, voice::get_voice_commands, voice::voice_command, voice::voice_start_listening, voice::voice_stop_listening, kiosk::get_kiosk_settings, kiosk::set_kiosk_settings, kiosk::start_kiosk, kiosk::stop_kiosk, kiosk::get_kiosk_status, kiosk::kiosk_next, kiosk::kiosk_previous, sanitize::get_sanitize_policy, sanitize::set_sanitize_policy, sanitize::sanitize_html, image_cache::cache_image, image_cache::clear_image_cache, netsim::get_netsim_rules, netsim::set_netsim_rule, netsim::delete_netsim_rule, netsim::clear_netsim_rules, favicon::get_favicon, fulltext::search_index_add, fulltext::search_query, filters::get_filter_rules, filters::save_filter_rule, filters::delete_filter_rule, filters::reorder_filter_rules, filters::preview_filter_rule, db::db_mark_hidden, filters::get_keyword_watches, filters::add_keyword_watch, filters::set_keyword_watch_enabled, filters::remove_keyword_watch, dedup::duplicates_of, dedup::get_dedup_settings, dedup::set_dedup_settings, retention::get_retention_settings, retention::set_retention_settings, retention::set_feed_retention, retention::prune_now, retention::get_retention_stats, article_notifications::get_article_notification_settings, article_notifications::set_article_notification_settings, article_notifications::set_feed_notifications, article_windows::open_article_window, miniplayer::open_miniplayer, miniplayer::close_miniplayer, miniplayer::get_player_state, miniplayer::set_player_state, miniplayer::player_control, shortcuts::get_global_shortcuts, shortcuts::set_global_shortcut, taskbar::set_badge_count, taskbar::set_progress, power::inhibit_sleep, power::release_sleep, ])
         .setup(|_app| {
+            // A restore staged by restore_from_remote replaces the data files before anything loads them
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                backup::apply_pending_restore(&data_dir);
+            }
+
             // Initialize snippet store and start global keyboard hook
             let snippet_store = Arc::new(snippets::SnippetStore::new());
             _app.manage(snippet_store.clone());
@@ -790,6 +2085,302 @@ pub fn run() {
             _app.manage(clip_store.clone());
             clipboard_history::start_clipboard_monitor(clip_store.clone(), _app.handle().clone());
 
+            // Initialize push notification targets (ntfy / Gotify)
+            let notif_store = Arc::new(notifications::NotificationStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                notif_store.set_data_dir(data_dir);
+            }
+            _app.manage(notif_store);
+
+            // Initialize new-article notification settings
+            let article_notif_store = Arc::new(article_notifications::ArticleNotificationStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                article_notif_store.set_data_dir(data_dir);
+            }
+            _app.manage(article_notif_store);
+
+            // Initialize Discord/Slack share targets
+            let share_store = Arc::new(share::ShareStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                share_store.set_data_dir(data_dir);
+            }
+            _app.manage(share_store);
+
+            // Initialize Matrix client config (sharing + keyword alerts)
+            let matrix_store = Arc::new(matrix::MatrixStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                matrix_store.set_data_dir(data_dir);
+            }
+            _app.manage(matrix_store);
+
+            // Initialize per-feed statistics (fetch outcomes, new items, reads)
+            let stats_store = Arc::new(feed_stats::FeedStatsStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                stats_store.set_data_dir(data_dir);
+            }
+            _app.manage(stats_store.clone());
+            feed_stats::start_flush_timer(stats_store);
+
+            // Initialize the recent-items window used by the analysis commands
+            let ingest_store = Arc::new(ingest::IngestStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                ingest_store.set_data_dir(data_dir);
+            }
+            _app.manage(ingest_store.clone());
+            ingest::start_flush_timer(ingest_store);
+
+            // Initialize daily keyword counts for trending topics
+            let trending_store = Arc::new(trending::TrendingStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                trending_store.set_data_dir(data_dir);
+            }
+            _app.manage(trending_store.clone());
+            trending::start_flush_timer(trending_store);
+
+            // Initialize the filter engine (topic mutes)
+            let filter_store = Arc::new(filters::FilterStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                filter_store.set_data_dir(data_dir);
+            }
+            _app.manage(filter_store);
+
+            // Load hosts allowed to use Windows integrated auth (NTLM/Negotiate)
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                integrated_auth::init(data_dir);
+            }
+
+            // Load hosts with saved Basic/Digest credentials (passwords stay in the keyring)
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                http_auth::init(data_dir);
+            }
+
+            // Load proxy settings before the shared HTTP client is first built
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                proxy::init(data_dir);
+            }
+
+            // Load extra root certificates and hosts exempt from certificate checks
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                tls::init(data_dir);
+            }
+
+            // Load the retry policy for transient fetch failures
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                retry::init(data_dir);
+            }
+
+            // Load hosts allowed to resolve to private addresses
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                private_hosts::init(data_dir);
+            }
+
+            // Load the naming templates for exports, downloads and digests
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                naming::init(data_dir);
+            }
+
+            // Load the sanitizer allowlists for article HTML
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                sanitize::init(data_dir);
+            }
+
+            // Load user-defined per-domain request headers
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                header_rules::init(data_dir);
+            }
+
+            // Load per-host request spacing
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                rate_limit::init(data_dir);
+            }
+
+            // Load the response body size cap
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                response_limit::init(data_dir);
+            }
+
+            // Load request / connect timeouts
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                timeouts::init(data_dir);
+            }
+
+            // Initialize Confluence/Jira connectors
+            let atlassian_store = Arc::new(atlassian::AtlassianStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                atlassian_store.set_data_dir(data_dir);
+            }
+            _app.manage(atlassian_store);
+
+            // Initialize arXiv/PubMed saved searches
+            let research_store = Arc::new(research::ResearchStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                research_store.set_data_dir(data_dir);
+            }
+            _app.manage(research_store);
+
+            // Load per-feed external command hooks
+            let hook_store = Arc::new(feed_hooks::FeedHookStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                hook_store.set_data_dir(data_dir);
+            }
+            _app.manage(hook_store);
+
+            // Load RSS-Bridge instance and subscriptions
+            let bridge_store = Arc::new(rss_bridge::RssBridgeStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                bridge_store.set_data_dir(data_dir);
+            }
+            _app.manage(bridge_store);
+
+            // Initialize the per-item media gallery
+            let gallery_store = Arc::new(gallery::GalleryStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                gallery_store.set_data_dir(data_dir);
+            }
+            _app.manage(gallery_store.clone());
+            gallery::start_flush_timer(gallery_store);
+
+            // Load comic-mode feeds and the extracted strip index
+            let comic_store = Arc::new(comics::ComicStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                comic_store.set_data_dir(data_dir);
+            }
+            _app.manage(comic_store);
+
+            // Load the last session's startup snapshot before the database
+            let startup_store = Arc::new(startup::StartupStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                startup_store.set_data_dir(data_dir);
+            }
+            _app.manage(startup_store);
+            startup::schedule_first_refresh(_app.handle().clone());
+
+            // Load search folding and dedup settings before the database (and its index) opens
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                search::init(data_dir.clone());
+                dedup::init(data_dir);
+            }
+
+            // Open the article database
+            let database = Arc::new(db::Database::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                match database.open(data_dir.clone()) {
+                    Ok(()) => {
+                        db::start_backfills(database.clone());
+                        fulltext::start(data_dir, database.clone());
+                    }
+                    Err(e) => eprintln!("[db] {e}"),
+                }
+            }
+            _app.manage(database);
+
+            // Saved searches, checked against every batch of new articles
+            let saved_search_store = Arc::new(saved_searches::SavedSearchStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                saved_search_store.set_data_dir(data_dir);
+            }
+            _app.manage(saved_search_store);
+
+            // Detached article windows and the session they are restored from
+            let article_window_store = Arc::new(article_windows::ArticleWindowStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                article_window_store.set_data_dir(data_dir);
+            }
+            _app.manage(article_window_store);
+
+            // Mini-player state, published by whichever part of the app is playing
+            _app.manage(Arc::new(miniplayer::PlayerStore::new()));
+
+            // Load notification sound settings
+            let sound_store = Arc::new(sounds::SoundStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                sound_store.set_data_dir(data_dir);
+            }
+            _app.manage(sound_store);
+
+            // Focus sessions (history only; a running session does not survive restart)
+            let focus_store = Arc::new(focus::FocusStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                focus_store.set_data_dir(data_dir);
+            }
+            _app.manage(focus_store);
+
+            // Kiosk mode settings (a rotation itself is not restored)
+            let kiosk_store = Arc::new(kiosk::KioskStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                kiosk_store.set_data_dir(data_dir);
+            }
+            _app.manage(kiosk_store);
+
+            // Lock keys / keyboard layout for the bar's status strip
+            input_state::start_poller(_app.handle().clone());
+
+            // OS reduced-motion / high-contrast settings
+            accessibility::start_watcher(_app.handle().clone());
+
+            // Online/offline transitions, so refreshing can pause while offline
+            connectivity::start_watcher(_app.handle().clone());
+
+            // Speed test endpoints for the network widget
+            let speedtest_store = Arc::new(speedtest::SpeedtestStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                speedtest_store.set_data_dir(data_dir);
+            }
+            _app.manage(speedtest_store);
+
+            // Backup config and the scheduled backup job
+            let backup_store = Arc::new(backup::BackupStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                backup_store.set_data_dir(data_dir);
+            }
+            _app.manage(backup_store);
+            backup::start_scheduler(_app.handle().clone());
+
+            // Account-free sync through a user-chosen synced folder
+            let sync_folder_store = Arc::new(sync_folder::SyncFolderStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                sync_folder_store.set_data_dir(data_dir);
+            }
+            _app.manage(sync_folder_store);
+            sync_folder::start_sync_timer(_app.handle().clone());
+
+            // Per-feed retention policies, applied by a background timer
+            let retention_store = Arc::new(retention::RetentionStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                retention_store.set_data_dir(data_dir);
+            }
+            _app.manage(retention_store);
+            retention::start_retention_timer(_app.handle().clone());
+
+            // On-disk HTTP cache for fetch_url / fetch_binary
+            if let Ok(cache_dir) = _app.path().app_cache_dir() {
+                http_cache::init(cache_dir.join("http"));
+            }
+
+            // Image cache served to the webview as sfimage: URLs
+            if let Ok(cache_dir) = _app.path().app_cache_dir() {
+                image_cache::init(cache_dir.join("images"));
+            }
+
+            // Per-host favicons for the feed list
+            if let Ok(cache_dir) = _app.path().app_cache_dir() {
+                favicon::init(cache_dir.join("favicons"));
+            }
+
+            // Permission grants for plugins, user scripts and webhooks
+            let permission_store = Arc::new(permissions::PermissionStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                permission_store.set_data_dir(data_dir);
+            }
+            _app.manage(permission_store);
+
+            // Per-feed timezone overrides for the lenient date parser
+            let date_store = Arc::new(dates::DateStore::new());
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                date_store.set_data_dir(data_dir);
+            }
+            _app.manage(date_store);
+
             // Re-register saved clip shortcuts on startup
             #[cfg(not(target_os = "android"))]
             {
@@ -814,6 +2405,18 @@ pub fn run() {
                 }
             }
 
+            // Voice commands: settings, and the push-to-talk shortcut when enabled
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                voice::init(data_dir);
+            }
+            voice::start(_app.handle());
+
+            // Global shortcuts for playback, navigation and refresh
+            if let Ok(data_dir) = _app.path().app_data_dir() {
+                shortcuts::init(data_dir);
+            }
+            shortcuts::start(_app.handle());
+
             #[cfg(not(target_os = "android"))]
             {
                 let window = _app.get_webview_window("main").expect("main window not found");
@@ -836,15 +2439,42 @@ pub fn run() {
                 window.set_maximizable(true).ok();
                 window.set_closable(true).ok();
 
-                // Re-apply DWM backdrop after every move/resize so the effect persists
+                // Back where it was last time, on the same monitor when it's still there
+                window_state::restore(_app.handle(), &window);
+                window_state::track(_app.handle(), &window);
+
+                // Launch straight into the widget bar when configured, unless it's there already
+                let restored_collapsed = _app.state::<AppState>().saved.lock().unwrap().is_some();
+                if _app.state::<Arc<startup::StartupStore>>().options().start_collapsed && !restored_collapsed {
+                    if let Err(e) = collapse(&window, &_app.state::<AppState>()) {
+                        eprintln!("[startup] Failed to start collapsed: {e}");
+                    }
+                }
+
+                // Article windows open at quit come back; they close with the main window
+                article_windows::restore_session(_app.handle());
+                let app_handle = _app.handle().clone();
+                let win_for_dock = window.clone();
+                window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::Destroyed = event {
+                        // A reserved AppBar strip would outlive the app otherwise
+                        dock::undock(&win_for_dock, &app_handle.state::<AppState>());
+                        article_windows::end_session(&app_handle);
+                        let _ = miniplayer::close_miniplayer(app_handle.clone());
+                    }
+                });
+
+                // Re-apply DWM backdrop after move/resize so the effect persists,
+                // throttled while dragging and once more when the drag ends
                 #[cfg(target_os = "windows")]
                 {
+                    repaint_on_drag_end(&window);
                     let win = window.clone();
                     window.on_window_event(move |event| {
                         match event {
                             tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
                                 if EFFECT_ACTIVE.load(std::sync::atomic::Ordering::Relaxed) {
-                                    force_dwm_repaint(&win);
+                                    throttled_dwm_repaint(&win);
                                 }
                             }
                             _ => {}
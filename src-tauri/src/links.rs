@@ -0,0 +1,180 @@
+use rusqlite::{params, params_from_iter, Connection};
+use scraper::{Html, Node};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use url::Url;
+
+use crate::db::{self, ArticleRow, Database};
+
+// Link graph between stored articles. Outbound links are recorded at ingest
+// under a normalized key, alongside each article's own key, so "which stored
+// articles link here" is an index lookup. Keys ignore the scheme, `www.`,
+// fragments, trailing slashes and tracking parameters.
+
+const MAX_LINKS_PER_ARTICLE: usize = 500;
+/// Query parameters that identify a campaign or referrer, not the page.
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "dclid", "mc_cid", "mc_eid", "igshid", "ref", "ref_src"];
+
+/// Comparison key for a page URL, e.g. `example.com/posts/1?id=2`. None for
+/// anything but http(s).
+pub fn link_key(url: &str) -> Option<String> {
+    let url = Url::parse(url.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_ascii_lowercase();
+    let mut key = host.strip_prefix("www.").unwrap_or(&host).to_string();
+    if let Some(port) = url.port() {
+        key.push_str(&format!(":{port}"));
+    }
+    key.push_str(url.path().trim_end_matches('/'));
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !name.starts_with("utm_") && !TRACKING_PARAMS.contains(&name.as_ref()))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if !query.is_empty() {
+        query.sort();
+        key.push('?');
+        key.push_str(&url::form_urlencoded::Serializer::new(String::new()).extend_pairs(query).finish());
+    }
+    Some(key)
+}
+
+/// Keys of the pages an article's HTML links to, other than itself. Expects
+/// absolute URLs, as stored articles have.
+fn outbound_keys(html: &str, own_key: Option<&str>) -> Vec<String> {
+    if !html.contains("<a") {
+        return Vec::new();
+    }
+    let fragment = Html::parse_fragment(html);
+    let mut seen = HashSet::new();
+    let mut keys = Vec::new();
+    for node in fragment.tree.nodes() {
+        let Node::Element(el) = node.value() else { continue };
+        let Some(key) = (el.name() == "a").then(|| el.attr("href")).flatten().and_then(link_key) else {
+            continue;
+        };
+        if Some(key.as_str()) != own_key && seen.insert(key.clone()) {
+            keys.push(key);
+            if keys.len() == MAX_LINKS_PER_ARTICLE {
+                break;
+            }
+        }
+    }
+    keys
+}
+
+// ── Index maintenance ────────────────────────────────────────────────
+
+/// Record the links of the articles with these ids from what is stored for them.
+pub fn index_stored(conn: &Connection, ids: &[&str]) -> rusqlite::Result<()> {
+    let mut select = conn.prepare_cached("SELECT url, COALESCE(content, summary) FROM articles WHERE id = ?1")?;
+    let mut set_key = conn.prepare_cached("UPDATE articles SET link_key = ?2 WHERE id = ?1")?;
+    let mut clear = conn.prepare_cached("DELETE FROM article_links WHERE source_id = ?1")?;
+    let mut insert = conn.prepare_cached("INSERT INTO article_links (source_id, target_key) VALUES (?1, ?2)")?;
+    for id in ids {
+        let (url, body): (String, String) = select.query_row([id], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        // '' marks articles without a usable URL as done for `backfill_batch`
+        let own_key = link_key(&url);
+        set_key.execute(params![id, own_key.as_deref().unwrap_or_default()])?;
+        clear.execute([id])?;
+        for key in outbound_keys(&body, own_key.as_deref()) {
+            insert.execute(params![id, key])?;
+        }
+    }
+    Ok(())
+}
+
+/// Record links for up to `limit` articles stored before the link graph
+/// existed. Returns how many were indexed.
+pub fn backfill_batch(conn: &mut Connection, limit: usize) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let ids: Vec<String> = tx
+        .prepare("SELECT id FROM articles WHERE link_key IS NULL LIMIT ?1")?
+        .query_map([limit as i64], |r| r.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    index_stored(&tx, &ids)?;
+    tx.commit()?;
+    Ok(ids.len())
+}
+
+// ── Queries ──────────────────────────────────────────────────────────
+
+/// Stored articles linking to `id`, newest first.
+pub fn backlinks(conn: &Connection, id: &str) -> rusqlite::Result<Vec<ArticleRow>> {
+    let sql = format!(
+        "SELECT {} FROM articles
+         WHERE id IN (SELECT l.source_id FROM article_links l
+                      JOIN articles target ON target.link_key = l.target_key
+                      WHERE target.id = ?1)
+           AND id != ?1
+         ORDER BY COALESCE(published_at, fetched_at) DESC, id",
+        db::ROW_COLUMNS
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let rows = stmt.query_map([id], db::row_from_sql)?;
+    rows.collect()
+}
+
+/// Stored articles that `id` links to, newest first.
+pub fn outlinks(conn: &Connection, id: &str) -> rusqlite::Result<Vec<ArticleRow>> {
+    let sql = format!(
+        "SELECT {} FROM articles
+         WHERE link_key IN (SELECT target_key FROM article_links WHERE source_id = ?1)
+           AND id != ?1
+         ORDER BY COALESCE(published_at, fetched_at) DESC, id",
+        db::ROW_COLUMNS
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let rows = stmt.query_map([id], db::row_from_sql)?;
+    rows.collect()
+}
+
+/// Number of other stored articles linking to each of `ids`. Ids without
+/// backlinks are left out.
+pub fn backlink_counts(conn: &Connection, ids: &[String]) -> rusqlite::Result<HashMap<String, u32>> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let sql = format!(
+        "SELECT target.id, COUNT(DISTINCT l.source_id) FROM articles target
+         JOIN article_links l ON l.target_key = target.link_key AND l.source_id != target.id
+         WHERE target.id IN ({})
+         GROUP BY target.id",
+        vec!["?"; ids.len()].join(",")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(ids), |r| Ok((r.get(0)?, r.get(1)?)))?;
+    rows.collect()
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Stored articles that link to this one.
+#[tauri::command]
+pub async fn get_backlinks(
+    article_id: String,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<ArticleRow>, String> {
+    db::run(&db, move |conn| backlinks(conn, &article_id)).await
+}
+
+/// Stored articles this one links to.
+#[tauri::command]
+pub async fn get_outlinks(
+    article_id: String,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<ArticleRow>, String> {
+    db::run(&db, move |conn| outlinks(conn, &article_id)).await
+}
+
+/// Backlink counts for a page of articles, for "referenced by N" badges.
+#[tauri::command]
+pub async fn get_backlink_counts(
+    article_ids: Vec<String>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<HashMap<String, u32>, String> {
+    db::run(&db, move |conn| backlink_counts(conn, &article_ids)).await
+}
@@ -0,0 +1,115 @@
+use gtk::prelude::*;
+use std::time::Duration;
+
+// Window effects on Linux, where Tauri applies none. KWin on X11 blurs what is
+// behind a window that asks through the `_KDE_NET_WM_BLUR_BEHIND_REGION`
+// property; everywhere else (GNOME, wlroots, KWin on Wayland) an effect leaves
+// the window plainly transparent, and without a compositor it stays opaque.
+// `backdrop` reports which of these the window gets, so the UI can use a more
+// opaque background where nothing is blurred.
+
+const BLUR_ATOM: &str = "_KDE_NET_WM_BLUR_BEHIND_REGION";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Backdrop {
+    Blur,
+    Transparent,
+    Opaque,
+}
+
+impl Backdrop {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Backdrop::Blur => "blur",
+            Backdrop::Transparent => "transparent",
+            Backdrop::Opaque => "opaque",
+        }
+    }
+}
+
+/// XDG_CURRENT_DESKTOP, e.g. `KDE` or `ubuntu:GNOME`.
+pub fn desktop() -> String {
+    std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default()
+}
+
+fn is_kde() -> bool {
+    std::env::var_os("KDE_FULL_SESSION").is_some()
+        || desktop().split(':').any(|d| d.eq_ignore_ascii_case("kde"))
+}
+
+/// Run `f` with the window's GDK window on the main thread, where GTK must be
+/// used, and wait for its answer.
+fn on_gdk_window<T: Send + 'static>(
+    window: &tauri::WebviewWindow,
+    f: impl FnOnce(&gtk::gdk::Window) -> T + Send + 'static,
+) -> Result<T, String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let win = window.clone();
+    window
+        .run_on_main_thread(move || {
+            let gdk_window = win.gtk_window().ok().and_then(|w| w.window());
+            let _ = tx.send(gdk_window.map(|w| f(&w)));
+        })
+        .map_err(|e| format!("run_on_main_thread: {e}"))?;
+    rx.recv_timeout(Duration::from_secs(2))
+        .map_err(|_| "Timed out waiting for the window".to_string())?
+        .ok_or_else(|| "The window has no native surface yet".to_string())
+}
+
+fn backdrop_of(gdk_window: &gtk::gdk::Window) -> Backdrop {
+    if !gdk_window.screen().is_composited() {
+        return Backdrop::Opaque;
+    }
+    // The blur property is an X11 one; on Wayland KWin needs a protocol GTK 3 doesn't speak
+    if gdk_window.display().type_().name() == "GdkX11Display" && is_kde() {
+        Backdrop::Blur
+    } else {
+        Backdrop::Transparent
+    }
+}
+
+/// What an effect would give this window.
+pub fn backdrop(window: &tauri::WebviewWindow) -> Result<Backdrop, String> {
+    on_gdk_window(window, backdrop_of)
+}
+
+/// Ask for the background to be blurred, or stop. Returns what the window
+/// actually gets.
+pub fn apply(window: &tauri::WebviewWindow, enabled: bool) -> Result<Backdrop, String> {
+    on_gdk_window(window, move |gdk_window| {
+        let backdrop = backdrop_of(gdk_window);
+        if backdrop != Backdrop::Blur {
+            return backdrop;
+        }
+        let atom = gtk::gdk::Atom::intern(BLUR_ATOM);
+        if enabled {
+            // No rectangles means the whole window
+            let cardinal = gtk::gdk::Atom::intern("CARDINAL");
+            let region = gtk::gdk::ChangeData::ULongs(&[]);
+            gtk::gdk::property_change(
+                gdk_window,
+                &atom,
+                &cardinal,
+                32,
+                gtk::gdk::PropMode::Replace,
+                region,
+            );
+            Backdrop::Blur
+        } else {
+            gtk::gdk::property_delete(gdk_window, &atom);
+            Backdrop::Transparent
+        }
+    })
+}
+
+/// Whole-window opacity; only visible with a compositor.
+pub fn set_opacity(window: &tauri::WebviewWindow, level: f64) -> Result<(), String> {
+    let win = window.clone();
+    window
+        .run_on_main_thread(move || {
+            if let Ok(gtk_window) = win.gtk_window() {
+                gtk_window.set_opacity(level);
+            }
+        })
+        .map_err(|e| format!("run_on_main_thread: {e}"))
+}
@@ -0,0 +1,191 @@
+use chrono::{Datelike, TimeZone, Timelike};
+use fixed_decimal::FixedDecimal;
+use icu_calendar::week::WeekCalculator;
+use icu_calendar::{DateTime, Gregorian};
+use icu_datetime::options::length;
+use icu_datetime::TypedDateTimeFormatter;
+use icu_decimal::FixedDecimalFormatter;
+use icu_locid::Locale;
+use icu_plurals::{PluralCategory, PluralRules};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// Locale-aware formatting from ICU4X's built-in CLDR data, so the frontend and
+// anything generated natively (digests, exports) format dates, numbers and
+// plurals the same way. Every command takes an optional BCP 47 tag and falls
+// back to the OS locale.
+
+const FALLBACK_LOCALE: &str = "en";
+const DEFAULT_FRACTION_DIGITS: u8 = 2;
+const MAX_FRACTION_DIGITS: u8 = 10;
+
+#[derive(Clone, Copy, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Style {
+    Full,
+    Long,
+    Medium,
+    Short,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LocaleInfo {
+    /// BCP 47 tag, e.g. "fr-FR".
+    pub locale: String,
+    /// ISO weekday: 1 = Monday … 7 = Sunday.
+    pub first_day_of_week: u8,
+    /// Days the first week of the year must have in it (ISO 8601: 4).
+    pub min_week_days: u8,
+}
+
+/// OS-style names ("fr_FR.UTF-8", "de_DE@euro") to a BCP 47 locale.
+fn parse_locale(tag: &str) -> Option<Locale> {
+    let tag = tag.split(['.', '@']).next().unwrap_or_default().replace('_', "-");
+    Locale::from_str(&tag).ok()
+}
+
+pub fn system_locale() -> Locale {
+    sys_locale::get_locale()
+        .and_then(|tag| parse_locale(&tag))
+        .unwrap_or_else(|| Locale::from_str(FALLBACK_LOCALE).expect("valid fallback locale"))
+}
+
+fn resolve(tag: Option<&str>) -> Result<Locale, String> {
+    match tag.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(tag) => parse_locale(tag).ok_or_else(|| format!("Invalid locale: {tag}")),
+        None => Ok(system_locale()),
+    }
+}
+
+pub fn locale_info(locale: &Locale) -> Result<LocaleInfo, String> {
+    let week = WeekCalculator::try_new(&locale.into()).map_err(|e| format!("No week data for {locale}: {e}"))?;
+    Ok(LocaleInfo {
+        locale: locale.to_string(),
+        first_day_of_week: week.first_weekday as u8,
+        min_week_days: week.min_week_days,
+    })
+}
+
+/// Format a timestamp (ms since epoch) in `time_zone` (IANA name) or the local zone.
+/// With neither style given, the date is shown in medium style.
+pub fn format_date(
+    timestamp_ms: i64,
+    locale: &Locale,
+    date_style: Option<Style>,
+    time_style: Option<Style>,
+    time_zone: Option<&str>,
+) -> Result<String, String> {
+    let utc = chrono::DateTime::from_timestamp_millis(timestamp_ms).ok_or("Timestamp out of range")?;
+    let local = match time_zone {
+        Some(name) => {
+            let tz: chrono_tz::Tz = name.parse().map_err(|_| format!("Unknown time zone: {name}"))?;
+            utc.with_timezone(&tz).naive_local()
+        }
+        None => chrono::Local.from_utc_datetime(&utc.naive_utc()).naive_local(),
+    };
+    let datetime = DateTime::try_new_gregorian_datetime(
+        local.year(),
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+    )
+    .map_err(|e| format!("Invalid date: {e}"))?;
+
+    let date = date_style.map(|style| match style {
+        Style::Full => length::Date::Full,
+        Style::Long => length::Date::Long,
+        Style::Medium => length::Date::Medium,
+        Style::Short => length::Date::Short,
+    });
+    // Longer time styles name the zone, which a plain local time doesn't carry
+    let time = time_style.map(|style| match style {
+        Style::Full | Style::Long | Style::Medium => length::Time::Medium,
+        Style::Short => length::Time::Short,
+    });
+    let bag = match (date, time) {
+        (Some(date), Some(time)) => length::Bag::from_date_time_style(date, time),
+        (None, Some(time)) => length::Bag::from_time_style(time),
+        (date, None) => length::Bag::from_date_style(date.unwrap_or(length::Date::Medium)),
+    };
+    let formatter = TypedDateTimeFormatter::<Gregorian>::try_new(&locale.into(), bag.into())
+        .map_err(|e| format!("No date formats for {locale}: {e}"))?;
+    Ok(formatter.format_to_string(&datetime))
+}
+
+/// Round to `max_fraction_digits` (trailing zeros dropped); NaN and infinities are rejected.
+fn to_decimal(value: f64, max_fraction_digits: u8) -> Result<FixedDecimal, String> {
+    if !value.is_finite() {
+        return Err(format!("Cannot format {value}"));
+    }
+    let digits = max_fraction_digits.min(MAX_FRACTION_DIGITS) as usize;
+    let decimal = FixedDecimal::from_str(&format!("{value:.digits$}"));
+    Ok(decimal.map_err(|e| format!("Invalid number: {e}"))?.trimmed_end())
+}
+
+/// Grouping and decimal separators per locale, e.g. 1234.5 → "1 234,5" in fr.
+pub fn format_number(value: f64, locale: &Locale, max_fraction_digits: Option<u8>) -> Result<String, String> {
+    let decimal = to_decimal(value, max_fraction_digits.unwrap_or(DEFAULT_FRACTION_DIGITS))?;
+    let formatter = FixedDecimalFormatter::try_new(&locale.into(), Default::default())
+        .map_err(|e| format!("No number formats for {locale}: {e}"))?;
+    Ok(formatter.format_to_string(&decimal))
+}
+
+/// CLDR plural category of `count`: "zero", "one", "two", "few", "many" or "other".
+pub fn plural_category(count: f64, locale: &Locale, ordinal: bool) -> Result<&'static str, String> {
+    let rules = match ordinal {
+        true => PluralRules::try_new_ordinal(&locale.into()),
+        false => PluralRules::try_new_cardinal(&locale.into()),
+    }
+    .map_err(|e| format!("No plural rules for {locale}: {e}"))?;
+    // Keep the digits as written ("1.50" and "1.5" can differ) rather than trimming
+    let digits = format!("{count}");
+    let decimal = FixedDecimal::from_str(&digits).map_err(|e| format!("Invalid number: {e}"))?;
+    Ok(match rules.category_for(&decimal) {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    })
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// The OS locale and its week conventions.
+#[tauri::command]
+pub fn get_locale_info() -> Result<LocaleInfo, String> {
+    locale_info(&system_locale())
+}
+
+#[tauri::command]
+pub fn format_date_localized(
+    timestamp_ms: i64,
+    locale: Option<String>,
+    date_style: Option<Style>,
+    time_style: Option<Style>,
+    time_zone: Option<String>,
+) -> Result<String, String> {
+    format_date(timestamp_ms, &resolve(locale.as_deref())?, date_style, time_style, time_zone.as_deref())
+}
+
+#[tauri::command]
+pub fn format_number_localized(
+    value: f64,
+    locale: Option<String>,
+    max_fraction_digits: Option<u8>,
+) -> Result<String, String> {
+    format_number(value, &resolve(locale.as_deref())?, max_fraction_digits)
+}
+
+/// Which plural form to use for `count`; `ordinal` for 1st/2nd/3rd-style forms.
+#[tauri::command]
+pub fn get_plural_category(
+    count: f64,
+    locale: Option<String>,
+    ordinal: Option<bool>,
+) -> Result<String, String> {
+    plural_category(count, &resolve(locale.as_deref())?, ordinal.unwrap_or(false)).map(String::from)
+}
@@ -0,0 +1,817 @@
+use ego_tree::NodeId;
+use scraper::node::Text;
+use scraper::{Html, Node};
+
+// LaTeX math in article content ($…$, \(…\), $$…$$, \[…\] and MathJax
+// <script type="math/tex">) converted to MathML at ingest. WebView2 and WebKit
+// render MathML natively, so formulas show without loading KaTeX/MathJax
+// scripts into sanitized content. Covers the LaTeX commonly found in science
+// blogs and arXiv abstracts; anything unknown is shown as an error token.
+
+/// Marks converted spans in the output; never appears in real content.
+const PLACEHOLDER: char = '\u{E001}';
+/// Elements whose text is never treated as math.
+const SKIPPED_ELEMENTS: &[&str] = &["pre", "code", "kbd", "samp", "script", "style", "textarea", "math", "svg"];
+/// Longer "formulas" are almost certainly a stray `$` pairing.
+const MAX_TEX_LEN: usize = 4000;
+
+// ── Symbol tables ────────────────────────────────────────────────────
+
+const GREEK: &[(&str, &str)] = &[
+    ("alpha", "α"), ("beta", "β"), ("gamma", "γ"), ("delta", "δ"), ("epsilon", "ϵ"),
+    ("varepsilon", "ε"), ("zeta", "ζ"), ("eta", "η"), ("theta", "θ"), ("vartheta", "ϑ"),
+    ("iota", "ι"), ("kappa", "κ"), ("lambda", "λ"), ("mu", "μ"), ("nu", "ν"), ("xi", "ξ"),
+    ("pi", "π"), ("varpi", "ϖ"), ("rho", "ρ"), ("varrho", "ϱ"), ("sigma", "σ"), ("varsigma", "ς"),
+    ("tau", "τ"), ("upsilon", "υ"), ("phi", "ϕ"), ("varphi", "φ"), ("chi", "χ"), ("psi", "ψ"),
+    ("omega", "ω"), ("infty", "∞"), ("partial", "∂"), ("nabla", "∇"), ("emptyset", "∅"),
+    ("varnothing", "∅"), ("hbar", "ℏ"), ("ell", "ℓ"), ("Re", "ℜ"), ("Im", "ℑ"), ("aleph", "ℵ"),
+    ("wp", "℘"),
+];
+
+/// Upright capitals.
+const GREEK_UPPER: &[(&str, &str)] = &[
+    ("Gamma", "Γ"), ("Delta", "Δ"), ("Theta", "Θ"), ("Lambda", "Λ"), ("Xi", "Ξ"), ("Pi", "Π"),
+    ("Sigma", "Σ"), ("Upsilon", "Υ"), ("Phi", "Φ"), ("Psi", "Ψ"), ("Omega", "Ω"),
+];
+
+const OPERATORS: &[(&str, &str)] = &[
+    ("pm", "±"), ("mp", "∓"), ("times", "×"), ("div", "÷"), ("cdot", "⋅"), ("ast", "∗"),
+    ("star", "⋆"), ("circ", "∘"), ("bullet", "∙"), ("leq", "≤"), ("le", "≤"), ("geq", "≥"),
+    ("ge", "≥"), ("neq", "≠"), ("ne", "≠"), ("approx", "≈"), ("equiv", "≡"), ("sim", "∼"),
+    ("simeq", "≃"), ("cong", "≅"), ("propto", "∝"), ("ll", "≪"), ("gg", "≫"), ("subset", "⊂"),
+    ("supset", "⊃"), ("subseteq", "⊆"), ("supseteq", "⊇"), ("in", "∈"), ("notin", "∉"),
+    ("ni", "∋"), ("cup", "∪"), ("cap", "∩"), ("setminus", "∖"), ("wedge", "∧"), ("land", "∧"),
+    ("vee", "∨"), ("lor", "∨"), ("neg", "¬"), ("lnot", "¬"), ("forall", "∀"), ("exists", "∃"),
+    ("to", "→"), ("rightarrow", "→"), ("leftarrow", "←"), ("gets", "←"), ("Rightarrow", "⇒"),
+    ("Leftarrow", "⇐"), ("leftrightarrow", "↔"), ("Leftrightarrow", "⇔"), ("iff", "⟺"),
+    ("implies", "⟹"), ("mapsto", "↦"), ("uparrow", "↑"), ("downarrow", "↓"), ("mid", "∣"),
+    ("parallel", "∥"), ("perp", "⊥"), ("angle", "∠"), ("oplus", "⊕"), ("otimes", "⊗"),
+    ("ldots", "…"), ("dots", "…"), ("cdots", "⋯"), ("vdots", "⋮"), ("ddots", "⋱"), ("colon", ":"),
+    ("langle", "⟨"), ("rangle", "⟩"), ("lfloor", "⌊"), ("rfloor", "⌋"), ("lceil", "⌈"),
+    ("rceil", "⌉"), ("vert", "|"), ("Vert", "‖"), ("lvert", "|"), ("rvert", "|"), ("lVert", "‖"),
+    ("rVert", "‖"), ("prime", "′"), ("degree", "°"), ("{", "{"), ("}", "}"), ("|", "‖"),
+];
+
+/// Big operators whose limits go above and below in display mode.
+const BIG_OPERATORS: &[(&str, &str)] = &[
+    ("sum", "∑"), ("prod", "∏"), ("coprod", "∐"), ("bigcup", "⋃"), ("bigcap", "⋂"),
+    ("bigoplus", "⨁"), ("bigotimes", "⨂"), ("bigvee", "⋁"), ("bigwedge", "⋀"),
+];
+
+const INTEGRALS: &[(&str, &str)] = &[("int", "∫"), ("iint", "∬"), ("iiint", "∭"), ("oint", "∮")];
+
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "lg", "exp", "det", "dim", "ker", "deg", "gcd", "arg", "Pr", "hom",
+];
+/// Functions that take limits like big operators.
+const LIMIT_FUNCTIONS: &[&str] = &["lim", "max", "min", "sup", "inf", "limsup", "liminf", "argmax", "argmin"];
+
+const SPACES: &[(&str, &str)] = &[
+    (",", "0.1667em"), (":", "0.2222em"), (">", "0.2222em"), (";", "0.2778em"), (" ", "0.25em"),
+    ("quad", "1em"), ("qquad", "2em"), ("enspace", "0.5em"), ("!", "-0.1667em"),
+];
+
+const ACCENTS: &[(&str, &str)] = &[
+    ("hat", "^"), ("widehat", "^"), ("bar", "¯"), ("overline", "‾"), ("vec", "→"), ("dot", "˙"),
+    ("ddot", "¨"), ("tilde", "~"), ("widetilde", "~"), ("check", "ˇ"), ("breve", "˘"),
+    ("acute", "´"), ("grave", "`"), ("overbrace", "⏞"), ("overrightarrow", "→"),
+];
+const UNDER_ACCENTS: &[(&str, &str)] = &[("underline", "_"), ("underbrace", "⏟")];
+
+/// Commands that change nothing visible here.
+const IGNORED: &[&str] = &[
+    "displaystyle", "textstyle", "scriptstyle", "limits", "nolimits", "big", "Big", "bigg",
+    "Bigg", "bigl", "bigr", "Bigl", "Bigr", "nonumber", "notag", "label", "tag", "color",
+];
+
+fn lookup<'a>(table: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    table.iter().find(|(k, _)| *k == name).map(|(_, v)| *v)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// ── Fonts ────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq)]
+enum Font {
+    Italic,
+    Upright,
+    Bold,
+    DoubleStruck,
+    Script,
+}
+
+/// Letters in the requested style, using the Unicode math alphanumerics
+/// (MathML Core only supports `mathvariant="normal"`).
+fn styled(c: char, font: Font) -> char {
+    let offset = |upper: u32, lower: u32| -> char {
+        let code = match c {
+            'A'..='Z' => upper + (c as u32 - 'A' as u32),
+            'a'..='z' => lower + (c as u32 - 'a' as u32),
+            _ => return c,
+        };
+        char::from_u32(code).unwrap_or(c)
+    };
+    match font {
+        Font::Italic | Font::Upright => c,
+        Font::Bold if c.is_ascii_digit() => char::from_u32(0x1D7CE + (c as u32 - '0' as u32)).unwrap_or(c),
+        Font::Bold => offset(0x1D400, 0x1D41A),
+        Font::DoubleStruck => match c {
+            'C' => 'ℂ',
+            'H' => 'ℍ',
+            'N' => 'ℕ',
+            'P' => 'ℙ',
+            'Q' => 'ℚ',
+            'R' => 'ℝ',
+            'Z' => 'ℤ',
+            '0'..='9' => char::from_u32(0x1D7D8 + (c as u32 - '0' as u32)).unwrap_or(c),
+            _ => offset(0x1D538, 0x1D552),
+        },
+        Font::Script => match c {
+            'B' => 'ℬ',
+            'E' => 'ℰ',
+            'F' => 'ℱ',
+            'H' => 'ℋ',
+            'I' => 'ℐ',
+            'L' => 'ℒ',
+            'M' => 'ℳ',
+            'R' => 'ℛ',
+            'e' => 'ℯ',
+            'g' => 'ℊ',
+            'o' => 'ℴ',
+            _ => offset(0x1D49C, 0x1D4B6),
+        },
+    }
+}
+
+// ── Tokenizer ────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Command(String),
+    Char(char),
+    Space,
+    Open,
+    Close,
+    Sup,
+    Sub,
+    Align,
+    NewRow,
+}
+
+fn tokenize(tex: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = tex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\\') => tokens.push(Token::NewRow),
+                Some(c) if c.is_ascii_alphabetic() => {
+                    let mut name = c.to_string();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                        name.push(c);
+                        chars.next();
+                    }
+                    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                        chars.next();
+                    }
+                    tokens.push(Token::Command(name));
+                }
+                Some(c) => tokens.push(Token::Command(c.to_string())),
+                None => {}
+            },
+            '%' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '^' => tokens.push(Token::Sup),
+            '_' => tokens.push(Token::Sub),
+            '&' => tokens.push(Token::Align),
+            '~' => tokens.push(Token::Command(" ".into())),
+            c if c.is_whitespace() => {
+                if tokens.last() != Some(&Token::Space) {
+                    tokens.push(Token::Space);
+                }
+            }
+            c => tokens.push(Token::Char(c)),
+        }
+    }
+    tokens
+}
+
+// ── Parser ───────────────────────────────────────────────────────────
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    font: Font,
+    display: bool,
+}
+
+/// A rendered element, remembering whether scripts attach as limits.
+struct Item {
+    markup: String,
+    limits: bool,
+}
+
+impl Item {
+    fn plain(markup: String) -> Self {
+        Item { markup, limits: false }
+    }
+}
+
+fn row(items: Vec<String>) -> String {
+    match items.len() {
+        0 => String::new(),
+        1 => items.into_iter().next().unwrap_or_default(),
+        _ => format!("<mrow>{}</mrow>", items.concat()),
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek() == Some(&Token::Space) {
+            self.pos += 1;
+        }
+    }
+
+    /// Elements up to the end of the current group, cell or row (not consumed).
+    fn parse_row(&mut self, until: Option<char>) -> Vec<String> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                None | Some(Token::Close) | Some(Token::Align) | Some(Token::NewRow) => break,
+                Some(Token::Command(c)) if c == "right" || c == "end" => break,
+                Some(Token::Char(c)) if Some(*c) == until => break,
+                _ => {}
+            }
+            if let Some(item) = self.parse_scripted() {
+                items.push(item);
+            }
+        }
+        items
+    }
+
+    /// An atom followed by any `^`/`_` scripts and primes.
+    fn parse_scripted(&mut self) -> Option<String> {
+        let base = self.parse_atom()?;
+        let (mut sub, mut sup) = (None, None);
+        let mut primes = String::new();
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some(Token::Sub) if sub.is_none() => {
+                    self.pos += 1;
+                    sub = Some(self.parse_argument());
+                }
+                Some(Token::Sup) if sup.is_none() => {
+                    self.pos += 1;
+                    sup = Some(self.parse_argument());
+                }
+                Some(Token::Char('\'')) => {
+                    self.pos += 1;
+                    primes.push('′');
+                }
+                _ => break,
+            }
+        }
+        if !primes.is_empty() {
+            let prime = format!("<mo>{primes}</mo>");
+            sup = Some(match sup {
+                Some(s) => format!("<mrow>{prime}{s}</mrow>"),
+                None => prime,
+            });
+        }
+        // Script elements need exactly one base element
+        let base_markup = match base.markup.as_str() {
+            "" => "<mrow></mrow>".to_string(),
+            markup => markup.to_string(),
+        };
+        let (under, over) = if base.limits { ("munder", "mover") } else { ("msub", "msup") };
+        let both = if base.limits { "munderover" } else { "msubsup" };
+        Some(match (sub, sup) {
+            (None, None) => base.markup,
+            (Some(b), None) => format!("<{under}>{base_markup}{b}</{under}>"),
+            (None, Some(p)) => format!("<{over}>{base_markup}{p}</{over}>"),
+            (Some(b), Some(p)) => format!("<{both}>{base_markup}{b}{p}</{both}>"),
+        })
+    }
+
+    /// A required argument: a `{group}` or a single token.
+    fn parse_argument(&mut self) -> String {
+        self.skip_spaces();
+        if self.peek() == Some(&Token::Open) {
+            self.pos += 1;
+            let items = self.parse_row(None);
+            if self.peek() == Some(&Token::Close) {
+                self.pos += 1;
+            }
+            return format!("<mrow>{}</mrow>", items.concat());
+        }
+        self.parse_atom().map(|i| i.markup).unwrap_or_else(|| "<mrow></mrow>".into())
+    }
+
+    fn parse_argument_in(&mut self, font: Font) -> String {
+        let outer = std::mem::replace(&mut self.font, font);
+        let arg = self.parse_argument();
+        self.font = outer;
+        arg
+    }
+
+    /// Raw text of a `{group}`, for `\text`, `\begin` and friends.
+    fn parse_raw_argument(&mut self) -> String {
+        self.skip_spaces();
+        let mut text = String::new();
+        if self.peek() != Some(&Token::Open) {
+            if let Some(Token::Char(c)) = self.peek() {
+                text.push(*c);
+                self.pos += 1;
+            }
+            return text;
+        }
+        self.pos += 1;
+        let mut depth = 0;
+        while let Some(token) = self.next() {
+            match token {
+                Token::Open => {
+                    depth += 1;
+                    text.push('{');
+                }
+                Token::Close if depth == 0 => break,
+                Token::Close => {
+                    depth -= 1;
+                    text.push('}');
+                }
+                Token::Char(c) => text.push(c),
+                Token::Space => text.push(' '),
+                Token::Command(c) if lookup(SPACES, &c).is_some() => text.push(' '),
+                Token::Command(c) => text.push_str(lookup(OPERATORS, &c).unwrap_or(&c)),
+                Token::Sup => text.push('^'),
+                Token::Sub => text.push('_'),
+                Token::Align => text.push('&'),
+                Token::NewRow => text.push(' '),
+            }
+        }
+        text
+    }
+
+    fn parse_optional(&mut self) -> Option<String> {
+        self.skip_spaces();
+        if self.peek() != Some(&Token::Char('[')) {
+            return None;
+        }
+        self.pos += 1;
+        let items = self.parse_row(Some(']'));
+        if self.peek() == Some(&Token::Char(']')) {
+            self.pos += 1;
+        }
+        Some(format!("<mrow>{}</mrow>", items.concat()))
+    }
+
+    fn identifier(&self, c: char) -> String {
+        let c = styled(c, self.font);
+        let text = escape(&c.to_string());
+        if self.font == Font::Upright && c.is_alphabetic() {
+            format!(r#"<mi mathvariant="normal">{text}</mi>"#)
+        } else {
+            format!("<mi>{text}</mi>")
+        }
+    }
+
+    fn parse_atom(&mut self) -> Option<Item> {
+        self.skip_spaces();
+        let token = self.next()?;
+        Some(match token {
+            Token::Open => {
+                let items = self.parse_row(None);
+                if self.peek() == Some(&Token::Close) {
+                    self.pos += 1;
+                }
+                Item::plain(format!("<mrow>{}</mrow>", items.concat()))
+            }
+            Token::Char(c) if c.is_ascii_digit() || c == '.' => {
+                let mut number = c.to_string();
+                while let Some(Token::Char(d)) = self.peek() {
+                    let d = *d;
+                    let next_is_digit =
+                        matches!(self.tokens.get(self.pos + 1), Some(Token::Char(n)) if n.is_ascii_digit());
+                    if d.is_ascii_digit() || (d == '.' && next_is_digit) {
+                        number.push(d);
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                if self.font == Font::Bold || self.font == Font::DoubleStruck {
+                    number = number.chars().map(|c| styled(c, self.font)).collect();
+                }
+                Item::plain(format!("<mn>{}</mn>", escape(&number)))
+            }
+            Token::Char(c) if c.is_alphabetic() => Item::plain(self.identifier(c)),
+            Token::Char(c) => {
+                let op = match c {
+                    '-' => '−',
+                    '*' => '∗',
+                    c => c,
+                };
+                let attrs = if "()[]".contains(c) { r#" stretchy="false""# } else { "" };
+                Item::plain(format!("<mo{attrs}>{}</mo>", escape(&op.to_string())))
+            }
+            Token::Command(name) => return self.parse_command(&name),
+            // Stray structure tokens render as nothing
+            Token::Close | Token::Align | Token::NewRow | Token::Space | Token::Sup | Token::Sub => {
+                Item::plain(String::new())
+            }
+        })
+    }
+
+    fn parse_command(&mut self, name: &str) -> Option<Item> {
+        if let Some(symbol) = lookup(GREEK, name) {
+            return Some(Item::plain(format!("<mi>{symbol}</mi>")));
+        }
+        if let Some(symbol) = lookup(GREEK_UPPER, name) {
+            return Some(Item::plain(format!(r#"<mi mathvariant="normal">{symbol}</mi>"#)));
+        }
+        if let Some(symbol) = lookup(OPERATORS, name) {
+            return Some(Item::plain(format!("<mo>{}</mo>", escape(symbol))));
+        }
+        if let Some(symbol) = lookup(BIG_OPERATORS, name) {
+            return Some(Item {
+                markup: format!(r#"<mo largeop="true" movablelimits="true">{symbol}</mo>"#),
+                limits: true,
+            });
+        }
+        if let Some(symbol) = lookup(INTEGRALS, name) {
+            return Some(Item::plain(format!(r#"<mo largeop="true">{symbol}</mo>"#)));
+        }
+        if FUNCTIONS.contains(&name) {
+            return Some(Item::plain(format!("<mi>{name}</mi>")));
+        }
+        if LIMIT_FUNCTIONS.contains(&name) {
+            let label = match name {
+                "limsup" => "lim sup",
+                "liminf" => "lim inf",
+                "argmax" => "arg max",
+                "argmin" => "arg min",
+                n => n,
+            };
+            return Some(Item {
+                markup: format!(r#"<mo movablelimits="true" form="prefix">{label}</mo>"#),
+                limits: self.display,
+            });
+        }
+        if let Some(width) = lookup(SPACES, name) {
+            return Some(Item::plain(format!(r#"<mspace width="{width}"/>"#)));
+        }
+        if let Some(accent) = lookup(ACCENTS, name) {
+            let base = self.parse_argument();
+            let stretchy = name.starts_with("wide") || name.starts_with("over");
+            let markup =
+                format!(r#"<mover accent="true">{base}<mo stretchy="{stretchy}">{accent}</mo></mover>"#);
+            return Some(Item::plain(markup));
+        }
+        if let Some(accent) = lookup(UNDER_ACCENTS, name) {
+            let base = self.parse_argument();
+            let markup =
+                format!(r#"<munder accentunder="true">{base}<mo stretchy="true">{accent}</mo></munder>"#);
+            return Some(Item { markup, limits: name == "underbrace" });
+        }
+        if IGNORED.contains(&name) {
+            if matches!(name, "label" | "tag" | "color") {
+                self.parse_raw_argument();
+            }
+            return Some(Item::plain(String::new()));
+        }
+        let markup = match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let num = self.parse_argument();
+                let den = self.parse_argument();
+                format!("<mfrac>{num}{den}</mfrac>")
+            }
+            "binom" | "dbinom" | "tbinom" => {
+                let n = self.parse_argument();
+                let k = self.parse_argument();
+                format!(r#"<mrow><mo>(</mo><mfrac linethickness="0">{n}{k}</mfrac><mo>)</mo></mrow>"#)
+            }
+            "sqrt" => match self.parse_optional() {
+                Some(index) => {
+                    let radicand = self.parse_argument();
+                    format!("<mroot>{radicand}{index}</mroot>")
+                }
+                None => format!("<msqrt>{}</msqrt>", self.parse_argument()),
+            },
+            "mathbf" | "boldsymbol" | "bm" => self.parse_argument_in(Font::Bold),
+            "mathbb" => self.parse_argument_in(Font::DoubleStruck),
+            "mathcal" | "mathscr" => self.parse_argument_in(Font::Script),
+            "mathrm" | "mathsf" | "mathtt" => self.parse_argument_in(Font::Upright),
+            "mathit" => self.parse_argument_in(Font::Italic),
+            "operatorname" => {
+                let text = self.parse_raw_argument();
+                format!("<mi>{}</mi>", escape(text.trim()))
+            }
+            "text" | "textrm" | "textit" | "textbf" | "textsf" | "texttt" | "mbox" => {
+                format!("<mtext>{}</mtext>", escape(&self.parse_raw_argument()))
+            }
+            "textcolor" => {
+                self.parse_raw_argument();
+                self.parse_argument()
+            }
+            "boxed" | "phantom" | "hphantom" | "vphantom" => {
+                let inner = self.parse_argument();
+                if name == "boxed" {
+                    inner
+                } else {
+                    format!("<mphantom>{inner}</mphantom>")
+                }
+            }
+            "not" => {
+                let next = self.parse_atom().map(|i| i.markup).unwrap_or_default();
+                match next.as_str() {
+                    "<mo>=</mo>" => "<mo>≠</mo>".to_string(),
+                    "<mo>∈</mo>" => "<mo>∉</mo>".to_string(),
+                    _ => format!("<mrow>{next}<mo>&#x338;</mo></mrow>"),
+                }
+            }
+            "left" => self.parse_fenced(),
+            "begin" => self.parse_environment(),
+            "%" | "$" | "#" | "&" | "_" => format!("<mi>{}</mi>", escape(name)),
+            _ => format!(r#"<merror><mtext>\{}</mtext></merror>"#, escape(name)),
+        };
+        Some(Item::plain(markup))
+    }
+
+    /// Delimiter after `\left`/`\right`; `.` means none.
+    fn parse_delimiter(&mut self) -> String {
+        self.skip_spaces();
+        let delim = match self.next() {
+            Some(Token::Char('.')) | None => return String::new(),
+            Some(Token::Char(c)) => c.to_string(),
+            Some(Token::Command(c)) => lookup(OPERATORS, &c).unwrap_or("").to_string(),
+            _ => String::new(),
+        };
+        if delim.is_empty() {
+            return delim;
+        }
+        format!(r#"<mo fence="true" stretchy="true">{}</mo>"#, escape(&delim))
+    }
+
+    fn parse_fenced(&mut self) -> String {
+        let open = self.parse_delimiter();
+        let inner = self.parse_row(None);
+        let close = if self.peek() == Some(&Token::Command("right".into())) {
+            self.pos += 1;
+            self.parse_delimiter()
+        } else {
+            String::new()
+        };
+        format!("<mrow>{open}{}{close}</mrow>", inner.concat())
+    }
+
+    fn parse_environment(&mut self) -> String {
+        let env = self.parse_raw_argument();
+        let env = env.trim().trim_end_matches('*');
+        if env == "array" {
+            self.parse_raw_argument();
+        }
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut cells: Vec<String> = Vec::new();
+        loop {
+            cells.push(row(self.parse_row(None)));
+            match self.next() {
+                Some(Token::Align) => {}
+                Some(Token::NewRow) => rows.push(std::mem::take(&mut cells)),
+                Some(Token::Command(c)) if c == "end" => {
+                    self.parse_raw_argument();
+                    break;
+                }
+                // `\right` or a stray `}`: the environment was never closed
+                _ => break,
+            }
+        }
+        if cells.iter().any(|c| !c.is_empty()) || rows.is_empty() {
+            rows.push(cells);
+        }
+        let aligned = matches!(env, "aligned" | "align" | "alignat" | "split" | "eqnarray");
+        let columnalign = match env {
+            "cases" => r#" columnalign="left""#,
+            _ if aligned => r#" columnalign="right left right left""#,
+            _ => "",
+        };
+        let body: String = rows
+            .iter()
+            .map(|cells| {
+                let tds: String = cells.iter().map(|c| format!("<mtd>{c}</mtd>")).collect();
+                format!("<mtr>{tds}</mtr>")
+            })
+            .collect();
+        let table = format!("<mtable{columnalign}>{body}</mtable>");
+        let (open, close) = match env {
+            "pmatrix" => ("(", ")"),
+            "bmatrix" => ("[", "]"),
+            "Bmatrix" => ("{", "}"),
+            "vmatrix" => ("|", "|"),
+            "Vmatrix" => ("‖", "‖"),
+            "cases" => ("{", ""),
+            _ => return table,
+        };
+        let fence = |d: &str| {
+            if d.is_empty() {
+                String::new()
+            } else {
+                format!(r#"<mo fence="true" stretchy="true">{d}</mo>"#)
+            }
+        };
+        format!("<mrow>{}{table}{}</mrow>", fence(open), fence(close))
+    }
+}
+
+/// MathML for a LaTeX formula, with the source kept as an annotation.
+pub fn latex_to_mathml(tex: &str, display: bool) -> String {
+    let mut parser = Parser { tokens: tokenize(tex), pos: 0, font: Font::Italic, display };
+    let mut items = Vec::new();
+    while parser.pos < parser.tokens.len() {
+        items.extend(parser.parse_row(None));
+        // Unbalanced `}`, `&` or `\\` at top level: skip and keep going
+        if parser.pos < parser.tokens.len() {
+            parser.pos += 1;
+        }
+    }
+    let mode = if display { "block" } else { "inline" };
+    format!(
+        concat!(
+            r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="{}">"#,
+            r#"<semantics><mrow>{}</mrow><annotation encoding="application/x-tex">{}</annotation></semantics>"#,
+            "</math>"
+        ),
+        mode,
+        items.concat(),
+        escape(tex.trim())
+    )
+}
+
+// ── HTML scanning ────────────────────────────────────────────────────
+
+enum Segment<'a> {
+    Text(&'a str),
+    Math(&'a str, bool),
+}
+
+/// Closing `$` of an inline formula: not after a space, not before a digit.
+fn inline_dollar_end(text: &str, from: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' if bytes.get(i + 1) == Some(&b'\n') => return None,
+            b'$' => {
+                let before_ok = i > from && !bytes[i - 1].is_ascii_whitespace();
+                let after_ok = !bytes.get(i + 1).is_some_and(|b| b.is_ascii_digit());
+                if before_ok && after_ok {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Split text on math delimiters. Inline `$` follows the pandoc rules so
+/// prices like "$5 and $10" stay text.
+fn split_math(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    let bytes = text.as_bytes();
+    while i < bytes.len() {
+        let rest = &text[i..];
+        let found = if let Some(inner) = rest.strip_prefix("\\(") {
+            inner.find("\\)").map(|end| (i + 2, i + 2 + end, 2, false))
+        } else if let Some(inner) = rest.strip_prefix("\\[") {
+            inner.find("\\]").map(|end| (i + 2, i + 2 + end, 2, true))
+        } else if let Some(inner) = rest.strip_prefix("$$") {
+            inner.find("$$").map(|end| (i + 2, i + 2 + end, 2, true))
+        } else if rest.starts_with("\\$") {
+            i += 2;
+            continue;
+        } else if rest.strip_prefix('$').is_some_and(|r| r.starts_with(|c: char| !c.is_whitespace())) {
+            inline_dollar_end(text, i + 1).map(|end| (i + 1, end, 1, false))
+        } else {
+            None
+        };
+        match found {
+            Some((from, to, close_len, display)) if to > from && to - from <= MAX_TEX_LEN => {
+                if start < i {
+                    segments.push(Segment::Text(&text[start..i]));
+                }
+                segments.push(Segment::Math(&text[from..to], display));
+                i = to + close_len;
+                start = i;
+            }
+            _ => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if start < text.len() {
+        segments.push(Segment::Text(&text[start..]));
+    }
+    segments
+}
+
+fn math_type(el: &scraper::node::Element) -> Option<bool> {
+    let kind = el.attr("type")?.to_ascii_lowercase();
+    kind.starts_with("math/tex").then(|| kind.contains("mode=display"))
+}
+
+/// Convert the LaTeX formulas in an HTML fragment to MathML.
+pub fn render_math(html: &str) -> String {
+    if !html.contains('$') && !html.contains("\\(") && !html.contains("\\[") && !html.contains("math/tex") {
+        return html.to_string();
+    }
+    let mut fragment = Html::parse_fragment(html);
+
+    // (node, replacement markup, node is a <script> to replace entirely)
+    let mut replacements: Vec<(NodeId, String, bool)> = Vec::new();
+    for node in fragment.tree.nodes() {
+        let skipped = node.ancestors().any(|a| match a.value() {
+            Node::Element(el) => SKIPPED_ELEMENTS.contains(&el.name()),
+            _ => false,
+        });
+        match node.value() {
+            Node::Element(el) if el.name() == "script" => {
+                let Some(display) = math_type(el) else { continue };
+                let tex: String =
+                    node.children().filter_map(|c| c.value().as_text().map(|t| t.to_string())).collect();
+                replacements.push((node.id(), latex_to_mathml(&tex, display), true));
+            }
+            Node::Text(text) if !skipped => {
+                let segments = split_math(text);
+                if !segments.iter().any(|s| matches!(s, Segment::Math(..))) {
+                    continue;
+                }
+                let markup: String = segments
+                    .iter()
+                    .map(|s| match s {
+                        Segment::Text(t) => escape(t),
+                        Segment::Math(tex, display) => latex_to_mathml(tex, *display),
+                    })
+                    .collect();
+                replacements.push((node.id(), markup, false));
+            }
+            _ => {}
+        }
+    }
+    if replacements.is_empty() {
+        return html.to_string();
+    }
+
+    for (i, (id, _, is_script)) in replacements.iter().enumerate() {
+        let placeholder = Node::Text(Text { text: format!("{PLACEHOLDER}{i}{PLACEHOLDER}").as_str().into() });
+        let Some(mut node) = fragment.tree.get_mut(*id) else { continue };
+        if *is_script {
+            node.insert_before(placeholder);
+            node.detach();
+        } else {
+            *node.value() = placeholder;
+        }
+    }
+    let mut out = fragment.root_element().inner_html();
+    for (i, (_, markup, _)) in replacements.iter().enumerate() {
+        out = out.replacen(&format!("{PLACEHOLDER}{i}{PLACEHOLDER}"), markup, 1);
+    }
+    out
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// MathML for one LaTeX formula.
+#[tauri::command]
+pub fn tex_to_mathml(tex: String, display: Option<bool>) -> String {
+    latex_to_mathml(&tex, display.unwrap_or(false))
+}
+
+/// Convert the LaTeX formulas in an HTML fragment to MathML.
+#[tauri::command]
+pub fn render_math_html(html: String) -> String {
+    render_math(&html)
+}
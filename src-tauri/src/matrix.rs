@@ -0,0 +1,309 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::share::SharedArticle;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const CONFIG_FILE: &str = "matrix_config.json";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MatrixRoom {
+    pub room_id: String,
+    #[serde(default)]
+    pub name: String,
+    /// Receive keyword alerts automatically.
+    #[serde(default)]
+    pub alerts: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct MatrixConfig {
+    /// e.g. `https://matrix.org`
+    pub homeserver: String,
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub rooms: Vec<MatrixRoom>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct JoinedRoom {
+    pub room_id: String,
+    pub name: Option<String>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct MatrixStore {
+    config: Mutex<Option<MatrixConfig>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl MatrixStore {
+    pub fn new() -> Self {
+        MatrixStore {
+            config: Mutex::new(None),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(CONFIG_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(cfg) = serde_json::from_str::<MatrixConfig>(&json) {
+                            *self.config.lock().unwrap() = Some(cfg);
+                        }
+                    }
+                    Err(e) => eprintln!("[matrix] Failed to read config: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let cfg = self.config.lock().unwrap();
+            let result = match cfg.as_ref() {
+                Some(c) => serde_json::to_string_pretty(c)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string())),
+                None => match std::fs::remove_file(&path) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+                    _ => Ok(()),
+                },
+            };
+            if let Err(e) = result {
+                eprintln!("[matrix] Failed to persist config: {e}");
+            }
+        }
+    }
+
+    pub fn get_config(&self) -> Option<MatrixConfig> {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: Option<MatrixConfig>) {
+        *self.config.lock().unwrap() = config;
+        self.save_to_disk();
+    }
+
+    fn require_config(&self) -> Result<MatrixConfig, String> {
+        self.get_config().ok_or_else(|| "Matrix is not configured".to_string())
+    }
+}
+
+// ── Client-server API ────────────────────────────────────────────────
+
+/// Build `{homeserver}/_matrix/client/v3/{segments...}` with each segment percent-encoded.
+fn api_url(cfg: &MatrixConfig, segments: &[&str]) -> Result<url::Url, String> {
+    let mut url = url::Url::parse(cfg.homeserver.trim_end_matches('/'))
+        .map_err(|e| format!("Invalid homeserver URL: {e}"))?;
+    url.path_segments_mut()
+        .map_err(|_| "Invalid homeserver URL".to_string())?
+        .pop_if_empty()
+        .extend(["_matrix", "client", "v3"])
+        .extend(segments);
+    Ok(url)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+async fn send_message(cfg: &MatrixConfig, room_id: &str, plain: String, html: String) -> Result<String, String> {
+    let txn_id = uuid::Uuid::new_v4().to_string();
+    let url = api_url(cfg, &["rooms", room_id, "send", "m.room.message", &txn_id])?;
+    let body = serde_json::json!({
+        "msgtype": "m.text",
+        "body": plain,
+        "format": "org.matrix.custom.html",
+        "formatted_body": html,
+    });
+
+    let client = crate::get_or_init_client()?;
+    let response = client
+        .put(url)
+        .bearer_auth(&cfg.access_token)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Matrix request failed: {e}"))?;
+
+    let status = response.status();
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Matrix response parse error: {e}"))?;
+    if !status.is_success() {
+        let err = json["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("Matrix HTTP {status}: {err}"));
+    }
+    Ok(json["event_id"].as_str().unwrap_or_default().to_string())
+}
+
+fn format_article(article: &SharedArticle) -> (String, String) {
+    let mut plain = format!("{}\n{}", article.title, article.url);
+    let mut html = format!(
+        "<p><a href=\"{}\"><strong>{}</strong></a></p>",
+        escape_html(&article.url),
+        escape_html(&article.title)
+    );
+    if !article.summary.is_empty() {
+        plain.push_str(&format!("\n\n{}", article.summary));
+        html.push_str(&format!("<blockquote>{}</blockquote>", escape_html(&article.summary)));
+    }
+    if !article.feed_name.is_empty() {
+        plain.push_str(&format!("\n— {}", article.feed_name));
+        html.push_str(&format!("<p><em>{}</em></p>", escape_html(&article.feed_name)));
+    }
+    (plain, html)
+}
+
+/// Post an alert to every configured room that has alerts enabled.
+/// Returns `(room_id, result)` pairs; an unconfigured client yields no rooms.
+pub async fn send_alert(
+    store: &MatrixStore,
+    title: &str,
+    message: &str,
+    link: Option<&str>,
+) -> Vec<(String, Result<String, String>)> {
+    let Some(cfg) = store.get_config() else {
+        return Vec::new();
+    };
+    let mut plain = format!("🔔 {title}\n{message}");
+    let mut html = format!("<p>🔔 <strong>{}</strong></p><p>{}</p>", escape_html(title), escape_html(message));
+    if let Some(link) = link {
+        plain.push_str(&format!("\n{link}"));
+        html.push_str(&format!("<p><a href=\"{0}\">{0}</a></p>", escape_html(link)));
+    }
+
+    let mut results = Vec::new();
+    for room in cfg.rooms.iter().filter(|r| r.alerts) {
+        let outcome = send_message(&cfg, &room.room_id, plain.clone(), html.clone()).await;
+        if let Err(ref e) = outcome {
+            eprintln!("[matrix] Alert to {} failed: {e}", room.room_id);
+        }
+        results.push((room.room_id.clone(), outcome));
+    }
+    results
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn matrix_get_config(store: tauri::State<'_, Arc<MatrixStore>>) -> Option<MatrixConfig> {
+    store.get_config()
+}
+
+/// Save the configuration after validating the access token with `whoami`.
+/// Passing `None` disconnects the client.
+#[tauri::command]
+pub async fn matrix_set_config(
+    config: Option<MatrixConfig>,
+    store: tauri::State<'_, Arc<MatrixStore>>,
+) -> Result<Option<MatrixConfig>, String> {
+    let Some(mut cfg) = config else {
+        store.set_config(None);
+        return Ok(None);
+    };
+
+    let url = api_url(&cfg, &["account", "whoami"])?;
+    let client = crate::get_or_init_client()?;
+    let response = client
+        .get(url)
+        .bearer_auth(&cfg.access_token)
+        .send()
+        .await
+        .map_err(|e| format!("Matrix request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Matrix rejected the access token (HTTP {})", response.status()));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Matrix response parse error: {e}"))?;
+    cfg.user_id = json["user_id"].as_str().map(|s| s.to_string());
+    eprintln!("[matrix] Connected as {:?}", cfg.user_id);
+
+    store.set_config(Some(cfg.clone()));
+    Ok(Some(cfg))
+}
+
+#[tauri::command]
+pub async fn matrix_list_joined_rooms(
+    window: tauri::WebviewWindow,
+    store: tauri::State<'_, Arc<MatrixStore>>,
+) -> Result<Vec<JoinedRoom>, String> {
+    let cfg = store.require_config()?;
+    crate::permissions::require_host(&window, &cfg.homeserver).await?;
+    let client = crate::get_or_init_client()?;
+
+    let response = client
+        .get(api_url(&cfg, &["joined_rooms"])?)
+        .bearer_auth(&cfg.access_token)
+        .send()
+        .await
+        .map_err(|e| format!("Matrix request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Matrix HTTP {}", response.status()));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Matrix response parse error: {e}"))?;
+
+    let mut rooms = Vec::new();
+    for id in json["joined_rooms"].as_array().cloned().unwrap_or_default() {
+        let Some(room_id) = id.as_str() else { continue };
+        // Room names are optional state; a missing name is not an error
+        let name = match client
+            .get(api_url(&cfg, &["rooms", room_id, "state", "m.room.name"])?)
+            .bearer_auth(&cfg.access_token)
+            .send()
+            .await
+        {
+            Ok(r) if r.status().is_success() => r
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|v| v["name"].as_str().map(|s| s.to_string())),
+            _ => None,
+        };
+        rooms.push(JoinedRoom {
+            room_id: room_id.to_string(),
+            name,
+        });
+    }
+    Ok(rooms)
+}
+
+/// Send an article to a room. Returns the Matrix event ID.
+#[tauri::command]
+pub async fn matrix_send_article(
+    room_id: String,
+    article: SharedArticle,
+    window: tauri::WebviewWindow,
+    store: tauri::State<'_, Arc<MatrixStore>>,
+) -> Result<String, String> {
+    let cfg = store.require_config()?;
+    crate::permissions::require_host(&window, &cfg.homeserver).await?;
+    let (plain, html) = format_article(&article);
+    send_message(&cfg, &room_id, plain, html).await
+}
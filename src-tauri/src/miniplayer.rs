@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager, PhysicalPosition};
+
+// The mini-player: a small always-on-top window with playback controls for
+// whatever is being read aloud or played. Playback itself stays where it is
+// (native TTS here, ElevenLabs and podcast audio in the main window); the
+// player window shows the state the owner publishes with `set_player_state`
+// and sends its buttons through `player_control`, which the owner receives as
+// `player-control`. Native TTS is paused, resumed and stopped here, so that
+// works even with the main window gone.
+
+pub const LABEL: &str = "miniplayer";
+const SIZE: (f64, f64) = (340.0, 96.0);
+/// Gap between the player and the corner of the screen's work area.
+const MARGIN: i32 = 24;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayerAction {
+    Play,
+    Pause,
+    Toggle,
+    Stop,
+    Next,
+    Previous,
+}
+
+/// What is playing, as published by the part of the app playing it.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct PlayerState {
+    /// `tts`, `elevenlabs`, `podcast`, ...; empty when nothing is loaded.
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub article_id: Option<String>,
+    #[serde(default)]
+    pub playing: bool,
+    #[serde(default)]
+    pub can_skip: bool,
+    /// Seconds, when the source knows them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+}
+
+pub struct PlayerStore {
+    state: Mutex<PlayerState>,
+    /// Where the user left the player this session; the next one opens there.
+    position: Mutex<Option<PhysicalPosition<i32>>>,
+}
+
+impl PlayerStore {
+    pub fn new() -> Self {
+        PlayerStore { state: Mutex::new(PlayerState::default()), position: Mutex::new(None) }
+    }
+}
+
+/// Bottom-right corner of the work area of the monitor the main window is on.
+fn default_position(app: &tauri::AppHandle) -> Option<PhysicalPosition<i32>> {
+    let main = app.get_webview_window("main")?;
+    let monitor = main.current_monitor().ok().flatten().or_else(|| app.primary_monitor().ok().flatten())?;
+    let area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    let (width, height) = ((SIZE.0 * scale) as i32, (SIZE.1 * scale) as i32);
+    let margin = (f64::from(MARGIN) * scale) as i32;
+    Some(PhysicalPosition::new(
+        area.position.x + area.size.width as i32 - width - margin,
+        area.position.y + area.size.height as i32 - height - margin,
+    ))
+}
+
+/// Carry out a player control, from the player window or a global shortcut.
+/// Play, pause and stop also drive native TTS when nothing else claims playback.
+pub fn control(app: &tauri::AppHandle, action: PlayerAction) -> Result<(), String> {
+    let store = app.state::<Arc<PlayerStore>>();
+    let source = store.state.lock().unwrap().source.clone();
+    if matches!(source.as_str(), "" | "tts") {
+        let pause = match action {
+            PlayerAction::Stop => {
+                crate::tts_stop()?;
+                let state = PlayerState::default();
+                *store.state.lock().unwrap() = state.clone();
+                let _ = app.emit("player-state", state);
+                None
+            }
+            PlayerAction::Pause => Some(true),
+            PlayerAction::Play => Some(false),
+            PlayerAction::Toggle => Some(!crate::tts_paused()),
+            PlayerAction::Next | PlayerAction::Previous => None,
+        };
+        let changed = match pause {
+            Some(true) => crate::tts_pause()?,
+            Some(false) => crate::tts_resume()?,
+            None => false,
+        };
+        if changed {
+            let mut state = store.state.lock().unwrap();
+            state.playing = pause == Some(false);
+            let _ = app.emit("player-state", state.clone());
+        }
+    }
+    app.emit("player-control", action).map_err(|e| format!("Failed to send player control: {e}"))
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Show the mini-player, creating it if needed. The window loads the app's
+/// own page and recognizes itself by its `miniplayer` label.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub fn open_miniplayer(app: tauri::AppHandle, store: tauri::State<'_, Arc<PlayerStore>>) -> Result<(), String> {
+    use tauri::{WebviewUrl, WebviewWindowBuilder};
+
+    if let Some(window) = app.get_webview_window(LABEL) {
+        let _ = window.unminimize();
+        return window.show().map_err(|e| format!("Failed to show mini-player: {e}"));
+    }
+    let window = WebviewWindowBuilder::new(&app, LABEL, WebviewUrl::default())
+        .title("SuperFlux Player")
+        .inner_size(SIZE.0, SIZE.1)
+        .resizable(false)
+        .maximizable(false)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Failed to create mini-player: {e}"))?;
+    if let Some(pos) = store.position.lock().unwrap().or_else(|| default_position(&app)) {
+        let _ = window.set_position(pos);
+    }
+
+    let app_handle = app.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Moved(pos) = event {
+            *app_handle.state::<Arc<PlayerStore>>().position.lock().unwrap() = Some(*pos);
+        }
+    });
+    window.show().map_err(|e| format!("Failed to show mini-player: {e}"))?;
+    eprintln!("[miniplayer] opened");
+    Ok(())
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub fn open_miniplayer() -> Result<(), String> {
+    Err("The mini-player is not available on this platform".into())
+}
+
+#[tauri::command]
+pub fn close_miniplayer(app: tauri::AppHandle) -> Result<bool, String> {
+    match app.get_webview_window(LABEL) {
+        Some(window) => window.close().map(|_| true).map_err(|e| format!("Failed to close mini-player: {e}")),
+        None => Ok(false),
+    }
+}
+
+#[tauri::command]
+pub fn get_player_state(store: tauri::State<'_, Arc<PlayerStore>>) -> PlayerState {
+    store.state.lock().unwrap().clone()
+}
+
+/// Publish what is playing; every window gets it as `player-state`.
+#[tauri::command]
+pub fn set_player_state(
+    state: PlayerState,
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<PlayerStore>>,
+) -> Result<(), String> {
+    *store.state.lock().unwrap() = state.clone();
+    app.emit("player-state", state).map_err(|e| format!("Failed to emit player state: {e}"))
+}
+
+/// A player button was pressed. Native TTS is stopped here; everything else
+/// goes to the playback owner as `player-control`.
+#[tauri::command]
+pub fn player_control(action: PlayerAction, app: tauri::AppHandle) -> Result<(), String> {
+    control(&app, action)
+}
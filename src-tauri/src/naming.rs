@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tera::{Tera, Value};
+
+// Naming templates for exported files, downloads and digest titles, e.g.
+// `{{feed|slug}}-{{date}}-{{title|slug}}`. Templates use Tera syntax over the
+// fields of `NamingContext`, plus `date`, `time`, `year`, `month` and `day`
+// for the moment of the export. `slug` is available as a filter.
+
+const TEMPLATES_FILE: &str = "naming_templates.json";
+const TEMPLATE_NAME: &str = "name";
+/// File names stay well under the 255-byte limit of common filesystems.
+const MAX_STEM_CHARS: usize = 150;
+/// Device names Windows won't accept as a file name, with or without an extension.
+const RESERVED_STEMS: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9", "lpt1",
+    "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateKind {
+    ExportFile,
+    DownloadFile,
+    DigestTitle,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct NamingTemplates {
+    /// Articles exported as HTML, Markdown, DOCX, PDF, ...
+    pub export_file: String,
+    /// Enclosures and media saved to disk.
+    pub download_file: String,
+    pub digest_title: String,
+}
+
+impl Default for NamingTemplates {
+    fn default() -> Self {
+        NamingTemplates {
+            export_file: "{{title|slug}}-{{date}}".into(),
+            download_file: "{{feed|slug}}-{{title|slug}}".into(),
+            digest_title: "{{feed}} digest, {{date}}".into(),
+        }
+    }
+}
+
+impl NamingTemplates {
+    fn get(&self, kind: TemplateKind) -> &str {
+        match kind {
+            TemplateKind::ExportFile => &self.export_file,
+            TemplateKind::DownloadFile => &self.download_file,
+            TemplateKind::DigestTitle => &self.digest_title,
+        }
+    }
+}
+
+/// What a name is built from. Missing text renders empty, missing numbers as nothing.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct NamingContext {
+    pub feed: String,
+    pub title: String,
+    pub author: String,
+    pub folder: String,
+    /// Output format or extension, e.g. `pdf`.
+    pub format: String,
+    /// Items in a batch or digest.
+    pub count: Option<u32>,
+    /// 1-based position in a batch.
+    pub index: Option<u32>,
+    /// Article date, rendered as `published` (YYYY-MM-DD). ms since epoch.
+    pub published_at: Option<i64>,
+}
+
+impl NamingContext {
+    /// Stand-in values for previews and validation.
+    fn sample() -> Self {
+        NamingContext {
+            feed: "Hacker News".into(),
+            title: "Show HN: A tiny RSS reader".into(),
+            author: "Jane Doe".into(),
+            folder: "Tech".into(),
+            format: "pdf".into(),
+            count: Some(12),
+            index: Some(1),
+            published_at: Some(chrono::Utc::now().timestamp_millis()),
+        }
+    }
+}
+
+static TEMPLATES: OnceLock<Mutex<NamingTemplates>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn templates_lock() -> &'static Mutex<NamingTemplates> {
+    TEMPLATES.get_or_init(|| Mutex::new(NamingTemplates::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(TEMPLATES_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(templates) = serde_json::from_str::<NamingTemplates>(&json) {
+            *templates_lock().lock().unwrap() = templates;
+        }
+    }
+}
+
+fn save_templates(templates: &NamingTemplates) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json = serde_json::to_string_pretty(templates)
+        .map_err(|e| format!("Failed to serialize naming templates: {e}"))?;
+    std::fs::write(dir.join(TEMPLATES_FILE), json).map_err(|e| format!("Failed to save naming templates: {e}"))
+}
+
+// ── Rendering ────────────────────────────────────────────────────────
+
+/// Lowercase ASCII-ish words joined by dashes: "Café & Crème!" gives "cafe-creme".
+pub fn slug(text: &str) -> String {
+    let folded = crate::search::fold(text, true);
+    let mut out = String::with_capacity(folded.len());
+    for c in folded.chars() {
+        if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
+
+fn slug_filter(value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    };
+    Ok(Value::String(slug(&text)))
+}
+
+/// Tera keeps the useful part of a message in the error's sources.
+fn describe(e: &tera::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = std::error::Error::source(e);
+    while let Some(inner) = source {
+        message.push_str(&format!(": {inner}"));
+        source = inner.source();
+    }
+    message
+}
+
+pub fn render(template: &str, context: &NamingContext) -> Result<String, String> {
+    let mut tera = Tera::default();
+    tera.register_filter("slug", slug_filter);
+    tera.add_raw_template(TEMPLATE_NAME, template).map_err(|e| format!("Invalid template: {}", describe(&e)))?;
+
+    let mut vars = tera::Context::from_serialize(context).map_err(|e| describe(&e))?;
+    let now = chrono::Local::now();
+    vars.insert("date", &now.format("%Y-%m-%d").to_string());
+    vars.insert("time", &now.format("%H-%M").to_string());
+    vars.insert("year", &now.format("%Y").to_string());
+    vars.insert("month", &now.format("%m").to_string());
+    vars.insert("day", &now.format("%d").to_string());
+    let published = context
+        .published_at
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string());
+    vars.insert("published", &published.unwrap_or_default());
+
+    let rendered = tera.render(TEMPLATE_NAME, &vars).map_err(|e| format!("Template error: {}", describe(&e)))?;
+    Ok(rendered.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Make a rendered name safe as a file name on every platform and add `ext`.
+fn sanitize_file_name(name: &str, ext: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '|') { '-' } else { c })
+        .filter(|c| !matches!(c, '<' | '>' | '"' | '?' | '*'))
+        .collect();
+    let mut stem: String = cleaned.trim().trim_matches('.').chars().take(MAX_STEM_CHARS).collect();
+    stem = stem.trim_end_matches([' ', '.']).to_string();
+    if stem.is_empty() {
+        stem = "export".into();
+    } else if RESERVED_STEMS.contains(&stem.to_ascii_lowercase().as_str()) {
+        stem.insert(0, '_');
+    }
+    match ext.trim_start_matches('.') {
+        "" => stem,
+        ext => format!("{stem}.{ext}"),
+    }
+}
+
+/// A file name from the saved template of `kind`, with extension `ext`.
+pub fn file_name(kind: TemplateKind, context: &NamingContext, ext: &str) -> Result<String, String> {
+    let template = templates_lock().lock().unwrap().get(kind).to_string();
+    Ok(sanitize_file_name(&render(&template, context)?, ext))
+}
+
+/// Render for display: titles as they are, file names sanitized with `context.format` as extension.
+fn render_as(kind: TemplateKind, template: &str, context: &NamingContext) -> Result<String, String> {
+    let rendered = render(template, context)?;
+    Ok(match kind {
+        TemplateKind::DigestTitle => rendered,
+        _ => sanitize_file_name(&rendered, &context.format),
+    })
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_naming_templates() -> NamingTemplates {
+    templates_lock().lock().unwrap().clone()
+}
+
+/// Save the templates after checking each renders.
+#[tauri::command]
+pub fn set_naming_templates(templates: NamingTemplates) -> Result<(), String> {
+    let sample = NamingContext::sample();
+    for kind in [TemplateKind::ExportFile, TemplateKind::DownloadFile, TemplateKind::DigestTitle] {
+        if templates.get(kind).trim().is_empty() {
+            return Err(format!("The {kind:?} template is empty"));
+        }
+        render(templates.get(kind), &sample)?;
+    }
+    save_templates(&templates)?;
+    *templates_lock().lock().unwrap() = templates;
+    Ok(())
+}
+
+/// What `template` gives for `context`, or for sample values without one.
+#[tauri::command]
+pub fn preview_naming_template(
+    template: String,
+    kind: TemplateKind,
+    context: Option<NamingContext>,
+) -> Result<String, String> {
+    render_as(kind, &template, &context.unwrap_or_else(NamingContext::sample))
+}
+
+/// A name from the saved template of `kind`, for exports and digests built by the frontend.
+#[tauri::command]
+pub fn render_name(kind: TemplateKind, context: NamingContext) -> Result<String, String> {
+    let template = templates_lock().lock().unwrap().get(kind).to_string();
+    render_as(kind, &template, &context)
+}
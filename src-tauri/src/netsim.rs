@@ -0,0 +1,252 @@
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::ResponseBuilderExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use url::Url;
+
+// Network simulation for development builds: per-host latency, failures and
+// canned responses injected in `send_get`, so the frontend's "timing out",
+// "rate limited" and "feed is down" states can be exercised on demand. Status
+// failures and fixtures are real responses and go through the same retry,
+// rate-limit and cache handling as the network; connection-level failures end
+// the request with the error it would have had. Rules live in memory only,
+// and release builds ignore them.
+
+const MAX_LATENCY_MS: u64 = 120_000;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SimFailure {
+    /// Waits out the host's request timeout first, like the real thing.
+    Timeout,
+    ConnectionRefused,
+    Dns,
+    Tls,
+    /// An HTTP error, e.g. 429 with `retry_after_secs` for rate limiting.
+    Status {
+        status: u16,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry_after_secs: Option<u64>,
+    },
+}
+
+/// A canned response served instead of the network.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Fixture {
+    #[serde(default = "default_status")]
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SimRule {
+    /// Generated when empty.
+    #[serde(default)]
+    pub id: String,
+    /// Host to match, subdomains included. `*` matches every host.
+    pub host: String,
+    /// Only URLs whose path starts with this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_prefix: Option<String>,
+    /// Added before every request to the host.
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Up to this much more, picked at random per request.
+    #[serde(default)]
+    pub jitter_ms: u64,
+    /// Chance (0.0-1.0) that a request fails with `failure`.
+    #[serde(default)]
+    pub failure_rate: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<SimFailure>,
+    /// Served for requests that don't fail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixture: Option<Fixture>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Requests this rule has handled.
+    #[serde(default)]
+    pub hits: u64,
+}
+
+impl SimRule {
+    fn matches(&self, url: &Url) -> bool {
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        let rule_host = self.host.trim().to_ascii_lowercase();
+        let host_ok = rule_host == "*" || host == rule_host || host.ends_with(&format!(".{rule_host}"));
+        host_ok && self.path_prefix.as_deref().is_none_or(|p| url.path().starts_with(p))
+    }
+}
+
+fn rules_lock() -> &'static Mutex<Vec<SimRule>> {
+    static RULES: OnceLock<Mutex<Vec<SimRule>>> = OnceLock::new();
+    RULES.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// The first enabled rule for `url`, counting the hit.
+fn rule_for(url: &str) -> Option<SimRule> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    let url = Url::parse(url).ok()?;
+    let mut rules = rules_lock().lock().unwrap();
+    let rule = rules.iter_mut().find(|r| r.enabled && r.matches(&url))?;
+    rule.hits += 1;
+    Some(rule.clone())
+}
+
+/// Whether requests to `url` are simulated, so cached copies shouldn't stand in for them.
+pub fn is_simulated(url: &str) -> bool {
+    if !cfg!(debug_assertions) {
+        return false;
+    }
+    let Ok(url) = Url::parse(url) else { return false };
+    rules_lock().lock().unwrap().iter().any(|r| r.enabled && r.matches(&url))
+}
+
+fn build_response(
+    url: &str,
+    status: u16,
+    headers: &[(String, String)],
+    body: String,
+) -> Result<reqwest::Response, String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+    let mut builder = tauri::http::Response::builder().status(status).url(parsed);
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name.as_str()), HeaderValue::from_str(value)) {
+            builder = builder.header(name, value);
+        }
+    }
+    let response = builder.body(body).map_err(|e| format!("Invalid simulated response: {e}"))?;
+    Ok(reqwest::Response::from(response))
+}
+
+/// Apply the rule for `url`, if any, in place of one network attempt.
+/// `Ok(None)` means no rule: send the request for real.
+pub async fn intercept(url: &str, host_timeout: Duration) -> Result<Option<reqwest::Response>, String> {
+    let Some(rule) = rule_for(url) else { return Ok(None) };
+    let jitter = if rule.jitter_ms > 0 { rand::random::<u64>() % (rule.jitter_ms + 1) } else { 0 };
+    let latency = Duration::from_millis(rule.latency_ms + jitter);
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+
+    let failed = rule.failure.as_ref().filter(|_| rand::random::<f64>() < rule.failure_rate);
+    if let Some(failure) = failed {
+        eprintln!("[netsim] {failure:?} for {url}");
+        return match failure {
+            SimFailure::Timeout => {
+                tokio::time::sleep(host_timeout.saturating_sub(latency)).await;
+                Err("Timeout: operation timed out (simulated)".into())
+            }
+            SimFailure::ConnectionRefused => Err("Connection failed: connection refused (simulated)".into()),
+            SimFailure::Dns => Err("Connection failed: dns error: failed to lookup address (simulated)".into()),
+            SimFailure::Tls => Err("TLS/Request error: invalid peer certificate (simulated)".into()),
+            SimFailure::Status { status, retry_after_secs } => {
+                let mut headers = vec![(CONTENT_TYPE.to_string(), "text/plain".to_string())];
+                if let Some(secs) = retry_after_secs {
+                    headers.push((RETRY_AFTER.to_string(), secs.to_string()));
+                }
+                build_response(url, *status, &headers, format!("Simulated HTTP {status}")).map(Some)
+            }
+        };
+    }
+    match rule.fixture {
+        Some(fixture) => {
+            eprintln!("[netsim] Fixture {} for {url}", fixture.status);
+            let mut headers: Vec<(String, String)> = fixture.headers.into_iter().collect();
+            if let Some(content_type) = fixture.content_type {
+                headers.push((CONTENT_TYPE.to_string(), content_type));
+            }
+            build_response(url, fixture.status, &headers, fixture.body).map(Some)
+        }
+        // Latency only: the real request follows
+        None => Ok(None),
+    }
+}
+
+fn validate(rule: &SimRule) -> Result<(), String> {
+    if rule.host.trim().is_empty() {
+        return Err("Host is required (use * for every host)".into());
+    }
+    if !(0.0..=1.0).contains(&rule.failure_rate) {
+        return Err("Failure rate must be between 0 and 1".into());
+    }
+    if rule.latency_ms + rule.jitter_ms > MAX_LATENCY_MS {
+        return Err(format!("Latency plus jitter must stay under {MAX_LATENCY_MS} ms"));
+    }
+    let statuses = [
+        rule.fixture.as_ref().map(|f| f.status),
+        match rule.failure {
+            Some(SimFailure::Status { status, .. }) => Some(status),
+            _ => None,
+        },
+    ];
+    if let Some(status) = statuses.into_iter().flatten().find(|s| !(100..=599).contains(s)) {
+        return Err(format!("Invalid HTTP status {status}"));
+    }
+    Ok(())
+}
+
+fn ensure_dev_build() -> Result<(), String> {
+    if cfg!(debug_assertions) {
+        Ok(())
+    } else {
+        Err("Network simulation is only available in development builds".into())
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_netsim_rules() -> Vec<SimRule> {
+    rules_lock().lock().unwrap().clone()
+}
+
+/// Add a rule, or replace the one with the same id. Earlier rules win when
+/// several match a URL.
+#[tauri::command]
+pub fn set_netsim_rule(mut rule: SimRule) -> Result<SimRule, String> {
+    ensure_dev_build()?;
+    validate(&rule)?;
+    if rule.id.is_empty() {
+        rule.id = uuid::Uuid::new_v4().to_string();
+    }
+    rule.hits = 0;
+    let mut rules = rules_lock().lock().unwrap();
+    match rules.iter_mut().find(|r| r.id == rule.id) {
+        Some(existing) => *existing = rule.clone(),
+        None => rules.push(rule.clone()),
+    }
+    eprintln!("[netsim] Rule for {} set", rule.host);
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn delete_netsim_rule(id: String) -> bool {
+    let mut rules = rules_lock().lock().unwrap();
+    let before = rules.len();
+    rules.retain(|r| r.id != id);
+    rules.len() != before
+}
+
+/// Remove every rule, back to the real network.
+#[tauri::command]
+pub fn clear_netsim_rules() {
+    rules_lock().lock().unwrap().clear();
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+
+use crate::ingest::now_millis;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const LOOKUP_URL: &str = "https://ipinfo.io/json";
+/// A cached answer is returned for this long.
+const CACHE_TTL_MS: u64 = 5 * 60 * 1000;
+/// Even a forced refresh cannot hit the lookup service more often than this.
+const MIN_LOOKUP_INTERVAL_MS: u64 = 30 * 1000;
+
+/// Interface name prefixes of tunnel drivers (`tun0`, `wg0`, `utun3`, …).
+const VPN_INTERFACE_PREFIXES: &[&str] = &["tun", "tap", "wg", "utun", "ppp", "ipsec"];
+/// Name fragments used by common VPN clients' adapters.
+const VPN_INTERFACE_NAMES: &[&str] = &[
+    "vpn", "wireguard", "nordlynx", "proton", "mullvad", "tailscale", "zerotier", "tap-windows",
+];
+
+#[derive(Clone, Serialize, Debug)]
+pub struct NetworkIdentity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    /// ISO 3166 country code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// Network operator (AS number and name).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    pub vpn_active: bool,
+    pub vpn_interfaces: Vec<String>,
+    /// A system proxy is set through the environment.
+    pub proxy_configured: bool,
+    pub checked_at: u64,
+    /// True if served from the cache rather than a fresh lookup.
+    pub cached: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IpInfo {
+    ip: Option<String>,
+    country: Option<String>,
+    city: Option<String>,
+    org: Option<String>,
+}
+
+fn cache() -> &'static Mutex<Option<NetworkIdentity>> {
+    static CACHE: OnceLock<Mutex<Option<NetworkIdentity>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+// ── Detection ────────────────────────────────────────────────────────
+
+/// VPN-looking interfaces that have carried traffic.
+fn vpn_interfaces() -> Vec<String> {
+    use sysinfo::Networks;
+    let nets = Networks::new_with_refreshed_list();
+    let mut names: Vec<String> = nets
+        .iter()
+        .filter(|(_, data)| data.total_received() + data.total_transmitted() > 0)
+        .map(|(name, _)| name.to_string())
+        .filter(|name| {
+            let lower = name.to_lowercase();
+            // Prefixes only, so "Teredo Tunneling" does not count as "tun"
+            VPN_INTERFACE_PREFIXES.iter().any(|p| lower.starts_with(p))
+                || VPN_INTERFACE_NAMES.iter().any(|n| lower.contains(n))
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn proxy_configured() -> bool {
+    ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"]
+        .iter()
+        .any(|k| std::env::var(k).is_ok_and(|v| !v.trim().is_empty()))
+}
+
+async fn lookup() -> Result<IpInfo, String> {
+    let client = crate::get_or_init_client()?;
+    let response = client
+        .get(LOOKUP_URL)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("IP lookup failed: {e}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("IP lookup HTTP {}", status.as_u16()));
+    }
+    response.json::<IpInfo>().await.map_err(|e| format!("Invalid IP lookup response: {e}"))
+}
+
+pub async fn network_identity(force: bool) -> NetworkIdentity {
+    let now = now_millis();
+    if let Some(cached) = cache().lock().unwrap().clone() {
+        let age = now.saturating_sub(cached.checked_at);
+        if age < CACHE_TTL_MS && (!force || age < MIN_LOOKUP_INTERVAL_MS) {
+            return NetworkIdentity { cached: true, ..cached };
+        }
+    }
+
+    let vpn_interfaces = tauri::async_runtime::spawn_blocking(vpn_interfaces).await.unwrap_or_default();
+    let identity = match lookup().await {
+        Ok(info) => NetworkIdentity {
+            ip: info.ip,
+            country: info.country,
+            city: info.city,
+            org: info.org,
+            vpn_active: !vpn_interfaces.is_empty(),
+            vpn_interfaces,
+            proxy_configured: proxy_configured(),
+            checked_at: now,
+            cached: false,
+            error: None,
+        },
+        Err(e) => NetworkIdentity {
+            ip: None,
+            country: None,
+            city: None,
+            org: None,
+            vpn_active: !vpn_interfaces.is_empty(),
+            vpn_interfaces,
+            proxy_configured: proxy_configured(),
+            checked_at: now,
+            cached: false,
+            error: Some(e),
+        },
+    };
+    // Failures are cached too, so an offline machine does not retry on every poll
+    *cache().lock().unwrap() = Some(identity.clone());
+    identity
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Public IP, location and VPN/proxy status. Cached for 5 minutes; `force`
+/// bypasses the cache but is still limited to one lookup per 30 seconds.
+#[tauri::command]
+pub async fn get_network_identity(force: Option<bool>) -> NetworkIdentity {
+    network_identity(force.unwrap_or(false)).await
+}
@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::Manager;
+
+use crate::permissions::{host_permission, Permission, PermissionStore, Principal, PrincipalKind};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const TARGETS_FILE: &str = "push_targets.json";
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PushTargetKind {
+    Ntfy,
+    Gotify,
+}
+
+/// A self-hosted push server that receives alerts in addition to the desktop notification.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PushTarget {
+    pub id: String,
+    pub kind: PushTargetKind,
+    pub name: String,
+    /// Base server URL, e.g. `https://ntfy.sh` or `https://gotify.example.com`.
+    pub server_url: String,
+    /// ntfy topic. Unused for Gotify.
+    #[serde(default)]
+    pub topic: String,
+    /// ntfy access token or Gotify application token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// 1-5 (ntfy scale). Mapped to 0-10 for Gotify.
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_priority() -> u8 {
+    3
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PushMessage {
+    pub title: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub click_url: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct PushResult {
+    pub target_id: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct NotificationStore {
+    targets: Mutex<Vec<PushTarget>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl NotificationStore {
+    pub fn new() -> Self {
+        NotificationStore {
+            targets: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(TARGETS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(targets) = serde_json::from_str::<Vec<PushTarget>>(&json) {
+                            eprintln!("[notifications] Loaded {} push targets", targets.len());
+                            *self.targets.lock().unwrap() = targets;
+                        }
+                    }
+                    Err(e) => eprintln!("[notifications] Failed to read push targets: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let targets = self.targets.lock().unwrap();
+            match serde_json::to_string_pretty(&*targets) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[notifications] Failed to write push targets: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[notifications] Failed to serialize push targets: {e}"),
+            }
+        }
+    }
+
+    pub fn get_targets(&self) -> Vec<PushTarget> {
+        self.targets.lock().unwrap().clone()
+    }
+
+    pub fn enabled_targets(&self) -> Vec<PushTarget> {
+        self.targets.lock().unwrap().iter().filter(|t| t.enabled).cloned().collect()
+    }
+
+    /// Insert or replace a target. An empty id gets a fresh UUID.
+    pub fn upsert(&self, mut target: PushTarget) -> PushTarget {
+        if target.id.is_empty() {
+            target.id = uuid::Uuid::new_v4().to_string();
+        }
+        target.priority = target.priority.clamp(1, 5);
+        target.server_url = target.server_url.trim_end_matches('/').to_string();
+        {
+            let mut targets = self.targets.lock().unwrap();
+            match targets.iter_mut().find(|t| t.id == target.id) {
+                Some(existing) => *existing = target.clone(),
+                None => targets.push(target.clone()),
+            }
+        }
+        self.save_to_disk();
+        target
+    }
+
+    pub fn delete(&self, id: &str) -> bool {
+        let mut targets = self.targets.lock().unwrap();
+        let before = targets.len();
+        targets.retain(|t| t.id != id);
+        let removed = targets.len() < before;
+        drop(targets);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+}
+
+// ── Push delivery ────────────────────────────────────────────────────
+
+/// A header-safe copy of `text`: control characters (CR/LF from a feed
+/// title would end the header) become spaces, and anything non-ASCII is sent
+/// RFC 2047-encoded, which ntfy decodes.
+fn header_text(text: &str) -> String {
+    use base64::Engine;
+
+    let clean: String = text.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+    let clean = clean.split_whitespace().collect::<Vec<_>>().join(" ");
+    if clean.is_ascii() {
+        clean
+    } else {
+        format!("=?UTF-8?B?{}?=", base64::engine::general_purpose::STANDARD.encode(clean))
+    }
+}
+
+async fn send_ntfy(client: &reqwest::Client, target: &PushTarget, msg: &PushMessage) -> Result<(), String> {
+    if target.topic.trim().is_empty() {
+        return Err("ntfy topic is empty".into());
+    }
+    let url = format!("{}/{}", target.server_url, target.topic.trim());
+    let mut req = client
+        .post(&url)
+        .header("Title", header_text(&msg.title))
+        .header("Priority", target.priority.to_string())
+        .header("Tags", "newspaper")
+        .body(msg.message.clone());
+    if let Some(ref click) = msg.click_url {
+        req = req.header("Click", header_text(click));
+    }
+    if let Some(ref token) = target.token {
+        req = req.bearer_auth(token);
+    }
+
+    let response = req.send().await.map_err(|e| format!("ntfy request failed: {e}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("ntfy HTTP {status}: {body}"));
+    }
+    Ok(())
+}
+
+async fn send_gotify(client: &reqwest::Client, target: &PushTarget, msg: &PushMessage) -> Result<(), String> {
+    let token = target.token.as_deref().ok_or("Gotify application token is missing")?;
+    let url = format!("{}/message", target.server_url);
+
+    let mut body = serde_json::json!({
+        "title": msg.title,
+        "message": msg.message,
+        // Gotify uses 0-10, ntfy-style 1-5 maps onto the even steps
+        "priority": target.priority as u32 * 2,
+    });
+    if let Some(ref click) = msg.click_url {
+        body["extras"] = serde_json::json!({
+            "client::notification": { "click": { "url": click } }
+        });
+    }
+
+    let response = client
+        .post(&url)
+        .header("X-Gotify-Key", token)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Gotify request failed: {e}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Gotify HTTP {status}: {body}"));
+    }
+    Ok(())
+}
+
+/// Send to one target. Each target must be granted its host and access to
+/// article content, prompting on first use.
+pub async fn send_to_target(app: &tauri::AppHandle, target: &PushTarget, msg: &PushMessage) -> Result<(), String> {
+    let principal = Principal {
+        kind: PrincipalKind::Webhook,
+        id: target.id.clone(),
+    };
+    let required = vec![host_permission(&target.server_url)?, Permission::ReadContent];
+    let perms = app.state::<Arc<PermissionStore>>();
+    crate::permissions::require(app, perms.inner(), &principal, Some(&target.name), required).await?;
+    let client = crate::get_or_init_client()?;
+    match target.kind {
+        PushTargetKind::Ntfy => send_ntfy(&client, target, msg).await,
+        PushTargetKind::Gotify => send_gotify(&client, target, msg).await,
+    }
+}
+
+/// Deliver a message to every enabled push target. Failures are reported per target.
+pub async fn push_to_all(app: &tauri::AppHandle, store: &NotificationStore, msg: &PushMessage) -> Vec<PushResult> {
+    let mut results = Vec::new();
+    for target in store.enabled_targets() {
+        let outcome = send_to_target(app, &target, msg).await;
+        if let Err(ref e) = outcome {
+            eprintln!("[notifications] Push to '{}' failed: {e}", target.name);
+        }
+        results.push(PushResult {
+            target_id: target.id.clone(),
+            ok: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+    results
+}
+
+pub(crate) fn suppressed(app: &tauri::AppHandle) -> bool {
+    app.state::<Arc<crate::focus::FocusStore>>().notifications_suppressed()
+}
+
+/// Show a native desktop notification, unless a focus session mutes notifications.
+pub fn notify_desktop(app: &tauri::AppHandle, title: &str, body: &str) {
+    use tauri_plugin_notification::NotificationExt;
+
+    if suppressed(app) {
+        return;
+    }
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("[notifications] Desktop notification failed: {e}");
+    }
+}
+
+/// Raise an alert from the backend: desktop notification, push targets and the
+/// Matrix rooms that opted into alerts. Nothing is sent while a focus session
+/// mutes notifications.
+pub async fn raise_alert(app: &tauri::AppHandle, msg: &PushMessage) {
+    if suppressed(app) {
+        return;
+    }
+    notify_desktop(app, &msg.title, &msg.message);
+    push_to_all(app, &app.state::<Arc<NotificationStore>>(), msg).await;
+    let matrix = app.state::<Arc<crate::matrix::MatrixStore>>();
+    crate::matrix::send_alert(&matrix, &msg.title, &msg.message, msg.click_url.as_deref()).await;
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_push_targets(store: tauri::State<'_, Arc<NotificationStore>>) -> Vec<PushTarget> {
+    store.get_targets()
+}
+
+#[tauri::command]
+pub fn save_push_target(
+    target: PushTarget,
+    store: tauri::State<'_, Arc<NotificationStore>>,
+) -> Result<PushTarget, String> {
+    if target.server_url.trim().is_empty() {
+        return Err("Server URL is required".into());
+    }
+    url::Url::parse(target.server_url.trim()).map_err(|e| format!("Invalid server URL: {e}"))?;
+    Ok(store.upsert(target))
+}
+
+#[tauri::command]
+pub fn delete_push_target(id: String, store: tauri::State<'_, Arc<NotificationStore>>) -> bool {
+    store.delete(&id)
+}
+
+#[tauri::command]
+pub async fn test_push_target(
+    id: String,
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<NotificationStore>>,
+) -> Result<(), String> {
+    let target = store
+        .get_targets()
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| "Push target not found".to_string())?;
+    let msg = PushMessage {
+        title: "SuperFlux".into(),
+        message: format!("Test notification for '{}'", target.name),
+        click_url: None,
+    };
+    send_to_target(&app, &target, &msg).await
+}
+
+/// Forward an alert (e.g. a watched-keyword match) to all enabled push targets
+/// and to the Matrix rooms that opted into alerts. Nothing is sent while a
+/// focus session mutes notifications.
+#[tauri::command]
+pub async fn send_push_notification(
+    title: String,
+    message: String,
+    click_url: Option<String>,
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<NotificationStore>>,
+    matrix_store: tauri::State<'_, Arc<crate::matrix::MatrixStore>>,
+    focus: tauri::State<'_, Arc<crate::focus::FocusStore>>,
+) -> Result<Vec<PushResult>, String> {
+    if focus.notifications_suppressed() {
+        return Ok(Vec::new());
+    }
+    let msg = PushMessage { title, message, click_url };
+    let mut results = push_to_all(&app, &store, &msg).await;
+    for (room_id, outcome) in
+        crate::matrix::send_alert(&matrix_store, &msg.title, &msg.message, msg.click_url.as_deref()).await
+    {
+        results.push(PushResult {
+            target_id: room_id,
+            ok: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+    Ok(results)
+}
@@ -0,0 +1,420 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::db::{Database, DbFeed};
+use crate::sounds::SoundStore;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const MAX_OPML_BYTES: usize = 20 * 1024 * 1024;
+/// Namespace for SuperFlux-specific outline attributes.
+const SF_NAMESPACE: &str = "https://superflux.app/opml/1.0";
+const SF_PREFIX: &str = "superflux:";
+
+/// Per-feed SuperFlux settings, carried as `superflux:*` outline attributes so a
+/// re-import restores behavior, not just the subscription.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct FeedSettings {
+    /// Minutes between refreshes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<u32>,
+    /// Id of the scraping rule used for full-text extraction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scraping_rule: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_sound: Option<String>,
+    /// Unrecognized `superflux:*` attributes, kept verbatim so files written by
+    /// newer versions survive a round-trip.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl FeedSettings {
+    const KNOWN: [&'static str; 4] = ["refreshInterval", "scrapingRule", "notify", "notificationSound"];
+
+    fn from_attributes(e: &BytesStart) -> Option<FeedSettings> {
+        let mut settings = FeedSettings::default();
+        let mut any = false;
+        for a in e.attributes().flatten() {
+            let key = String::from_utf8_lossy(a.key.as_ref()).to_string();
+            let Some(name) = key.strip_prefix(SF_PREFIX) else { continue };
+            let Ok(value) = a.unescape_value() else { continue };
+            let value = value.trim().to_string();
+            any = true;
+            match name {
+                "refreshInterval" => settings.refresh_interval = value.parse().ok(),
+                "scrapingRule" => settings.scraping_rule = Some(value).filter(|v| !v.is_empty()),
+                "notify" => settings.notify = value.parse().ok(),
+                "notificationSound" => settings.notification_sound = Some(value).filter(|v| !v.is_empty()),
+                _ => {
+                    settings.extra.insert(name.to_string(), value);
+                }
+            }
+        }
+        any.then_some(settings)
+    }
+
+    fn attributes(&self) -> Vec<(String, String)> {
+        let mut attrs = Vec::new();
+        if let Some(v) = self.refresh_interval {
+            attrs.push(("refreshInterval".to_string(), v.to_string()));
+        }
+        if let Some(v) = &self.scraping_rule {
+            attrs.push(("scrapingRule".to_string(), v.clone()));
+        }
+        if let Some(v) = self.notify {
+            attrs.push(("notify".to_string(), v.to_string()));
+        }
+        if let Some(v) = &self.notification_sound {
+            attrs.push(("notificationSound".to_string(), v.clone()));
+        }
+        let extra = self.extra.iter().filter(|(k, _)| !Self::KNOWN.contains(&k.as_str()));
+        attrs.extend(extra.map(|(k, v)| (k.clone(), v.clone())));
+        attrs
+            .into_iter()
+            .map(|(k, v)| (format!("{SF_PREFIX}{k}"), v))
+            .collect()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum OpmlNode {
+    Folder {
+        title: String,
+        children: Vec<OpmlNode>,
+    },
+    Feed {
+        title: String,
+        xml_url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        html_url: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// The outline `type` attribute (`rss`, `atom`, …).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        feed_type: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        settings: Option<FeedSettings>,
+    },
+}
+
+#[derive(Clone, Serialize, Debug, Default)]
+pub struct OpmlImport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub outlines: Vec<OpmlNode>,
+    pub feed_count: usize,
+    pub folder_count: usize,
+    /// Feeds already listed earlier in the file, dropped from the tree.
+    pub duplicates: usize,
+    /// Outlines whose feed URL is invalid or not HTTP(S).
+    pub skipped: usize,
+}
+
+// ── Parsing ──────────────────────────────────────────────────────────
+
+/// Attributes are matched case-insensitively: exporters disagree on `xmlUrl` vs `xmlurl`.
+fn attr(e: &BytesStart, name: &str) -> Option<String> {
+    e.attributes().flatten().find_map(|a| {
+        if !a.key.as_ref().eq_ignore_ascii_case(name.as_bytes()) {
+            return None;
+        }
+        let value = a.unescape_value().ok()?.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    })
+}
+
+struct Parser {
+    result: OpmlImport,
+    seen: HashSet<String>,
+    /// Open `<outline>` elements. `None` marks a feed outline with children,
+    /// whose children are attached to the enclosing folder instead.
+    stack: Vec<Option<(String, Vec<OpmlNode>)>>,
+}
+
+impl Parser {
+    fn push_node(&mut self, node: OpmlNode) {
+        let parent = self.stack.iter_mut().rev().find_map(|f| f.as_mut());
+        match parent {
+            Some((_, children)) => children.push(node),
+            None => self.result.outlines.push(node),
+        }
+    }
+
+    /// An outline with `xmlUrl` is a feed; one without is a folder.
+    fn outline(&mut self, e: &BytesStart, has_children: bool) {
+        let title = attr(e, "title").or_else(|| attr(e, "text"));
+        let Some(xml_url) = attr(e, "xmlUrl") else {
+            if has_children {
+                self.stack.push(Some((title.unwrap_or_else(|| "Untitled".into()), Vec::new())));
+            }
+            return;
+        };
+        if has_children {
+            self.stack.push(None);
+        }
+        let Ok(parsed) = url::Url::parse(&xml_url) else {
+            self.result.skipped += 1;
+            return;
+        };
+        if !matches!(parsed.scheme(), "http" | "https") {
+            self.result.skipped += 1;
+            return;
+        }
+        if !self.seen.insert(parsed.to_string()) {
+            self.result.duplicates += 1;
+            return;
+        }
+        self.push_node(OpmlNode::Feed {
+            title: title.unwrap_or_else(|| parsed.host_str().unwrap_or(&xml_url).to_string()),
+            html_url: attr(e, "htmlUrl"),
+            description: attr(e, "description"),
+            feed_type: attr(e, "type").map(|t| t.to_lowercase()),
+            settings: FeedSettings::from_attributes(e),
+            xml_url,
+        });
+        self.result.feed_count += 1;
+    }
+
+    fn close_outline(&mut self) {
+        if let Some(Some((title, children))) = self.stack.pop() {
+            self.result.folder_count += 1;
+            self.push_node(OpmlNode::Folder { title, children });
+        }
+    }
+}
+
+pub fn parse_opml(xml: &str) -> Result<OpmlImport, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut parser = Parser {
+        result: OpmlImport::default(),
+        seen: HashSet::new(),
+        stack: Vec::new(),
+    };
+    let mut saw_opml = false;
+    let mut in_head_title = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"opml" => saw_opml = true,
+                b"title" if parser.stack.is_empty() => in_head_title = true,
+                b"outline" => parser.outline(&e, true),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"outline" => {
+                parser.outline(&e, false);
+            }
+            Ok(Event::Text(t)) if in_head_title => {
+                let title = t.unescape().map_err(|e| format!("Invalid OPML: {e}"))?;
+                if !title.trim().is_empty() {
+                    parser.result.title = Some(title.trim().to_string());
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"title" => in_head_title = false,
+                b"outline" => parser.close_outline(),
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Invalid OPML at byte {}: {e}", reader.error_position())),
+            _ => {}
+        }
+    }
+    if !saw_opml {
+        return Err("Not an OPML file".into());
+    }
+    // Unclosed outlines in a truncated file still yield what was read
+    while !parser.stack.is_empty() {
+        parser.close_outline();
+    }
+    Ok(parser.result)
+}
+
+fn decode_input(bytes: &[u8]) -> Result<String, String> {
+    if bytes.len() > MAX_OPML_BYTES {
+        return Err("OPML file is too large (max 20 MB)".into());
+    }
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+// ── Export ───────────────────────────────────────────────────────────
+
+/// Build the folder tree from the DB's `/`-separated folder paths.
+pub fn tree_from_feeds(feeds: Vec<DbFeed>) -> Vec<OpmlNode> {
+    fn insert(nodes: &mut Vec<OpmlNode>, path: &[&str], feed: OpmlNode) {
+        let Some((first, rest)) = path.split_first() else {
+            nodes.push(feed);
+            return;
+        };
+        let existing = nodes
+            .iter()
+            .position(|n| matches!(n, OpmlNode::Folder { title, .. } if title == first));
+        let idx = existing.unwrap_or_else(|| {
+            nodes.push(OpmlNode::Folder {
+                title: first.to_string(),
+                children: Vec::new(),
+            });
+            nodes.len() - 1
+        });
+        if let OpmlNode::Folder { children, .. } = &mut nodes[idx] {
+            insert(children, rest, feed);
+        }
+    }
+
+    let mut roots = Vec::new();
+    for f in feeds {
+        let path: Vec<&str> = f.folder.split('/').map(str::trim).filter(|p| !p.is_empty()).collect();
+        let node = OpmlNode::Feed {
+            title: if f.title.is_empty() { f.url.clone() } else { f.title.clone() },
+            xml_url: f.url.clone(),
+            html_url: f.html_url.clone(),
+            description: None,
+            feed_type: Some("rss".into()),
+            settings: None,
+        };
+        insert(&mut roots, &path, node);
+    }
+    roots
+}
+
+/// Fill in the notification sounds the backend keeps per feed URL.
+pub fn attach_feed_sounds(nodes: &mut [OpmlNode], feed_sounds: &HashMap<String, String>) {
+    for node in nodes {
+        match node {
+            OpmlNode::Folder { children, .. } => attach_feed_sounds(children, feed_sounds),
+            OpmlNode::Feed { xml_url, settings, .. } => {
+                if let Some(sound) = feed_sounds.get(xml_url.as_str()) {
+                    settings.get_or_insert_with(FeedSettings::default).notification_sound = Some(sound.clone());
+                }
+            }
+        }
+    }
+}
+
+fn write_outlines(writer: &mut Writer<Vec<u8>>, nodes: &[OpmlNode]) -> std::io::Result<()> {
+    for node in nodes {
+        match node {
+            OpmlNode::Folder { title, children } => {
+                let start = BytesStart::new("outline")
+                    .with_attributes([("text", title.as_str()), ("title", title.as_str())]);
+                if children.is_empty() {
+                    writer.write_event(Event::Empty(start))?;
+                } else {
+                    writer.write_event(Event::Start(start))?;
+                    write_outlines(writer, children)?;
+                    writer.write_event(Event::End(BytesEnd::new("outline")))?;
+                }
+            }
+            OpmlNode::Feed { title, xml_url, html_url, description, feed_type, settings } => {
+                let mut e = BytesStart::new("outline").with_attributes([
+                    ("type", feed_type.as_deref().unwrap_or("rss")),
+                    ("text", title.as_str()),
+                    ("title", title.as_str()),
+                    ("xmlUrl", xml_url.as_str()),
+                ]);
+                if let Some(html) = html_url {
+                    e.push_attribute(("htmlUrl", html.as_str()));
+                }
+                if let Some(desc) = description {
+                    e.push_attribute(("description", desc.as_str()));
+                }
+                for (key, value) in settings.iter().flat_map(FeedSettings::attributes) {
+                    e.push_attribute((key.as_str(), value.as_str()));
+                }
+                writer.write_event(Event::Empty(e))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Serialize a subscription tree as an OPML 2.0 document.
+pub fn write_opml(title: &str, outlines: &[OpmlNode]) -> Result<String, String> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    let mut write = || -> std::io::Result<()> {
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer.write_event(Event::Start(BytesStart::new("opml").with_attributes([("version", "2.0"), ("xmlns:superflux", SF_NAMESPACE)])))?;
+        writer.write_event(Event::Start(BytesStart::new("head")))?;
+        for (tag, text) in [("title", title.to_string()), ("dateCreated", chrono::Utc::now().to_rfc2822())] {
+            writer.write_event(Event::Start(BytesStart::new(tag)))?;
+            writer.write_event(Event::Text(BytesText::new(&text)))?;
+            writer.write_event(Event::End(BytesEnd::new(tag)))?;
+        }
+        writer.write_event(Event::End(BytesEnd::new("head")))?;
+        writer.write_event(Event::Start(BytesStart::new("body")))?;
+        write_outlines(&mut writer, outlines)?;
+        writer.write_event(Event::End(BytesEnd::new("body")))?;
+        writer.write_event(Event::End(BytesEnd::new("opml")))
+    };
+    write().map_err(|e| format!("Failed to write OPML: {e}"))?;
+    String::from_utf8(writer.into_inner()).map_err(|e| format!("OPML encoding error: {e}"))
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Parse an OPML file given either a path or its base64 content, returning
+/// the subscription tree. Nothing is subscribed here; the frontend applies it.
+#[tauri::command]
+pub async fn opml_import(path: Option<String>, base64_data: Option<String>) -> Result<OpmlImport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let bytes = match (path, base64_data) {
+            (Some(path), _) => std::fs::read(&path).map_err(|e| format!("Failed to read OPML file: {e}"))?,
+            (None, Some(data)) => STANDARD
+                .decode(data.trim())
+                .map_err(|e| format!("base64 decode error: {e}"))?,
+            (None, None) => return Err("Either a path or base64 data is required".into()),
+        };
+        parse_opml(&decode_input(&bytes)?)
+    })
+    .await
+    .map_err(|e| format!("OPML import task failed: {e}"))?
+}
+
+/// Export subscriptions as OPML through a save dialog. Uses `outlines` when
+/// given, otherwise the feeds stored in the database. Per-feed settings are
+/// written as `superflux:*` attributes. Returns `false` if the
+/// user cancelled.
+#[tauri::command]
+pub async fn opml_export(
+    outlines: Option<Vec<OpmlNode>>,
+    title: Option<String>,
+    db: tauri::State<'_, Arc<Database>>,
+    sounds: tauri::State<'_, Arc<SoundStore>>,
+) -> Result<bool, String> {
+    let outlines = match outlines {
+        Some(o) => o,
+        None => {
+            let mut tree = tree_from_feeds(crate::db::run(&db, |conn| crate::db::list_feeds(conn)).await?);
+            attach_feed_sounds(&mut tree, &sounds.get_settings().feed_sounds);
+            tree
+        }
+    };
+    if outlines.is_empty() {
+        return Err("No subscriptions to export".into());
+    }
+    let xml = write_opml(title.as_deref().unwrap_or("SuperFlux subscriptions"), &outlines)?;
+
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("superflux-subscriptions-{}.opml", chrono::Local::now().format("%Y-%m-%d")))
+        .add_filter("OPML", &["opml", "xml"])
+        .save_file()
+        .await;
+
+    match dialog {
+        Some(handle) => {
+            std::fs::write(handle.path(), xml.as_bytes()).map_err(|e| format!("Failed to write file: {e}"))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,433 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+use crate::ingest::now_millis;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const GRANTS_FILE: &str = "permissions.json";
+/// Unanswered prompts are treated as a one-off denial.
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+/// Extension code runs in windows labeled `plugin:<id>` or `script:<id>`.
+const PLUGIN_WINDOW_PREFIX: &str = "plugin:";
+const SCRIPT_WINDOW_PREFIX: &str = "script:";
+/// The window whose settings UI edits grants and answers prompts.
+const SETTINGS_WINDOW: &str = "main";
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrincipalKind {
+    /// SuperFlux's own integrations, which the user configures directly.
+    App,
+    Plugin,
+    Script,
+    Webhook,
+}
+
+/// The extension asking for access: a plugin, user script or webhook, by id.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Principal {
+    pub kind: PrincipalKind,
+    pub id: String,
+}
+
+impl Principal {
+    /// The principal behind a command, from the label of the invoking window.
+    /// Webviews never name themselves, so extension code can't pass as the app.
+    pub fn of(window: &tauri::WebviewWindow) -> Self {
+        let label = window.label();
+        let (kind, id) = if let Some(id) = label.strip_prefix(PLUGIN_WINDOW_PREFIX) {
+            (PrincipalKind::Plugin, id)
+        } else if let Some(id) = label.strip_prefix(SCRIPT_WINDOW_PREFIX) {
+            (PrincipalKind::Script, id)
+        } else {
+            (PrincipalKind::App, label)
+        };
+        Principal { kind, id: id.to_string() }
+    }
+
+    pub fn is_app(&self) -> bool {
+        self.kind == PrincipalKind::App
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Permission {
+    /// Contact this host over the network.
+    Host { host: String },
+    /// Receive article titles, summaries and content.
+    ReadContent,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Grant {
+    pub principal: Principal,
+    /// Display name shown in prompts and settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Allowed host patterns: `example.com`, `*.example.com`, or `*`.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    /// Hosts the user refused with "remember".
+    #[serde(default)]
+    pub denied_hosts: Vec<String>,
+    /// `None` until the user has been asked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_content: Option<bool>,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+/// Emitted as `permission-request`; answered with `respond_permission_request`.
+#[derive(Clone, Serialize, Debug)]
+pub struct PermissionPrompt {
+    pub request_id: String,
+    pub principal: Principal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub permissions: Vec<Permission>,
+}
+
+struct Decision {
+    allow: bool,
+    remember: bool,
+}
+
+/// `*` matches any host; `*.example.com` matches example.com and its subdomains.
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    if pattern == "*" {
+        return true;
+    }
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host == domain || host.ends_with(&format!(".{domain}")),
+        None => host == pattern,
+    }
+}
+
+impl Grant {
+    fn decision(&self, permission: &Permission) -> Option<bool> {
+        match permission {
+            Permission::Host { host } => {
+                if self.denied_hosts.iter().any(|p| host_matches(p, host)) {
+                    Some(false)
+                } else if self.hosts.iter().any(|p| host_matches(p, host)) {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+            Permission::ReadContent => self.read_content,
+        }
+    }
+
+    fn record(&mut self, permission: &Permission, allow: bool) {
+        match permission {
+            Permission::Host { host } => {
+                let host = host.to_ascii_lowercase();
+                let (add, remove) = if allow {
+                    (&mut self.hosts, &mut self.denied_hosts)
+                } else {
+                    (&mut self.denied_hosts, &mut self.hosts)
+                };
+                remove.retain(|h| h != &host);
+                if !add.contains(&host) {
+                    add.push(host);
+                }
+            }
+            Permission::ReadContent => self.read_content = Some(allow),
+        }
+    }
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct PermissionStore {
+    grants: Mutex<Vec<Grant>>,
+    data_dir: Mutex<Option<PathBuf>>,
+    /// Prompts waiting for the UI, by request id.
+    pending: Mutex<HashMap<String, mpsc::Sender<Decision>>>,
+}
+
+impl PermissionStore {
+    pub fn new() -> Self {
+        PermissionStore {
+            grants: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(GRANTS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(grants) = serde_json::from_str::<Vec<Grant>>(&json) {
+                            *self.grants.lock().unwrap() = grants;
+                        }
+                    }
+                    Err(e) => eprintln!("[permissions] Failed to read grants: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let grants = self.grants.lock().unwrap();
+            match serde_json::to_string_pretty(&*grants) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[permissions] Failed to write grants: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[permissions] Failed to serialize grants: {e}"),
+            }
+        }
+    }
+
+    pub fn get_grants(&self) -> Vec<Grant> {
+        self.grants.lock().unwrap().clone()
+    }
+
+    /// Replace the grant for `grant.principal`, as edited in settings.
+    pub fn set_grant(&self, mut grant: Grant) {
+        grant.updated_at = now_millis();
+        {
+            let mut grants = self.grants.lock().unwrap();
+            grants.retain(|g| g.principal != grant.principal);
+            grants.push(grant);
+        }
+        self.save_to_disk();
+    }
+
+    pub fn revoke(&self, principal: &Principal) -> bool {
+        let removed = {
+            let mut grants = self.grants.lock().unwrap();
+            let before = grants.len();
+            grants.retain(|g| &g.principal != principal);
+            grants.len() != before
+        };
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+
+    /// Remembered answer for a permission, if any.
+    pub fn decision(&self, principal: &Principal, permission: &Permission) -> Option<bool> {
+        let grants = self.grants.lock().unwrap();
+        grants.iter().find(|g| &g.principal == principal)?.decision(permission)
+    }
+
+    fn remember(&self, principal: &Principal, label: Option<&str>, permissions: &[Permission], allow: bool) {
+        {
+            let mut grants = self.grants.lock().unwrap();
+            let idx = match grants.iter().position(|g| &g.principal == principal) {
+                Some(i) => i,
+                None => {
+                    grants.push(Grant {
+                        principal: principal.clone(),
+                        label: None,
+                        hosts: Vec::new(),
+                        denied_hosts: Vec::new(),
+                        read_content: None,
+                        updated_at: 0,
+                    });
+                    grants.len() - 1
+                }
+            };
+            let grant = &mut grants[idx];
+            for p in permissions {
+                grant.record(p, allow);
+            }
+            if let Some(label) = label {
+                grant.label = Some(label.to_string());
+            }
+            grant.updated_at = now_millis();
+        }
+        self.save_to_disk();
+    }
+
+    fn respond(&self, request_id: &str, decision: Decision) -> bool {
+        match self.pending.lock().unwrap().remove(request_id) {
+            Some(tx) => tx.send(decision).is_ok(),
+            None => false,
+        }
+    }
+}
+
+// ── Enforcement ──────────────────────────────────────────────────────
+
+/// The host a URL targets, as a `Permission::Host`.
+pub fn host_permission(url: &str) -> Result<Permission, String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+    let host = parsed.host_str().ok_or("URL has no host")?;
+    Ok(Permission::Host { host: host.to_string() })
+}
+
+/// Fail unless `principal` holds every permission in `required`. Permissions
+/// without a remembered answer are asked for in a single `permission-request`
+/// prompt. The app itself needs no grants.
+pub async fn require(
+    app: &tauri::AppHandle,
+    store: &Arc<PermissionStore>,
+    principal: &Principal,
+    label: Option<&str>,
+    required: Vec<Permission>,
+) -> Result<(), String> {
+    if principal.is_app() {
+        return Ok(());
+    }
+    let mut missing = Vec::new();
+    for permission in required {
+        match store.decision(principal, &permission) {
+            Some(true) => {}
+            Some(false) => return Err(denied_message(principal, &permission)),
+            None => missing.push(permission),
+        }
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let (tx, rx) = mpsc::channel();
+    store.pending.lock().unwrap().insert(request_id.clone(), tx);
+    let prompt = PermissionPrompt {
+        request_id: request_id.clone(),
+        principal: principal.clone(),
+        label: label.map(String::from),
+        permissions: missing.clone(),
+    };
+    if let Err(e) = app.emit("permission-request", &prompt) {
+        store.pending.lock().unwrap().remove(&request_id);
+        return Err(format!("Failed to request permission: {e}"));
+    }
+    let answer = tauri::async_runtime::spawn_blocking(move || rx.recv_timeout(PROMPT_TIMEOUT).ok())
+        .await
+        .ok()
+        .flatten();
+    store.pending.lock().unwrap().remove(&request_id);
+
+    let Some(decision) = answer else {
+        return Err("Permission request timed out".into());
+    };
+    if decision.remember {
+        store.remember(principal, label, &missing, decision.allow);
+    }
+    if decision.allow {
+        Ok(())
+    } else {
+        Err(denied_message(principal, &missing[0]))
+    }
+}
+
+/// `require` a host permission for `url` from the window that invoked a command.
+pub async fn require_host(window: &tauri::WebviewWindow, url: &str) -> Result<(), String> {
+    let app = window.app_handle();
+    let store = app.state::<Arc<PermissionStore>>();
+    require(app, store.inner(), &Principal::of(window), None, vec![host_permission(url)?]).await
+}
+
+fn denied_message(principal: &Principal, permission: &Permission) -> String {
+    let kind = match principal.kind {
+        PrincipalKind::App => "App",
+        PrincipalKind::Plugin => "Plugin",
+        PrincipalKind::Script => "Script",
+        PrincipalKind::Webhook => "Webhook",
+    };
+    match permission {
+        Permission::Host { host } => format!("{kind} '{}' is not allowed to contact {host}", principal.id),
+        Permission::ReadContent => format!("{kind} '{}' is not allowed to read article content", principal.id),
+    }
+}
+
+/// Grants change only from the main window's settings, never from the
+/// extensions they govern.
+fn require_settings_window(window: &tauri::WebviewWindow) -> Result<(), String> {
+    if window.label() == SETTINGS_WINDOW {
+        Ok(())
+    } else {
+        Err("Permissions can only be changed from SuperFlux settings".into())
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_permission_grants(store: tauri::State<'_, Arc<PermissionStore>>) -> Vec<Grant> {
+    store.get_grants()
+}
+
+#[tauri::command]
+pub fn set_permission_grant(
+    grant: Grant,
+    window: tauri::WebviewWindow,
+    store: tauri::State<'_, Arc<PermissionStore>>,
+) -> Result<(), String> {
+    require_settings_window(&window)?;
+    store.set_grant(grant);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn revoke_permissions(
+    principal: Principal,
+    window: tauri::WebviewWindow,
+    store: tauri::State<'_, Arc<PermissionStore>>,
+) -> Result<bool, String> {
+    require_settings_window(&window)?;
+    Ok(store.revoke(&principal))
+}
+
+/// Answer a `permission-request` prompt. Returns false if it already expired.
+#[tauri::command]
+pub fn respond_permission_request(
+    request_id: String,
+    allow: bool,
+    remember: bool,
+    window: tauri::WebviewWindow,
+    store: tauri::State<'_, Arc<PermissionStore>>,
+) -> Result<bool, String> {
+    require_settings_window(&window)?;
+    Ok(store.respond(&request_id, Decision { allow, remember }))
+}
+
+/// Check (prompting if needed) that a principal holds `permissions`, for
+/// features enforced on the frontend side such as handing article content to a plugin.
+/// Only the app's own windows may ask on behalf of another principal; extension
+/// windows always ask for themselves.
+#[tauri::command]
+pub async fn request_permissions(
+    principal: Option<Principal>,
+    label: Option<String>,
+    permissions: Vec<Permission>,
+    window: tauri::WebviewWindow,
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<PermissionStore>>,
+) -> Result<(), String> {
+    let caller = Principal::of(&window);
+    let principal = match principal {
+        Some(principal) if caller.is_app() => principal,
+        _ => caller,
+    };
+    require(&app, store.inner(), &principal, label.as_deref(), permissions).await
+}
@@ -0,0 +1,143 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// Keeps the machine from suspending during long read-aloud sessions and podcast
+// playback. One inhibition is held at a time, by a thread of its own that takes
+// it from the OS, waits for `release_sleep` and gives it back: Windows ties the
+// execution state to the thread that set it, and on Linux the inhibition lives
+// as long as the D-Bus connection that asked for it. Quitting releases it too.
+
+struct Inhibition {
+    reason: String,
+    release: Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+static INHIBITION: Mutex<Option<Inhibition>> = Mutex::new(None);
+
+/// How long the OS gets to grant the inhibition.
+const GRANT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(target_os = "windows")]
+fn hold(_reason: &str, granted: Sender<Result<(), String>>, release: Receiver<()>) {
+    extern "system" {
+        fn SetThreadExecutionState(flags: u32) -> u32;
+    }
+    const ES_CONTINUOUS: u32 = 0x8000_0000;
+    const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+
+    if unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED) } == 0 {
+        let _ = granted.send(Err("SetThreadExecutionState failed".into()));
+        return;
+    }
+    let _ = granted.send(Ok(()));
+    let _ = release.recv();
+    unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+}
+
+#[cfg(target_os = "macos")]
+fn hold(_reason: &str, granted: Sender<Result<(), String>>, release: Receiver<()>) {
+    // caffeinate holds an IOPMAssertion and drops it when killed or when we exit
+    let child = std::process::Command::new("caffeinate")
+        .args(["-i", "-w", &std::process::id().to_string()])
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            let _ = granted.send(Err(format!("Failed to start caffeinate: {e}")));
+            return;
+        }
+    };
+    let _ = granted.send(Ok(()));
+    let _ = release.recv();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(target_os = "linux")]
+fn hold(reason: &str, granted: Sender<Result<(), String>>, release: Receiver<()>) {
+    const SERVICE: &str = "org.freedesktop.ScreenSaver";
+    const PATH: &str = "/org/freedesktop/ScreenSaver";
+
+    let inhibit = || -> Result<(zbus::blocking::Connection, u32), String> {
+        let conn = zbus::blocking::Connection::session().map_err(|e| format!("D-Bus session: {e}"))?;
+        let reply = conn
+            .call_method(Some(SERVICE), PATH, Some(SERVICE), "Inhibit", &("SuperFlux", reason))
+            .map_err(|e| format!("ScreenSaver.Inhibit: {e}"))?;
+        let cookie = reply.body().deserialize::<u32>().map_err(|e| format!("ScreenSaver.Inhibit: {e}"))?;
+        Ok((conn, cookie))
+    };
+    let (conn, cookie) = match inhibit() {
+        Ok(held) => held,
+        Err(e) => {
+            let _ = granted.send(Err(e));
+            return;
+        }
+    };
+    let _ = granted.send(Ok(()));
+    let _ = release.recv();
+    let _ = conn.call_method(Some(SERVICE), PATH, Some(SERVICE), "UnInhibit", &(cookie,));
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn hold(_reason: &str, granted: Sender<Result<(), String>>, _release: Receiver<()>) {
+    let _ = granted.send(Err("Preventing sleep is not supported on this platform".into()));
+}
+
+fn release(inhibition: Inhibition) {
+    let _ = inhibition.release.send(());
+    let _ = inhibition.thread.join();
+    eprintln!("[power] Sleep allowed again ({})", inhibition.reason);
+}
+
+fn inhibit(reason: String) -> Result<(), String> {
+    let mut current = INHIBITION.lock().unwrap();
+    if current.as_ref().is_some_and(|held| held.reason == reason) {
+        return Ok(());
+    }
+    if let Some(previous) = current.take() {
+        release(previous);
+    }
+
+    let (granted_tx, granted_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel();
+    let thread_reason = reason.clone();
+    let thread = std::thread::spawn(move || hold(&thread_reason, granted_tx, release_rx));
+    let inhibition = Inhibition { reason, release: release_tx, thread };
+    match granted_rx.recv_timeout(GRANT_TIMEOUT) {
+        Ok(Ok(())) => {
+            eprintln!("[power] Preventing sleep: {}", inhibition.reason);
+            *current = Some(inhibition);
+            Ok(())
+        }
+        Ok(Err(e)) => Err(e),
+        Err(_) => {
+            // Don't join: the thread may still be stuck asking
+            let _ = inhibition.release.send(());
+            Err("Timed out asking the system to stay awake".into())
+        }
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Keep the machine awake until `release_sleep`, e.g. while reading aloud.
+/// A new reason replaces the current one.
+#[tauri::command]
+pub async fn inhibit_sleep(reason: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || inhibit(reason))
+        .await
+        .map_err(|e| format!("Sleep inhibition failed: {e}"))?
+}
+
+/// Let the machine sleep again. Returns whether sleep was being prevented.
+#[tauri::command]
+pub async fn release_sleep() -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let held = INHIBITION.lock().unwrap().take();
+        held.map(release).is_some()
+    })
+    .await
+    .map_err(|e| format!("Sleep release failed: {e}"))
+}
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use url::{Host, Url};
+
+// Private-network guard for requests made on behalf of feed content
+// (`fetch_url`, `http_request`, SSE, WebSockets). Those URLs can come from anywhere, so
+// loopback, private and link-local targets are refused unless the user
+// allowlists the host, e.g. a FreshRSS instance on the LAN. Names are checked
+// when they are resolved, so a public name pointing at 127.0.0.1 is caught as
+// well; IP literals, which are never resolved, are checked per URL and per
+// redirect. Behind a proxy the proxy resolves names, so only literals are checked.
+
+const ALLOWLIST_FILE: &str = "private_hosts.json";
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct PrivateHostAllowlist {
+    /// Host names or IP addresses that may be private.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+}
+
+static ALLOWLIST: OnceLock<Mutex<PrivateHostAllowlist>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn allowlist_lock() -> &'static Mutex<PrivateHostAllowlist> {
+    ALLOWLIST.get_or_init(|| Mutex::new(PrivateHostAllowlist::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(ALLOWLIST_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(allowlist) = serde_json::from_str::<PrivateHostAllowlist>(&json) {
+            eprintln!("[private_hosts] {} hosts allowed on private networks", allowlist.hosts.len());
+            *allowlist_lock().lock().unwrap() = allowlist;
+        }
+    }
+}
+
+fn save_allowlist(allowlist: &PrivateHostAllowlist) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json =
+        serde_json::to_string_pretty(allowlist).map_err(|e| format!("Failed to serialize private hosts: {e}"))?;
+    std::fs::write(dir.join(ALLOWLIST_FILE), json).map_err(|e| format!("Failed to save private hosts: {e}"))
+}
+
+/// Lowercased, without the brackets of an IPv6 literal.
+fn normalize(host: &str) -> String {
+    host.trim().trim_start_matches('[').trim_end_matches(']').to_ascii_lowercase()
+}
+
+// ── Checks ───────────────────────────────────────────────────────────
+
+/// Loopback, private, link-local, carrier-grade NAT, multicast and unspecified
+/// addresses, including IPv4 ones mapped into IPv6.
+pub fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                // 0.0.0.0/8 and 100.64.0.0/10
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64)
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_private(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    // fc00::/7 unique local, fe80::/10 link-local, fec0::/10 site-local
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+                    || (first & 0xffc0) == 0xfec0
+            }
+        },
+    }
+}
+
+/// Whether `host` may be reached on a private address: allowlisted, or the proxy itself.
+pub fn is_allowed(host: &str) -> bool {
+    let host = normalize(host);
+    let proxy = crate::proxy::address().and_then(|a| a.rsplit_once(':').map(|(h, _)| normalize(h)));
+    proxy.as_deref() == Some(host.as_str()) || allowlist_lock().lock().unwrap().hosts.contains(&host)
+}
+
+/// Refuse URLs whose host is a private IP literal. Names are left to `GuardedResolver`.
+pub fn check_url(url: &Url) -> Result<(), String> {
+    let ip: IpAddr = match url.host() {
+        Some(Host::Ipv4(ip)) => ip.into(),
+        Some(Host::Ipv6(ip)) => ip.into(),
+        _ => return Ok(()),
+    };
+    if is_private(ip) && !is_allowed(&ip.to_string()) {
+        return Err(format!("Blocked request to private address {ip}; allow the host to reach it"));
+    }
+    Ok(())
+}
+
+/// Resolve `host` with the system resolver, failing if it has a private
+/// address unless allowed. Any private address fails the whole lookup, so a
+/// name can't mix in one.
+pub async fn lookup(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve {host}: {e}"))?
+        .collect();
+    if !is_allowed(host) {
+        if let Some(addr) = addrs.iter().find(|a| is_private(a.ip())) {
+            let ip = addr.ip();
+            eprintln!("[private_hosts] Blocked {host} ({ip})");
+            return Err(format!("Blocked request to {host}: it resolves to private address {ip}"));
+        }
+    }
+    Ok(addrs)
+}
+
+/// `lookup` as the shared HTTP client's resolver.
+pub struct GuardedResolver;
+
+impl reqwest::dns::Resolve for GuardedResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs = lookup(&host, 0).await?;
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_private_host_allowlist() -> PrivateHostAllowlist {
+    allowlist_lock().lock().unwrap().clone()
+}
+
+/// Allow or disallow fetches from `host` to private addresses.
+#[tauri::command]
+pub fn set_private_host_allowed(host: String, allowed: bool) -> Result<(), String> {
+    let host = normalize(&host);
+    if host.is_empty() {
+        return Err("Host is required".into());
+    }
+    let mut allowlist = allowlist_lock().lock().unwrap();
+    allowlist.hosts.retain(|h| *h != host);
+    if allowed {
+        eprintln!("[private_hosts] Private addresses allowed for {host}");
+        allowlist.hosts.push(host);
+        allowlist.hosts.sort();
+    }
+    save_allowlist(&allowlist)
+}
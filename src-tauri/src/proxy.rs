@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+// Proxy used by the shared HTTP client: system, direct, manual HTTP(S) or SOCKS5.
+// Proxy passwords live in the OS keyring; proxy.json only records the mode,
+// addresses and username.
+
+const CONFIG_FILE: &str = "proxy.json";
+const KEYRING_SERVICE: &str = "SuperFlux proxy";
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum ProxyConfig {
+    /// Proxy from the environment (`HTTP_PROXY`, `HTTPS_PROXY`, `NO_PROXY`) and,
+    /// on Windows, the system proxy settings.
+    #[default]
+    System,
+    /// Never use a proxy.
+    Direct,
+    Manual {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        http_url: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        https_url: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        username: Option<String>,
+        /// Only accepted by `set_proxy`; never saved to disk or returned.
+        #[serde(default, skip_serializing)]
+        password: Option<String>,
+        /// Comma-separated hosts to reach directly, e.g. `localhost,.corp.example`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        no_proxy: Option<String>,
+    },
+    /// All traffic through a SOCKS5 proxy, e.g. Tor at `127.0.0.1:9050`.
+    Socks5 {
+        /// `host:port`, with or without a `socks5://` prefix.
+        address: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        username: Option<String>,
+        /// Only accepted by `set_proxy`; never saved to disk or returned.
+        #[serde(default, skip_serializing)]
+        password: Option<String>,
+        /// Resolve host names through the proxy (`socks5h`) so lookups don't leak.
+        #[serde(default = "default_remote_dns")]
+        remote_dns: bool,
+    },
+}
+
+fn default_remote_dns() -> bool {
+    true
+}
+
+impl ProxyConfig {
+    /// Username and password slots of the modes that authenticate.
+    fn credentials_mut(&mut self) -> Option<(&mut Option<String>, &mut Option<String>)> {
+        match self {
+            ProxyConfig::Manual { username, password, .. } | ProxyConfig::Socks5 { username, password, .. } => {
+                Some((username, password))
+            }
+            _ => None,
+        }
+    }
+
+    fn username(&self) -> Option<&str> {
+        match self {
+            ProxyConfig::Manual { username, .. } | ProxyConfig::Socks5 { username, .. } => username.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+static CONFIG: OnceLock<Mutex<ProxyConfig>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn config_lock() -> &'static Mutex<ProxyConfig> {
+    CONFIG.get_or_init(|| Mutex::new(ProxyConfig::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(CONFIG_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        match serde_json::from_str::<ProxyConfig>(&json) {
+            Ok(config) => {
+                eprintln!("[proxy] Loaded proxy settings: {}", mode_name(&config));
+                *config_lock().lock().unwrap() = config;
+            }
+            Err(e) => eprintln!("[proxy] Failed to parse {CONFIG_FILE}: {e}"),
+        }
+    }
+}
+
+fn save_config(config: &ProxyConfig) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json =
+        serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize proxy settings: {e}"))?;
+    std::fs::write(dir.join(CONFIG_FILE), json).map_err(|e| format!("Failed to save proxy settings: {e}"))
+}
+
+fn mode_name(config: &ProxyConfig) -> &'static str {
+    match config {
+        ProxyConfig::System => "system",
+        ProxyConfig::Direct => "direct",
+        ProxyConfig::Manual { .. } => "manual",
+        ProxyConfig::Socks5 { .. } => "socks5",
+    }
+}
+
+fn keyring_entry(username: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, username).map_err(|e| format!("Keyring error: {e}"))
+}
+
+fn stored_password(username: &str) -> Option<String> {
+    match keyring_entry(username).and_then(|e| e.get_password().map_err(|e| format!("Keyring error: {e}"))) {
+        Ok(p) => Some(p),
+        Err(e) => {
+            eprintln!("[proxy] No password for proxy user {username}: {e}");
+            None
+        }
+    }
+}
+
+// ── Client configuration ─────────────────────────────────────────────
+
+fn invalid_url(url: &str, e: reqwest::Error) -> String {
+    format!("Invalid proxy URL '{url}': {e}")
+}
+
+/// `socks5://` or `socks5h://` URL for an address given with or without a scheme.
+fn socks_url(address: &str, remote_dns: bool) -> String {
+    let address = address.trim();
+    let host = address.split_once("://").map_or(address, |(_, rest)| rest).trim_end_matches('/');
+    let scheme = if remote_dns { "socks5h" } else { "socks5" };
+    format!("{scheme}://{host}")
+}
+
+/// Apply the current proxy settings to a client builder.
+pub fn apply(builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder, String> {
+    let config = config_lock().lock().unwrap().clone();
+    match config {
+        ProxyConfig::System => Ok(builder),
+        ProxyConfig::Direct => Ok(builder.no_proxy()),
+        ProxyConfig::Manual { http_url, https_url, username, no_proxy, .. } => {
+            let password = username.as_deref().and_then(stored_password);
+            let no_proxy = no_proxy.as_deref().and_then(reqwest::NoProxy::from_string);
+            let configure = |proxy: reqwest::Proxy| {
+                let proxy = match (&username, &password) {
+                    (Some(user), Some(pass)) => proxy.basic_auth(user, pass),
+                    _ => proxy,
+                };
+                proxy.no_proxy(no_proxy.clone())
+            };
+            // Manual mode replaces the system proxy even for a scheme left blank
+            let mut builder = builder.no_proxy();
+            if let Some(url) = http_url.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+                let proxy = reqwest::Proxy::http(url).map_err(|e| invalid_url(url, e))?;
+                builder = builder.proxy(configure(proxy));
+            }
+            if let Some(url) = https_url.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+                let proxy = reqwest::Proxy::https(url).map_err(|e| invalid_url(url, e))?;
+                builder = builder.proxy(configure(proxy));
+            }
+            Ok(builder)
+        }
+        ProxyConfig::Socks5 { address, username, remote_dns, .. } => {
+            let url = socks_url(&address, remote_dns);
+            let mut proxy = reqwest::Proxy::all(&url).map_err(|e| invalid_url(&url, e))?;
+            if let Some(user) = username.as_deref() {
+                proxy = proxy.basic_auth(user, &stored_password(user).unwrap_or_default());
+            }
+            Ok(builder.no_proxy().proxy(proxy))
+        }
+    }
+}
+
+/// Whether the user set a manual or SOCKS5 proxy, which connections made
+/// outside the shared client must not bypass.
+pub fn is_explicit() -> bool {
+    matches!(&*config_lock().lock().unwrap(), ProxyConfig::Manual { .. } | ProxyConfig::Socks5 { .. })
+}
+
+/// `host:port` of the proxy every request goes through, if any, for
+/// reachability checks that shouldn't assume a direct route.
+pub fn address() -> Option<String> {
+    let from_url = |url: &str| {
+        let url = url::Url::parse(url.trim()).ok()?;
+        Some(format!("{}:{}", url.host_str()?, url.port_or_known_default().unwrap_or(1080)))
+    };
+    match &*config_lock().lock().unwrap() {
+        ProxyConfig::System => ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+            .iter()
+            .find_map(|k| std::env::var(k).ok().filter(|v| !v.trim().is_empty()))
+            .and_then(|v| from_url(&v)),
+        ProxyConfig::Direct => None,
+        ProxyConfig::Manual { http_url, https_url, .. } => {
+            https_url.as_deref().or(http_url.as_deref()).filter(|u| !u.trim().is_empty()).and_then(from_url)
+        }
+        ProxyConfig::Socks5 { address, remote_dns, .. } => from_url(&socks_url(address, *remote_dns)),
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_proxy() -> ProxyConfig {
+    config_lock().lock().unwrap().clone()
+}
+
+/// Save proxy settings and rebuild the shared HTTP client with them.
+#[tauri::command]
+pub fn set_proxy(mut config: ProxyConfig) -> Result<(), String> {
+    match &config {
+        ProxyConfig::Manual { http_url, https_url, .. } => {
+            let blank = |u: &Option<String>| u.as_deref().is_none_or(|u| u.trim().is_empty());
+            if blank(http_url) && blank(https_url) {
+                return Err("Manual proxy needs an HTTP or HTTPS proxy URL".into());
+            }
+            validate_url(http_url)?;
+            validate_url(https_url)?;
+        }
+        ProxyConfig::Socks5 { address, remote_dns, .. } => {
+            if address.trim().is_empty() {
+                return Err("SOCKS5 proxy needs an address".into());
+            }
+            validate_url(&Some(socks_url(address, *remote_dns)))?;
+        }
+        ProxyConfig::System | ProxyConfig::Direct => {}
+    }
+    if let Some((username, password)) = config.credentials_mut() {
+        if username.as_deref().is_some_and(|u| u.trim().is_empty()) {
+            *username = None;
+        }
+        match (username.as_deref(), password.take()) {
+            (Some(user), Some(pass)) => keyring_entry(user)?
+                .set_password(&pass)
+                .map_err(|e| format!("Failed to store proxy password in the keyring: {e}"))?,
+            (Some(user), None) if stored_password(user).is_none() => {
+                return Err("A password is required for the proxy user".into());
+            }
+            _ => {}
+        }
+    }
+
+    let previous = config_lock().lock().unwrap().clone();
+    if let Some(old_user) = previous.username().filter(|u| config.username() != Some(*u)) {
+        if let Ok(entry) = keyring_entry(old_user) {
+            let _ = entry.delete_credential();
+        }
+    }
+    save_config(&config)?;
+    eprintln!("[proxy] Proxy mode set to {}", mode_name(&config));
+    *config_lock().lock().unwrap() = config;
+    crate::reset_client();
+    Ok(())
+}
+
+fn validate_url(url: &Option<String>) -> Result<(), String> {
+    match url.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+        Some(url) => reqwest::Proxy::all(url).map(|_| ()).map_err(|e| invalid_url(url, e)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// Per-host politeness throttle for outgoing GETs. Requests to the same host
+// are spaced by a minimum interval (queued in arrival order), and a host that
+// answers 429/503 with `Retry-After` is left alone until that time has passed.
+
+const CONFIG_FILE: &str = "rate_limits.json";
+/// Never wait longer than this for a single slot, whatever the server asks.
+const MAX_WAIT: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RateLimitConfig {
+    /// Minimum gap between two requests to the same host.
+    pub default_interval_ms: u64,
+    /// Per-host gaps, e.g. `{"reddit.com": 2000}`. Subdomains inherit.
+    #[serde(default)]
+    pub hosts: HashMap<String, u64>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            default_interval_ms: 500,
+            hosts: HashMap::from([
+                ("reddit.com".into(), 2000),
+                ("youtube.com".into(), 1000),
+                // arXiv asks API clients for 3 s between calls; NCBI allows 3/s without a key
+                ("arxiv.org".into(), 3000),
+                ("ncbi.nlm.nih.gov".into(), 350),
+            ]),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    fn interval_for(&self, host: &str) -> Duration {
+        let mut domain = host;
+        loop {
+            if let Some(ms) = self.hosts.get(domain) {
+                return Duration::from_millis(*ms);
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => domain = parent,
+                _ => return Duration::from_millis(self.default_interval_ms),
+            }
+        }
+    }
+}
+
+static CONFIG: OnceLock<Mutex<RateLimitConfig>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Earliest time the next request to each host may start.
+static NEXT_SLOT: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+fn config_lock() -> &'static Mutex<RateLimitConfig> {
+    CONFIG.get_or_init(|| Mutex::new(RateLimitConfig::default()))
+}
+
+fn slots_lock() -> &'static Mutex<HashMap<String, Instant>> {
+    NEXT_SLOT.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(CONFIG_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(config) = serde_json::from_str::<RateLimitConfig>(&json) {
+            *config_lock().lock().unwrap() = config;
+        }
+    }
+}
+
+fn host_key(host: &str) -> String {
+    let host = host.to_ascii_lowercase();
+    host.strip_prefix("www.").map(String::from).unwrap_or(host)
+}
+
+/// Wait for this host's next free slot and reserve it.
+pub async fn acquire(host: &str) {
+    let key = host_key(host);
+    let interval = config_lock().lock().unwrap().interval_for(&key);
+    let now = Instant::now();
+    let start = {
+        let mut slots = slots_lock().lock().unwrap();
+        let start = slots.get(&key).copied().filter(|s| *s > now).unwrap_or(now).min(now + MAX_WAIT);
+        slots.insert(key.clone(), start + interval);
+        start
+    };
+    if start > now {
+        let wait = start - now;
+        if wait >= Duration::from_secs(1) {
+            eprintln!("[rate_limit] Waiting {wait:?} before contacting {key}");
+        }
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Push the host's next slot back after a 429/503 with `Retry-After`.
+pub fn defer(host: &str, retry_after: Duration) {
+    let key = host_key(host);
+    let until = Instant::now() + retry_after.min(MAX_WAIT);
+    let mut slots = slots_lock().lock().unwrap();
+    let slot = slots.entry(key.clone()).or_insert(until);
+    if *slot < until {
+        *slot = until;
+    }
+    eprintln!("[rate_limit] {key} asked to back off for {retry_after:?}");
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_rate_limits() -> RateLimitConfig {
+    config_lock().lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_rate_limits(mut config: RateLimitConfig) -> Result<(), String> {
+    config.hosts = config
+        .hosts
+        .into_iter()
+        .map(|(host, ms)| (host_key(host.trim()), ms))
+        .filter(|(host, _)| !host.is_empty())
+        .collect();
+    if let Some(dir) = DATA_DIR.get() {
+        let json =
+            serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize rate limits: {e}"))?;
+        std::fs::write(dir.join(CONFIG_FILE), json).map_err(|e| format!("Failed to save rate limits: {e}"))?;
+    }
+    *config_lock().lock().unwrap() = config;
+    Ok(())
+}
@@ -0,0 +1,523 @@
+use ego_tree::{NodeId, NodeRef};
+use regex::Regex;
+use scraper::node::Element;
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use url::Url;
+
+use crate::content::{normalize_images, process_article_html, DEFAULT_IMAGE_WIDTH};
+use crate::text::{snippet_from_html, SNIPPET_LEN};
+
+// Full article text from a web page, after Mozilla's Readability: paragraphs
+// score their ancestors by length and commas, the best-scoring container (with
+// related siblings) is taken as the article, then stripped of the boilerplate
+// inside it. Metadata comes from JSON-LD and meta tags before the page itself.
+
+/// Elements never part of an article.
+const REMOVED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "link", "meta", "form", "input", "button", "select", "textarea",
+    "nav", "aside", "footer", "dialog", "object", "embed", "canvas",
+];
+/// Block elements that keep a `div` from counting as a paragraph.
+const BLOCK_CHILDREN: &[&str] = &["blockquote", "dl", "div", "img", "ol", "p", "pre", "table", "ul", "figure"];
+/// Containers dropped from the result when they look like boilerplate.
+const CONDITIONAL_ELEMENTS: &[&str] = &["div", "section", "ul", "ol", "table", "header"];
+/// Attributes kept on the result; styling and scripting ones are dropped.
+const KEPT_ATTRIBUTES: &[&str] = &[
+    "href", "src", "srcset", "sizes", "alt", "title", "width", "height", "colspan", "rowspan", "datetime",
+    "cite", "lang", "dir", "start", "type", "controls", "poster", "allowfullscreen",
+];
+/// Embeds worth keeping; other iframes are ads and widgets.
+const EMBED_HOSTS: &[&str] =
+    &["youtube.com", "youtube-nocookie.com", "player.vimeo.com", "dailymotion.com", "twitter.com", "x.com"];
+/// Text shorter than this doesn't score.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+const TITLE_SEPARATORS: &[&str] = &[" | ", " - ", " – ", " — ", " :: ", " » ", " / "];
+
+#[derive(Clone, Serialize, Debug, Default)]
+pub struct ExtractedArticle {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byline: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lead_image: Option<String>,
+    /// Cleaned article HTML, URLs absolute when the page URL is known.
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    /// ms since epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub word_count: usize,
+}
+
+/// Class or id of page furniture: comments, share bars, sidebars, ...
+fn unlikely_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(concat!(
+            r"(?i)-ad-|ai2html|banner|breadcrumbs|combx|comment|community|cover-wrap|disqus|extra|footer|gdpr|",
+            r"header|legends|menu|related|remark|replies|rss|shoutbox|sidebar|skyscraper|social|sponsor|",
+            r"supplemental|ad-break|agegate|pagination|pager|popup|yom-remote|newsletter|subscribe|share",
+        ))
+        .unwrap()
+    })
+}
+
+/// Overrides `unlikely_re` for wrappers like `main-content` or `article-header`.
+fn maybe_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)and|article|body|column|content|main|shadow").unwrap())
+}
+
+fn positive_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)article|body|content|entry|hentry|h-entry|main|page|post|text|blog|story").unwrap()
+    })
+}
+
+fn negative_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(concat!(
+            r"(?i)-ad-|hidden|^hid$|\bhid\b|banner|combx|comment|com-|contact|foot|footer|footnote|gdpr|",
+            r"masthead|media|meta|outbrain|promo|related|scroll|share|shoutbox|sidebar|skyscraper|sponsor|",
+            r"shopping|tags|tool|widget",
+        ))
+        .unwrap()
+    })
+}
+
+// ── Text measures ────────────────────────────────────────────────────
+
+fn text_of(el: ElementRef) -> String {
+    el.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn class_and_id(el: &Element) -> String {
+    format!("{} {}", el.attr("class").unwrap_or(""), el.attr("id").unwrap_or(""))
+}
+
+/// -25 for a boilerplate-sounding class or id, +25 for an article-sounding one.
+fn class_weight(el: &Element) -> f64 {
+    let mut weight = 0.0;
+    for value in [el.attr("class"), el.attr("id")].into_iter().flatten() {
+        if negative_re().is_match(value) {
+            weight -= 25.0;
+        }
+        if positive_re().is_match(value) {
+            weight += 25.0;
+        }
+    }
+    weight
+}
+
+/// Share of the text that sits in links; in-page links count for less.
+fn link_density(el: ElementRef, text_len: usize) -> f64 {
+    if text_len == 0 {
+        return 0.0;
+    }
+    let links = Selector::parse("a").unwrap();
+    let linked: f64 = el
+        .select(&links)
+        .map(|a| {
+            let weight = if a.value().attr("href").is_some_and(|h| h.starts_with('#')) { 0.3 } else { 1.0 };
+            text_of(a).chars().count() as f64 * weight
+        })
+        .sum();
+    linked / text_len as f64
+}
+
+fn initial_score(el: &Element) -> f64 {
+    let base = match el.name() {
+        "div" | "article" | "main" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    base + class_weight(el)
+}
+
+fn is_element(node: &NodeRef<Node>, name: &str) -> bool {
+    matches!(node.value(), Node::Element(el) if el.name() == name)
+}
+
+// ── Metadata ─────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct Metadata {
+    title: Option<String>,
+    byline: Option<String>,
+    image: Option<String>,
+    excerpt: Option<String>,
+    site_name: Option<String>,
+    published: Option<String>,
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!value.is_empty()).then_some(value)
+}
+
+/// First string in a JSON-LD value that may be a string, an object with
+/// `key`, or a list of either.
+fn ld_string(value: &Value, key: &str) -> Option<String> {
+    match value {
+        Value::String(s) => non_empty(s),
+        Value::Object(o) => o.get(key).and_then(|v| ld_string(v, key)),
+        Value::Array(items) => items.iter().find_map(|v| ld_string(v, key)),
+        _ => None,
+    }
+}
+
+/// The Article object of the page's JSON-LD, looking inside `@graph` lists.
+fn ld_article(doc: &Html) -> Option<serde_json::Map<String, Value>> {
+    let selector = Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+    let mut pending: Vec<Value> = doc
+        .select(&selector)
+        .filter_map(|s| serde_json::from_str(&s.text().collect::<String>()).ok())
+        .collect();
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::Array(items) => pending.extend(items),
+            Value::Object(mut object) => {
+                if let Some(graph) = object.remove("@graph") {
+                    pending.push(graph);
+                }
+                let types = object.get("@type").map(|t| t.to_string()).unwrap_or_default();
+                if types.contains("Article") || types.contains("BlogPosting") || types.contains("Report") {
+                    return Some(object);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn metadata(doc: &Html) -> Metadata {
+    let meta = |names: &[&str]| {
+        names.iter().find_map(|name| {
+            let selector = Selector::parse(&format!(r#"meta[property="{name}"], meta[name="{name}"]"#)).ok()?;
+            doc.select(&selector).find_map(|m| m.value().attr("content").and_then(non_empty))
+        })
+    };
+    let ld = ld_article(doc).unwrap_or_default();
+    let ld_field = |key: &str, inner: &str| ld.get(key).and_then(|v| ld_string(v, inner));
+
+    Metadata {
+        title: ld_field("headline", "").or_else(|| meta(&["og:title", "twitter:title"])).or_else(|| {
+            let selector = Selector::parse("title").unwrap();
+            doc.select(&selector).next().and_then(|t| non_empty(&t.text().collect::<String>())).map(clean_title)
+        }),
+        // Author URLs (article:author on some sites) aren't names
+        byline: ld_field("author", "name")
+            .or_else(|| meta(&["author", "article:author", "byl", "parsely-author", "dc.creator"]))
+            .filter(|b| !b.starts_with("http")),
+        image: ld_field("image", "url").or_else(|| meta(&["og:image", "og:image:url", "twitter:image"])),
+        excerpt: meta(&["og:description", "description", "twitter:description"]),
+        site_name: meta(&["og:site_name", "application-name"]).or_else(|| ld_field("publisher", "name")),
+        published: ld_field("datePublished", "")
+            .or_else(|| meta(&["article:published_time", "date", "dc.date", "parsely-pub-date"])),
+    }
+}
+
+/// "Story title | Site name" gives "Story title", unless that leaves too little.
+fn clean_title(title: String) -> String {
+    for separator in TITLE_SEPARATORS {
+        if let Some((head, _)) = title.rsplit_once(separator) {
+            if head.split_whitespace().count() >= 3 {
+                return head.trim().to_string();
+            }
+        }
+    }
+    title
+}
+
+/// A byline shown in the page, for when no meta tag names the author.
+fn byline_in_page(doc: &Html) -> Option<String> {
+    let selector = Selector::parse(
+        r#"[rel="author"], [itemprop="author"], .byline, .author, .post-author, .entry-author"#,
+    )
+    .unwrap();
+    doc.select(&selector).map(text_of).find(|t| !t.is_empty() && t.chars().count() < 100)
+}
+
+// ── Content ──────────────────────────────────────────────────────────
+
+/// Drop scripts, navigation and elements whose class or id marks them as page furniture.
+fn strip_unlikely(doc: &mut Html) {
+    let doomed: Vec<NodeId> = doc
+        .tree
+        .nodes()
+        .filter(|node| {
+            let Node::Element(el) = node.value() else { return false };
+            if REMOVED_ELEMENTS.contains(&el.name()) || el.attr("hidden").is_some() {
+                return true;
+            }
+            if matches!(el.name(), "html" | "body" | "article" | "main" | "a") {
+                return false;
+            }
+            let names = class_and_id(el);
+            let hidden = el.attr("aria-hidden") == Some("true")
+                || el.attr("style").is_some_and(|s| s.replace(' ', "").contains("display:none"));
+            hidden || (unlikely_re().is_match(&names) && !maybe_re().is_match(&names))
+        })
+        .map(|n| n.id())
+        .collect();
+    for id in doomed {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+}
+
+/// A `div` holding only inline content is a paragraph in all but name.
+fn is_paragraph(el: ElementRef) -> bool {
+    match el.value().name() {
+        "p" | "pre" | "td" | "section" | "h2" | "h3" | "h4" | "h5" | "h6" => true,
+        "div" => !el.descendent_elements().skip(1).any(|d| BLOCK_CHILDREN.contains(&d.value().name())),
+        _ => false,
+    }
+}
+
+/// Score every paragraph's ancestors and pick the best one, with its score.
+fn top_candidate(doc: &Html) -> Option<(NodeId, HashMap<NodeId, f64>)> {
+    let mut scores: HashMap<NodeId, f64> = HashMap::new();
+    for el in doc.root_element().descendent_elements().filter(|el| is_paragraph(*el)) {
+        let text = text_of(el);
+        let len = text.chars().count();
+        if len < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score =
+            1.0 + text.matches([',', '，', '、']).count() as f64 + (len as f64 / 100.0).floor().min(3.0);
+        let ancestors = el.ancestors().filter(|a| matches!(a.value(), Node::Element(_))).take(5);
+        for (level, ancestor) in ancestors.enumerate() {
+            let Node::Element(ancestor_el) = ancestor.value() else { continue };
+            let divider = match level {
+                0 => 1.0,
+                1 => 2.0,
+                n => n as f64 * 3.0,
+            };
+            *scores.entry(ancestor.id()).or_insert_with(|| initial_score(ancestor_el)) += score / divider;
+        }
+    }
+    // Containers that are mostly links are menus, however long
+    for (id, score) in scores.iter_mut() {
+        if let Some(el) = doc.tree.get(*id).and_then(ElementRef::wrap) {
+            *score *= 1.0 - link_density(el, text_of(el).chars().count());
+        }
+    }
+    let (mut top, _) = scores.iter().max_by(|a, b| a.1.total_cmp(b.1)).map(|(id, s)| (*id, *s))?;
+    // A lone child is no better than its parent, which may also hold the title or images
+    while let Some(parent) = doc.tree.get(top).and_then(|n| n.parent()) {
+        let siblings = parent.children().filter(|c| matches!(c.value(), Node::Element(_))).count();
+        if siblings != 1 || is_element(&parent, "body") || is_element(&parent, "html") {
+            break;
+        }
+        top = parent.id();
+    }
+    Some((top, scores))
+}
+
+/// The top candidate plus siblings that look like part of the same article.
+fn gather(doc: &Html, top: NodeId, scores: &HashMap<NodeId, f64>) -> String {
+    let top_node = doc.tree.get(top).unwrap();
+    let top_score = scores.get(&top).copied().unwrap_or(0.0);
+    let Some(parent) = top_node.parent().filter(|p| matches!(p.value(), Node::Element(_))) else {
+        return ElementRef::wrap(top_node).map(|el| el.inner_html()).unwrap_or_default();
+    };
+    let threshold = (top_score * 0.2).max(10.0);
+    let top_class = ElementRef::wrap(top_node).and_then(|el| el.value().attr("class")).unwrap_or("");
+
+    let mut html = String::new();
+    for sibling in parent.children().filter_map(ElementRef::wrap) {
+        let keep = if sibling.id() == top {
+            true
+        } else {
+            let same_class = !top_class.is_empty() && sibling.value().attr("class") == Some(top_class);
+            let bonus = if same_class { top_score * 0.2 } else { 0.0 };
+            let score = scores.get(&sibling.id()).copied();
+            if score.is_some_and(|s| s + bonus >= threshold) {
+                true
+            } else if sibling.value().name() == "p" {
+                let text = text_of(sibling);
+                let len = text.chars().count();
+                let density = link_density(sibling, len);
+                (len > 80 && density < 0.25)
+                    || (len > 0 && density == 0.0 && (text.ends_with('.') || text.contains(". ")))
+            } else {
+                false
+            }
+        };
+        if keep {
+            html.push_str(&sibling.html());
+        }
+    }
+    html
+}
+
+fn is_embed(el: &Element) -> bool {
+    let host =
+        el.attr("src").and_then(|s| Url::parse(s.trim()).ok()).and_then(|u| u.host_str().map(String::from));
+    host.is_some_and(|h| EMBED_HOSTS.iter().any(|e| h == *e || h.ends_with(&format!(".{e}"))))
+}
+
+/// Whether a container in the article is boilerplate: a negative class, more
+/// images than text, lists that aren't lists, or mostly links.
+fn is_boilerplate(el: ElementRef) -> bool {
+    let weight = class_weight(el.value());
+    if weight < 0.0 {
+        return true;
+    }
+    let text = text_of(el);
+    if text.matches(',').count() >= 10 {
+        return false;
+    }
+    let count = |name: &str| el.descendent_elements().filter(|d| d.value().name() == name).count();
+    let (paragraphs, images, items) = (count("p"), count("img"), count("li"));
+    let media = images + count("video") + count("iframe") + count("pre") + count("figure");
+    let len = text.chars().count();
+    let density = link_density(el, len);
+    let list = matches!(el.value().name(), "ul" | "ol");
+    let in_figure = el.ancestors().any(|a| is_element(&a, "figure"));
+
+    (images > 1 && (paragraphs as f64) < images as f64 * 0.5 && !in_figure)
+        || (!list && items > paragraphs + 10)
+        || (weight < 25.0 && density > 0.2 && !list)
+        || (weight >= 25.0 && density > 0.5)
+        || (list && density > 0.5)
+        || (len < MIN_PARAGRAPH_CHARS && media == 0)
+}
+
+/// Strip what's left of the page around the article text.
+fn clean(html: &str, title: &str) -> String {
+    let mut fragment = Html::parse_fragment(html);
+    let mut doomed: HashSet<NodeId> = HashSet::new();
+    for el in fragment.root_element().descendent_elements().skip(1) {
+        if el.ancestors().any(|a| doomed.contains(&a.id())) {
+            continue;
+        }
+        let name = el.value().name();
+        let remove = match name {
+            "iframe" => !is_embed(el.value()),
+            // The page's own heading repeats the title shown above the article
+            "h1" => text_of(el) == title,
+            "p" => text_of(el).is_empty() && el.descendent_elements().nth(1).is_none(),
+            _ if REMOVED_ELEMENTS.contains(&name) => true,
+            _ if CONDITIONAL_ELEMENTS.contains(&name) => is_boilerplate(el),
+            _ => false,
+        };
+        if remove {
+            doomed.insert(el.id());
+        }
+    }
+    for id in &doomed {
+        if let Some(mut node) = fragment.tree.get_mut(*id) {
+            node.detach();
+        }
+    }
+    let ids: Vec<NodeId> =
+        fragment.tree.nodes().filter(|n| matches!(n.value(), Node::Element(_))).map(|n| n.id()).collect();
+    for id in ids {
+        let Some(mut node) = fragment.tree.get_mut(id) else { continue };
+        let Node::Element(el) = node.value() else { continue };
+        el.attrs.retain(|(name, value)| {
+            KEPT_ATTRIBUTES.contains(&&*name.local)
+                && !value.trim_start().to_lowercase().starts_with("javascript:")
+        });
+    }
+    fragment.root_element().inner_html()
+}
+
+/// Extract the article from a page's HTML. `page_url` resolves relative URLs.
+pub fn extract(html: &str, page_url: Option<&str>) -> Result<ExtractedArticle, String> {
+    let mut doc = Html::parse_document(html);
+    let meta = metadata(&doc);
+    let lang = doc.root_element().value().attr("lang").and_then(non_empty);
+    let byline_text = meta.byline.clone().or_else(|| byline_in_page(&doc));
+
+    strip_unlikely(&mut doc);
+    let (top, scores) = top_candidate(&doc).ok_or("No article text found on the page")?;
+    let raw = gather(&doc, top, &scores);
+
+    let title = meta.title.clone().unwrap_or_else(|| {
+        let h1 = Selector::parse("h1").unwrap();
+        doc.select(&h1).map(text_of).find(|t| !t.is_empty()).unwrap_or_default()
+    });
+    let cleaned = clean(&normalize_images(&raw, DEFAULT_IMAGE_WIDTH), &title);
+    let content = match page_url {
+        Some(url) => process_article_html(&cleaned, url),
+        None => cleaned,
+    };
+    let text = crate::text::html_to_text(&content);
+    if text.chars().count() < MIN_PARAGRAPH_CHARS {
+        return Err("No article text found on the page".into());
+    }
+
+    let absolute = |src: String| match page_url.and_then(|u| Url::parse(u).ok()) {
+        Some(base) => base.join(&src).map(|u| u.to_string()).unwrap_or(src),
+        None => src,
+    };
+    let first_image = || {
+        let img = Selector::parse("img[src]").unwrap();
+        let fragment = Html::parse_fragment(&content);
+        let src = fragment.select(&img).find_map(|i| i.value().attr("src").map(String::from));
+        src.map(absolute)
+    };
+    let byline = byline_text.map(|b| crate::authors::parse_byline(&b).join(", ")).filter(|b| !b.is_empty());
+    Ok(ExtractedArticle {
+        title,
+        byline,
+        lead_image: meta.image.map(absolute).or_else(first_image),
+        excerpt: meta.excerpt.or_else(|| non_empty(&snippet_from_html(&content, SNIPPET_LEN))),
+        site_name: meta.site_name,
+        published_at: meta
+            .published
+            .and_then(|p| crate::dates::parse_date(&p, None))
+            .map(|d| d.timestamp_millis()),
+        lang,
+        url: page_url.map(String::from),
+        word_count: text.split_whitespace().count(),
+        content,
+    })
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Readable content of a page: fetched when given an http(s) URL, otherwise
+/// parsed from the HTML passed in (`base_url` then resolves its links).
+#[tauri::command]
+pub async fn extract_article(
+    url_or_html: String,
+    base_url: Option<String>,
+) -> Result<ExtractedArticle, String> {
+    let input = url_or_html.trim();
+    let is_url =
+        (input.starts_with("http://") || input.starts_with("https://")) && !input.contains(char::is_whitespace);
+    if !is_url {
+        let html = url_or_html;
+        return tauri::async_runtime::spawn_blocking(move || extract(&html, base_url.as_deref()))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    let page = crate::fetch_document(input).await?;
+    let started = std::time::Instant::now();
+    let article = tauri::async_runtime::spawn_blocking(move || extract(&page.body, Some(&page.url)))
+        .await
+        .map_err(|e| e.to_string())??;
+    eprintln!("[readability] Extracted {} words from {input} in {:?}", article.word_count, started.elapsed());
+    Ok(article)
+}
@@ -0,0 +1,276 @@
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::content::process_article_html;
+use crate::feed_parser::{Enclosure, ParsedFeed, ParsedItem};
+use crate::text::{snippet_from_html, SNIPPET_LEN};
+
+// Subreddits through Reddit's public JSON listings, for when the .rss
+// endpoints are refused. Posts come out as the items the RSS feed would give
+// (same ids, links and author format), so switching between the two doesn't
+// duplicate anything already stored.
+
+const SITE: &str = "https://www.reddit.com";
+const SORTS: &[&str] = &["hot", "new", "top", "rising", "controversial"];
+/// Periods for `top` and `controversial`.
+const PERIODS: &[&str] = &["hour", "day", "week", "month", "year", "all"];
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 100;
+/// Tries for a listing answered with 429.
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait after a 429 that doesn't say how long; doubled per attempt.
+const BASE_BACKOFF: Duration = Duration::from_secs(2);
+/// Longest 429 wait worth sitting through; past that the fetch fails.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct Listing {
+    data: ListingData,
+}
+
+#[derive(Deserialize)]
+struct ListingData {
+    children: Vec<Child>,
+}
+
+#[derive(Deserialize)]
+struct Child {
+    kind: String,
+    data: Post,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Post {
+    /// Fullname, e.g. `t3_abc123`; the RSS feed's entry id.
+    name: String,
+    title: String,
+    permalink: String,
+    /// Link target; the permalink itself for self posts.
+    url: String,
+    author: String,
+    selftext_html: Option<String>,
+    created_utc: f64,
+    thumbnail: String,
+    link_flair_text: Option<String>,
+    preview: Option<Preview>,
+    secure_media: Option<Media>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Preview {
+    images: Vec<PreviewImage>,
+}
+
+#[derive(Deserialize)]
+struct PreviewImage {
+    source: ImageSource,
+}
+
+#[derive(Deserialize)]
+struct ImageSource {
+    url: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Media {
+    reddit_video: Option<RedditVideo>,
+}
+
+#[derive(Deserialize)]
+struct RedditVideo {
+    fallback_url: String,
+    duration: Option<u64>,
+}
+
+// ── Listings ─────────────────────────────────────────────────────────
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `r/rust`, `/r/rust/` and `rust+golang` all give the bare name(s).
+fn subreddit_name(input: &str) -> Result<String, String> {
+    let name = input.trim().trim_matches('/');
+    let name = name.strip_prefix("r/").unwrap_or(name);
+    let valid = name.len() >= 2 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '+');
+    valid.then(|| name.to_string()).ok_or_else(|| format!("Invalid subreddit: {input}"))
+}
+
+/// The subreddit, sort and period of a Reddit RSS URL such as
+/// `https://www.reddit.com/r/rust/top/.rss?t=week`.
+pub fn rss_target(url: &str) -> Option<(String, String, Option<String>)> {
+    let url = url::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    if host != "reddit.com" && !host.ends_with(".reddit.com") {
+        return None;
+    }
+    let segments: Vec<&str> = url.path().split('/').filter(|s| !s.is_empty()).collect();
+    let (subreddit, rest) = match segments.as_slice() {
+        ["r", subreddit, rest @ ..] => (*subreddit, rest),
+        _ => return None,
+    };
+    let (subreddit, sort) = match rest {
+        [] => (subreddit.strip_suffix(".rss")?, "hot"),
+        [".rss"] => (subreddit, "hot"),
+        [sort, ".rss"] => (subreddit, *sort),
+        [sort] => (subreddit, sort.strip_suffix(".rss")?),
+        _ => return None,
+    };
+    let period = url.query_pairs().find(|(k, _)| k == "t").map(|(_, v)| v.into_owned());
+    Some((subreddit.to_string(), sort.to_string(), period))
+}
+
+fn post_item(post: Post) -> ParsedItem {
+    let permalink = format!("{SITE}{}", post.permalink);
+    let thumbnail = post
+        .preview
+        .as_ref()
+        .and_then(|p| p.images.first())
+        .map(|i| i.source.url.clone())
+        // Otherwise "self", "default", "nsfw" and the like
+        .or_else(|| post.thumbnail.starts_with("http").then(|| post.thumbnail.clone()));
+
+    // Laid out like the RSS entry: the post or a linked preview, then the links
+    let mut html = match (&post.selftext_html, &thumbnail) {
+        (Some(text), _) => text.clone(),
+        (None, Some(image)) => format!(
+            "<p><a href=\"{}\"><img src=\"{}\" alt=\"{}\"></a></p>",
+            escape_html(&post.url),
+            escape_html(image),
+            escape_html(&post.title)
+        ),
+        (None, None) => String::new(),
+    };
+    html.push_str(&format!(
+        "<p>submitted by <a href=\"{SITE}/user/{author}\">/u/{author}</a> \
+         <a href=\"{}\">[link]</a> <a href=\"{}\">[comments]</a></p>",
+        escape_html(&post.url),
+        escape_html(&permalink),
+        author = escape_html(&post.author),
+    ));
+    let content = process_article_html(&html, &permalink);
+
+    let enclosures = post
+        .secure_media
+        .and_then(|m| m.reddit_video)
+        .map(|video| Enclosure {
+            url: video.fallback_url,
+            mime_type: Some("video/mp4".into()),
+            length: None,
+            duration: video.duration,
+        })
+        .into_iter()
+        .collect();
+    ParsedItem {
+        id: post.name,
+        title: post.title,
+        url: permalink,
+        author: format!("/u/{}", post.author),
+        summary: String::new(),
+        snippet: snippet_from_html(&content, SNIPPET_LEN),
+        content: Some(content),
+        published_at: Some((post.created_utc * 1000.0) as i64),
+        updated_at: None,
+        categories: post.link_flair_text.into_iter().filter(|f| !f.is_empty()).collect(),
+        enclosures,
+        thumbnail,
+    }
+}
+
+/// GET a listing, sitting out 429s: Reddit says how long in `Retry-After` or
+/// `X-Ratelimit-Reset`, and the host is left alone (see `rate_limit`) until then.
+async fn get_listing(url: &str) -> Result<String, String> {
+    let mut headers = HeaderMap::new();
+    // Reddit asks API clients for a descriptive agent, and rejects browser-like ones here
+    headers.insert(USER_AGENT, HeaderValue::from_static(crate::RSS_USER_AGENT));
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    let mut attempt = 1;
+    loop {
+        let response = crate::send_get(url, headers.clone()).await?;
+        let status = response.status();
+        if status.is_success() {
+            return crate::read_body(url, response).await;
+        }
+        if status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(format!("Reddit answered HTTP {}", status.as_u16()));
+        }
+        let reset = response
+            .headers()
+            .get("x-ratelimit-reset")
+            .and_then(|v| v.to_str().ok()?.trim().parse::<f64>().ok())
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64);
+        let wait = crate::retry::retry_after(response.headers())
+            .or(reset)
+            .unwrap_or(BASE_BACKOFF * 2u32.pow(attempt - 1));
+        if attempt >= MAX_ATTEMPTS || wait > MAX_BACKOFF {
+            return Err(format!("Reddit is rate limiting requests; try again in {}s", wait.as_secs().max(1)));
+        }
+        crate::rate_limit::defer("reddit.com", wait);
+        attempt += 1;
+    }
+}
+
+/// Posts of a subreddit as a feed. `sort` is one of `SORTS` (default `hot`);
+/// `period` narrows `top` and `controversial`.
+pub async fn fetch_subreddit(
+    subreddit: &str,
+    sort: Option<&str>,
+    period: Option<&str>,
+    limit: Option<u32>,
+) -> Result<ParsedFeed, String> {
+    let subreddit = subreddit_name(subreddit)?;
+    let sort = sort.unwrap_or("hot").to_ascii_lowercase();
+    if !SORTS.contains(&sort.as_str()) {
+        return Err(format!("Unknown sort '{sort}', expected one of {}", SORTS.join(", ")));
+    }
+    let mut url = url::Url::parse(&format!("{SITE}/r/{subreddit}/{sort}.json")).map_err(|e| e.to_string())?;
+    url.query_pairs_mut()
+        .append_pair("limit", &limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT).to_string())
+        // Unescaped HTML and image URLs
+        .append_pair("raw_json", "1");
+    let dated = matches!(sort.as_str(), "top" | "controversial");
+    if let Some(period) = period.filter(|p| dated && PERIODS.contains(p)) {
+        url.query_pairs_mut().append_pair("t", period);
+    }
+
+    let body = get_listing(url.as_str()).await?;
+    let listing: Listing = serde_json::from_str(&body).map_err(|e| format!("Unexpected Reddit response: {e}"))?;
+    let items: Vec<ParsedItem> = listing
+        .data
+        .children
+        .into_iter()
+        .filter(|c| c.kind == "t3")
+        .map(|c| post_item(c.data))
+        .collect();
+    eprintln!("[reddit] r/{subreddit} ({sort}) returned {} posts", items.len());
+    Ok(ParsedFeed {
+        title: format!("r/{subreddit}"),
+        link: format!("{SITE}/r/{subreddit}/"),
+        description: String::new(),
+        icon: None,
+        updated_at: items.iter().filter_map(|i| i.published_at).max(),
+        permanent_url: None,
+        items,
+    })
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// A subreddit's posts from the JSON API, as `parse_feed` would return its RSS feed.
+#[tauri::command]
+pub async fn fetch_reddit(
+    subreddit: String,
+    sort: Option<String>,
+    period: Option<String>,
+    limit: Option<u32>,
+) -> Result<ParsedFeed, String> {
+    fetch_subreddit(&subreddit, sort.as_deref(), period.as_deref(), limit).await
+}
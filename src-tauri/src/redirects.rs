@@ -0,0 +1,65 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::future::Future;
+
+// Redirect chains for requests made through the shared clients. reqwest
+// follows redirects internally and only exposes the final URL, so the client's
+// redirect policy records each hop into a task-local log that `track` reads back.
+
+const MAX_REDIRECTS: usize = 10;
+
+#[derive(Clone, Serialize, Debug)]
+pub struct RedirectHop {
+    /// URL that answered with the redirect.
+    pub url: String,
+    pub status: u16,
+}
+
+tokio::task_local! {
+    static LOG: RefCell<Vec<RedirectHop>>;
+}
+
+/// Follows up to ten redirects like reqwest's default, logging each hop for `track`.
+/// A `guarded` client also refuses redirects to private IP literals.
+pub fn policy(guarded: bool) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        let previous = attempt.previous();
+        if previous.len() > MAX_REDIRECTS {
+            return attempt.error("too many redirects");
+        }
+        if guarded {
+            if let Err(e) = crate::private_hosts::check_url(attempt.url()) {
+                return attempt.error(e);
+            }
+        }
+        let hop = RedirectHop {
+            url: previous.last().map(|u| u.to_string()).unwrap_or_default(),
+            status: attempt.status().as_u16(),
+        };
+        // Only requests run under `track` have a log
+        let _ = LOG.try_with(|log| {
+            let mut log = log.borrow_mut();
+            // A first redirect means a new request (retry, auth round-trip): start over
+            if previous.len() == 1 {
+                log.clear();
+            }
+            log.push(hop);
+        });
+        attempt.follow()
+    })
+}
+
+/// Run `fut` and return the redirects taken by the last request it sent.
+pub async fn track<F: Future>(fut: F) -> (F::Output, Vec<RedirectHop>) {
+    LOG.scope(RefCell::new(Vec::new()), async {
+        let output = fut.await;
+        (output, LOG.with(|log| log.take()))
+    })
+    .await
+}
+
+/// Whether every hop was a permanent redirect (301/308), i.e. the final URL
+/// should replace the stored one.
+pub fn is_permanent(hops: &[RedirectHop]) -> bool {
+    !hops.is_empty() && hops.iter().all(|h| matches!(h.status, 301 | 308))
+}
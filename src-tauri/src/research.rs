@@ -0,0 +1,653 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::content::process_article_html;
+use crate::feed_parser::{Enclosure, ParsedItem};
+use crate::text::{snippet_from_html, SNIPPET_LEN};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SOURCES_FILE: &str = "research_sources.json";
+const DEFAULT_MAX_RESULTS: u32 = 50;
+const MAX_RESULTS: u32 = 200;
+const ARXIV_API: &str = "https://export.arxiv.org/api/query";
+const EUTILS_API: &str = "https://eutils.ncbi.nlm.nih.gov/entrez/eutils";
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResearchKind {
+    Arxiv,
+    Pubmed,
+}
+
+/// A saved arXiv or PubMed search, polled like a feed (newest first).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ResearchSource {
+    pub id: String,
+    pub kind: ResearchKind,
+    pub name: String,
+    /// arXiv `search_query` (e.g. `cat:cs.CL AND ti:retrieval`; plain words
+    /// search all fields) or a PubMed term (e.g. `crispr[tiab] AND 2024[dp]`).
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_results: Option<u32>,
+    /// NCBI API key, raising the E-utilities limit from 3 to 10 requests/s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug, Default)]
+pub struct PaperAuthor {
+    /// Surname, or the full name of a collective author.
+    pub family: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub given: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub affiliations: Vec<String>,
+}
+
+/// Bibliographic fields for the citation exporter.
+#[derive(Clone, Serialize, Debug, Default)]
+pub struct Citation {
+    pub authors: Vec<PaperAuthor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pages: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    /// arXiv identifier without version, e.g. `2401.01234`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arxiv_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmcid: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct Paper {
+    #[serde(flatten)]
+    pub item: ParsedItem,
+    pub citation: Citation,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct ResearchFeed {
+    pub title: String,
+    pub link: String,
+    pub items: Vec<Paper>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct ResearchStore {
+    sources: Mutex<Vec<ResearchSource>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl ResearchStore {
+    pub fn new() -> Self {
+        ResearchStore {
+            sources: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SOURCES_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(sources) = serde_json::from_str::<Vec<ResearchSource>>(&json) {
+                            eprintln!("[research] Loaded {} sources", sources.len());
+                            *self.sources.lock().unwrap() = sources;
+                        }
+                    }
+                    Err(e) => eprintln!("[research] Failed to read sources: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let sources = self.sources.lock().unwrap();
+            match serde_json::to_string_pretty(&*sources) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[research] Failed to write sources: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[research] Failed to serialize sources: {e}"),
+            }
+        }
+    }
+
+    pub fn get_sources(&self) -> Vec<ResearchSource> {
+        self.sources.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<ResearchSource> {
+        self.sources.lock().unwrap().iter().find(|s| s.id == id).cloned()
+    }
+
+    /// Insert or replace a source. An empty id gets a fresh UUID.
+    pub fn upsert(&self, mut source: ResearchSource) -> ResearchSource {
+        if source.id.is_empty() {
+            source.id = uuid::Uuid::new_v4().to_string();
+        }
+        source.query = source.query.trim().to_string();
+        source.api_key = source.api_key.filter(|k| !k.trim().is_empty());
+        {
+            let mut sources = self.sources.lock().unwrap();
+            match sources.iter_mut().find(|s| s.id == source.id) {
+                Some(existing) => *existing = source.clone(),
+                None => sources.push(source.clone()),
+            }
+        }
+        self.save_to_disk();
+        source
+    }
+
+    pub fn delete(&self, id: &str) -> bool {
+        let mut sources = self.sources.lock().unwrap();
+        let before = sources.len();
+        sources.retain(|s| s.id != id);
+        let removed = sources.len() < before;
+        drop(sources);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+}
+
+// ── XML ──────────────────────────────────────────────────────────────
+
+/// Minimal element tree; `text` holds all descendant text in document order.
+#[derive(Default)]
+struct XmlNode {
+    name: String,
+    attrs: Vec<(String, String)>,
+    text: String,
+    children: Vec<XmlNode>,
+}
+
+impl XmlNode {
+    fn child(&self, name: &str) -> Option<&XmlNode> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlNode> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Descendant reached through a `/`-separated path of local names.
+    fn find(&self, path: &str) -> Option<&XmlNode> {
+        path.split('/').try_fold(self, |node, name| node.child(name))
+    }
+
+    /// Collapsed text at `path`, or `None` when missing or blank.
+    fn text_at(&self, path: &str) -> Option<String> {
+        self.find(path).map(XmlNode::clean_text).filter(|t| !t.is_empty())
+    }
+
+    fn clean_text(&self) -> String {
+        self.text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+fn element(e: &BytesStart) -> XmlNode {
+    XmlNode {
+        name: String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+        attrs: e
+            .attributes()
+            .flatten()
+            .map(|a| {
+                let key = String::from_utf8_lossy(a.key.local_name().as_ref()).into_owned();
+                let value = a.unescape_value().map(|v| v.into_owned()).unwrap_or_default();
+                (key, value)
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn parse_xml(xml: &str) -> Result<XmlNode, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack = vec![XmlNode::default()];
+    loop {
+        let text = match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                stack.push(element(&e));
+                continue;
+            }
+            Ok(Event::Empty(e)) => {
+                let node = element(&e);
+                stack.last_mut().unwrap().children.push(node);
+                continue;
+            }
+            Ok(Event::End(_)) => {
+                if stack.len() > 1 {
+                    let node = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(node);
+                }
+                continue;
+            }
+            Ok(Event::Text(t)) => t.unescape().map_err(|e| format!("Invalid XML: {e}"))?.into_owned(),
+            Ok(Event::CData(c)) => String::from_utf8_lossy(&c.into_inner()).into_owned(),
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Invalid XML at byte {}: {e}", reader.error_position())),
+            _ => continue,
+        };
+        for node in stack.iter_mut() {
+            node.text.push_str(&text);
+        }
+    }
+    // Close anything left open by a truncated response
+    while stack.len() > 1 {
+        let node = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(node);
+    }
+    Ok(stack.pop().unwrap())
+}
+
+// ── Item building ────────────────────────────────────────────────────
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Abstract paragraphs as HTML, each optionally headed by a section label.
+fn abstract_html(sections: &[(Option<String>, String)]) -> String {
+    sections
+        .iter()
+        .map(|(label, text)| match label {
+            Some(label) => format!("<p><strong>{}.</strong> {}</p>", escape_html(label), escape_html(text)),
+            None => format!("<p>{}</p>", escape_html(text)),
+        })
+        .collect()
+}
+
+fn byline(authors: &[PaperAuthor]) -> String {
+    authors
+        .iter()
+        .map(|a| match a.given.is_empty() {
+            true => a.family.clone(),
+            false => format!("{} {}", a.given, a.family),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Feed item for a paper; the abstract is both summary and content.
+fn paper_item(id: String, title: String, url: String, sections: &[(Option<String>, String)]) -> ParsedItem {
+    // Abstracts go through the ingest pipeline so arXiv's inline TeX becomes MathML
+    let summary = process_article_html(&abstract_html(sections), &url);
+    ParsedItem {
+        id,
+        title,
+        url,
+        author: String::new(),
+        snippet: snippet_from_html(&summary, SNIPPET_LEN),
+        content: Some(summary.clone()),
+        summary,
+        published_at: None,
+        updated_at: None,
+        categories: Vec::new(),
+        enclosures: Vec::new(),
+        thumbnail: None,
+    }
+}
+
+fn pdf_enclosure(url: String) -> Enclosure {
+    Enclosure {
+        url,
+        mime_type: Some("application/pdf".into()),
+        length: None,
+        duration: None,
+    }
+}
+
+fn date_millis(year: i32, month: u32, day: u32) -> Option<i64> {
+    let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp_millis())
+}
+
+// ── arXiv ────────────────────────────────────────────────────────────
+
+/// Plain words become an all-fields AND search; anything with a field prefix is passed through.
+fn arxiv_query(query: &str) -> String {
+    if query.contains(':') {
+        return query.to_string();
+    }
+    query.split_whitespace().map(|w| format!("all:{w}")).collect::<Vec<_>>().join(" AND ")
+}
+
+/// `http://arxiv.org/abs/2401.01234v2` → `2401.01234`.
+fn arxiv_id(entry_id: &str) -> String {
+    let id = entry_id.split_once("/abs/").map_or(entry_id, |(_, id)| id);
+    match id.rsplit_once('v') {
+        Some((base, v)) if !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()) => base.into(),
+        _ => id.into(),
+    }
+}
+
+/// arXiv lists names as "Given Family"; the last word is taken as the surname.
+fn split_name(name: &str) -> (String, String) {
+    match name.trim().rsplit_once(' ') {
+        Some((given, family)) => (family.to_string(), given.to_string()),
+        None => (name.trim().to_string(), String::new()),
+    }
+}
+
+fn rfc3339_millis(node: &XmlNode, name: &str) -> Option<i64> {
+    let text = node.text_at(name)?;
+    chrono::DateTime::parse_from_rfc3339(&text).ok().map(|d| d.timestamp_millis())
+}
+
+fn arxiv_paper(entry: &XmlNode) -> Option<Paper> {
+    let arxiv_id = arxiv_id(&entry.text_at("id")?);
+    let link = |pred: &dyn Fn(&XmlNode) -> bool| {
+        entry.children_named("link").find(|l| pred(l)).and_then(|l| l.attr("href")).map(String::from)
+    };
+    let url = link(&|l| l.attr("rel") == Some("alternate"))
+        .unwrap_or_else(|| format!("https://arxiv.org/abs/{arxiv_id}"));
+    let pdf = link(&|l| l.attr("title") == Some("pdf") || l.attr("type") == Some("application/pdf"));
+
+    let authors: Vec<PaperAuthor> = entry
+        .children_named("author")
+        .filter_map(|a| {
+            let (family, given) = split_name(&a.text_at("name")?);
+            let affiliations = a.children_named("affiliation").map(XmlNode::clean_text).collect();
+            Some(PaperAuthor { family, given, affiliations })
+        })
+        .collect();
+    let published_at = rfc3339_millis(entry, "published");
+    let year = published_at
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|d| chrono::Datelike::year(&d));
+    let categories = entry
+        .children_named("category")
+        .filter_map(|c| c.attr("term"))
+        .map(String::from)
+        .collect();
+    let abstract_text = entry.text_at("summary").unwrap_or_default();
+
+    let title = entry.text_at("title").unwrap_or_default();
+    let item = ParsedItem {
+        author: byline(&authors),
+        published_at,
+        updated_at: rfc3339_millis(entry, "updated"),
+        categories,
+        enclosures: pdf.into_iter().map(pdf_enclosure).collect(),
+        ..paper_item(format!("arxiv:{arxiv_id}"), title, url, &[(None, abstract_text)])
+    };
+    Some(Paper {
+        item,
+        citation: Citation {
+            authors,
+            journal: entry.text_at("journal_ref"),
+            year,
+            doi: entry.text_at("doi"),
+            arxiv_id: Some(arxiv_id),
+            ..Default::default()
+        },
+    })
+}
+
+async fn poll_arxiv(source: &ResearchSource, max: u32) -> Result<Vec<Paper>, String> {
+    let mut url = url::Url::parse(ARXIV_API).map_err(|e| e.to_string())?;
+    url.query_pairs_mut()
+        .append_pair("search_query", &arxiv_query(&source.query))
+        .append_pair("sortBy", "submittedDate")
+        .append_pair("sortOrder", "descending")
+        .append_pair("max_results", &max.to_string());
+    let xml = crate::fetch_text(url.as_str()).await?;
+    let root = parse_xml(&xml)?;
+    let feed = root.child("feed").ok_or("Unexpected arXiv response")?;
+    Ok(feed.children_named("entry").filter_map(arxiv_paper).collect())
+}
+
+// ── PubMed ───────────────────────────────────────────────────────────
+
+fn eutils_url(source: &ResearchSource, tool: &str, params: &[(&str, &str)]) -> Result<url::Url, String> {
+    let mut url = url::Url::parse(&format!("{EUTILS_API}/{tool}")).map_err(|e| e.to_string())?;
+    {
+        let mut query = url.query_pairs_mut();
+        query.append_pair("db", "pubmed").append_pair("tool", "superflux");
+        for (key, value) in params {
+            query.append_pair(key, value);
+        }
+        if let Some(key) = &source.api_key {
+            query.append_pair("api_key", key.trim());
+        }
+    }
+    Ok(url)
+}
+
+fn month_number(month: &str) -> Option<u32> {
+    if let Ok(n) = month.parse::<u32>() {
+        return (1..=12).contains(&n).then_some(n);
+    }
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let prefix = month.get(..3)?.to_ascii_lowercase();
+    MONTHS.iter().position(|m| *m == prefix).map(|i| i as u32 + 1)
+}
+
+/// `PubDate` / `ArticleDate` as (year, ms since epoch). `MedlineDate` ranges
+/// like "1998 Dec-1999 Jan" only yield the year.
+fn pubmed_date(date: &XmlNode) -> (Option<i32>, Option<i64>) {
+    let year = date
+        .text_at("Year")
+        .or_else(|| date.text_at("MedlineDate").and_then(|d| d.get(..4).map(String::from)))
+        .and_then(|y| y.parse::<i32>().ok());
+    let Some(y) = year else { return (None, None) };
+    let month = date.text_at("Month").and_then(|m| month_number(&m)).unwrap_or(1);
+    let day = date.text_at("Day").and_then(|d| d.parse().ok()).unwrap_or(1);
+    (year, date_millis(y, month, day))
+}
+
+fn pubmed_paper(article: &XmlNode) -> Option<Paper> {
+    let citation_node = article.child("MedlineCitation")?;
+    let pmid = citation_node.text_at("PMID")?;
+    let details = citation_node.child("Article")?;
+    let journal = details.child("Journal");
+    let issue = journal.and_then(|j| j.child("JournalIssue"));
+
+    let authors: Vec<PaperAuthor> = details
+        .find("AuthorList")
+        .map(|list| {
+            list.children_named("Author")
+                .filter_map(|a| {
+                    let affiliations =
+                        a.children_named("AffiliationInfo").filter_map(|i| i.text_at("Affiliation")).collect();
+                    let (family, given) = match a.text_at("LastName") {
+                        Some(last) => (last, a.text_at("ForeName").or_else(|| a.text_at("Initials"))),
+                        None => (a.text_at("CollectiveName")?, None),
+                    };
+                    Some(PaperAuthor { family, given: given.unwrap_or_default(), affiliations })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let sections: Vec<(Option<String>, String)> = details
+        .find("Abstract")
+        .map(|a| {
+            a.children_named("AbstractText")
+                .map(|t| (t.attr("Label").map(String::from), t.clean_text()))
+                .filter(|(_, text)| !text.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let ids = article.find("PubmedData/ArticleIdList");
+    let id_of = |kind: &str| {
+        ids.and_then(|list| list.children_named("ArticleId").find(|i| i.attr("IdType") == Some(kind)))
+            .map(XmlNode::clean_text)
+    };
+    let doi = id_of("doi").or_else(|| {
+        details
+            .children_named("ELocationID")
+            .find(|e| e.attr("EIdType") == Some("doi"))
+            .map(XmlNode::clean_text)
+    });
+    let pmcid = id_of("pmc");
+
+    // Electronic publication date is exact; the issue date is often month-only
+    let (issue_year, issue_date) = issue.and_then(|i| i.child("PubDate")).map(pubmed_date).unwrap_or_default();
+    let (_, epub_date) = details.child("ArticleDate").map(pubmed_date).unwrap_or_default();
+    let keywords = citation_node
+        .find("MeshHeadingList")
+        .map(|list| list.children_named("MeshHeading").filter_map(|h| h.text_at("DescriptorName")).collect())
+        .unwrap_or_default();
+    // Free full text in PubMed Central is the only PDF PubMed can point to
+    let enclosures = pmcid
+        .iter()
+        .map(|id| pdf_enclosure(format!("https://www.ncbi.nlm.nih.gov/pmc/articles/{id}/pdf/")))
+        .collect();
+
+    let title = details.text_at("ArticleTitle").unwrap_or_default();
+    let url = format!("https://pubmed.ncbi.nlm.nih.gov/{pmid}/");
+    let item = ParsedItem {
+        author: byline(&authors),
+        published_at: epub_date.or(issue_date),
+        categories: keywords,
+        enclosures,
+        ..paper_item(format!("pubmed:{pmid}"), title, url, &sections)
+    };
+    Some(Paper {
+        item,
+        citation: Citation {
+            authors,
+            journal: journal.and_then(|j| j.text_at("Title")),
+            volume: issue.and_then(|i| i.text_at("Volume")),
+            issue: issue.and_then(|i| i.text_at("Issue")),
+            pages: details.text_at("Pagination/MedlinePgn"),
+            year: issue_year,
+            doi,
+            pmid: Some(pmid),
+            pmcid,
+            ..Default::default()
+        },
+    })
+}
+
+async fn poll_pubmed(source: &ResearchSource, max: u32) -> Result<Vec<Paper>, String> {
+    let max = max.to_string();
+    let search = eutils_url(
+        source,
+        "esearch.fcgi",
+        &[("term", &source.query), ("retmax", &max), ("sort", "pub_date"), ("retmode", "json")],
+    )?;
+    let json: serde_json::Value = serde_json::from_str(&crate::fetch_text(search.as_str()).await?)
+        .map_err(|e| format!("Invalid PubMed search response: {e}"))?;
+    if let Some(error) = json.pointer("/esearchresult/ERROR").and_then(|e| e.as_str()) {
+        return Err(format!("PubMed search failed: {error}"));
+    }
+    let ids: Vec<&str> = json
+        .pointer("/esearchresult/idlist")
+        .and_then(|l| l.as_array())
+        .map(|l| l.iter().filter_map(|id| id.as_str()).collect())
+        .unwrap_or_default();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let fetch = eutils_url(source, "efetch.fcgi", &[("id", &ids.join(",")), ("retmode", "xml")])?;
+    let root = parse_xml(&crate::fetch_text(fetch.as_str()).await?)?;
+    let set = root.child("PubmedArticleSet").ok_or("Unexpected PubMed response")?;
+    let mut papers: Vec<Paper> = set.children_named("PubmedArticle").filter_map(pubmed_paper).collect();
+    // efetch returns records in PMID order; restore the search's date order
+    papers.sort_by_key(|p| ids.iter().position(|id| p.citation.pmid.as_deref() == Some(*id)));
+    Ok(papers)
+}
+
+pub async fn poll_source(source: &ResearchSource) -> Result<ResearchFeed, String> {
+    let max = source.max_results.unwrap_or(DEFAULT_MAX_RESULTS).clamp(1, MAX_RESULTS);
+    let query: String = url::form_urlencoded::byte_serialize(source.query.as_bytes()).collect();
+    let (items, link) = match source.kind {
+        ResearchKind::Arxiv => (
+            poll_arxiv(source, max).await,
+            format!("https://arxiv.org/search/?query={query}&searchtype=all&order=-announced_date_first"),
+        ),
+        ResearchKind::Pubmed => (
+            poll_pubmed(source, max).await,
+            format!("https://pubmed.ncbi.nlm.nih.gov/?term={query}&sort=date"),
+        ),
+    };
+    match items {
+        Ok(ref list) => eprintln!("[research] '{}' returned {} papers", source.name, list.len()),
+        Err(ref e) => eprintln!("[research] '{}' failed: {e}", source.name),
+    }
+    Ok(ResearchFeed {
+        title: source.name.clone(),
+        link,
+        items: items?,
+    })
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_research_sources(store: tauri::State<'_, Arc<ResearchStore>>) -> Vec<ResearchSource> {
+    store.get_sources()
+}
+
+#[tauri::command]
+pub fn save_research_source(
+    source: ResearchSource,
+    store: tauri::State<'_, Arc<ResearchStore>>,
+) -> Result<ResearchSource, String> {
+    if source.query.trim().is_empty() {
+        return Err("Search query is required".into());
+    }
+    if source.name.trim().is_empty() {
+        return Err("Name is required".into());
+    }
+    Ok(store.upsert(source))
+}
+
+#[tauri::command]
+pub fn delete_research_source(id: String, store: tauri::State<'_, Arc<ResearchStore>>) -> bool {
+    store.delete(&id)
+}
+
+/// Run a saved search and return the matching papers as a feed, newest first.
+#[tauri::command]
+pub async fn fetch_research_source(
+    id: String,
+    store: tauri::State<'_, Arc<ResearchStore>>,
+) -> Result<ResearchFeed, String> {
+    let source = store.get(&id).ok_or("Source not found")?;
+    poll_source(&source).await
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+// Cap on how much of a response body `fetch_url` and `http_request` will
+// buffer. Bodies are read chunk by chunk and the request is dropped as soon as
+// the cap is crossed, so a URL pointing at a huge file can't exhaust memory.
+
+const CONFIG_FILE: &str = "response_limits.json";
+const MIN_BODY_BYTES: u64 = 64 * 1024;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ResponseLimits {
+    /// Largest body buffered in memory, in bytes.
+    pub max_body_bytes: u64,
+}
+
+impl Default for ResponseLimits {
+    fn default() -> Self {
+        ResponseLimits {
+            max_body_bytes: 20 * 1024 * 1024,
+        }
+    }
+}
+
+static LIMITS: OnceLock<Mutex<ResponseLimits>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn limits_lock() -> &'static Mutex<ResponseLimits> {
+    LIMITS.get_or_init(|| Mutex::new(ResponseLimits::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(CONFIG_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(limits) = serde_json::from_str::<ResponseLimits>(&json) {
+            *limits_lock().lock().unwrap() = limits;
+        }
+    }
+}
+
+pub fn max_body_bytes() -> u64 {
+    limits_lock().lock().unwrap().max_body_bytes
+}
+
+fn too_large(limit: u64) -> String {
+    format!("Response body exceeds the {} limit", format_size(limit))
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} KB", bytes / 1024)
+    }
+}
+
+/// Read the whole body, failing once more than `limit` bytes arrive. A
+/// `Content-Length` above the limit fails before anything is read.
+pub async fn read_bytes(mut response: reqwest::Response, limit: u64) -> Result<Vec<u8>, String> {
+    if response.content_length().is_some_and(|len| len > limit) {
+        return Err(too_large(limit));
+    }
+    let mut body = Vec::with_capacity(response.content_length().unwrap_or(0) as usize);
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response body: {e}"))?
+    {
+        if (body.len() + chunk.len()) as u64 > limit {
+            return Err(too_large(limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Like `read_bytes`, decoded with the charset from `Content-Type` (UTF-8 by default).
+pub async fn read_text(response: reqwest::Response, limit: u64) -> Result<String, String> {
+    let encoding = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|ct| ct.split(';').find_map(|p| p.trim().strip_prefix("charset=")))
+        .and_then(|label| encoding_rs::Encoding::for_label(label.trim_matches('"').as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    let bytes = read_bytes(response, limit).await?;
+    let (text, _, _) = encoding.decode(&bytes);
+    Ok(text.into_owned())
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_response_limits() -> ResponseLimits {
+    limits_lock().lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_response_limits(limits: ResponseLimits) -> Result<(), String> {
+    if limits.max_body_bytes < MIN_BODY_BYTES {
+        return Err(format!("Limit must be at least {}", format_size(MIN_BODY_BYTES)));
+    }
+    if let Some(dir) = DATA_DIR.get() {
+        let json =
+            serde_json::to_string_pretty(&limits).map_err(|e| format!("Failed to serialize limits: {e}"))?;
+        std::fs::write(dir.join(CONFIG_FILE), json).map_err(|e| format!("Failed to save limits: {e}"))?;
+    }
+    *limits_lock().lock().unwrap() = limits;
+    Ok(())
+}
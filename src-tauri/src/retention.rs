@@ -0,0 +1,391 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+use crate::db::{self, Database};
+use crate::ingest::now_millis;
+
+// Retention: old articles are deleted per feed, by count ("keep 200") and/or
+// age ("keep 90 days"), sparing starred and optionally unread ones. A
+// background timer applies the policies every few hours; `prune_now` runs
+// them on demand. Deletes go through the article triggers, so the search
+// index, link graph, tags and duplicate groups follow. Each deleted id is
+// kept in `pruned_articles` so the next refresh of a feed that still lists it
+// doesn't insert it again as a new unread article; those tombstones expire
+// after `TOMBSTONE_DAYS`.
+
+const SETTINGS_FILE: &str = "retention.json";
+/// How often the timer checks whether a prune is due.
+const CHECK_INTERVAL_SECS: u64 = 10 * 60;
+/// Vacuum once this share of the database file is free pages.
+const VACUUM_FREE_RATIO: f64 = 0.25;
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+/// Feeds rarely list an item for longer than this.
+const TOMBSTONE_DAYS: i64 = 365;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct RetentionPolicy {
+    /// Keep at most this many articles, newest first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u32>,
+    /// Delete articles published longer ago than this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u32>,
+    #[serde(default = "default_true")]
+    pub keep_starred: bool,
+    #[serde(default)]
+    pub keep_unread: bool,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy { max_items: None, max_age_days: None, keep_starred: true, keep_unread: false }
+    }
+}
+
+impl RetentionPolicy {
+    fn is_unlimited(&self) -> bool {
+        self.max_items.is_none() && self.max_age_days.is_none()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct RetentionSettings {
+    /// Run the policies in the background.
+    pub enabled: bool,
+    pub interval_hours: u32,
+    /// Policy for feeds without their own.
+    pub default_policy: RetentionPolicy,
+    /// Per-feed policies by feed id.
+    pub feeds: HashMap<String, RetentionPolicy>,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        RetentionSettings {
+            enabled: false,
+            interval_hours: 24,
+            default_policy: RetentionPolicy::default(),
+            feeds: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct PruneReport {
+    pub ran_at: u64,
+    pub deleted: u64,
+    /// Deleted articles per feed id, for feeds that lost any.
+    pub per_feed: HashMap<String, u64>,
+    /// Database space the deleted rows took up.
+    pub bytes_reclaimed: u64,
+    /// Database file size afterwards.
+    pub db_size: u64,
+    /// Whether the file was compacted, returning the space to the disk.
+    pub vacuumed: bool,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct RetentionStats {
+    pub article_count: u64,
+    pub starred_count: u64,
+    pub db_size: u64,
+    /// Free pages inside the file, given back by the next vacuum.
+    pub free_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_prune: Option<PruneReport>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct RetentionState {
+    #[serde(default)]
+    settings: RetentionSettings,
+    #[serde(default)]
+    last_prune: Option<PruneReport>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct RetentionStore {
+    state: Mutex<RetentionState>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl RetentionStore {
+    pub fn new() -> Self {
+        RetentionStore {
+            state: Mutex::new(RetentionState::default()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SETTINGS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(state) = serde_json::from_str::<RetentionState>(&json) {
+                            *self.state.lock().unwrap() = state;
+                        }
+                    }
+                    Err(e) => eprintln!("[retention] Failed to read settings: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let state = self.state.lock().unwrap();
+            match serde_json::to_string_pretty(&*state) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[retention] Failed to write settings: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[retention] Failed to serialize settings: {e}"),
+            }
+        }
+    }
+
+    pub fn settings(&self) -> RetentionSettings {
+        self.state.lock().unwrap().settings.clone()
+    }
+
+    pub fn set_settings(&self, settings: RetentionSettings) {
+        self.state.lock().unwrap().settings = settings;
+        self.save_to_disk();
+    }
+
+    /// Set or clear (back to the default policy) one feed's policy.
+    pub fn set_feed_policy(&self, feed_id: &str, policy: Option<RetentionPolicy>) -> RetentionSettings {
+        let mut state = self.state.lock().unwrap();
+        match policy {
+            Some(policy) => state.settings.feeds.insert(feed_id.to_string(), policy),
+            None => state.settings.feeds.remove(feed_id),
+        };
+        let settings = state.settings.clone();
+        drop(state);
+        self.save_to_disk();
+        settings
+    }
+
+    fn last_prune(&self) -> Option<PruneReport> {
+        self.state.lock().unwrap().last_prune.clone()
+    }
+
+    fn record(&self, report: &PruneReport) {
+        self.state.lock().unwrap().last_prune = Some(report.clone());
+        self.save_to_disk();
+    }
+
+    fn is_due(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        let interval = u64::from(state.settings.interval_hours.max(1)) * 3_600_000;
+        state.settings.enabled && state.last_prune.as_ref().is_none_or(|r| now_millis() >= r.ran_at + interval)
+    }
+}
+
+// ── Pruning ──────────────────────────────────────────────────────────
+
+/// (page size, total pages, free pages) of the database file.
+fn page_stats(conn: &Connection) -> rusqlite::Result<(u64, u64, u64)> {
+    let pragma = |name: &str| conn.query_row(&format!("PRAGMA {name}"), [], |r| r.get::<_, i64>(0));
+    Ok((pragma("page_size")? as u64, pragma("page_count")? as u64, pragma("freelist_count")? as u64))
+}
+
+/// Articles of a feed its policy no longer keeps.
+fn expired_ids(
+    conn: &Connection,
+    feed_id: &str,
+    policy: &RetentionPolicy,
+    now: i64,
+) -> rusqlite::Result<Vec<String>> {
+    let cutoff = policy.max_age_days.map(|days| now - i64::from(days) * DAY_MS);
+    let mut stmt = conn.prepare_cached(
+        "SELECT id FROM (
+            SELECT id, is_starred, is_read, COALESCE(published_at, fetched_at) AS at,
+                   ROW_NUMBER() OVER (ORDER BY COALESCE(published_at, fetched_at) DESC, id) AS n
+            FROM articles WHERE feed_id = ?1
+         )
+         WHERE ((?2 IS NOT NULL AND n > ?2) OR (?3 IS NOT NULL AND at < ?3))
+           AND NOT (?4 AND is_starred = 1)
+           AND NOT (?5 AND is_read = 0)",
+    )?;
+    let rows = stmt.query_map(
+        params![feed_id, policy.max_items, cutoff, policy.keep_starred, policy.keep_unread],
+        |r| r.get(0),
+    )?;
+    rows.collect()
+}
+
+/// Apply `settings` to every feed with stored articles, then compact the file
+/// if `vacuum` says so or, when it is None, if much of it is free.
+pub fn prune(
+    conn: &mut Connection,
+    settings: &RetentionSettings,
+    vacuum: Option<bool>,
+) -> rusqlite::Result<PruneReport> {
+    let (page_size, pages_before, free_before) = page_stats(conn)?;
+    let now = now_millis() as i64;
+    let feed_ids: Vec<String> = conn
+        .prepare("SELECT DISTINCT feed_id FROM articles")?
+        .query_map([], |r| r.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut per_feed = HashMap::new();
+    let tx = conn.transaction()?;
+    {
+        let mut delete = tx.prepare_cached("DELETE FROM articles WHERE id = ?1")?;
+        let mut tombstone = tx.prepare_cached(
+            "INSERT OR REPLACE INTO pruned_articles (id, feed_id, pruned_at) VALUES (?1, ?2, ?3)",
+        )?;
+        for feed_id in feed_ids {
+            let policy = settings.feeds.get(&feed_id).unwrap_or(&settings.default_policy);
+            if policy.is_unlimited() {
+                continue;
+            }
+            let ids = expired_ids(&tx, &feed_id, policy, now)?;
+            for id in &ids {
+                delete.execute([id])?;
+                tombstone.execute(params![id, feed_id, now])?;
+            }
+            if !ids.is_empty() {
+                per_feed.insert(feed_id, ids.len() as u64);
+            }
+        }
+        tx.execute("DELETE FROM pruned_articles WHERE pruned_at < ?1", [now - TOMBSTONE_DAYS * DAY_MS])?;
+    }
+    tx.commit()?;
+
+    let (_, pages_after, free_after) = page_stats(conn)?;
+    let used_before = pages_before - free_before;
+    let used_after = pages_after - free_after;
+    let vacuumed = match vacuum {
+        Some(always) => always,
+        None => pages_after > 0 && free_after as f64 / pages_after as f64 >= VACUUM_FREE_RATIO,
+    };
+    if vacuumed {
+        conn.execute_batch("VACUUM")?;
+    }
+    let (_, pages_final, _) = page_stats(conn)?;
+    Ok(PruneReport {
+        ran_at: now as u64,
+        deleted: per_feed.values().sum(),
+        per_feed,
+        bytes_reclaimed: used_before.saturating_sub(used_after) * page_size,
+        db_size: pages_final * page_size,
+        vacuumed,
+    })
+}
+
+async fn run_prune(app: &tauri::AppHandle, vacuum: Option<bool>) -> Result<PruneReport, String> {
+    let store = app.state::<Arc<RetentionStore>>().inner().clone();
+    let db = app.state::<Arc<Database>>().inner().clone();
+    let settings = store.settings();
+    let report = db::run(&db, move |conn| prune(conn, &settings, vacuum)).await?;
+    store.record(&report);
+    if report.deleted > 0 {
+        eprintln!("[retention] Deleted {} articles ({} bytes)", report.deleted, report.bytes_reclaimed);
+        let _ = app.emit("retention-pruned", &report);
+    }
+    Ok(report)
+}
+
+/// Prune in the background whenever the configured interval has passed.
+pub fn start_retention_timer(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        if !app.state::<Arc<RetentionStore>>().is_due() {
+            continue;
+        }
+        if let Err(e) = tauri::async_runtime::block_on(run_prune(&app, None)) {
+            eprintln!("[retention] Prune failed: {e}");
+        }
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_retention_settings(store: tauri::State<'_, Arc<RetentionStore>>) -> RetentionSettings {
+    store.settings()
+}
+
+#[tauri::command]
+pub fn set_retention_settings(
+    settings: RetentionSettings,
+    store: tauri::State<'_, Arc<RetentionStore>>,
+) -> Result<(), String> {
+    if settings.interval_hours == 0 {
+        return Err("Interval must be at least an hour".into());
+    }
+    let policies = std::iter::once(&settings.default_policy).chain(settings.feeds.values());
+    if policies.into_iter().any(|p| p.max_items == Some(0) || p.max_age_days == Some(0)) {
+        return Err("Limits must be at least 1".into());
+    }
+    store.set_settings(settings);
+    Ok(())
+}
+
+/// Give a feed its own policy, or `None` to fall back to the default.
+#[tauri::command]
+pub fn set_feed_retention(
+    feed_id: String,
+    policy: Option<RetentionPolicy>,
+    store: tauri::State<'_, Arc<RetentionStore>>,
+) -> Result<RetentionSettings, String> {
+    if policy.as_ref().is_some_and(|p| p.max_items == Some(0) || p.max_age_days == Some(0)) {
+        return Err("Limits must be at least 1".into());
+    }
+    Ok(store.set_feed_policy(&feed_id, policy))
+}
+
+/// Apply the retention policies now, whether or not background pruning is on.
+/// `vacuum` forces (or skips) compacting the database afterwards.
+#[tauri::command]
+pub async fn prune_now(vacuum: Option<bool>, app: tauri::AppHandle) -> Result<PruneReport, String> {
+    run_prune(&app, vacuum).await
+}
+
+#[tauri::command]
+pub async fn get_retention_stats(
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, Arc<RetentionStore>>,
+) -> Result<RetentionStats, String> {
+    let last_prune = store.last_prune();
+    db::run(&db, move |conn| {
+        let (page_size, pages, free) = page_stats(conn)?;
+        let (article_count, starred_count): (i64, i64) =
+            conn.query_row("SELECT COUNT(*), COALESCE(SUM(is_starred), 0) FROM articles", [], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })?;
+        Ok(RetentionStats {
+            article_count: article_count as u64,
+            starred_count: starred_count as u64,
+            db_size: pages * page_size,
+            free_bytes: free * page_size,
+            last_prune,
+        })
+    })
+    .await
+}
@@ -0,0 +1,116 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// Retry policy for feed GETs: transient failures (5xx, timeouts, refused
+// connections) are retried with exponential backoff and full jitter instead of
+// surfacing every hiccup in the UI. Other errors fail immediately.
+
+const POLICY_FILE: &str = "retry_policy.json";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RetryPolicy {
+    /// Total tries including the first; 1 disables retrying.
+    pub max_attempts: u32,
+    /// Backoff before the second try; doubled for each one after.
+    pub base_delay_ms: u64,
+    /// Cap on a single wait. A `Retry-After` longer than this is not waited out.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before try `attempt + 1`, or `None` if no more tries are allowed.
+    /// A server-sent `Retry-After` replaces the computed backoff.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let max = Duration::from_millis(self.max_delay_ms);
+        if let Some(wait) = retry_after {
+            return (wait <= max).then_some(wait);
+        }
+        let ceiling = self
+            .base_delay_ms
+            .saturating_mul(1u64 << (attempt - 1).min(16))
+            .min(self.max_delay_ms);
+        Some(Duration::from_millis(rand::random::<u64>() % (ceiling + 1)))
+    }
+}
+
+static POLICY: OnceLock<Mutex<RetryPolicy>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn policy_lock() -> &'static Mutex<RetryPolicy> {
+    POLICY.get_or_init(|| Mutex::new(RetryPolicy::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(POLICY_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(policy) = serde_json::from_str::<RetryPolicy>(&json) {
+            *policy_lock().lock().unwrap() = policy;
+        }
+    }
+}
+
+pub fn policy() -> RetryPolicy {
+    policy_lock().lock().unwrap().clone()
+}
+
+/// Statuses worth another try: server errors only.
+pub fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+}
+
+/// Transport failures worth another try.
+pub fn is_transient_error(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout()
+}
+
+/// `Retry-After` as a delay, from either delta-seconds or an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let ms = (at.timestamp_millis() - chrono::Utc::now().timestamp_millis()).max(0);
+    Some(Duration::from_millis(ms as u64))
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_retry_policy() -> RetryPolicy {
+    policy()
+}
+
+#[tauri::command]
+pub fn set_retry_policy(policy: RetryPolicy) -> Result<(), String> {
+    if !(1..=10).contains(&policy.max_attempts) {
+        return Err("Attempts must be between 1 and 10".into());
+    }
+    if policy.base_delay_ms > policy.max_delay_ms {
+        return Err("Base delay can't exceed the maximum delay".into());
+    }
+    if let Some(dir) = DATA_DIR.get() {
+        let json =
+            serde_json::to_string_pretty(&policy).map_err(|e| format!("Failed to serialize policy: {e}"))?;
+        std::fs::write(dir.join(POLICY_FILE), json).map_err(|e| format!("Failed to save retry policy: {e}"))?;
+    }
+    *policy_lock().lock().unwrap() = policy;
+    Ok(())
+}
@@ -0,0 +1,407 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const CONFIG_FILE: &str = "rss_bridge.json";
+/// RSS-Bridge puts parameters shared by every context under this key.
+const GLOBAL_CONTEXT: &str = "global";
+
+/// A feed generated by a bridge on the user's RSS-Bridge instance.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BridgeSubscription {
+    pub id: String,
+    pub name: String,
+    pub bridge: String,
+    #[serde(default)]
+    pub context: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    pub feed_url: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct RssBridgeConfig {
+    /// Instance root, e.g. `https://rss-bridge.org/bridge01`.
+    #[serde(default)]
+    pub instance_url: String,
+    #[serde(default)]
+    pub subscriptions: Vec<BridgeSubscription>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct BridgeOption {
+    pub label: String,
+    pub value: String,
+}
+
+/// One form field, flattened from RSS-Bridge's parameter metadata.
+#[derive(Clone, Serialize, Debug)]
+pub struct BridgeParam {
+    pub key: String,
+    pub label: String,
+    /// `text`, `number`, `list` or `checkbox`.
+    pub kind: String,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    pub options: Vec<BridgeOption>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct BridgeContext {
+    /// Empty when the bridge has a single, unnamed context.
+    pub name: String,
+    pub params: Vec<BridgeParam>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct BridgeInfo {
+    /// Bridge identifier used in URLs (e.g. `Twitch`).
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    pub contexts: Vec<BridgeContext>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct RssBridgeStore {
+    config: Mutex<RssBridgeConfig>,
+    /// Bridge metadata from the last `action=list`, used to validate URLs.
+    bridges: Mutex<Vec<BridgeInfo>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl RssBridgeStore {
+    pub fn new() -> Self {
+        RssBridgeStore {
+            config: Mutex::new(RssBridgeConfig::default()),
+            bridges: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(CONFIG_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(config) = serde_json::from_str::<RssBridgeConfig>(&json) {
+                            eprintln!("[rss_bridge] Loaded {} subscriptions", config.subscriptions.len());
+                            *self.config.lock().unwrap() = config;
+                        }
+                    }
+                    Err(e) => eprintln!("[rss_bridge] Failed to read config: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let config = self.config.lock().unwrap();
+            match serde_json::to_string_pretty(&*config) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[rss_bridge] Failed to write config: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[rss_bridge] Failed to serialize config: {e}"),
+            }
+        }
+    }
+
+    pub fn get_config(&self) -> RssBridgeConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    fn instance_url(&self) -> Result<String, String> {
+        let url = self.config.lock().unwrap().instance_url.clone();
+        if url.is_empty() {
+            return Err("No RSS-Bridge instance configured".into());
+        }
+        Ok(url)
+    }
+
+    pub fn set_instance(&self, url: String) {
+        self.config.lock().unwrap().instance_url = url;
+        self.bridges.lock().unwrap().clear();
+        self.save_to_disk();
+    }
+
+    pub fn add_subscription(&self, sub: BridgeSubscription) {
+        self.config.lock().unwrap().subscriptions.push(sub);
+        self.save_to_disk();
+    }
+
+    pub fn remove_subscription(&self, id: &str) -> bool {
+        let mut config = self.config.lock().unwrap();
+        let before = config.subscriptions.len();
+        config.subscriptions.retain(|s| s.id != id);
+        let removed = config.subscriptions.len() < before;
+        drop(config);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+}
+
+// ── Instance API ─────────────────────────────────────────────────────
+
+fn value_to_string(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(if *b { "on".into() } else { String::new() }),
+        _ => None,
+    }
+}
+
+/// List values are `label → value`, optionally grouped one level deep.
+fn parse_options(values: Option<&Value>) -> Vec<BridgeOption> {
+    let mut options = Vec::new();
+    if let Some(Value::Object(map)) = values {
+        for (label, value) in map {
+            match value {
+                Value::Object(group) => {
+                    for (inner, v) in group {
+                        if let Some(value) = value_to_string(v) {
+                            options.push(BridgeOption { label: format!("{label} / {inner}"), value });
+                        }
+                    }
+                }
+                v => {
+                    if let Some(value) = value_to_string(v) {
+                        options.push(BridgeOption { label: label.clone(), value });
+                    }
+                }
+            }
+        }
+    }
+    options
+}
+
+fn parse_params(params: Option<&Value>) -> Vec<BridgeParam> {
+    let Some(Value::Object(map)) = params else {
+        return Vec::new();
+    };
+    map.iter()
+        .map(|(key, meta)| {
+            let opt_str = |k: &str| meta.get(k).and_then(value_to_string).filter(|s| !s.is_empty());
+            BridgeParam {
+                key: key.clone(),
+                label: opt_str("name").unwrap_or_else(|| key.clone()),
+                kind: opt_str("type").unwrap_or_else(|| "text".into()),
+                required: meta.get("required").and_then(Value::as_bool).unwrap_or(false),
+                default_value: opt_str("defaultValue"),
+                example: opt_str("exampleValue"),
+                pattern: opt_str("pattern"),
+                help: opt_str("title"),
+                options: parse_options(meta.get("values")),
+            }
+        })
+        .collect()
+}
+
+fn parse_bridge(id: &str, meta: &Value) -> BridgeInfo {
+    let str_field = |k: &str| meta.get(k).and_then(Value::as_str).unwrap_or("").to_string();
+    let contexts = match meta.get("parameters") {
+        Some(Value::Object(parameters)) => {
+            // Global parameters apply to every named context, so fold them into each form
+            let global = parse_params(parameters.get(GLOBAL_CONTEXT));
+            let mut contexts: Vec<BridgeContext> = parameters
+                .iter()
+                .filter(|(name, _)| name.as_str() != GLOBAL_CONTEXT)
+                .map(|(name, params)| {
+                    let mut params = parse_params(Some(params));
+                    params.extend(global.iter().cloned());
+                    BridgeContext { name: name.clone(), params }
+                })
+                .collect();
+            if contexts.is_empty() {
+                contexts.push(BridgeContext { name: String::new(), params: global });
+            }
+            contexts
+        }
+        // PHP serialises unnamed contexts as a list (`[]` when there are no
+        // parameters); they are all sent without a context name
+        Some(Value::Array(unnamed)) => vec![BridgeContext {
+            name: String::new(),
+            params: unnamed.iter().flat_map(|params| parse_params(Some(params))).collect(),
+        }],
+        _ => vec![BridgeContext { name: String::new(), params: Vec::new() }],
+    };
+
+    BridgeInfo {
+        id: id.to_string(),
+        name: str_field("name"),
+        description: str_field("description"),
+        uri: str_field("uri"),
+        icon: meta.get("icon").and_then(Value::as_str).filter(|s| !s.is_empty()).map(String::from),
+        contexts,
+    }
+}
+
+pub async fn list_bridges(instance_url: &str) -> Result<Vec<BridgeInfo>, String> {
+    let client = crate::get_or_init_client()?;
+    let response = client
+        .get(format!("{instance_url}/"))
+        .query(&[("action", "list")])
+        .send()
+        .await
+        .map_err(|e| format!("RSS-Bridge request failed: {e}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("RSS-Bridge HTTP {status}"));
+    }
+    let json: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Not an RSS-Bridge instance (invalid list response): {e}"))?;
+    let bridges = json
+        .get("bridges")
+        .and_then(Value::as_object)
+        .ok_or("Not an RSS-Bridge instance (missing bridge list)")?;
+
+    let mut list: Vec<BridgeInfo> = bridges
+        .iter()
+        // Whitelisting on the instance marks unavailable bridges as inactive
+        .filter(|(_, meta)| meta.get("status").and_then(Value::as_str) != Some("inactive"))
+        .map(|(id, meta)| parse_bridge(id, meta))
+        .collect();
+    list.sort_by_key(|b| b.name.to_lowercase());
+    Ok(list)
+}
+
+/// Build the `action=display` URL, checking required parameters when metadata is known.
+pub fn build_feed_url(
+    instance_url: &str,
+    bridge: Option<&BridgeInfo>,
+    bridge_id: &str,
+    context: &str,
+    params: &BTreeMap<String, String>,
+) -> Result<String, String> {
+    if let Some(info) = bridge {
+        let ctx = info
+            .contexts
+            .iter()
+            .find(|c| c.name == context)
+            .ok_or_else(|| format!("Unknown context '{context}' for {bridge_id}"))?;
+        for p in ctx.params.iter().filter(|p| p.required) {
+            if params.get(&p.key).is_none_or(|v| v.trim().is_empty()) {
+                return Err(format!("'{}' is required", p.label));
+            }
+        }
+    }
+
+    let mut url = url::Url::parse(&format!("{instance_url}/")).map_err(|e| format!("Invalid instance URL: {e}"))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query.append_pair("action", "display").append_pair("bridge", bridge_id);
+        if !context.is_empty() {
+            query.append_pair("context", context);
+        }
+        for (k, v) in params.iter().filter(|(_, v)| !v.is_empty()) {
+            query.append_pair(k, v);
+        }
+        query.append_pair("format", "Atom");
+    }
+    Ok(url.to_string())
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn rss_bridge_get_config(store: tauri::State<'_, Arc<RssBridgeStore>>) -> RssBridgeConfig {
+    store.get_config()
+}
+
+/// Point at an RSS-Bridge instance. The instance is probed before saving.
+#[tauri::command]
+pub async fn rss_bridge_set_instance(
+    instance_url: String,
+    store: tauri::State<'_, Arc<RssBridgeStore>>,
+) -> Result<usize, String> {
+    let instance_url = instance_url.trim().trim_end_matches('/').to_string();
+    url::Url::parse(&instance_url).map_err(|e| format!("Invalid instance URL: {e}"))?;
+    let bridges = list_bridges(&instance_url).await?;
+    let count = bridges.len();
+    store.set_instance(instance_url);
+    *store.bridges.lock().unwrap() = bridges;
+    Ok(count)
+}
+
+/// Available bridges with their parameter forms.
+#[tauri::command]
+pub async fn rss_bridge_list_bridges(
+    store: tauri::State<'_, Arc<RssBridgeStore>>,
+) -> Result<Vec<BridgeInfo>, String> {
+    let bridges = list_bridges(&store.instance_url()?).await?;
+    *store.bridges.lock().unwrap() = bridges.clone();
+    Ok(bridges)
+}
+
+#[tauri::command]
+pub fn rss_bridge_build_url(
+    bridge: String,
+    context: String,
+    params: BTreeMap<String, String>,
+    store: tauri::State<'_, Arc<RssBridgeStore>>,
+) -> Result<String, String> {
+    let bridges = store.bridges.lock().unwrap();
+    let info = bridges.iter().find(|b| b.id == bridge);
+    build_feed_url(&store.instance_url()?, info, &bridge, &context, &params)
+}
+
+#[tauri::command]
+pub fn rss_bridge_subscribe(
+    name: String,
+    bridge: String,
+    context: String,
+    params: BTreeMap<String, String>,
+    store: tauri::State<'_, Arc<RssBridgeStore>>,
+) -> Result<BridgeSubscription, String> {
+    let feed_url = {
+        let bridges = store.bridges.lock().unwrap();
+        let info = bridges.iter().find(|b| b.id == bridge);
+        build_feed_url(&store.instance_url()?, info, &bridge, &context, &params)?
+    };
+    let sub = BridgeSubscription {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: if name.trim().is_empty() { bridge.clone() } else { name.trim().to_string() },
+        bridge,
+        context,
+        params,
+        feed_url,
+    };
+    store.add_subscription(sub.clone());
+    Ok(sub)
+}
+
+#[tauri::command]
+pub fn rss_bridge_unsubscribe(id: String, store: tauri::State<'_, Arc<RssBridgeStore>>) -> bool {
+    store.remove_subscription(&id)
+}
@@ -0,0 +1,313 @@
+use ego_tree::NodeId;
+use scraper::{Html, Node};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use url::Url;
+
+// Article HTML scrubbed with ammonia before the frontend displays it. Scripts,
+// styles, event handlers and unknown tags go; what stays is decided by the
+// policy: iframes only from allowed hosts (YouTube, Vimeo, ...), optionally
+// media and inline styles. The MathML and highlighting classes the ingest
+// step produces (`math`, `highlight`) are always kept.
+
+const POLICY_FILE: &str = "sanitize_policy.json";
+
+/// Tags beyond ammonia's defaults that article content needs.
+const MEDIA_TAGS: &[&str] = &["video", "audio", "source", "track"];
+const MATHML_TAGS: &[&str] = &[
+    "math", "semantics", "annotation", "mrow", "mi", "mn", "mo", "mtext", "mspace", "mfrac", "msqrt", "mroot",
+    "msub", "msup", "msubsup", "munder", "mover", "munderover", "mtable", "mtr", "mtd", "mphantom", "merror",
+    "mstyle", "mpadded",
+];
+const TAG_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("img", &["srcset", "sizes", "loading", "decoding"]),
+    ("video", &["src", "poster", "controls", "width", "height", "loop", "muted", "playsinline", "preload"]),
+    ("audio", &["src", "controls", "loop", "muted", "preload"]),
+    ("source", &["src", "srcset", "sizes", "type", "media"]),
+    ("track", &["src", "kind", "srclang", "label", "default"]),
+    ("iframe", &["src", "width", "height", "title", "allowfullscreen", "loading"]),
+    ("code", &["class"]),
+    ("pre", &["class"]),
+    ("span", &["class"]),
+    ("math", &["display"]),
+    ("mi", &["mathvariant"]),
+    ("mo", &["fence", "stretchy", "largeop", "movablelimits", "accent", "form", "lspace", "rspace"]),
+    ("mfrac", &["linethickness"]),
+    ("mover", &["accent"]),
+    ("munder", &["accentunder"]),
+    ("munderover", &["accent", "accentunder"]),
+    ("mspace", &["width", "height", "depth"]),
+    ("mtable", &["columnalign", "rowalign"]),
+    ("mtd", &["columnalign", "columnspan", "rowspan"]),
+    ("mstyle", &["displaystyle", "scriptlevel"]),
+    ("annotation", &["encoding"]),
+];
+/// Embeds run scripts, so they get the least that players need.
+const IFRAME_SANDBOX: &str = "allow-scripts allow-same-origin allow-popups allow-presentation";
+/// Classes kept from the input: syntax highlighting and code languages.
+const KEPT_CLASS_PREFIXES: &[&str] = &["sf-hl", "language-"];
+
+/// Hosts that only serve tracking pixels, matched with their subdomains.
+const TRACKER_HOSTS: &[&str] = &[
+    "pixel.wp.com",
+    "stats.wordpress.com",
+    "google-analytics.com",
+    "doubleclick.net",
+    "pixel.quantserve.com",
+    "scorecardresearch.com",
+    "ct.pinterest.com",
+    "px.ads.linkedin.com",
+    "analytics.twitter.com",
+    "pixel.mathtag.com",
+    "rss.buysellads.com",
+    "feeds.feedblitz.com",
+];
+/// Path prefixes of feed-service pixels and share buttons on otherwise useful hosts.
+const TRACKER_PATHS: &[(&str, &str)] = &[
+    ("feeds.feedburner.com", "/~r/"),
+    ("feeds.feedburner.com", "/~ff/"),
+    ("feedproxy.google.com", "/~r/"),
+    ("www.facebook.com", "/tr"),
+];
+/// Query parameters that only identify the campaign or the click.
+const TRACKING_PARAMS: &[&str] =
+    &["fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "_hsenc", "_hsmi", "igshid"];
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct SanitizePolicy {
+    /// Hosts whose iframes are kept, subdomains included. Empty = no iframes.
+    pub iframe_hosts: Vec<String>,
+    pub images: bool,
+    /// `<video>`, `<audio>` and their sources.
+    pub media: bool,
+    /// Drop tracking pixels and strip `utm_*`-style parameters from links.
+    pub strip_trackers: bool,
+    /// Keep `style` attributes. `<style>` blocks are always removed.
+    pub inline_styles: bool,
+    /// More tags to allow, e.g. custom elements a feed relies on.
+    pub extra_tags: Vec<String>,
+    /// More attributes to allow on every tag.
+    pub extra_attributes: Vec<String>,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        SanitizePolicy {
+            iframe_hosts: ["youtube.com", "youtube-nocookie.com", "player.vimeo.com", "w.soundcloud.com"]
+                .map(String::from)
+                .to_vec(),
+            images: true,
+            media: true,
+            strip_trackers: true,
+            inline_styles: false,
+            extra_tags: Vec::new(),
+            extra_attributes: Vec::new(),
+        }
+    }
+}
+
+static POLICY: OnceLock<Mutex<SanitizePolicy>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn policy_lock() -> &'static Mutex<SanitizePolicy> {
+    POLICY.get_or_init(|| Mutex::new(SanitizePolicy::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(POLICY_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(policy) = serde_json::from_str::<SanitizePolicy>(&json) {
+            *policy_lock().lock().unwrap() = policy;
+        }
+    }
+}
+
+fn save_policy(policy: &SanitizePolicy) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json =
+        serde_json::to_string_pretty(policy).map_err(|e| format!("Failed to serialize sanitize policy: {e}"))?;
+    std::fs::write(dir.join(POLICY_FILE), json).map_err(|e| format!("Failed to save sanitize policy: {e}"))
+}
+
+// ── Filtering ────────────────────────────────────────────────────────
+
+fn host_matches(host: &str, allowed: &str) -> bool {
+    let allowed = allowed.trim().trim_start_matches("www.").to_ascii_lowercase();
+    let host = host.trim_start_matches("www.");
+    !allowed.is_empty() && (host == allowed || host.ends_with(&format!(".{allowed}")))
+}
+
+fn is_tracker(src: &str) -> bool {
+    let Ok(url) = Url::parse(src) else { return false };
+    let Some(host) = url.host_str() else { return false };
+    TRACKER_HOSTS.iter().any(|t| host_matches(host, t))
+        || TRACKER_PATHS.iter().any(|(h, path)| host == *h && url.path().starts_with(path))
+}
+
+/// 1×1 images, the usual shape of a tracking pixel.
+fn is_pixel(el: &scraper::node::Element) -> bool {
+    let tiny = |name| {
+        let value = el.attr(name).map(|v| v.trim().trim_end_matches("px"));
+        value.is_some_and(|v| v.parse::<u32>().is_ok_and(|n| n <= 1))
+    };
+    tiny("width") && tiny("height")
+}
+
+/// Remove iframes from hosts not on the list and, with `strip_trackers`,
+/// tracking pixels. ammonia can only drop attributes, which would leave empty
+/// frames and broken images behind.
+fn remove_elements<'h>(html: &'h str, policy: &SanitizePolicy) -> Cow<'h, str> {
+    let may_have_pixels = policy.strip_trackers && html.contains("<img");
+    if !(html.contains("<iframe") || may_have_pixels) {
+        return Cow::Borrowed(html);
+    }
+    let mut fragment = Html::parse_fragment(html);
+    let doomed: Vec<NodeId> = fragment
+        .tree
+        .nodes()
+        .filter(|node| match node.value() {
+            Node::Element(el) if el.name() == "iframe" => {
+                let src = el.attr("src").map(secure_embed_url).and_then(|s| Url::parse(&s).ok());
+                let host = src.as_ref().and_then(|u| u.host_str());
+                !host.is_some_and(|h| policy.iframe_hosts.iter().any(|a| host_matches(h, a)))
+            }
+            Node::Element(el) if el.name() == "img" && policy.strip_trackers => {
+                is_pixel(el) || el.attr("src").is_some_and(|s| is_tracker(s.trim()))
+            }
+            _ => false,
+        })
+        .map(|node| node.id())
+        .collect();
+    if doomed.is_empty() {
+        return Cow::Borrowed(html);
+    }
+    for id in doomed {
+        if let Some(mut node) = fragment.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    Cow::Owned(fragment.root_element().inner_html())
+}
+
+/// Embeds load over https; old feeds still carry `http://` and `//` players.
+fn secure_embed_url(src: &str) -> String {
+    let src = src.trim();
+    match src.strip_prefix("http://").or_else(|| src.strip_prefix("//")) {
+        Some(rest) => format!("https://{rest}"),
+        None => src.to_string(),
+    }
+}
+
+/// Drop campaign and click identifiers from a link.
+fn strip_tracking_params(href: &str) -> Option<String> {
+    let mut url = Url::parse(href).ok()?;
+    let is_tracking = |key: &str| key.starts_with("utm_") || TRACKING_PARAMS.contains(&key);
+    if !url.query_pairs().any(|(k, _)| is_tracking(&k)) {
+        return None;
+    }
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| !is_tracking(k))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    Some(url.to_string())
+}
+
+fn filter_attribute<'u>(
+    element: &str,
+    attribute: &str,
+    value: &'u str,
+    strip_trackers: bool,
+) -> Option<Cow<'u, str>> {
+    match (element, attribute) {
+        (_, "class") => {
+            let kept: Vec<&str> = value
+                .split_whitespace()
+                .filter(|c| KEPT_CLASS_PREFIXES.iter().any(|p| c.starts_with(p)))
+                .collect();
+            (!kept.is_empty()).then(|| Cow::Owned(kept.join(" ")))
+        }
+        ("iframe", "src") => Some(Cow::Owned(secure_embed_url(value))),
+        ("a", "href") if strip_trackers => match strip_tracking_params(value) {
+            Some(stripped) => Some(Cow::Owned(stripped)),
+            None => Some(Cow::Borrowed(value)),
+        },
+        _ => Some(Cow::Borrowed(value)),
+    }
+}
+
+/// `<script>` and `<style>` are removed with their content; ammonia refuses to also allow them.
+fn is_content_removed(tag: &str) -> bool {
+    matches!(tag.to_ascii_lowercase().as_str(), "script" | "style")
+}
+
+/// Scrub `html` according to `policy`.
+pub fn sanitize(html: &str, policy: &SanitizePolicy) -> String {
+    let html = remove_elements(html, policy);
+
+    let mut builder = ammonia::Builder::default();
+    builder.add_tags(MATHML_TAGS);
+    for (tag, attributes) in TAG_ATTRIBUTES {
+        builder.add_tag_attributes(*tag, attributes.iter());
+    }
+    if policy.images {
+        builder.add_tags(&["picture", "source"]);
+    } else {
+        builder.rm_tags(&["img", "map", "area"]);
+    }
+    if policy.media {
+        builder.add_tags(MEDIA_TAGS);
+    }
+    if !policy.iframe_hosts.is_empty() {
+        builder
+            .add_tags(&["iframe"])
+            .set_tag_attribute_value("iframe", "sandbox", IFRAME_SANDBOX)
+            // YouTube refuses to play embeds that send no referrer
+            .set_tag_attribute_value("iframe", "referrerpolicy", "strict-origin-when-cross-origin");
+    }
+    if policy.inline_styles {
+        builder.add_generic_attributes(&["style"]);
+    }
+    builder.add_tags(policy.extra_tags.iter().filter(|t| !is_content_removed(t)));
+    builder.add_generic_attributes(policy.extra_attributes.iter());
+    let strip_trackers = policy.strip_trackers;
+    builder.attribute_filter(move |element, attribute, value| {
+        filter_attribute(element, attribute, value, strip_trackers)
+    });
+    builder.clean(&html).to_string()
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_sanitize_policy() -> SanitizePolicy {
+    policy_lock().lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_sanitize_policy(policy: SanitizePolicy) -> Result<(), String> {
+    if let Some(tag) = policy.extra_tags.iter().find(|t| is_content_removed(t)) {
+        return Err(format!("<{tag}> can't be allowed"));
+    }
+    save_policy(&policy)?;
+    *policy_lock().lock().unwrap() = policy;
+    Ok(())
+}
+
+/// Scrub article HTML for display, with `policy` or the saved one.
+#[tauri::command]
+pub async fn sanitize_html(html: String, policy: Option<SanitizePolicy>) -> Result<String, String> {
+    let policy = policy.unwrap_or_else(get_sanitize_policy);
+    tauri::async_runtime::spawn_blocking(move || sanitize(&html, &policy))
+        .await
+        .map_err(|e| format!("Sanitizer failed: {e}"))
+}
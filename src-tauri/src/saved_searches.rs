@@ -0,0 +1,315 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+use crate::db::{self, ArticleQuery, ArticleRow, Database};
+use crate::ingest::now_millis;
+use crate::notifications::{self, PushMessage};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SEARCHES_FILE: &str = "saved_searches.json";
+/// Article titles listed in one alert; the rest are only counted.
+const MAX_ALERT_TITLES: usize = 3;
+
+/// A search kept for reuse. Newly stored articles are checked against it, and
+/// the reader can list its matches as a virtual folder.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    /// Free text, matched like the search box: every word must occur.
+    pub query: String,
+    /// Only match articles from these feeds. None = all feeds.
+    #[serde(default)]
+    pub feed_ids: Option<Vec<String>>,
+    /// Alert on new matches: desktop notification, push targets and Matrix rooms.
+    #[serde(default)]
+    pub notify: bool,
+    /// List as a virtual folder in the sidebar.
+    #[serde(default = "default_show_as_folder")]
+    pub show_as_folder: bool,
+    #[serde(default)]
+    pub created_at: u64,
+    /// Last time a new article matched (ms since epoch).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_match_at: Option<u64>,
+}
+
+fn default_show_as_folder() -> bool {
+    true
+}
+
+/// Payload of `saved-search-match`, emitted for every search with new hits.
+#[derive(Clone, Serialize, Debug)]
+pub struct SavedSearchMatch {
+    pub search_id: String,
+    pub name: String,
+    pub article_ids: Vec<String>,
+}
+
+struct Hit {
+    id: String,
+    title: String,
+    url: String,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct SavedSearchStore {
+    searches: Mutex<Vec<SavedSearch>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl SavedSearchStore {
+    pub fn new() -> Self {
+        SavedSearchStore {
+            searches: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SEARCHES_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(searches) = serde_json::from_str::<Vec<SavedSearch>>(&json) {
+                            eprintln!("[saved_searches] Loaded {} saved searches", searches.len());
+                            *self.searches.lock().unwrap() = searches;
+                        }
+                    }
+                    Err(e) => eprintln!("[saved_searches] Failed to read saved searches: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let searches = self.searches.lock().unwrap();
+            match serde_json::to_string_pretty(&*searches) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[saved_searches] Failed to write saved searches: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[saved_searches] Failed to serialize saved searches: {e}"),
+            }
+        }
+    }
+
+    pub fn list(&self) -> Vec<SavedSearch> {
+        self.searches.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<SavedSearch> {
+        self.searches.lock().unwrap().iter().find(|s| s.id == id).cloned()
+    }
+
+    /// Insert or replace a search. An empty id gets a fresh UUID.
+    pub fn upsert(&self, mut search: SavedSearch) -> SavedSearch {
+        if search.id.is_empty() {
+            search.id = uuid::Uuid::new_v4().to_string();
+            search.created_at = now_millis();
+        }
+        search.name = search.name.trim().to_string();
+        search.query = search.query.trim().to_string();
+        if search.name.is_empty() {
+            search.name = search.query.clone();
+        }
+        {
+            let mut searches = self.searches.lock().unwrap();
+            match searches.iter_mut().find(|s| s.id == search.id) {
+                Some(existing) => {
+                    search.created_at = existing.created_at;
+                    search.last_match_at = existing.last_match_at;
+                    *existing = search.clone();
+                }
+                None => searches.push(search.clone()),
+            }
+        }
+        self.save_to_disk();
+        search
+    }
+
+    pub fn delete(&self, id: &str) -> bool {
+        let mut searches = self.searches.lock().unwrap();
+        let before = searches.len();
+        searches.retain(|s| s.id != id);
+        let removed = searches.len() < before;
+        drop(searches);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+
+    fn record_match(&self, id: &str) {
+        if let Some(search) = self.searches.lock().unwrap().iter_mut().find(|s| s.id == id) {
+            search.last_match_at = Some(now_millis());
+        }
+        self.save_to_disk();
+    }
+}
+
+// ── Matching ─────────────────────────────────────────────────────────
+
+/// The articles among `candidates` that `search` matches.
+fn matching(conn: &Connection, search: &SavedSearch, candidates: &[String]) -> rusqlite::Result<Vec<Hit>> {
+    let Some(expr) = crate::search::match_expression(&search.query) else {
+        return Ok(Vec::new());
+    };
+    let candidates = serde_json::to_string(candidates).unwrap_or_default();
+    let feeds = search.feed_ids.as_ref().map(|ids| serde_json::to_string(ids).unwrap_or_default());
+    let mut stmt = conn.prepare_cached(
+        "SELECT a.id, a.title, a.url FROM search_index
+         JOIN search_docs d ON d.rowid = search_index.rowid
+         JOIN articles a ON a.id = d.article_id
+         WHERE search_index MATCH ?1
+           AND a.id IN (SELECT value FROM json_each(?2))
+           AND (?3 IS NULL OR a.feed_id IN (SELECT value FROM json_each(?3)))",
+    )?;
+    let hits = stmt.query_map(params![expr, candidates, feeds], |r| {
+        Ok(Hit { id: r.get(0)?, title: r.get(1)?, url: r.get(2)? })
+    })?;
+    hits.collect()
+}
+
+fn unread_count(conn: &Connection, search: &SavedSearch) -> rusqlite::Result<u32> {
+    let Some(expr) = crate::search::match_expression(&search.query) else {
+        return Ok(0);
+    };
+    let feeds = search.feed_ids.as_ref().map(|ids| serde_json::to_string(ids).unwrap_or_default());
+    conn.prepare_cached(
+        "SELECT COUNT(*) FROM articles
+         WHERE is_read = 0 AND is_hidden = 0
+           AND id IN (SELECT d.article_id FROM search_index JOIN search_docs d ON d.rowid = search_index.rowid
+                      WHERE search_index MATCH ?1)
+           AND (?2 IS NULL OR feed_id IN (SELECT value FROM json_each(?2)))",
+    )?
+    .query_row(params![expr, feeds], |r| r.get(0))
+}
+
+fn alert_message(search: &SavedSearch, hits: &[Hit]) -> PushMessage {
+    let title = match hits.len() {
+        1 => format!("{}: 1 new article", search.name),
+        n => format!("{}: {n} new articles", search.name),
+    };
+    let mut lines: Vec<String> = hits.iter().take(MAX_ALERT_TITLES).map(|h| h.title.clone()).collect();
+    if hits.len() > MAX_ALERT_TITLES {
+        lines.push(format!("and {} more", hits.len() - MAX_ALERT_TITLES));
+    }
+    let click_url = match hits {
+        [hit] if !hit.url.is_empty() => Some(hit.url.clone()),
+        _ => None,
+    };
+    PushMessage { title, message: lines.join("\n"), click_url }
+}
+
+/// Check newly stored articles against the saved searches: each search with
+/// hits emits `saved-search-match` and, if it asks to, raises an alert.
+pub async fn check_new_articles(app: tauri::AppHandle, ids: Vec<String>) {
+    let store = app.state::<Arc<SavedSearchStore>>().inner().clone();
+    let searches = store.list();
+    if ids.is_empty() || searches.is_empty() {
+        return;
+    }
+    let db = app.state::<Arc<Database>>().inner().clone();
+    let found = db::run(&db, move |conn| {
+        let mut found = Vec::new();
+        for search in searches {
+            let hits = matching(conn, &search, &ids)?;
+            if !hits.is_empty() {
+                found.push((search, hits));
+            }
+        }
+        Ok(found)
+    })
+    .await;
+    let found = match found {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("[saved_searches] Matching new articles failed: {e}");
+            return;
+        }
+    };
+    for (search, hits) in found {
+        store.record_match(&search.id);
+        let payload = SavedSearchMatch {
+            search_id: search.id.clone(),
+            name: search.name.clone(),
+            article_ids: hits.iter().map(|h| h.id.clone()).collect(),
+        };
+        let _ = app.emit("saved-search-match", payload);
+        if search.notify {
+            notifications::raise_alert(&app, &alert_message(&search, &hits)).await;
+        }
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_saved_searches(store: tauri::State<'_, Arc<SavedSearchStore>>) -> Vec<SavedSearch> {
+    store.list()
+}
+
+#[tauri::command]
+pub fn save_saved_search(
+    search: SavedSearch,
+    store: tauri::State<'_, Arc<SavedSearchStore>>,
+) -> Result<SavedSearch, String> {
+    if crate::search::match_expression(&search.query).is_none() {
+        return Err("The search needs at least one word".into());
+    }
+    Ok(store.upsert(search))
+}
+
+#[tauri::command]
+pub fn delete_saved_search(id: String, store: tauri::State<'_, Arc<SavedSearchStore>>) -> bool {
+    store.delete(&id)
+}
+
+/// The virtual folder: articles matching a saved search. `query` pages and
+/// filters as in `db_query_articles`; its search and feed fields are replaced.
+#[tauri::command]
+pub async fn get_saved_search_articles(
+    id: String,
+    query: Option<ArticleQuery>,
+    store: tauri::State<'_, Arc<SavedSearchStore>>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<Vec<ArticleRow>, String> {
+    let search = store.get(&id).ok_or("Saved search not found")?;
+    let query = ArticleQuery {
+        search: Some(search.query),
+        feed_ids: search.feed_ids,
+        ..query.unwrap_or_default()
+    };
+    db::run(&db, move |conn| db::query_articles(conn, &query)).await
+}
+
+/// Unread matches per saved search shown as a folder, keyed by search id.
+#[tauri::command]
+pub async fn get_saved_search_counts(
+    store: tauri::State<'_, Arc<SavedSearchStore>>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<HashMap<String, u32>, String> {
+    let searches: Vec<SavedSearch> = store.list().into_iter().filter(|s| s.show_as_folder).collect();
+    db::run(&db, move |conn| {
+        searches.iter().map(|search| Ok((search.id.clone(), unread_count(conn, search)?))).collect()
+    })
+    .await
+}
@@ -0,0 +1,514 @@
+use jieba_rs::Jieba;
+use rusqlite::{params, Connection};
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::db::{self, Database};
+use crate::text::html_to_text;
+
+// Full-text search over articles. Titles, authors and bodies are folded before
+// they reach the FTS5 index — compatibility-normalized, lowercased, accents
+// stripped, optionally transliterated to Latin — and queries get the same
+// treatment, so "Zurich" finds "Zürich" and "moskva" can find "Москва".
+// Chinese, Japanese and Korean have no spaces between words, so their runs are
+// segmented first: jieba for Chinese, overlapping bigrams for Japanese/Korean.
+// Word stems in the chosen languages are indexed alongside the words, and
+// user-defined synonyms widen each query term when the query is built.
+
+const SETTINGS_FILE: &str = "search_settings.json";
+/// Characters of an article the language detector looks at.
+const DETECT_SAMPLE_CHARS: usize = 1000;
+
+/// How CJK text is split into words. `Auto` picks per article with the
+/// language detector; the others force one language for every feed.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CjkMode {
+    #[default]
+    Auto,
+    Chinese,
+    Japanese,
+    Korean,
+    /// Leave CJK runs whole (only exact matches on whole runs work).
+    Off,
+}
+
+/// Snowball stemmer languages.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StemLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+impl StemLanguage {
+    fn algorithm(self) -> Algorithm {
+        match self {
+            StemLanguage::Arabic => Algorithm::Arabic,
+            StemLanguage::Danish => Algorithm::Danish,
+            StemLanguage::Dutch => Algorithm::Dutch,
+            StemLanguage::English => Algorithm::English,
+            StemLanguage::Finnish => Algorithm::Finnish,
+            StemLanguage::French => Algorithm::French,
+            StemLanguage::German => Algorithm::German,
+            StemLanguage::Greek => Algorithm::Greek,
+            StemLanguage::Hungarian => Algorithm::Hungarian,
+            StemLanguage::Italian => Algorithm::Italian,
+            StemLanguage::Norwegian => Algorithm::Norwegian,
+            StemLanguage::Portuguese => Algorithm::Portuguese,
+            StemLanguage::Romanian => Algorithm::Romanian,
+            StemLanguage::Russian => Algorithm::Russian,
+            StemLanguage::Spanish => Algorithm::Spanish,
+            StemLanguage::Swedish => Algorithm::Swedish,
+            StemLanguage::Tamil => Algorithm::Tamil,
+            StemLanguage::Turkish => Algorithm::Turkish,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct SearchSettings {
+    /// Index and search Cyrillic and Greek text by its Latin transliteration.
+    pub transliterate: bool,
+    pub cjk: CjkMode,
+    /// Also index word stems in these languages, so "running" finds "runs".
+    pub stemming: Vec<StemLanguage>,
+    /// Groups of interchangeable words or phrases, e.g. `[["nyc", "new york"]]`.
+    /// Only applied to queries, so editing them needs no reindex.
+    pub synonyms: Vec<Vec<String>>,
+}
+
+impl SearchSettings {
+    /// Whether both produce the same index terms.
+    fn same_index(&self, other: &SearchSettings) -> bool {
+        self.transliterate == other.transliterate && self.cjk == other.cjk && self.stemming == other.stemming
+    }
+}
+
+static SETTINGS: OnceLock<Mutex<SearchSettings>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn settings_lock() -> &'static Mutex<SearchSettings> {
+    SETTINGS.get_or_init(|| Mutex::new(SearchSettings::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(SETTINGS_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(settings) = serde_json::from_str::<SearchSettings>(&json) {
+            *settings_lock().lock().unwrap() = settings;
+        }
+    }
+}
+
+fn settings() -> SearchSettings {
+    settings_lock().lock().unwrap().clone()
+}
+
+// ── Folding ──────────────────────────────────────────────────────────
+
+/// Letters that don't decompose but have a conventional plain-Latin spelling.
+fn latin_fold(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ø' => "o",
+        'ł' => "l",
+        'đ' | 'ð' => "d",
+        'þ' => "th",
+        'ı' => "i",
+        'ħ' => "h",
+        'ŧ' => "t",
+        _ => return None,
+    })
+}
+
+/// Lowercase Cyrillic (Russian, Ukrainian, Serbian) and Greek to Latin.
+const TRANSLITERATION: &[(char, &str)] = &[
+    ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"), ('е', "e"), ('ё', "e"), ('ж', "zh"),
+    ('з', "z"), ('и', "i"), ('й', "y"), ('к', "k"), ('л', "l"), ('м', "m"), ('н', "n"), ('о', "o"),
+    ('п', "p"), ('р', "r"), ('с', "s"), ('т', "t"), ('у', "u"), ('ф', "f"), ('х', "kh"), ('ц', "ts"),
+    ('ч', "ch"), ('ш', "sh"), ('щ', "shch"), ('ъ', ""), ('ы', "y"), ('ь', ""), ('э', "e"), ('ю', "yu"),
+    ('я', "ya"), ('є', "ye"), ('і', "i"), ('ї', "yi"), ('ґ', "g"), ('ђ', "dj"), ('ј', "j"), ('љ', "lj"),
+    ('њ', "nj"), ('ћ', "c"), ('џ', "dz"),
+    ('α', "a"), ('β', "v"), ('γ', "g"), ('δ', "d"), ('ε', "e"), ('ζ', "z"), ('η', "i"), ('θ', "th"),
+    ('ι', "i"), ('κ', "k"), ('λ', "l"), ('μ', "m"), ('ν', "n"), ('ξ', "x"), ('ο', "o"), ('π', "p"),
+    ('ρ', "r"), ('σ', "s"), ('ς', "s"), ('τ', "t"), ('υ', "y"), ('φ', "f"), ('χ', "ch"), ('ψ', "ps"),
+    ('ω', "o"),
+];
+
+fn transliteration(c: char) -> Option<&'static str> {
+    TRANSLITERATION.iter().find(|(from, _)| *from == c).map(|(_, to)| *to)
+}
+
+fn push_mapped(out: &mut String, c: char, transliterate: bool) -> bool {
+    match latin_fold(c).or_else(|| transliterate.then(|| transliteration(c)).flatten()) {
+        Some(s) => {
+            out.push_str(s);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Search form of `text`: NFKD, lowercase, combining marks dropped, special
+/// letters spelled out, and Cyrillic/Greek transliterated when asked.
+pub fn fold(text: &str, transliterate: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        // NFKD would split Hangul into jamo and strip kana voicing marks
+        if is_cjk(c) {
+            out.push(c);
+            continue;
+        }
+        // Whole letters first, so й stays "y" instead of losing its breve to "i"
+        if push_mapped(&mut out, c, transliterate) {
+            continue;
+        }
+        for d in std::iter::once(c).nfkd().filter(|d| !is_combining_mark(*d)) {
+            if !push_mapped(&mut out, d, transliterate) {
+                out.push(d);
+            }
+        }
+    }
+    out
+}
+
+// ── CJK segmentation ─────────────────────────────────────────────────
+
+fn is_kana(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}')
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c, '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}')
+}
+
+fn is_han(c: char) -> bool {
+    matches!(
+        c,
+        '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FFFF}'
+    )
+}
+
+fn is_cjk(c: char) -> bool {
+    is_han(c) || is_kana(c) || is_hangul(c)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Segmenter {
+    /// Dictionary words (Chinese).
+    Words,
+    /// Overlapping character pairs (Japanese, Korean).
+    Bigrams,
+}
+
+fn jieba() -> &'static Jieba {
+    static JIEBA: OnceLock<Jieba> = OnceLock::new();
+    JIEBA.get_or_init(Jieba::new)
+}
+
+/// Segmenter for an article, from the setting or the detected language.
+fn segmenter_for(text: &str, mode: CjkMode) -> Option<Segmenter> {
+    match mode {
+        CjkMode::Chinese => Some(Segmenter::Words),
+        CjkMode::Japanese | CjkMode::Korean => Some(Segmenter::Bigrams),
+        CjkMode::Off => None,
+        CjkMode::Auto if !text.chars().any(is_cjk) => None,
+        CjkMode::Auto => match whatlang::detect_lang(text) {
+            Some(whatlang::Lang::Cmn) => Some(Segmenter::Words),
+            Some(whatlang::Lang::Jpn | whatlang::Lang::Kor) => Some(Segmenter::Bigrams),
+            // Mostly non-CJK text quoting a few characters: judge by script
+            _ if text.chars().any(|c| is_kana(c) || is_hangul(c)) => Some(Segmenter::Bigrams),
+            _ => Some(Segmenter::Words),
+        },
+    }
+}
+
+/// Segmenters to try for a query. Short queries are too small to detect, so
+/// Han-only ones are matched both ways when the setting is `Auto`.
+fn query_segmenters(query: &str, mode: CjkMode) -> Vec<Option<Segmenter>> {
+    match mode {
+        CjkMode::Auto if query.chars().any(|c| is_kana(c) || is_hangul(c)) => vec![Some(Segmenter::Bigrams)],
+        CjkMode::Auto if query.chars().any(is_han) => vec![Some(Segmenter::Words), Some(Segmenter::Bigrams)],
+        CjkMode::Auto => vec![None],
+        mode => vec![segmenter_for(query, mode)],
+    }
+}
+
+fn push_cjk_run(terms: &mut Vec<String>, run: &str, segmenter: Option<Segmenter>) {
+    match segmenter {
+        None => terms.push(run.to_string()),
+        Some(Segmenter::Words) => terms.extend(
+            jieba()
+                .cut_for_search(run, true)
+                .into_iter()
+                .filter(|w| w.chars().any(char::is_alphanumeric))
+                .map(String::from),
+        ),
+        Some(Segmenter::Bigrams) => {
+            let chars: Vec<char> = run.chars().collect();
+            match chars.len() {
+                0 => {}
+                1 => terms.push(run.to_string()),
+                _ => terms.extend(chars.windows(2).map(|pair| pair.iter().collect())),
+            }
+        }
+    }
+}
+
+/// Split folded text into index terms: words as usual, CJK runs per `segmenter`.
+fn terms(folded: &str, segmenter: Option<Segmenter>) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in folded.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        // A word can mix scripts ("iphone手机"); segment only its CJK stretches
+        let mut rest = word;
+        while let Some(start) = rest.find(is_cjk) {
+            if start > 0 {
+                terms.push(rest[..start].to_string());
+            }
+            let cjk = &rest[start..];
+            let end = cjk.find(|c: char| !is_cjk(c)).unwrap_or(cjk.len());
+            push_cjk_run(&mut terms, &cjk[..end], segmenter);
+            rest = &cjk[end..];
+        }
+        if !rest.is_empty() {
+            terms.push(rest.to_string());
+        }
+    }
+    terms
+}
+
+// ── Stemming and synonyms ────────────────────────────────────────────
+
+fn stemmers(languages: &[StemLanguage]) -> Vec<Stemmer> {
+    languages.iter().map(|language| Stemmer::create(language.algorithm())).collect()
+}
+
+/// The distinct stems of `term` that differ from it.
+fn stems(term: &str, stemmers: &[Stemmer]) -> Vec<String> {
+    let mut stems: Vec<String> = Vec::new();
+    for stemmer in stemmers {
+        let stem = stemmer.stem(term);
+        if !stem.is_empty() && stem != term && !stems.iter().any(|s| *s == stem) {
+            stems.push(stem.into_owned());
+        }
+    }
+    stems
+}
+
+/// Synonym groups split into terms the way queries are, dropping empty members.
+fn synonym_terms(settings: &SearchSettings, segmenter: Option<Segmenter>) -> Vec<Vec<Vec<String>>> {
+    settings
+        .synonyms
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|member| terms(&fold(member, settings.transliterate), segmenter))
+                .filter(|member| !member.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|group| group.len() > 1)
+        .collect()
+}
+
+/// FTS5 string for a run of terms: a prefix phrase, so the last word may be partial.
+fn phrase(terms: &[String]) -> String {
+    format!("\"{}\"*", terms.join(" "))
+}
+
+/// Query terms with their alternatives: at each position, the longest synonym
+/// member matching there is swapped for all of its group; any other term also
+/// matches its stems.
+fn expand(terms: &[String], synonyms: &[Vec<Vec<String>>], stemmers: &[Stemmer]) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut i = 0;
+    while i < terms.len() {
+        let found = synonyms
+            .iter()
+            .flat_map(|group| group.iter().map(move |member| (group, member)))
+            .filter(|(_, member)| terms[i..].starts_with(member))
+            .max_by_key(|(_, member)| member.len());
+        let (alternatives, len) = match found {
+            Some((group, member)) => (group.iter().map(|m| phrase(m)).collect(), member.len()),
+            None => {
+                let term = &terms[i];
+                let stems = stems(term, stemmers).into_iter().map(|stem| format!("\"{stem}\""));
+                (std::iter::once(format!("\"{term}\"*")).chain(stems).collect::<Vec<_>>(), 1)
+            }
+        };
+        parts.push(match alternatives.len() {
+            1 => alternatives.join(""),
+            _ => format!("({})", alternatives.join(" OR ")),
+        });
+        i += len;
+    }
+    parts
+}
+
+/// FTS5 query for free text: every word must match, as a prefix, or one of its
+/// stems or synonyms.
+pub fn match_expression(search: &str) -> Option<String> {
+    let settings = settings();
+    let folded = fold(search, settings.transliterate);
+    let stemmers = stemmers(&settings.stemming);
+    let mut groups: Vec<String> = Vec::new();
+    for segmenter in query_segmenters(&folded, settings.cjk) {
+        let synonyms = synonym_terms(&settings, segmenter);
+        // FTS5 only allows implicit AND between plain phrases, not parenthesized groups
+        let group = expand(&terms(&folded, segmenter), &synonyms, &stemmers).join(" AND ");
+        if !group.is_empty() && !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+    match groups.len() {
+        0 => None,
+        1 => groups.pop(),
+        _ => Some(groups.iter().map(|g| format!("({g})")).collect::<Vec<_>>().join(" OR ")),
+    }
+}
+
+// ── Index maintenance ────────────────────────────────────────────────
+
+/// (Re)index one article. `body_html` is its content, or summary when it has none.
+pub fn index_article(
+    conn: &Connection,
+    id: &str,
+    title: &str,
+    author: &str,
+    body_html: &str,
+    settings: &SearchSettings,
+) -> rusqlite::Result<()> {
+    let rowid: i64 = conn
+        .prepare_cached(
+            "INSERT INTO search_docs (article_id) VALUES (?1)
+             ON CONFLICT(article_id) DO UPDATE SET article_id = excluded.article_id
+             RETURNING rowid",
+        )?
+        .query_row([id], |r| r.get(0))?;
+    conn.prepare_cached("DELETE FROM search_index WHERE rowid = ?1")?.execute([rowid])?;
+    let body = html_to_text(body_html);
+    // Detected on the raw text, which is what the language model was trained on
+    let sample: String = title.chars().chain([' ']).chain(body.chars()).take(DETECT_SAMPLE_CHARS).collect();
+    let segmenter = segmenter_for(&sample, settings.cjk);
+    let [title, author, body] =
+        [title, author, body.as_str()].map(|text| terms(&fold(text, settings.transliterate), segmenter));
+    let stemmers = stemmers(&settings.stemming);
+    let stem_text: Vec<String> =
+        title.iter().chain(&author).chain(&body).flat_map(|term| stems(term, &stemmers)).collect();
+    conn.prepare_cached(
+        "INSERT INTO search_index (rowid, title, author, body, stems) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?
+    .execute(params![rowid, title.join(" "), author.join(" "), body.join(" "), stem_text.join(" ")])?;
+    Ok(())
+}
+
+/// Reindex the articles with these ids from what is stored for them.
+pub fn index_stored(conn: &Connection, ids: &[&str]) -> rusqlite::Result<()> {
+    let settings = settings();
+    let mut stmt = conn.prepare_cached(
+        "SELECT title, author, COALESCE(content, summary) FROM articles WHERE id = ?1",
+    )?;
+    for id in ids {
+        let (title, author, body): (String, String, String) =
+            stmt.query_row([id], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?;
+        index_article(conn, id, &title, &author, &body, &settings)?;
+    }
+    Ok(())
+}
+
+/// Rebuild the whole index, e.g. after the folding settings changed.
+pub fn reindex_all(conn: &mut Connection) -> rusqlite::Result<usize> {
+    let settings = settings();
+    let tx = conn.transaction()?;
+    tx.execute("INSERT INTO search_index (search_index) VALUES ('delete-all')", [])?;
+    tx.execute("DELETE FROM search_docs", [])?;
+    let mut count = 0;
+    {
+        let mut stmt = tx.prepare("SELECT id, title, author, COALESCE(content, summary) FROM articles")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let (id, title, author, body): (String, String, String, String) =
+                (row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?);
+            index_article(&tx, &id, &title, &author, &body, &settings)?;
+            count += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(count)
+}
+
+/// Index up to `limit` articles that have no search document yet: those
+/// stored before search existed or before a migration emptied the index.
+/// Returns how many were indexed.
+pub fn backfill_batch(conn: &mut Connection, limit: usize) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let ids: Vec<String> = tx
+        .prepare(
+            "SELECT id FROM articles a
+             WHERE NOT EXISTS (SELECT 1 FROM search_docs d WHERE d.article_id = a.id) LIMIT ?1",
+        )?
+        .query_map([limit as i64], |r| r.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    index_stored(&tx, &ids)?;
+    tx.commit()?;
+    Ok(ids.len())
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_search_settings() -> SearchSettings {
+    settings_lock().lock().unwrap().clone()
+}
+
+/// Save the search settings, rebuilding the index when the folding,
+/// segmentation or stemming changed.
+#[tauri::command]
+pub async fn set_search_settings(
+    settings: SearchSettings,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<(), String> {
+    if let Some(dir) = DATA_DIR.get() {
+        let json = serde_json::to_string_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize search settings: {e}"))?;
+        std::fs::write(dir.join(SETTINGS_FILE), json)
+            .map_err(|e| format!("Failed to save search settings: {e}"))?;
+    }
+    let previous = std::mem::replace(&mut *settings_lock().lock().unwrap(), settings.clone());
+    if !previous.same_index(&settings) {
+        db::run(&db, reindex_all).await?;
+    }
+    Ok(())
+}
+
+/// Rebuild the search index from the stored articles. Returns how many were indexed.
+#[tauri::command]
+pub async fn rebuild_search_index(db: tauri::State<'_, Arc<Database>>) -> Result<usize, String> {
+    db::run(&db, reindex_all).await
+}
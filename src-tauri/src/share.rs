@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::permissions::{host_permission, Permission, PermissionStore, Principal, PrincipalKind};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const TARGETS_FILE: &str = "share_targets.json";
+const DEFAULT_TEMPLATE: &str = "**{title}**\n{summary}\n{link}";
+/// Discord rejects `content` longer than 2000 chars; Slack section text is capped at 3000.
+const MAX_DISCORD_CONTENT: usize = 2000;
+const MAX_SLACK_TEXT: usize = 3000;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareTargetKind {
+    Discord,
+    Slack,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ShareTarget {
+    pub id: String,
+    pub name: String,
+    pub kind: ShareTargetKind,
+    pub webhook_url: String,
+    /// Message template. Placeholders: {title}, {summary}, {link}, {feed}, {image}.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SharedArticle {
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub feed_name: String,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct ShareStore {
+    targets: Mutex<Vec<ShareTarget>>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl ShareStore {
+    pub fn new() -> Self {
+        ShareStore {
+            targets: Mutex::new(Vec::new()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(TARGETS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(targets) = serde_json::from_str::<Vec<ShareTarget>>(&json) {
+                            eprintln!("[share] Loaded {} share targets", targets.len());
+                            *self.targets.lock().unwrap() = targets;
+                        }
+                    }
+                    Err(e) => eprintln!("[share] Failed to read share targets: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let targets = self.targets.lock().unwrap();
+            match serde_json::to_string_pretty(&*targets) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[share] Failed to write share targets: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[share] Failed to serialize share targets: {e}"),
+            }
+        }
+    }
+
+    pub fn get_targets(&self) -> Vec<ShareTarget> {
+        self.targets.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<ShareTarget> {
+        self.targets.lock().unwrap().iter().find(|t| t.id == id).cloned()
+    }
+
+    pub fn upsert(&self, mut target: ShareTarget) -> ShareTarget {
+        if target.id.is_empty() {
+            target.id = uuid::Uuid::new_v4().to_string();
+        }
+        {
+            let mut targets = self.targets.lock().unwrap();
+            match targets.iter_mut().find(|t| t.id == target.id) {
+                Some(existing) => *existing = target.clone(),
+                None => targets.push(target.clone()),
+            }
+        }
+        self.save_to_disk();
+        target
+    }
+
+    pub fn delete(&self, id: &str) -> bool {
+        let mut targets = self.targets.lock().unwrap();
+        let before = targets.len();
+        targets.retain(|t| t.id != id);
+        let removed = targets.len() < before;
+        drop(targets);
+        if removed {
+            self.save_to_disk();
+        }
+        removed
+    }
+}
+
+// ── Templates ────────────────────────────────────────────────────────
+
+pub fn render_template(template: &str, article: &SharedArticle) -> String {
+    template
+        .replace("{title}", &article.title)
+        .replace("{summary}", &article.summary)
+        .replace("{link}", &article.url)
+        .replace("{feed}", &article.feed_name)
+        .replace("{image}", article.image_url.as_deref().unwrap_or(""))
+        .trim()
+        .to_string()
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+fn discord_payload(text: &str, article: &SharedArticle) -> serde_json::Value {
+    let mut embed = serde_json::json!({
+        "title": truncate_chars(&article.title, 256),
+        "url": article.url,
+        "description": truncate_chars(&article.summary, 4096),
+    });
+    if !article.feed_name.is_empty() {
+        embed["footer"] = serde_json::json!({ "text": article.feed_name });
+    }
+    if let Some(ref image) = article.image_url {
+        embed["image"] = serde_json::json!({ "url": image });
+    }
+    serde_json::json!({
+        "content": truncate_chars(text, MAX_DISCORD_CONTENT),
+        "embeds": [embed],
+    })
+}
+
+fn slack_payload(text: &str, article: &SharedArticle) -> serde_json::Value {
+    let text = truncate_chars(text, MAX_SLACK_TEXT);
+    let mut blocks = vec![serde_json::json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text },
+    })];
+    if let Some(ref image) = article.image_url {
+        blocks.push(serde_json::json!({
+            "type": "image",
+            "image_url": image,
+            "alt_text": truncate_chars(&article.title, 2000),
+        }));
+    }
+    serde_json::json!({ "text": text, "blocks": blocks })
+}
+
+pub async fn post_to_target(target: &ShareTarget, article: &SharedArticle) -> Result<(), String> {
+    let template = target.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    let text = render_template(template, article);
+    let payload = match target.kind {
+        ShareTargetKind::Discord => discord_payload(&text, article),
+        ShareTargetKind::Slack => slack_payload(&text, article),
+    };
+
+    let client = crate::get_or_init_client()?;
+    let response = client
+        .post(&target.webhook_url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| format!("Webhook request failed: {e}"))?;
+
+    let status = response.status();
+    eprintln!("[share] '{}' → {status}", target.name);
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Webhook HTTP {status}: {body}"));
+    }
+    Ok(())
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_share_targets(store: tauri::State<'_, Arc<ShareStore>>) -> Vec<ShareTarget> {
+    store.get_targets()
+}
+
+#[tauri::command]
+pub fn save_share_target(
+    target: ShareTarget,
+    store: tauri::State<'_, Arc<ShareStore>>,
+) -> Result<ShareTarget, String> {
+    let parsed = url::Url::parse(target.webhook_url.trim())
+        .map_err(|e| format!("Invalid webhook URL: {e}"))?;
+    if parsed.scheme() != "https" {
+        return Err("Webhook URL must use https".into());
+    }
+    Ok(store.upsert(target))
+}
+
+#[tauri::command]
+pub fn delete_share_target(id: String, store: tauri::State<'_, Arc<ShareStore>>) -> bool {
+    store.delete(&id)
+}
+
+/// Render the message a target would post, without sending it.
+#[tauri::command]
+pub fn preview_share_message(
+    target_id: String,
+    article: SharedArticle,
+    store: tauri::State<'_, Arc<ShareStore>>,
+) -> Result<String, String> {
+    let target = store.get(&target_id).ok_or("Share target not found")?;
+    Ok(render_template(target.template.as_deref().unwrap_or(DEFAULT_TEMPLATE), &article))
+}
+
+/// Post an article to a webhook. Each target must be granted its host and
+/// access to article content, prompting on first use.
+#[tauri::command]
+pub async fn share_article(
+    target_id: String,
+    article: SharedArticle,
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<ShareStore>>,
+    perms: tauri::State<'_, Arc<PermissionStore>>,
+) -> Result<(), String> {
+    let target = store.get(&target_id).ok_or("Share target not found")?;
+    let principal = Principal {
+        kind: PrincipalKind::Webhook,
+        id: target.id.clone(),
+    };
+    let required = vec![host_permission(&target.webhook_url)?, Permission::ReadContent];
+    crate::permissions::require(&app, perms.inner(), &principal, Some(&target.name), required).await?;
+    post_to_target(&target, &article).await
+}
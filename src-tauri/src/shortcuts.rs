@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+use crate::miniplayer::{self, PlayerAction};
+
+// System-wide shortcuts that work while the window is in the background or
+// hidden in the tray. Playback actions go through the mini-player's controls
+// (`player-control`, native TTS stopped here); the others are emitted as
+// `global-shortcut` with the action for the frontend. Bindings use the
+// global-shortcut plugin's syntax, media keys included, e.g.
+// `CommandOrControl+Alt+R` or `MediaPlayPause`. None are bound by default.
+
+const SETTINGS_FILE: &str = "global_shortcuts.json";
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    TtsPlayPause,
+    TtsStop,
+    NextArticle,
+    PreviousArticle,
+    RefreshAll,
+}
+
+static BINDINGS: OnceLock<Mutex<BTreeMap<ShortcutAction, String>>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn bindings_lock() -> &'static Mutex<BTreeMap<ShortcutAction, String>> {
+    BINDINGS.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(SETTINGS_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(bindings) = serde_json::from_str::<BTreeMap<ShortcutAction, String>>(&json) {
+            *bindings_lock().lock().unwrap() = bindings;
+        }
+    }
+}
+
+fn save_bindings(bindings: &BTreeMap<ShortcutAction, String>) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json =
+        serde_json::to_string_pretty(bindings).map_err(|e| format!("Failed to serialize shortcuts: {e}"))?;
+    std::fs::write(dir.join(SETTINGS_FILE), json).map_err(|e| format!("Failed to save shortcuts: {e}"))
+}
+
+fn trigger(app: &tauri::AppHandle, action: ShortcutAction) {
+    let result = match action {
+        ShortcutAction::TtsPlayPause => miniplayer::control(app, PlayerAction::Toggle),
+        ShortcutAction::TtsStop => miniplayer::control(app, PlayerAction::Stop),
+        _ => app.emit("global-shortcut", action).map_err(|e| e.to_string()),
+    };
+    if let Err(e) = result {
+        eprintln!("[shortcuts] {action:?} failed: {e}");
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+fn register(app: &tauri::AppHandle, action: ShortcutAction, shortcut: &str) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            if let ShortcutState::Pressed = event.state {
+                trigger(app, action);
+            }
+        })
+        .map_err(|e| format!("Failed to register shortcut '{shortcut}': {e}"))
+}
+
+#[cfg(not(target_os = "android"))]
+fn unregister(app: &tauri::AppHandle, shortcut: &str) {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let _ = app.global_shortcut().unregister(shortcut);
+}
+
+#[cfg(target_os = "android")]
+fn register(_app: &tauri::AppHandle, _action: ShortcutAction, _shortcut: &str) -> Result<(), String> {
+    Err("Global shortcuts are not available on this platform".into())
+}
+
+#[cfg(target_os = "android")]
+fn unregister(_app: &tauri::AppHandle, _shortcut: &str) {}
+
+/// Register the saved shortcuts at startup. One that can't be registered
+/// (e.g. taken by another app) is reported and skipped.
+pub fn start(app: &tauri::AppHandle) {
+    let bindings = bindings_lock().lock().unwrap().clone();
+    for (action, shortcut) in &bindings {
+        if let Err(e) = register(app, *action, shortcut) {
+            eprintln!("[shortcuts] {e}");
+        }
+    }
+    if !bindings.is_empty() {
+        eprintln!("[shortcuts] {} global shortcuts registered", bindings.len());
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_global_shortcuts() -> BTreeMap<ShortcutAction, String> {
+    bindings_lock().lock().unwrap().clone()
+}
+
+/// Bind `action` to `shortcut`, or unbind it with `None`. The previous binding
+/// stays when the new one can't be registered.
+#[tauri::command]
+pub fn set_global_shortcut(
+    action: ShortcutAction,
+    shortcut: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let shortcut = shortcut.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let mut bindings = bindings_lock().lock().unwrap();
+    if let Some(new) = &shortcut {
+        let taken = bindings.iter().find(|(a, s)| **a != action && s.eq_ignore_ascii_case(new));
+        if let Some((other, _)) = taken {
+            return Err(format!("'{new}' is already bound to {other:?}"));
+        }
+    }
+
+    let previous = bindings.get(&action).cloned();
+    if previous == shortcut {
+        return Ok(());
+    }
+    if let Some(previous) = &previous {
+        unregister(&app, previous);
+    }
+    if let Some(new) = &shortcut {
+        if let Err(e) = register(&app, action, new) {
+            if let Some(previous) = &previous {
+                let _ = register(&app, action, previous);
+            }
+            return Err(e);
+        }
+    }
+
+    match shortcut {
+        Some(new) => bindings.insert(action, new),
+        None => bindings.remove(&action),
+    };
+    save_bindings(&bindings)
+}
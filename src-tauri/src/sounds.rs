@@ -0,0 +1,410 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SETTINGS_FILE: &str = "sound_settings.json";
+const SOUNDS_DIR: &str = "sounds";
+const CUSTOM_PREFIX: &str = "custom:";
+const MAX_SOUND_BYTES: u64 = 5 * 1024 * 1024;
+const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "ogg", "flac"];
+
+/// (frequency Hz, duration ms) steps; a frequency of 0 is a rest.
+type Tones = &'static [(f32, u64)];
+
+/// Built-in sounds are synthesized, so no audio assets ship with the app.
+const BUILTIN_SOUNDS: &[(&str, &str, Tones)] = &[
+    ("chime", "Chime", &[(880.0, 110), (1320.0, 220)]),
+    ("ping", "Ping", &[(1568.0, 140)]),
+    ("pop", "Pop", &[(440.0, 55)]),
+    ("bell", "Bell", &[(660.0, 90), (990.0, 90), (1320.0, 260)]),
+    ("alert", "Alert", &[(988.0, 120), (0.0, 60), (988.0, 120), (0.0, 60), (988.0, 120)]),
+];
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SoundSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 0.0 – 1.0
+    #[serde(default = "default_volume")]
+    pub volume: f32,
+    /// Sound for events without a feed/rule override. `None` = silent.
+    #[serde(default = "default_sound")]
+    pub default_sound: Option<String>,
+    /// Feed URL → sound id.
+    #[serde(default)]
+    pub feed_sounds: HashMap<String, String>,
+    /// Rule id → sound id. Takes precedence over the feed sound.
+    #[serde(default)]
+    pub rule_sounds: HashMap<String, String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_volume() -> f32 {
+    0.7
+}
+
+fn default_sound() -> Option<String> {
+    Some("chime".into())
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        SoundSettings {
+            enabled: default_enabled(),
+            volume: default_volume(),
+            default_sound: default_sound(),
+            feed_sounds: HashMap::new(),
+            rule_sounds: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct SoundInfo {
+    pub id: String,
+    pub name: String,
+    pub builtin: bool,
+}
+
+// ── Playback ─────────────────────────────────────────────────────────
+
+enum SoundSource {
+    Tones(Tones),
+    File(PathBuf),
+}
+
+#[cfg(not(target_os = "android"))]
+mod player {
+    use super::SoundSource;
+    use rodio::source::{SineWave, Source};
+    use rodio::{Decoder, OutputStream, Sink};
+    use std::sync::mpsc::{channel, Sender};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::Duration;
+
+    static PLAYER: OnceLock<Mutex<Sender<(SoundSource, f32)>>> = OnceLock::new();
+
+    /// The output stream is not `Send`, so it lives on a dedicated audio thread
+    /// that is opened on first use and plays requests as they arrive.
+    fn sender() -> &'static Mutex<Sender<(SoundSource, f32)>> {
+        PLAYER.get_or_init(|| {
+            let (tx, rx) = channel::<(SoundSource, f32)>();
+            std::thread::spawn(move || {
+                let (_stream, handle) = match OutputStream::try_default() {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("[sounds] No audio output device: {e}");
+                        return;
+                    }
+                };
+                for (source, volume) in rx {
+                    let sink = match Sink::try_new(&handle) {
+                        Ok(s) => s,
+                        Err(e) => {
+                            eprintln!("[sounds] Failed to create sink: {e}");
+                            continue;
+                        }
+                    };
+                    sink.set_volume(volume);
+                    match source {
+                        SoundSource::Tones(tones) => {
+                            for &(freq, ms) in tones {
+                                let tone = SineWave::new(freq.max(1.0))
+                                    .take_duration(Duration::from_millis(ms))
+                                    .fade_in(Duration::from_millis(4))
+                                    .amplify(if freq > 0.0 { 0.25 } else { 0.0 });
+                                sink.append(tone);
+                            }
+                        }
+                        SoundSource::File(path) => {
+                            let decoded = std::fs::File::open(&path)
+                                .map_err(|e| e.to_string())
+                                .and_then(|f| Decoder::new(std::io::BufReader::new(f)).map_err(|e| e.to_string()));
+                            match decoded {
+                                Ok(d) => sink.append(d),
+                                Err(e) => eprintln!("[sounds] Cannot play {}: {e}", path.display()),
+                            }
+                        }
+                    }
+                    // Overlapping notifications play concurrently
+                    sink.detach();
+                }
+            });
+            Mutex::new(tx)
+        })
+    }
+
+    pub fn play(source: SoundSource, volume: f32) -> Result<(), String> {
+        sender()
+            .lock()
+            .unwrap()
+            .send((source, volume))
+            .map_err(|_| "Audio output is unavailable".to_string())
+    }
+
+    pub fn validate(path: &std::path::Path) -> Result<(), String> {
+        let file = std::fs::File::open(path).map_err(|e| format!("Failed to open sound: {e}"))?;
+        Decoder::new(std::io::BufReader::new(file))
+            .map(|_| ())
+            .map_err(|e| format!("Unsupported or corrupt audio file: {e}"))
+    }
+}
+
+#[cfg(target_os = "android")]
+mod player {
+    use super::SoundSource;
+
+    pub fn play(_source: SoundSource, _volume: f32) -> Result<(), String> {
+        Err("Sound playback is not supported on this platform".into())
+    }
+
+    pub fn validate(_path: &std::path::Path) -> Result<(), String> {
+        Err("Sound playback is not supported on this platform".into())
+    }
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct SoundStore {
+    settings: Mutex<SoundSettings>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl SoundStore {
+    pub fn new() -> Self {
+        SoundStore {
+            settings: Mutex::new(SoundSettings::default()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SETTINGS_FILE))
+    }
+
+    fn sounds_dir(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SOUNDS_DIR))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(settings) = serde_json::from_str::<SoundSettings>(&json) {
+                            *self.settings.lock().unwrap() = settings;
+                        }
+                    }
+                    Err(e) => eprintln!("[sounds] Failed to read settings: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let settings = self.settings.lock().unwrap();
+            match serde_json::to_string_pretty(&*settings) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[sounds] Failed to write settings: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[sounds] Failed to serialize settings: {e}"),
+            }
+        }
+    }
+
+    pub fn get_settings(&self) -> SoundSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    pub fn set_settings(&self, mut settings: SoundSettings) {
+        settings.volume = settings.volume.clamp(0.0, 1.0);
+        *self.settings.lock().unwrap() = settings;
+        self.save_to_disk();
+    }
+
+    fn resolve(&self, id: &str) -> Result<SoundSource, String> {
+        if let Some(file) = id.strip_prefix(CUSTOM_PREFIX) {
+            // Custom ids are bare file names inside the sounds directory
+            if file.contains(['/', '\\']) || file.starts_with('.') {
+                return Err("Invalid sound id".into());
+            }
+            let path = self.sounds_dir().ok_or("App data directory unavailable")?.join(file);
+            if !path.exists() {
+                return Err(format!("Sound '{id}' not found"));
+            }
+            return Ok(SoundSource::File(path));
+        }
+        BUILTIN_SOUNDS
+            .iter()
+            .find(|(b, _, _)| *b == id)
+            .map(|(_, _, tones)| SoundSource::Tones(tones))
+            .ok_or_else(|| format!("Unknown sound '{id}'"))
+    }
+
+    pub fn play(&self, id: &str, volume: Option<f32>) -> Result<(), String> {
+        let volume = volume.unwrap_or_else(|| self.settings.lock().unwrap().volume).clamp(0.0, 1.0);
+        player::play(self.resolve(id)?, volume)
+    }
+
+    /// The sound for an event: rule override, then feed override, then the default.
+    pub fn sound_for(&self, feed_url: Option<&str>, rule_id: Option<&str>) -> Option<String> {
+        let settings = self.settings.lock().unwrap();
+        if !settings.enabled {
+            return None;
+        }
+        rule_id
+            .and_then(|r| settings.rule_sounds.get(r))
+            .or_else(|| feed_url.and_then(|f| settings.feed_sounds.get(f)))
+            .cloned()
+            .or_else(|| settings.default_sound.clone())
+            .filter(|s| !s.is_empty())
+    }
+
+    pub fn list(&self) -> Vec<SoundInfo> {
+        let mut list: Vec<SoundInfo> = BUILTIN_SOUNDS
+            .iter()
+            .map(|(id, name, _)| SoundInfo {
+                id: id.to_string(),
+                name: name.to_string(),
+                builtin: true,
+            })
+            .collect();
+        if let Some(Ok(entries)) = self.sounds_dir().map(std::fs::read_dir) {
+            let mut custom: Vec<SoundInfo> = entries
+                .flatten()
+                .filter_map(|e| e.file_name().into_string().ok())
+                .map(|file| SoundInfo {
+                    name: Path::new(&file)
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| file.clone()),
+                    id: format!("{CUSTOM_PREFIX}{file}"),
+                    builtin: false,
+                })
+                .collect();
+            custom.sort_by_key(|s| s.name.to_lowercase());
+            list.extend(custom);
+        }
+        list
+    }
+
+    /// Copy a user sound file into the app's sounds directory.
+    pub fn import(&self, source: &Path) -> Result<SoundInfo, String> {
+        let ext = source
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .filter(|e| SUPPORTED_EXTENSIONS.contains(&e.as_str()))
+            .ok_or("Supported formats: WAV, MP3, OGG, FLAC")?;
+        let size = std::fs::metadata(source).map_err(|e| format!("Cannot read file: {e}"))?.len();
+        if size > MAX_SOUND_BYTES {
+            return Err("Sound files are limited to 5 MB".into());
+        }
+        player::validate(source)?;
+
+        let dir = self.sounds_dir().ok_or("App data directory unavailable")?;
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create sounds directory: {e}"))?;
+        let stem: String = source
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+            .collect();
+        let stem = if stem.trim().is_empty() { "sound".to_string() } else { stem.trim().to_string() };
+        let mut file = format!("{stem}.{ext}");
+        let mut n = 2;
+        while dir.join(&file).exists() {
+            file = format!("{stem} ({n}).{ext}");
+            n += 1;
+        }
+        std::fs::copy(source, dir.join(&file)).map_err(|e| format!("Failed to copy sound: {e}"))?;
+        Ok(SoundInfo {
+            id: format!("{CUSTOM_PREFIX}{file}"),
+            name: stem,
+            builtin: false,
+        })
+    }
+
+    /// Delete a custom sound and drop any assignment pointing to it.
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let SoundSource::File(path) = self.resolve(id)? else {
+            return Err("Built-in sounds cannot be deleted".into());
+        };
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete sound: {e}"))?;
+        {
+            let mut settings = self.settings.lock().unwrap();
+            settings.feed_sounds.retain(|_, s| s != id);
+            settings.rule_sounds.retain(|_, s| s != id);
+            if settings.default_sound.as_deref() == Some(id) {
+                settings.default_sound = default_sound();
+            }
+        }
+        self.save_to_disk();
+        Ok(())
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_sound_settings(store: tauri::State<'_, Arc<SoundStore>>) -> SoundSettings {
+    store.get_settings()
+}
+
+#[tauri::command]
+pub fn save_sound_settings(settings: SoundSettings, store: tauri::State<'_, Arc<SoundStore>>) {
+    store.set_settings(settings);
+}
+
+#[tauri::command]
+pub fn list_sounds(store: tauri::State<'_, Arc<SoundStore>>) -> Vec<SoundInfo> {
+    store.list()
+}
+
+#[tauri::command]
+pub fn import_sound(path: String, store: tauri::State<'_, Arc<SoundStore>>) -> Result<SoundInfo, String> {
+    store.import(Path::new(&path))
+}
+
+#[tauri::command]
+pub fn delete_sound(id: String, store: tauri::State<'_, Arc<SoundStore>>) -> Result<(), String> {
+    store.delete(&id)
+}
+
+/// Preview a sound. `volume` defaults to the configured volume.
+#[tauri::command]
+pub fn play_sound(id: String, volume: Option<f32>, store: tauri::State<'_, Arc<SoundStore>>) -> Result<(), String> {
+    store.play(&id, volume)
+}
+
+/// Play the sound configured for a notification event. Returns false when silent
+/// or muted by a focus session.
+#[tauri::command]
+pub fn play_notification_sound(
+    feed_url: Option<String>,
+    rule_id: Option<String>,
+    store: tauri::State<'_, Arc<SoundStore>>,
+    focus: tauri::State<'_, Arc<crate::focus::FocusStore>>,
+) -> Result<bool, String> {
+    if focus.notifications_suppressed() {
+        return Ok(false);
+    }
+    match store.sound_for(feed_url.as_deref(), rule_id.as_deref()) {
+        Some(id) => store.play(&id, None).map(|_| true),
+        None => Ok(false),
+    }
+}
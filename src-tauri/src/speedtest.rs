@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const CONFIG_FILE: &str = "speedtest.json";
+const PING_SAMPLES: usize = 5;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+const MAX_TRANSFER_BYTES: u64 = 500 * 1024 * 1024;
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SpeedtestConfig {
+    /// GET endpoint; `{bytes}` is replaced with `download_bytes`.
+    #[serde(default = "default_download_url")]
+    pub download_url: String,
+    /// POST endpoint that accepts and discards a body.
+    #[serde(default = "default_upload_url")]
+    pub upload_url: String,
+    #[serde(default = "default_download_bytes")]
+    pub download_bytes: u64,
+    #[serde(default = "default_upload_bytes")]
+    pub upload_bytes: u64,
+}
+
+fn default_download_url() -> String {
+    "https://speed.cloudflare.com/__down?bytes={bytes}".into()
+}
+
+fn default_upload_url() -> String {
+    "https://speed.cloudflare.com/__up".into()
+}
+
+fn default_download_bytes() -> u64 {
+    25_000_000
+}
+
+fn default_upload_bytes() -> u64 {
+    10_000_000
+}
+
+impl Default for SpeedtestConfig {
+    fn default() -> Self {
+        SpeedtestConfig {
+            download_url: default_download_url(),
+            upload_url: default_upload_url(),
+            download_bytes: default_download_bytes(),
+            upload_bytes: default_upload_bytes(),
+        }
+    }
+}
+
+/// Emitted as `speedtest-progress` while a test runs.
+#[derive(Clone, Serialize, Debug)]
+pub struct SpeedtestProgress {
+    /// `ping`, `download` or `upload`.
+    pub phase: String,
+    pub bytes: u64,
+    pub total: u64,
+    pub mbps: f64,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct SpeedtestResult {
+    pub latency_ms: f64,
+    pub jitter_ms: f64,
+    pub download_mbps: f64,
+    pub upload_mbps: f64,
+    pub bytes_down: u64,
+    pub bytes_up: u64,
+    pub server: String,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct SpeedtestStore {
+    config: Mutex<SpeedtestConfig>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl SpeedtestStore {
+    pub fn new() -> Self {
+        SpeedtestStore {
+            config: Mutex::new(SpeedtestConfig::default()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(CONFIG_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(config) = serde_json::from_str::<SpeedtestConfig>(&json) {
+                            *self.config.lock().unwrap() = config;
+                        }
+                    }
+                    Err(e) => eprintln!("[speedtest] Failed to read config: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let config = self.config.lock().unwrap();
+            match serde_json::to_string_pretty(&*config) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[speedtest] Failed to write config: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[speedtest] Failed to serialize config: {e}"),
+            }
+        }
+    }
+
+    pub fn get_config(&self) -> SpeedtestConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: SpeedtestConfig) {
+        *self.config.lock().unwrap() = config;
+        self.save_to_disk();
+    }
+}
+
+// ── Measurement ──────────────────────────────────────────────────────
+
+fn mbps(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64().max(0.001);
+    (bytes as f64 * 8.0 / secs / 1_000_000.0 * 100.0).round() / 100.0
+}
+
+fn emit(app: &tauri::AppHandle, phase: &str, bytes: u64, total: u64, mbps: f64) {
+    let _ = app.emit(
+        "speedtest-progress",
+        SpeedtestProgress {
+            phase: phase.into(),
+            bytes,
+            total,
+            mbps,
+        },
+    );
+}
+
+/// Round-trip times of small requests to the download endpoint: (mean, jitter).
+async fn measure_latency(client: &reqwest::Client, url: &str) -> Result<(f64, f64), String> {
+    let mut samples = Vec::with_capacity(PING_SAMPLES);
+    for _ in 0..PING_SAMPLES {
+        let start = Instant::now();
+        client
+            .head(url)
+            .send()
+            .await
+            .map_err(|e| format!("Latency probe failed: {e}"))?;
+        samples.push(start.elapsed().as_secs_f64() * 1000.0);
+    }
+    // The first request pays for DNS, TCP and TLS setup
+    let samples = &samples[1..];
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let jitter = samples.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f64>() / (samples.len() - 1) as f64;
+    Ok(((mean * 10.0).round() / 10.0, (jitter * 10.0).round() / 10.0))
+}
+
+async fn measure_download(
+    app: &tauri::AppHandle,
+    client: &reqwest::Client,
+    url: &str,
+    total: u64,
+) -> Result<(u64, f64), String> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Download test failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Download test HTTP {}", response.status().as_u16()));
+    }
+    let start = Instant::now();
+    let mut last_emit = start;
+    let mut bytes = 0u64;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Download test failed: {e}"))?
+    {
+        bytes += chunk.len() as u64;
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            emit(app, "download", bytes, total, mbps(bytes, start.elapsed()));
+            last_emit = Instant::now();
+        }
+    }
+    let result = mbps(bytes, start.elapsed());
+    emit(app, "download", bytes, total, result);
+    Ok((bytes, result))
+}
+
+async fn measure_upload(
+    app: &tauri::AppHandle,
+    client: &reqwest::Client,
+    url: &str,
+    total: u64,
+) -> Result<f64, String> {
+    // Random bytes so compressing proxies cannot shrink the payload
+    let payload: Vec<u8> = (0..total).map(|_| rand::random::<u8>()).collect();
+    emit(app, "upload", 0, total, 0.0);
+    let start = Instant::now();
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+        .body(payload)
+        .send()
+        .await
+        .map_err(|e| format!("Upload test failed: {e}"))?;
+    let elapsed = start.elapsed();
+    if !response.status().is_success() {
+        return Err(format!("Upload test HTTP {}", response.status().as_u16()));
+    }
+    let result = mbps(total, elapsed);
+    emit(app, "upload", total, total, result);
+    Ok(result)
+}
+
+async fn run(app: &tauri::AppHandle, config: SpeedtestConfig) -> Result<SpeedtestResult, String> {
+    let download_bytes = config.download_bytes.clamp(1_000_000, MAX_TRANSFER_BYTES);
+    let upload_bytes = config.upload_bytes.clamp(100_000, MAX_TRANSFER_BYTES);
+    let download_url = config.download_url.replace("{bytes}", &download_bytes.to_string());
+    let server = url::Url::parse(&download_url)
+        .map_err(|e| format!("Invalid download URL: {e}"))?
+        .host_str()
+        .unwrap_or("")
+        .to_string();
+    url::Url::parse(&config.upload_url).map_err(|e| format!("Invalid upload URL: {e}"))?;
+
+    // Separate client: the shared one's 30s timeout would cut slow links short,
+    // and its pooled connections would skew latency.
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(120))
+        .connect_timeout(Duration::from_secs(15))
+        .no_gzip()
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+
+    let ping_url = config.download_url.replace("{bytes}", "0");
+    emit(app, "ping", 0, 0, 0.0);
+    let (latency_ms, jitter_ms) = measure_latency(&client, &ping_url).await?;
+    let (bytes_down, download_mbps) = measure_download(app, &client, &download_url, download_bytes).await?;
+    let upload_mbps = measure_upload(app, &client, &config.upload_url, upload_bytes).await?;
+
+    Ok(SpeedtestResult {
+        latency_ms,
+        jitter_ms,
+        download_mbps,
+        upload_mbps,
+        bytes_down,
+        bytes_up: upload_bytes,
+        server,
+    })
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_speedtest_config(store: tauri::State<'_, Arc<SpeedtestStore>>) -> SpeedtestConfig {
+    store.get_config()
+}
+
+#[tauri::command]
+pub fn set_speedtest_config(
+    config: SpeedtestConfig,
+    store: tauri::State<'_, Arc<SpeedtestStore>>,
+) -> Result<(), String> {
+    url::Url::parse(&config.download_url.replace("{bytes}", "1")).map_err(|e| format!("Invalid download URL: {e}"))?;
+    url::Url::parse(&config.upload_url).map_err(|e| format!("Invalid upload URL: {e}"))?;
+    store.set_config(config);
+    Ok(())
+}
+
+/// Measure latency, download and upload throughput against the configured
+/// endpoints, emitting `speedtest-progress` events along the way.
+#[tauri::command]
+pub async fn run_speedtest(
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Arc<SpeedtestStore>>,
+) -> Result<SpeedtestResult, String> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A speed test is already running".into());
+    }
+    let result = run(&app, store.get_config()).await;
+    RUNNING.store(false, Ordering::SeqCst);
+    result
+}
@@ -0,0 +1,280 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::sync::Notify;
+use url::Url;
+
+// Server-Sent Events. Each subscription keeps a streaming GET open on the async
+// runtime and emits every event to the webview as `sse-event`; connection
+// changes go out as `sse-status`. Dropped connections are resumed with
+// `Last-Event-ID` after the server's `retry` delay, backing off on repeated failures.
+
+const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+const MAX_RETRY: Duration = Duration::from_secs(300);
+/// Reconnect when nothing, not even a keep-alive comment, arrives for this long.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+/// Replaces the client's whole-request timeout, which would cut the stream off.
+const STREAM_TIMEOUT: Duration = Duration::from_secs(24 * 3600);
+
+#[derive(Clone, Serialize, Debug)]
+struct SseEvent {
+    subscription_id: String,
+    /// "message" unless the server named it.
+    event: String,
+    data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+struct SseStatus {
+    subscription_id: String,
+    /// "open", "reconnecting" or "closed".
+    state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+static SUBSCRIPTIONS: OnceLock<Mutex<HashMap<String, Arc<Notify>>>> = OnceLock::new();
+
+fn subscriptions_lock() -> &'static Mutex<HashMap<String, Arc<Notify>>> {
+    SUBSCRIPTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// ── Event stream parsing ─────────────────────────────────────────────
+
+struct Message {
+    event: String,
+    data: String,
+    id: Option<String>,
+}
+
+/// Incremental `text/event-stream` parser; bytes may be split anywhere.
+struct Parser {
+    line: Vec<u8>,
+    after_cr: bool,
+    event: String,
+    data: String,
+    last_id: Option<String>,
+    retry: Duration,
+}
+
+impl Parser {
+    fn new() -> Self {
+        Parser {
+            line: Vec::new(),
+            after_cr: false,
+            event: String::new(),
+            data: String::new(),
+            last_id: None,
+            retry: DEFAULT_RETRY,
+        }
+    }
+
+    fn feed(&mut self, bytes: &[u8]) -> Vec<Message> {
+        let mut messages = Vec::new();
+        for &b in bytes {
+            // CRLF is one line break
+            if std::mem::take(&mut self.after_cr) && b == b'\n' {
+                continue;
+            }
+            match b {
+                b'\r' | b'\n' => {
+                    self.after_cr = b == b'\r';
+                    let line = String::from_utf8_lossy(&std::mem::take(&mut self.line)).into_owned();
+                    messages.extend(self.process_line(&line));
+                }
+                _ => self.line.push(b),
+            }
+        }
+        messages
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<Message> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        // Comment, typically a keep-alive
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(ms) = value.parse() {
+                    self.retry = Duration::from_millis(ms);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<Message> {
+        let event = std::mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        Some(Message {
+            event: if event.is_empty() { "message".to_string() } else { event },
+            data,
+            id: self.last_id.clone(),
+        })
+    }
+}
+
+// ── Connection loop ──────────────────────────────────────────────────
+
+enum End {
+    /// The stream ended or stalled; reconnect. `true` if it delivered anything.
+    Dropped(bool, String),
+    /// The server refused the stream; don't reconnect.
+    Fatal(String),
+}
+
+fn emit_status(app: &tauri::AppHandle, id: &str, state: &'static str, error: Option<String>) {
+    let _ = app.emit("sse-status", SseStatus { subscription_id: id.to_string(), state, error });
+}
+
+async fn stream_once(
+    app: &tauri::AppHandle,
+    id: &str,
+    url: &Url,
+    headers: &HeaderMap,
+    parser: &mut Parser,
+) -> End {
+    let client = match crate::client_for(url) {
+        Ok(client) => client,
+        Err(e) => return End::Fatal(e),
+    };
+    let mut request = client.get(url.as_str()).headers(headers.clone()).timeout(STREAM_TIMEOUT);
+    if let Some(last_id) = &parser.last_id {
+        request = request.header("Last-Event-ID", last_id.as_str());
+    }
+    let mut response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => return End::Dropped(false, format!("Connection failed: {e}")),
+    };
+    let status = response.status();
+    // 204 is the server's way of saying "stop reconnecting"
+    if status == reqwest::StatusCode::NO_CONTENT || status.is_client_error() {
+        return End::Fatal(format!("HTTP {}", status.as_u16()));
+    }
+    if !status.is_success() {
+        return End::Dropped(false, format!("HTTP {}", status.as_u16()));
+    }
+    let content_type = response.headers().get(CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    if !content_type.starts_with("text/event-stream") {
+        return End::Fatal(format!("Not an event stream: {content_type}"));
+    }
+    emit_status(app, id, "open", None);
+
+    let mut received = false;
+    loop {
+        let chunk = match tokio::time::timeout(IDLE_TIMEOUT, response.chunk()).await {
+            Ok(Ok(Some(chunk))) => chunk,
+            Ok(Ok(None)) => return End::Dropped(received, "Stream closed by server".into()),
+            Ok(Err(e)) => return End::Dropped(received, format!("Stream error: {e}")),
+            Err(_) => return End::Dropped(received, format!("No data for {}s", IDLE_TIMEOUT.as_secs())),
+        };
+        received = true;
+        for message in parser.feed(&chunk) {
+            let event = SseEvent {
+                subscription_id: id.to_string(),
+                event: message.event,
+                data: message.data,
+                id: message.id,
+            };
+            let _ = app.emit("sse-event", event);
+        }
+    }
+}
+
+async fn run(app: tauri::AppHandle, id: String, url: Url, headers: HeaderMap, stop: Arc<Notify>) {
+    let mut parser = Parser::new();
+    let mut failures = 0;
+    let error = loop {
+        let end = tokio::select! {
+            end = stream_once(&app, &id, &url, &headers, &mut parser) => end,
+            _ = stop.notified() => break None,
+        };
+        let error = match end {
+            End::Fatal(e) => break Some(e),
+            End::Dropped(received, e) => {
+                failures = if received { 1 } else { failures + 1 };
+                e
+            }
+        };
+        let delay = (parser.retry * 2u32.pow(failures.min(6) - 1)).min(MAX_RETRY);
+        eprintln!("[sse] {url}: {error}, reconnecting in {delay:?}");
+        emit_status(&app, &id, "reconnecting", Some(error));
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = stop.notified() => break None,
+        }
+    };
+    if let Some(e) = &error {
+        eprintln!("[sse] {url}: {e}, giving up");
+    }
+    subscriptions_lock().lock().unwrap().remove(&id);
+    emit_status(&app, &id, "closed", error);
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Open an event stream. Returns the subscription id carried by every
+/// `sse-event` / `sse-status` it emits; pass it to `sse_close` to stop.
+#[tauri::command]
+pub async fn sse_subscribe(
+    url: String,
+    headers: Option<HashMap<String, String>>,
+    window: tauri::WebviewWindow,
+    app: tauri::AppHandle,
+) -> Result<String, String> {
+    let parsed = Url::parse(&url).map_err(|e| format!("Invalid URL: {e}"))?;
+    crate::permissions::require_host(&window, &url).await?;
+    let mut header_map = crate::get_headers_for_url(&parsed);
+    header_map.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+    header_map.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    if let Some(auth) = crate::http_auth::preemptive(&parsed) {
+        header_map.insert(AUTHORIZATION, auth);
+    }
+    for (key, value) in headers.unwrap_or_default() {
+        let name = HeaderName::try_from(key.as_str()).map_err(|e| format!("Invalid header name '{key}': {e}"))?;
+        let value =
+            HeaderValue::from_str(&value).map_err(|e| format!("Invalid header value for '{key}': {e}"))?;
+        header_map.insert(name, value);
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let stop = Arc::new(Notify::new());
+    subscriptions_lock().lock().unwrap().insert(id.clone(), stop.clone());
+    tauri::async_runtime::spawn(run(app, id.clone(), parsed, header_map, stop));
+    Ok(id)
+}
+
+/// Close a subscription. Returns false if it was already closed.
+#[tauri::command]
+pub fn sse_close(id: String) -> bool {
+    match subscriptions_lock().lock().unwrap().remove(&id) {
+        Some(stop) => {
+            stop.notify_one();
+            true
+        }
+        None => false,
+    }
+}
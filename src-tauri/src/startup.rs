@@ -0,0 +1,331 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+use crate::db::{self, ArticleQuery, ArticleRow, Database, DbFeed};
+use crate::ingest::now_millis;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const SNAPSHOT_FILE: &str = "startup_snapshot.json";
+/// Rows kept per list; enough to fill the first screen.
+const FIRST_PAGE_SIZE: u32 = 50;
+const MAX_VIEWS: usize = 8;
+const OPTIONS_FILE: &str = "startup_options.json";
+const MAX_REFRESH_DELAY_SECS: u64 = 600;
+/// "Idle" for the deferred refresh: overall CPU below this, checked every poll.
+const IDLE_CPU_PERCENT: f32 = 30.0;
+const IDLE_POLL: Duration = Duration::from_secs(2);
+/// Refresh anyway if the machine never settles.
+const MAX_IDLE_WAIT: Duration = Duration::from_secs(120);
+
+/// A list the UI shows at launch, identified by a frontend key
+/// (e.g. `"all"`, `"feed:<id>"`, `"folder:Tech"`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StartupView {
+    pub key: String,
+    pub query: ArticleQuery,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SnapshotFeed {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub unread: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct FolderNode {
+    pub name: String,
+    /// Full `/`-separated path; empty for the root.
+    pub path: String,
+    pub unread: u32,
+    pub feeds: Vec<SnapshotFeed>,
+    pub children: Vec<FolderNode>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SnapshotList {
+    pub key: String,
+    pub rows: Vec<ArticleRow>,
+}
+
+/// Everything the first paint needs, answered without touching the database.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StartupSnapshot {
+    pub saved_at: u64,
+    pub folders: FolderNode,
+    pub unread_counts: HashMap<String, u32>,
+    pub lists: Vec<SnapshotList>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct StartupOptions {
+    /// Open straight into the collapsed widget bar.
+    pub start_collapsed: bool,
+    /// Reopen the folder/feed/article that was showing at exit.
+    pub restore_last_view: bool,
+    /// Hold the first full refresh for this long after launch.
+    pub refresh_delay_secs: u64,
+    /// After the delay, also wait (up to two minutes) for the CPU to go quiet.
+    pub refresh_when_idle: bool,
+}
+
+/// Where the user was, as reported by the frontend while navigating.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct LastView {
+    pub folder: Option<String>,
+    pub feed_id: Option<String>,
+    pub article_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct OptionsFile {
+    options: StartupOptions,
+    last_view: Option<LastView>,
+}
+
+/// What the UI should do at launch, resolved from the options.
+#[derive(Clone, Serialize, Debug)]
+pub struct StartupPlan {
+    pub collapsed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_view: Option<LastView>,
+    /// The first refresh is being held back; wait for `startup-refresh`.
+    pub refresh_pending: bool,
+}
+
+// ── Snapshot building ────────────────────────────────────────────────
+
+fn insert_feed(root: &mut FolderNode, feed: SnapshotFeed, folder: &str) {
+    let mut node = root;
+    node.unread += feed.unread;
+    for name in folder.split('/').map(str::trim).filter(|n| !n.is_empty()) {
+        let path = match node.path.as_str() {
+            "" => name.to_string(),
+            parent => format!("{parent}/{name}"),
+        };
+        let index = match node.children.iter().position(|c| c.name == name) {
+            Some(i) => i,
+            None => {
+                node.children.push(FolderNode { name: name.to_string(), path, ..Default::default() });
+                node.children.len() - 1
+            }
+        };
+        node = &mut node.children[index];
+        node.unread += feed.unread;
+    }
+    node.feeds.push(feed);
+}
+
+fn sort_tree(node: &mut FolderNode) {
+    node.children.sort_by_key(|c| c.name.to_lowercase());
+    node.feeds.sort_by_key(|f| f.title.to_lowercase());
+    node.children.iter_mut().for_each(sort_tree);
+}
+
+fn folder_tree(feeds: Vec<DbFeed>, unread_counts: &HashMap<String, u32>) -> FolderNode {
+    let mut root = FolderNode::default();
+    for feed in feeds {
+        let unread = unread_counts.get(&feed.id).copied().unwrap_or(0);
+        let entry = SnapshotFeed { id: feed.id, title: feed.title, url: feed.url, unread };
+        insert_feed(&mut root, entry, &feed.folder);
+    }
+    sort_tree(&mut root);
+    root
+}
+
+fn build(conn: &rusqlite::Connection, views: &[StartupView]) -> rusqlite::Result<StartupSnapshot> {
+    let unread_counts = db::unread_counts(conn)?;
+    let folders = folder_tree(db::list_feeds(conn)?, &unread_counts);
+    let mut lists = Vec::new();
+    for view in views.iter().take(MAX_VIEWS) {
+        let query = ArticleQuery {
+            limit: Some(view.query.limit.unwrap_or(FIRST_PAGE_SIZE).min(FIRST_PAGE_SIZE)),
+            offset: None,
+            ..view.query.clone()
+        };
+        lists.push(SnapshotList { key: view.key.clone(), rows: db::query_articles(conn, &query)? });
+    }
+    Ok(StartupSnapshot { saved_at: now_millis(), folders, unread_counts, lists })
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct StartupStore {
+    snapshot: Mutex<Option<StartupSnapshot>>,
+    settings: Mutex<OptionsFile>,
+    /// Set once the deferred first refresh may run.
+    refresh_released: AtomicBool,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl StartupStore {
+    pub fn new() -> Self {
+        StartupStore {
+            snapshot: Mutex::new(None),
+            settings: Mutex::new(OptionsFile::default()),
+            refresh_released: AtomicBool::new(false),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(SNAPSHOT_FILE))
+    }
+
+    fn options_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(OPTIONS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(json) = self.options_path().and_then(|p| std::fs::read_to_string(p).ok()) {
+            match serde_json::from_str::<OptionsFile>(&json) {
+                Ok(settings) => *self.settings.lock().unwrap() = settings,
+                Err(e) => eprintln!("[startup] Ignoring unreadable startup options: {e}"),
+            }
+        }
+        let Some(path) = self.file_path() else { return };
+        let Ok(json) = std::fs::read_to_string(&path) else { return };
+        match serde_json::from_str::<StartupSnapshot>(&json) {
+            Ok(snapshot) => *self.snapshot.lock().unwrap() = Some(snapshot),
+            Err(e) => eprintln!("[startup] Ignoring unreadable snapshot: {e}"),
+        }
+    }
+
+    pub fn options(&self) -> StartupOptions {
+        self.settings.lock().unwrap().options.clone()
+    }
+
+    fn save_settings(&self, settings: &OptionsFile) -> Result<(), String> {
+        if let Some(path) = self.options_path() {
+            let json = serde_json::to_string_pretty(settings)
+                .map_err(|e| format!("Failed to serialize startup options: {e}"))?;
+            std::fs::write(&path, json).map_err(|e| format!("Failed to save startup options: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Written to a temp file first so a crash mid-write never leaves a torn snapshot.
+    fn save(&self, snapshot: StartupSnapshot) -> Result<(), String> {
+        if let Some(path) = self.file_path() {
+            let json =
+                serde_json::to_string(&snapshot).map_err(|e| format!("Failed to serialize snapshot: {e}"))?;
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, json).map_err(|e| format!("Failed to write snapshot: {e}"))?;
+            std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to replace snapshot: {e}"))?;
+        }
+        *self.snapshot.lock().unwrap() = Some(snapshot);
+        Ok(())
+    }
+}
+
+// ── Deferred refresh ─────────────────────────────────────────────────
+
+fn wait_for_idle() {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_cpu_usage();
+    let start = Instant::now();
+    while start.elapsed() < MAX_IDLE_WAIT {
+        std::thread::sleep(IDLE_POLL);
+        sys.refresh_cpu_usage();
+        if sys.global_cpu_usage() < IDLE_CPU_PERCENT {
+            return;
+        }
+    }
+    eprintln!("[startup] CPU still busy after {MAX_IDLE_WAIT:?}, refreshing anyway");
+}
+
+/// Hold the first full refresh back as configured, then emit `startup-refresh`.
+/// Without a delay the refresh is released immediately and nothing is emitted.
+pub fn schedule_first_refresh(app: tauri::AppHandle) {
+    let store = app.state::<Arc<StartupStore>>().inner().clone();
+    let options = store.options();
+    if options.refresh_delay_secs == 0 && !options.refresh_when_idle {
+        store.refresh_released.store(true, Ordering::SeqCst);
+        return;
+    }
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(options.refresh_delay_secs));
+        if options.refresh_when_idle {
+            wait_for_idle();
+        }
+        eprintln!("[startup] Releasing deferred refresh");
+        store.refresh_released.store(true, Ordering::SeqCst);
+        let _ = app.emit("startup-refresh", ());
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// The snapshot saved by the last session, if any. Read at launch, so this
+/// answers immediately; the UI then refreshes from the database as usual.
+#[tauri::command]
+pub fn get_startup_state(store: tauri::State<'_, Arc<StartupStore>>) -> Option<StartupSnapshot> {
+    store.snapshot.lock().unwrap().clone()
+}
+
+/// Rebuild the snapshot from the database for the lists currently on screen.
+/// Call when the app goes idle or is about to close.
+#[tauri::command]
+pub async fn save_startup_snapshot(
+    views: Vec<StartupView>,
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, Arc<StartupStore>>,
+) -> Result<u64, String> {
+    let snapshot = db::run(&db, move |conn| build(conn, &views)).await?;
+    let saved_at = snapshot.saved_at;
+    store.save(snapshot)?;
+    Ok(saved_at)
+}
+
+#[tauri::command]
+pub fn get_startup_options(store: tauri::State<'_, Arc<StartupStore>>) -> StartupOptions {
+    store.options()
+}
+
+/// Takes effect at the next launch.
+#[tauri::command]
+pub fn set_startup_options(
+    options: StartupOptions,
+    store: tauri::State<'_, Arc<StartupStore>>,
+) -> Result<(), String> {
+    if options.refresh_delay_secs > MAX_REFRESH_DELAY_SECS {
+        return Err(format!("Refresh delay must be at most {MAX_REFRESH_DELAY_SECS} seconds"));
+    }
+    let mut settings = store.settings.lock().unwrap();
+    settings.options = options;
+    store.save_settings(&settings)
+}
+
+/// Remember the current view for `restore_last_view`.
+#[tauri::command]
+pub fn set_last_view(view: LastView, store: tauri::State<'_, Arc<StartupStore>>) -> Result<(), String> {
+    let mut settings = store.settings.lock().unwrap();
+    settings.last_view = Some(view);
+    store.save_settings(&settings)
+}
+
+/// How to start: collapsed or not, which view to reopen, and whether the
+/// first refresh is still being held back.
+#[tauri::command]
+pub fn get_startup_plan(store: tauri::State<'_, Arc<StartupStore>>) -> StartupPlan {
+    let settings = store.settings.lock().unwrap();
+    StartupPlan {
+        collapsed: settings.options.start_collapsed,
+        last_view: settings.last_view.clone().filter(|_| settings.options.restore_last_view),
+        refresh_pending: !store.refresh_released.load(Ordering::SeqCst),
+    }
+}
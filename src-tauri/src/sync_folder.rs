@@ -0,0 +1,450 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+use crate::db::Database;
+use crate::ingest::now_millis;
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const STATE_FILE: &str = "sync_folder.json";
+/// Subdirectory created inside the user's synced folder.
+const SYNC_SUBDIR: &str = "superflux-sync";
+const JOURNAL_EXT: &str = "jsonl";
+/// Journal entries older than this are compacted away.
+const MAX_ENTRY_AGE_MS: u64 = 90 * 24 * 60 * 60 * 1000;
+/// A lock older than this is assumed to belong to a crashed instance.
+const STALE_LOCK_MS: u64 = 2 * 60 * 1000;
+const SYNC_INTERVAL_SECS: u64 = 5 * 60;
+
+static SYNCING: AtomicBool = AtomicBool::new(false);
+
+/// One change, as recorded by the frontend (`op` is e.g. `read`, `unread`,
+/// `star`, `unstar`, `subscribe`, `unsubscribe`; `key` the article id or feed URL).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ChangeInput {
+    pub op: String,
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ChangeEntry {
+    pub id: String,
+    pub device_id: String,
+    /// ms since epoch, used for last-writer-wins.
+    pub timestamp: u64,
+    pub op: String,
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+impl ChangeEntry {
+    /// Ops that overwrite each other on the same key.
+    fn family(&self) -> &str {
+        match self.op.as_str() {
+            "read" | "unread" => "read",
+            "star" | "unstar" => "star",
+            "subscribe" | "unsubscribe" => "subscription",
+            other => other,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct SyncFolderState {
+    pub device_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    /// Local changes not yet written to the folder.
+    #[serde(default)]
+    pub pending: Vec<ChangeEntry>,
+    /// Remote entry ids already applied here.
+    #[serde(default)]
+    pub applied: HashSet<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync_at: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct SyncFolderInfo {
+    pub device_id: String,
+    pub folder: Option<String>,
+    pub enabled: bool,
+    pub pending: usize,
+    pub last_sync_at: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Debug, Default)]
+pub struct SyncReport {
+    pub pushed: usize,
+    /// Winning remote changes, for the frontend to apply (read/star state is
+    /// already applied to the database).
+    pub pulled: Vec<ChangeEntry>,
+    /// Remote changes dropped because a newer change to the same key exists.
+    pub superseded: usize,
+    /// Conflicted copies created by the sync tool that were merged.
+    pub conflicted_files: Vec<String>,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+pub struct SyncFolderStore {
+    state: Mutex<SyncFolderState>,
+    data_dir: Mutex<Option<PathBuf>>,
+}
+
+impl SyncFolderStore {
+    pub fn new() -> Self {
+        SyncFolderStore {
+            state: Mutex::new(SyncFolderState::default()),
+            data_dir: Mutex::new(None),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+        let mut state = self.state.lock().unwrap();
+        if state.device_id.is_empty() {
+            state.device_id = uuid::Uuid::new_v4().to_string();
+            drop(state);
+            self.save_to_disk();
+        }
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(STATE_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(state) = serde_json::from_str::<SyncFolderState>(&json) {
+                            *self.state.lock().unwrap() = state;
+                        }
+                    }
+                    Err(e) => eprintln!("[sync_folder] Failed to read state: {e}"),
+                }
+            }
+        }
+    }
+
+    fn save_to_disk(&self) {
+        if let Some(path) = self.file_path() {
+            let state = self.state.lock().unwrap();
+            match serde_json::to_string(&*state) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[sync_folder] Failed to write state: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[sync_folder] Failed to serialize state: {e}"),
+            }
+        }
+    }
+
+    pub fn info(&self) -> SyncFolderInfo {
+        let state = self.state.lock().unwrap();
+        SyncFolderInfo {
+            device_id: state.device_id.clone(),
+            folder: state.folder.clone(),
+            enabled: state.enabled,
+            pending: state.pending.len(),
+            last_sync_at: state.last_sync_at,
+        }
+    }
+
+    pub fn configure(&self, folder: Option<String>, enabled: bool) {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.folder != folder {
+                // A different folder has a different history
+                state.applied.clear();
+                state.last_sync_at = None;
+            }
+            state.folder = folder;
+            state.enabled = enabled;
+        }
+        self.save_to_disk();
+    }
+
+    /// Queue local changes. Nothing is recorded while sync is off.
+    pub fn record(&self, changes: Vec<ChangeInput>) -> usize {
+        let now = now_millis();
+        let count = {
+            let mut state = self.state.lock().unwrap();
+            if !state.enabled || state.folder.is_none() {
+                return 0;
+            }
+            let device_id = state.device_id.clone();
+            let count = changes.len();
+            state.pending.extend(changes.into_iter().map(|c| ChangeEntry {
+                id: uuid::Uuid::new_v4().to_string(),
+                device_id: device_id.clone(),
+                timestamp: now,
+                op: c.op,
+                key: c.key,
+                value: c.value,
+            }));
+            count
+        };
+        self.save_to_disk();
+        count
+    }
+}
+
+// ── Journal files ────────────────────────────────────────────────────
+
+fn read_journal(path: &Path) -> Vec<ChangeEntry> {
+    let Ok(text) = std::fs::read_to_string(path) else { return Vec::new() };
+    // Sync tools can leave a half-written last line; skip what does not parse
+    text.lines().filter_map(|l| serde_json::from_str(l).ok()).collect()
+}
+
+/// Rewrite a journal atomically so sync tools never pick up a partial file.
+fn write_journal(path: &Path, entries: &[ChangeEntry]) -> Result<(), String> {
+    let mut out = String::new();
+    for e in entries {
+        out.push_str(&serde_json::to_string(e).map_err(|e| format!("Serialize error: {e}"))?);
+        out.push('\n');
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, out).map_err(|e| format!("Failed to write journal: {e}"))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace journal: {e}"))
+}
+
+/// Exclusive lock file for this device's journal, removed on drop.
+struct JournalLock(PathBuf);
+
+impl JournalLock {
+    fn acquire(dir: &Path, device_id: &str) -> Result<JournalLock, String> {
+        let path = dir.join(format!("{device_id}.lock"));
+        for _ in 0..2 {
+            match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(JournalLock(path)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let age = std::fs::metadata(&path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|t| t.elapsed().ok())
+                        .map(|d| d.as_millis() as u64)
+                        .unwrap_or(0);
+                    if age < STALE_LOCK_MS {
+                        return Err("Sync folder is locked by another SuperFlux instance".into());
+                    }
+                    let _ = std::fs::remove_file(&path);
+                }
+                Err(e) => return Err(format!("Failed to lock sync folder: {e}")),
+            }
+        }
+        Err("Failed to lock sync folder".into())
+    }
+}
+
+impl Drop for JournalLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Map a journal file name to its device id. Conflicted copies keep the
+/// original name as a prefix: Dropbox `x (conflicted copy …).jsonl`,
+/// Syncthing `x.sync-conflict-….jsonl`, OneDrive `x-HOSTNAME.jsonl`.
+fn journal_owner(file_name: &str) -> Option<(String, bool)> {
+    let stem = file_name.strip_suffix(&format!(".{JOURNAL_EXT}"))?;
+    // Device ids are UUIDs: 36 chars
+    let id = stem.get(..36)?;
+    if uuid::Uuid::parse_str(id).is_err() {
+        return None;
+    }
+    Some((id.to_string(), stem.len() > 36))
+}
+
+// ── Sync ─────────────────────────────────────────────────────────────
+
+/// One sync pass: write pending changes to this device's journal, read every
+/// other journal (and conflicted copies), and resolve with last-writer-wins.
+fn sync_pass(store: &SyncFolderStore, db: &Database) -> Result<SyncReport, String> {
+    let (device_id, folder, pending, applied) = {
+        let state = store.state.lock().unwrap();
+        let folder = state
+            .folder
+            .clone()
+            .filter(|_| state.enabled)
+            .ok_or("Folder sync is not enabled")?;
+        (state.device_id.clone(), folder, state.pending.clone(), state.applied.clone())
+    };
+    let dir = Path::new(&folder).join(SYNC_SUBDIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Cannot access sync folder: {e}"))?;
+    let _lock = JournalLock::acquire(&dir, &device_id)?;
+    let cutoff = now_millis().saturating_sub(MAX_ENTRY_AGE_MS);
+
+    let mut report = SyncReport::default();
+    let mut own: HashMap<String, ChangeEntry> = HashMap::new();
+    let mut remote: HashMap<String, ChangeEntry> = HashMap::new();
+    let mut own_conflicts: Vec<PathBuf> = Vec::new();
+
+    let entries = std::fs::read_dir(&dir).map_err(|e| format!("Cannot read sync folder: {e}"))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some((owner, conflicted)) = journal_owner(&name) else { continue };
+        if conflicted {
+            report.conflicted_files.push(name.clone());
+        }
+        let target = if owner == device_id { &mut own } else { &mut remote };
+        for e in read_journal(&entry.path()) {
+            target.insert(e.id.clone(), e);
+        }
+        if conflicted && owner == device_id {
+            own_conflicts.push(entry.path());
+        }
+    }
+
+    // Push: own journal = what is there + conflicted copies of it + pending
+    report.pushed = pending.len();
+    for e in pending.iter() {
+        own.insert(e.id.clone(), e.clone());
+    }
+    let mut own_list: Vec<ChangeEntry> = own.into_values().filter(|e| e.timestamp >= cutoff).collect();
+    own_list.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+    write_journal(&dir.join(format!("{device_id}.{JOURNAL_EXT}")), &own_list)?;
+    for path in own_conflicts {
+        let _ = std::fs::remove_file(path);
+    }
+
+    // Pull: the newest change per (family, key) wins across all devices
+    let mut winners: HashMap<(String, String), ChangeEntry> = HashMap::new();
+    for e in own_list.iter().chain(remote.values()) {
+        let slot = (e.family().to_string(), e.key.clone());
+        let newer = winners
+            .get(&slot)
+            .is_none_or(|w| (e.timestamp, &e.device_id) > (w.timestamp, &w.device_id));
+        if newer {
+            winners.insert(slot, e.clone());
+        }
+    }
+    let winning_ids: HashSet<&str> = winners.values().map(|e| e.id.as_str()).collect();
+    for e in remote.values().filter(|e| !applied.contains(&e.id)) {
+        if winning_ids.contains(e.id.as_str()) {
+            report.pulled.push(e.clone());
+        } else {
+            report.superseded += 1;
+        }
+    }
+    report.pulled.sort_by_key(|e| e.timestamp);
+    apply_to_db(db, &report.pulled);
+
+    {
+        let mut state = store.state.lock().unwrap();
+        let pushed: HashSet<&str> = pending.iter().map(|e| e.id.as_str()).collect();
+        // Changes recorded during this pass stay pending
+        state.pending.retain(|e| !pushed.contains(e.id.as_str()));
+        state.applied = remote
+            .values()
+            .filter(|e| e.timestamp >= cutoff)
+            .map(|e| e.id.clone())
+            .collect();
+        state.last_sync_at = Some(now_millis());
+    }
+    store.save_to_disk();
+    Ok(report)
+}
+
+/// Read and starred state lives in the database, so it is applied here.
+fn apply_to_db(db: &Database, changes: &[ChangeEntry]) {
+    let ids = |op: &str| -> Vec<String> {
+        changes.iter().filter(|c| c.op == op).map(|c| c.key.clone()).collect()
+    };
+    let result = db.with(|conn| {
+        crate::db::set_read(conn, &ids("read"), true)?;
+        crate::db::set_read(conn, &ids("unread"), false)?;
+        crate::db::set_starred(conn, &ids("star"), true)?;
+        crate::db::set_starred(conn, &ids("unstar"), false)
+    });
+    if let Err(e) = result {
+        eprintln!("[sync_folder] Failed to apply changes to the database: {e}");
+    }
+}
+
+pub async fn sync_now(store: Arc<SyncFolderStore>, db: Arc<Database>) -> Result<SyncReport, String> {
+    if SYNCING.swap(true, Ordering::SeqCst) {
+        return Err("A sync is already running".into());
+    }
+    let result = tauri::async_runtime::spawn_blocking(move || sync_pass(&store, &db))
+        .await
+        .map_err(|e| format!("Sync task failed: {e}"));
+    SYNCING.store(false, Ordering::SeqCst);
+    result?
+}
+
+/// Sync periodically while enabled, emitting `sync-changes` when remote changes arrive.
+pub fn start_sync_timer(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(SYNC_INTERVAL_SECS));
+        let store = app.state::<Arc<SyncFolderStore>>().inner().clone();
+        if !store.info().enabled {
+            continue;
+        }
+        let db = app.state::<Arc<Database>>().inner().clone();
+        match tauri::async_runtime::block_on(sync_now(store, db)) {
+            Ok(report) if !report.pulled.is_empty() => {
+                let _ = app.emit("sync-changes", &report);
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[sync_folder] Sync failed: {e}"),
+        }
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_sync_folder(store: tauri::State<'_, Arc<SyncFolderStore>>) -> SyncFolderInfo {
+    store.info()
+}
+
+/// Point sync at a folder managed by Dropbox, Syncthing, OneDrive, …
+#[tauri::command]
+pub fn set_sync_folder(
+    folder: Option<String>,
+    enabled: bool,
+    store: tauri::State<'_, Arc<SyncFolderStore>>,
+) -> Result<SyncFolderInfo, String> {
+    let folder = folder.map(|f| f.trim().to_string()).filter(|f| !f.is_empty());
+    if let Some(f) = &folder {
+        if !Path::new(f).is_dir() {
+            return Err(format!("Folder does not exist: {f}"));
+        }
+    } else if enabled {
+        return Err("A folder is required to enable sync".into());
+    }
+    store.configure(folder, enabled);
+    Ok(store.info())
+}
+
+/// Queue local changes for the next sync. Returns how many were recorded.
+#[tauri::command]
+pub fn sync_record_changes(
+    changes: Vec<ChangeInput>,
+    store: tauri::State<'_, Arc<SyncFolderStore>>,
+) -> usize {
+    store.record(changes)
+}
+
+#[tauri::command]
+pub async fn sync_folder_now(
+    store: tauri::State<'_, Arc<SyncFolderStore>>,
+    db: tauri::State<'_, Arc<Database>>,
+) -> Result<SyncReport, String> {
+    sync_now(store.inner().clone(), db.inner().clone()).await
+}
@@ -0,0 +1,132 @@
+use serde::Deserialize;
+use tauri::Manager;
+
+// The app's taskbar button and dock icon: the unread count, so new items show
+// while the app is minimized or in the background, and the progress of long
+// operations such as refresh-all or large downloads. macOS, and Linux launchers
+// that speak the Unity API, draw the badge themselves; on Windows the count is
+// drawn here into the small overlay icon of the taskbar button.
+
+/// What `set_progress` shows, matching the taskbar's own progress states.
+#[derive(Clone, Copy, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressState {
+    /// No progress shown.
+    None,
+    Normal,
+    /// Busy without a known fraction.
+    Indeterminate,
+    Paused,
+    Error,
+}
+
+/// Side of the Windows overlay icon; the shell scales it to the small icon size.
+#[cfg(target_os = "windows")]
+const OVERLAY_SIZE: u32 = 32;
+#[cfg(target_os = "windows")]
+const OVERLAY_COLOR: [u8; 3] = [0xE5, 0x39, 0x35];
+
+/// 3x5 glyphs for 0-9 and `+`, one row per byte, high bit on the left.
+#[cfg(target_os = "windows")]
+const GLYPHS: [[u8; 5]; 11] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+    [0b000, 0b010, 0b111, 0b010, 0b000],
+];
+
+/// A red disc with the count in white, `99+` past two digits.
+#[cfg(target_os = "windows")]
+fn overlay_icon(count: u32) -> tauri::image::Image<'static> {
+    const SCALE: u32 = 2;
+    let size = OVERLAY_SIZE;
+    let text = if count > 99 { "99+".to_string() } else { count.to_string() };
+    let glyphs: Vec<usize> = text.chars().map(|c| c.to_digit(10).map_or(10, |d| d as usize)).collect();
+    let text_width = glyphs.len() as u32 * 4 * SCALE - SCALE;
+    let (left, top) = ((size - text_width) / 2, (size - 5 * SCALE) / 2);
+
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    let center = size as f32 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let (dx, dy) = (x as f32 + 0.5 - center, y as f32 + 0.5 - center);
+            // Antialiased edge: coverage fades over the last pixel of the radius
+            let coverage = (center - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            if coverage == 0.0 {
+                continue;
+            }
+            let (gx, gy) = (x.wrapping_sub(left) / SCALE, y.wrapping_sub(top) / SCALE);
+            let lit = x >= left
+                && y >= top
+                && gy < 5
+                && gx % 4 < 3
+                && glyphs
+                    .get((gx / 4) as usize)
+                    .is_some_and(|g| GLYPHS[*g][gy as usize] & (0b100 >> (gx % 4)) != 0);
+            let i = ((y * size + x) * 4) as usize;
+            let [r, g, b] = if lit { [0xFF; 3] } else { OVERLAY_COLOR };
+            rgba[i..i + 4].copy_from_slice(&[r, g, b, (coverage * 255.0) as u8]);
+        }
+    }
+    tauri::image::Image::new_owned(rgba, size, size)
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Show `count` on the main window's taskbar button or the dock icon; zero
+/// clears it.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub fn set_badge_count(count: u32, app: tauri::AppHandle) -> Result<(), String> {
+    let window = app.get_webview_window("main").ok_or("Main window not found")?;
+    let count = (count > 0).then_some(count);
+    #[cfg(target_os = "windows")]
+    window
+        .set_overlay_icon(count.map(overlay_icon))
+        .map_err(|e| format!("Failed to set overlay icon: {e}"))?;
+    #[cfg(not(target_os = "windows"))]
+    window
+        .set_badge_count(count.map(i64::from))
+        .map_err(|e| format!("Failed to set badge: {e}"))?;
+    Ok(())
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub fn set_badge_count(_count: u32) -> Result<(), String> {
+    Ok(())
+}
+
+/// Show progress on the main window's taskbar button or the dock icon.
+/// `value` runs from 0.0 to 1.0 and is ignored for `none` and `indeterminate`.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub fn set_progress(value: Option<f64>, state: ProgressState, app: tauri::AppHandle) -> Result<(), String> {
+    use tauri::window::{ProgressBarState, ProgressBarStatus};
+
+    let window = app.get_webview_window("main").ok_or("Main window not found")?;
+    let status = match state {
+        ProgressState::None => ProgressBarStatus::None,
+        ProgressState::Normal => ProgressBarStatus::Normal,
+        ProgressState::Indeterminate => ProgressBarStatus::Indeterminate,
+        ProgressState::Paused => ProgressBarStatus::Paused,
+        ProgressState::Error => ProgressBarStatus::Error,
+    };
+    let progress = value.filter(|v| v.is_finite()).map(|v| (v.clamp(0.0, 1.0) * 100.0).round() as u64);
+    window
+        .set_progress_bar(ProgressBarState { status: Some(status), progress })
+        .map_err(|e| format!("Failed to set progress: {e}"))
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub fn set_progress(_value: Option<f64>, _state: ProgressState) -> Result<(), String> {
+    Ok(())
+}
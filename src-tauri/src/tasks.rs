@@ -0,0 +1,242 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use crate::ingest::now_millis;
+
+// Watchdog for background work. Long-running jobs (feed fetches, pandoc runs…)
+// go through `supervise`, which lists them for `get_background_tasks`, lets the
+// user cancel them, and kills any that stop making progress for too long.
+
+#[derive(Clone, Serialize, Debug)]
+pub struct TaskInfo {
+    pub id: u64,
+    /// Caller-chosen id for `cancel_request`, on tasks started from a command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// "fetch", "pandoc", …
+    pub kind: String,
+    pub label: String,
+    /// ms since epoch.
+    pub started_at: u64,
+    pub last_progress_at: u64,
+    /// 0.0–1.0 when the task reports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f32>,
+    /// Killed after this long without progress.
+    pub stall_timeout_ms: u64,
+    pub cancelling: bool,
+}
+
+struct Entry {
+    info: TaskInfo,
+    cancelled: Arc<AtomicBool>,
+    wake: Arc<Notify>,
+}
+
+static TASKS: OnceLock<Mutex<HashMap<u64, Entry>>> = OnceLock::new();
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn tasks_lock() -> &'static Mutex<HashMap<u64, Entry>> {
+    TASKS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Unregisters a task when dropped, so it leaves the list even if the
+/// supervising future is dropped before finishing. Blocking work still running
+/// for it is flagged to stop.
+struct Registration {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        tasks_lock().lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Passed to a supervised job so it can report progress and notice cancellation.
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+    /// Record progress, resetting the stall timer.
+    pub fn progress(&self, fraction: Option<f32>) {
+        if let Some(entry) = tasks_lock().lock().unwrap().get_mut(&self.id) {
+            entry.info.last_progress_at = now_millis();
+            if fraction.is_some() {
+                entry.info.progress = fraction.map(|f| f.clamp(0.0, 1.0));
+            }
+        }
+    }
+
+    /// Set once the task is cancelled or timed out; blocking work should stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Run `job` as a registered background task. It is dropped (and its handle
+/// flagged) when cancelled or after `stall_timeout` without progress.
+pub async fn supervise<T, F, Fut>(kind: &str, label: &str, stall_timeout: Duration, job: F) -> Result<T, String>
+where
+    F: FnOnce(TaskHandle) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    supervise_request(None, kind, label, stall_timeout, job).await
+}
+
+/// `supervise` for work started by a command that passed a `request_id`, so the
+/// frontend can abort it with `cancel_request` without knowing the task id.
+pub async fn supervise_request<T, F, Fut>(
+    request_id: Option<&str>,
+    kind: &str,
+    label: &str,
+    stall_timeout: Duration,
+    job: F,
+) -> Result<T, String>
+where
+    F: FnOnce(TaskHandle) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let wake = Arc::new(Notify::new());
+    let now = now_millis();
+    tasks_lock().lock().unwrap().insert(
+        id,
+        Entry {
+            info: TaskInfo {
+                id,
+                request_id: request_id.map(String::from),
+                kind: kind.to_string(),
+                label: label.to_string(),
+                started_at: now,
+                last_progress_at: now,
+                progress: None,
+                stall_timeout_ms: stall_timeout.as_millis() as u64,
+                cancelling: false,
+            },
+            cancelled: cancelled.clone(),
+            wake: wake.clone(),
+        },
+    );
+
+    let _registration = Registration { id, cancelled: cancelled.clone() };
+
+    let job = job(TaskHandle { id, cancelled: cancelled.clone() });
+    tokio::pin!(job);
+    loop {
+        let idle = tasks_lock()
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map_or(0, |e| now_millis().saturating_sub(e.info.last_progress_at));
+        let Some(remaining) = stall_timeout.checked_sub(Duration::from_millis(idle)).filter(|d| !d.is_zero())
+        else {
+            eprintln!("[tasks] Killing {kind} task '{label}': no progress for {stall_timeout:?}");
+            cancelled.store(true, Ordering::SeqCst);
+            break Err(format!("Timed out: no progress for {}s", stall_timeout.as_secs()));
+        };
+        tokio::select! {
+            out = &mut job => break out,
+            _ = wake.notified() => {
+                eprintln!("[tasks] Cancelled {kind} task '{label}'");
+                break Err("Cancelled".to_string());
+            }
+            // Progress may have moved the deadline; re-check before killing
+            _ = tokio::time::sleep(remaining) => {}
+        }
+    }
+}
+
+fn read_all(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// `Command::output` on a blocking thread, killing the process if the task is
+/// cancelled or times out meanwhile.
+pub async fn run_process(handle: &TaskHandle, mut cmd: Command) -> Result<Output, String> {
+    let handle = handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start process: {e}"))?;
+        let stdout = read_all(child.stdout.take().ok_or("No stdout pipe")?);
+        let stderr = read_all(child.stderr.take().ok_or("No stderr pipe")?);
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if handle.is_cancelled() => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err("Cancelled".to_string());
+                }
+                Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+                Err(e) => return Err(format!("Failed to wait for process: {e}")),
+            }
+        };
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    })
+    .await
+    .map_err(|e| format!("Process task failed: {e}"))?
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Everything currently running in the background, oldest first.
+#[tauri::command]
+pub fn get_background_tasks() -> Vec<TaskInfo> {
+    let mut list: Vec<TaskInfo> = tasks_lock().lock().unwrap().values().map(|e| e.info.clone()).collect();
+    list.sort_by_key(|t| t.started_at);
+    list
+}
+
+fn cancel(entry: &mut Entry) {
+    entry.info.cancelling = true;
+    entry.cancelled.store(true, Ordering::SeqCst);
+    entry.wake.notify_one();
+}
+
+/// Cancel a background task. Returns false if it already finished.
+#[tauri::command]
+pub fn cancel_background_task(id: u64) -> bool {
+    let mut tasks = tasks_lock().lock().unwrap();
+    let Some(entry) = tasks.get_mut(&id) else { return false };
+    cancel(entry);
+    true
+}
+
+/// Abort the in-flight request started with `request_id` (fetch_url,
+/// fetch_urls, fetch_binary). Returns false if it already finished.
+#[tauri::command]
+pub fn cancel_request(request_id: String) -> bool {
+    let mut tasks = tasks_lock().lock().unwrap();
+    let mut found = false;
+    for entry in tasks.values_mut().filter(|e| e.info.request_id.as_deref() == Some(request_id.as_str())) {
+        cancel(entry);
+        found = true;
+    }
+    found
+}
@@ -0,0 +1,119 @@
+// Shared text helpers for the analysis modules (clustering, trending, …).
+
+/// English + French stopwords, plus feed boilerplate that carries no topic signal.
+const STOPWORDS: &[&str] = &[
+    // English
+    "the", "and", "for", "are", "but", "not", "you", "all", "any", "can", "had", "her", "was",
+    "one", "our", "out", "has", "have", "his", "how", "its", "may", "new", "now", "old", "see",
+    "two", "way", "who", "did", "get", "got", "let", "say", "she", "too", "use", "from", "with",
+    "this", "that", "they", "them", "then", "than", "there", "their", "what", "when", "where",
+    "which", "while", "will", "would", "could", "should", "about", "after", "before", "into",
+    "over", "under", "again", "more", "most", "some", "such", "only", "other", "also", "just",
+    "very", "been", "being", "were", "your", "yours", "here", "these", "those", "because",
+    "does", "doing", "each", "few", "both", "between", "through", "during", "above", "below",
+    "off", "why", "own", "same", "says", "said", "first", "last", "year", "years",
+    "week", "today", "make", "made", "many", "much", "like", "still", "even", "back", "well",
+    // French
+    "les", "des", "une", "est", "pas", "par", "pour", "sur", "dans", "avec", "que", "qui",
+    "son", "ses", "sont", "aux", "mais", "comme", "plus", "tout", "tous", "cette", "ces",
+    "elle", "ils", "nous", "vous", "leur", "leurs", "être", "fait", "faire", "été", "ont",
+    "sans", "sous", "entre", "après", "avant", "encore", "aussi", "bien", "très", "deux",
+    "selon", "dont", "quand", "même", "notre", "votre", "était", "peut",
+    // Feed boilerplate
+    "http", "https", "www", "com", "html", "read", "continue", "reading", "appeared", "post",
+    "comments", "article", "via", "link",
+];
+
+pub fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(&word)
+}
+
+/// Lowercase, split on non-alphanumerics, drop stopwords, short tokens and pure numbers.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(|w| w.to_lowercase())
+        .filter(|w| !is_stopword(w) && !w.chars().all(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+/// Default length of list-view snippets, in characters.
+pub const SNIPPET_LEN: usize = 280;
+
+/// Elements whose text is never shown.
+const HIDDEN_ELEMENTS: &[&str] =
+    &["script", "style", "noscript", "template", "head", "title", "iframe", "svg"];
+/// Elements that break words apart when their tags are removed.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p", "div", "br", "hr", "li", "ul", "ol", "dd", "dt", "h1", "h2", "h3", "h4", "h5", "h6",
+    "blockquote", "pre", "table", "tr", "td", "th", "section", "article", "header", "footer",
+    "figure", "figcaption", "img",
+];
+
+/// Plain-text preview of an HTML fragment: tags stripped, entities decoded,
+/// whitespace collapsed, cut on a word boundary to at most `max_len` chars
+/// (including the trailing `…`). The fragment is only parsed, never rendered.
+pub fn snippet_from_html(html: &str, max_len: usize) -> String {
+    use ego_tree::iter::Edge;
+    use scraper::{Html, Node};
+
+    if !html.contains(['<', '&']) {
+        return truncate_words(&html.split_whitespace().collect::<Vec<_>>().join(" "), max_len);
+    }
+    let fragment = Html::parse_fragment(html);
+    let mut raw = String::new();
+    let mut visible = 0;
+    let mut hidden_depth = 0;
+    for edge in fragment.root_element().traverse() {
+        let (node, opening) = match edge {
+            Edge::Open(node) => (node, true),
+            Edge::Close(node) => (node, false),
+        };
+        match node.value() {
+            Node::Element(e) if HIDDEN_ELEMENTS.contains(&e.name()) => {
+                hidden_depth = if opening { hidden_depth + 1 } else { hidden_depth - 1 };
+            }
+            Node::Element(e) if BLOCK_ELEMENTS.contains(&e.name()) => raw.push(' '),
+            Node::Text(t) if opening && hidden_depth == 0 => {
+                raw.push_str(t);
+                visible += t.chars().filter(|c| !c.is_whitespace()).count();
+                // Enough text to truncate from; skip the rest of a long article
+                if visible > max_len {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    truncate_words(&raw.split_whitespace().collect::<Vec<_>>().join(" "), max_len)
+}
+
+/// All visible text of an HTML fragment, whitespace collapsed (for indexing).
+pub fn html_to_text(html: &str) -> String {
+    snippet_from_html(html, usize::MAX)
+}
+
+fn truncate_words(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    if max_len == 0 {
+        return String::new();
+    }
+    let cut: String = text.chars().take(max_len - 1).collect();
+    // Back up to the last space unless that would drop more than half the snippet
+    let cut = match cut.rfind(' ') {
+        Some(i) if cut[..i].chars().count() >= max_len / 2 => &cut[..i],
+        _ => cut.as_str(),
+    };
+    let cut = cut.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | ':' | '-' | '.'));
+    format!("{cut}…")
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Plain-text list preview of an HTML fragment (default 280 chars).
+#[tauri::command]
+pub fn html_to_snippet(html: String, max_len: Option<usize>) -> String {
+    snippet_from_html(&html, max_len.unwrap_or(SNIPPET_LEN))
+}
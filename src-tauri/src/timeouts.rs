@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// HTTP timeouts. The connect timeout is fixed per client, so changing it
+// rebuilds the shared clients; the overall timeout is set on each request and
+// can be raised for slow hosts or overridden for a single call.
+
+const CONFIG_FILE: &str = "timeouts.json";
+// Kept under the fetch watchdog's stall timeout so a slow request fails on its own first.
+const MAX_SECS: u64 = 300;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TimeoutConfig {
+    /// Whole request, from connecting to the last body byte.
+    pub request_secs: u64,
+    pub connect_secs: u64,
+    /// Per-host request timeouts, e.g. `{"rss.home.lan": 60}`. Subdomains inherit.
+    #[serde(default)]
+    pub hosts: HashMap<String, u64>,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        TimeoutConfig {
+            request_secs: 30,
+            connect_secs: 15,
+            hosts: HashMap::new(),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    fn request_secs_for(&self, host: &str) -> u64 {
+        let mut domain = host;
+        loop {
+            if let Some(secs) = self.hosts.get(domain) {
+                return *secs;
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => domain = parent,
+                _ => return self.request_secs,
+            }
+        }
+    }
+}
+
+static CONFIG: OnceLock<Mutex<TimeoutConfig>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+tokio::task_local! {
+    static OVERRIDE: Duration;
+}
+
+fn config_lock() -> &'static Mutex<TimeoutConfig> {
+    CONFIG.get_or_init(|| Mutex::new(TimeoutConfig::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(CONFIG_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(config) = serde_json::from_str::<TimeoutConfig>(&json) {
+            *config_lock().lock().unwrap() = config;
+        }
+    }
+}
+
+/// Set the client-wide timeouts on a client builder.
+pub fn apply(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let config = config_lock().lock().unwrap();
+    builder
+        .timeout(Duration::from_secs(config.request_secs))
+        .connect_timeout(Duration::from_secs(config.connect_secs))
+}
+
+/// Timeout for one request to `host`: the call's override, else the host's, else the global one.
+pub fn for_host(host: &str) -> Duration {
+    if let Ok(timeout) = OVERRIDE.try_with(|d| *d) {
+        return timeout;
+    }
+    let host = host.to_ascii_lowercase();
+    Duration::from_secs(config_lock().lock().unwrap().request_secs_for(&host))
+}
+
+/// Run `fut` with every request it sends using `timeout` (if given) instead of the configured one.
+pub async fn scoped<F: Future>(timeout: Option<Duration>, fut: F) -> F::Output {
+    match timeout {
+        Some(timeout) => OVERRIDE.scope(timeout, fut).await,
+        None => fut.await,
+    }
+}
+
+/// A per-call `timeout_secs` argument, bounded like the settings.
+pub fn from_secs(secs: Option<u64>) -> Option<Duration> {
+    secs.map(|s| Duration::from_secs(s.clamp(1, MAX_SECS)))
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_timeouts() -> TimeoutConfig {
+    config_lock().lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_timeouts(mut config: TimeoutConfig) -> Result<(), String> {
+    if !(1..=MAX_SECS).contains(&config.request_secs) {
+        return Err(format!("Request timeout must be between 1 and {MAX_SECS} seconds"));
+    }
+    if config.connect_secs == 0 || config.connect_secs > config.request_secs {
+        return Err("Connect timeout must be at least 1 second and no longer than the request timeout".into());
+    }
+    if config.hosts.values().any(|secs| !(1..=MAX_SECS).contains(secs)) {
+        return Err(format!("Host timeouts must be between 1 and {MAX_SECS} seconds"));
+    }
+    config.hosts = config
+        .hosts
+        .into_iter()
+        .map(|(host, secs)| (host.trim().to_ascii_lowercase(), secs))
+        .filter(|(host, _)| !host.is_empty())
+        .collect();
+    if let Some(dir) = DATA_DIR.get() {
+        let json =
+            serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize timeouts: {e}"))?;
+        std::fs::write(dir.join(CONFIG_FILE), json).map_err(|e| format!("Failed to save timeouts: {e}"))?;
+    }
+    *config_lock().lock().unwrap() = config;
+    crate::reset_client();
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use crate::ingest::now_millis;
+
+// Extra TLS trust for self-hosted servers (FreshRSS, Miniflux) behind a private CA
+// or a self-signed certificate. Added roots apply to every request; skipping
+// certificate checks is an explicit per-host opt-in served by a separate client.
+
+const TRUST_FILE: &str = "tls_trust.json";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TrustedCertificate {
+    /// SHA-256 of the first certificate's DER, hex encoded.
+    pub fingerprint: String,
+    pub label: String,
+    pub pem: String,
+    pub added_at: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct TlsTrust {
+    #[serde(default)]
+    pub certificates: Vec<TrustedCertificate>,
+    /// Hosts whose certificate is not verified at all.
+    #[serde(default)]
+    pub insecure_hosts: Vec<String>,
+}
+
+static TRUST: OnceLock<Mutex<TlsTrust>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn trust_lock() -> &'static Mutex<TlsTrust> {
+    TRUST.get_or_init(|| Mutex::new(TlsTrust::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(TRUST_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(trust) = serde_json::from_str::<TlsTrust>(&json) {
+            eprintln!(
+                "[tls] {} extra root certificates, {} hosts without verification",
+                trust.certificates.len(),
+                trust.insecure_hosts.len()
+            );
+            *trust_lock().lock().unwrap() = trust;
+        }
+    }
+}
+
+fn save_trust(trust: &TlsTrust) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json = serde_json::to_string_pretty(trust).map_err(|e| format!("Failed to serialize TLS trust: {e}"))?;
+    std::fs::write(dir.join(TRUST_FILE), json).map_err(|e| format!("Failed to save TLS trust: {e}"))
+}
+
+/// DER bytes of each `CERTIFICATE` block in a PEM string.
+fn pem_blocks(pem: &str) -> Vec<Vec<u8>> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+    for line in pem.lines().map(str::trim) {
+        if line == "-----BEGIN CERTIFICATE-----" {
+            current = Some(String::new());
+        } else if line == "-----END CERTIFICATE-----" {
+            if let Some(der) = current.take().and_then(|b64| STANDARD.decode(b64).ok()) {
+                blocks.push(der);
+            }
+        } else if let Some(b64) = current.as_mut() {
+            b64.push_str(line);
+        }
+    }
+    blocks
+}
+
+// ── Client configuration ─────────────────────────────────────────────
+
+/// Add the trusted roots to a client builder. Certificates that no longer
+/// parse are skipped rather than breaking every request.
+pub fn apply(mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let trust = trust_lock().lock().unwrap();
+    for cert in &trust.certificates {
+        match reqwest::Certificate::from_pem_bundle(cert.pem.as_bytes()) {
+            Ok(certs) => {
+                for c in certs {
+                    builder = builder.add_root_certificate(c);
+                }
+            }
+            Err(e) => eprintln!("[tls] Skipping certificate '{}': {e}", cert.label),
+        }
+    }
+    builder
+}
+
+/// Whether requests to `host` may skip certificate verification.
+pub fn accepts_invalid_certs(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    trust_lock().lock().unwrap().insecure_hosts.contains(&host)
+}
+
+/// DER bytes of the trusted roots, for connections that don't go through reqwest.
+pub fn root_certificates() -> Vec<Vec<u8>> {
+    trust_lock().lock().unwrap().certificates.iter().flat_map(|cert| pem_blocks(&cert.pem)).collect()
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_tls_trust() -> TlsTrust {
+    trust_lock().lock().unwrap().clone()
+}
+
+/// Trust the CA certificate(s) in `pem` for all HTTPS requests.
+#[tauri::command]
+pub fn add_root_certificate(pem: String, label: Option<String>) -> Result<TrustedCertificate, String> {
+    let certs = reqwest::Certificate::from_pem_bundle(pem.as_bytes()).map_err(|e| format!("Invalid PEM: {e}"))?;
+    let blocks = pem_blocks(&pem);
+    if certs.is_empty() || blocks.is_empty() {
+        return Err("No certificate found in PEM".into());
+    }
+    let fingerprint = format!("{:x}", Sha256::digest(&blocks[0]));
+    let cert = TrustedCertificate {
+        label: label
+            .filter(|l| !l.trim().is_empty())
+            .unwrap_or_else(|| format!("Certificate {}", &fingerprint[..16])),
+        fingerprint,
+        pem,
+        added_at: now_millis(),
+    };
+    {
+        let mut trust = trust_lock().lock().unwrap();
+        trust.certificates.retain(|c| c.fingerprint != cert.fingerprint);
+        trust.certificates.push(cert.clone());
+        save_trust(&trust)?;
+    }
+    eprintln!("[tls] Trusted root certificate '{}'", cert.label);
+    crate::reset_client();
+    Ok(cert)
+}
+
+#[tauri::command]
+pub fn remove_root_certificate(fingerprint: String) -> Result<bool, String> {
+    let removed = {
+        let mut trust = trust_lock().lock().unwrap();
+        let before = trust.certificates.len();
+        trust.certificates.retain(|c| c.fingerprint != fingerprint);
+        let removed = trust.certificates.len() != before;
+        if removed {
+            save_trust(&trust)?;
+        }
+        removed
+    };
+    if removed {
+        crate::reset_client();
+    }
+    Ok(removed)
+}
+
+/// Opt a host in or out of skipping certificate verification.
+#[tauri::command]
+pub fn set_accept_invalid_certs(host: String, accept: bool) -> Result<(), String> {
+    let host = host.trim().to_ascii_lowercase();
+    if host.is_empty() {
+        return Err("Host is required".into());
+    }
+    let mut trust = trust_lock().lock().unwrap();
+    trust.insecure_hosts.retain(|h| *h != host);
+    if accept {
+        eprintln!("[tls] Certificate verification disabled for {host}");
+        trust.insecure_hosts.push(host);
+        trust.insecure_hosts.sort();
+    }
+    save_trust(&trust)
+}
@@ -0,0 +1,202 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::ingest::StoredItem;
+use crate::text::{is_stopword, tokenize};
+
+// ── Data model ───────────────────────────────────────────────────────
+
+const COUNTS_FILE: &str = "keyword_counts.json";
+const DATE_FMT: &str = "%Y-%m-%d";
+const DAYS_KEPT: i64 = 60;
+/// Days before the window used as the "normal" frequency baseline.
+const BASELINE_DAYS: i64 = 28;
+const MIN_COUNT: u32 = 3;
+const MAX_RESULTS: usize = 30;
+
+#[derive(Clone, Serialize, Debug)]
+pub struct TrendingTerm {
+    pub term: String,
+    /// Items mentioning the term in the window.
+    pub count: u32,
+    /// Expected count for a window of the same length, from the baseline period.
+    pub baseline: f64,
+    /// (count + 1) / (baseline + 1); > 1 means above normal.
+    pub score: f64,
+}
+
+// ── Persistent store ─────────────────────────────────────────────────
+
+/// Per-day document frequencies: date → term → number of items mentioning it.
+pub struct TrendingStore {
+    daily: Mutex<BTreeMap<String, HashMap<String, u32>>>,
+    data_dir: Mutex<Option<PathBuf>>,
+    dirty: AtomicBool,
+}
+
+impl TrendingStore {
+    pub fn new() -> Self {
+        TrendingStore {
+            daily: Mutex::new(BTreeMap::new()),
+            data_dir: Mutex::new(None),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_data_dir(&self, dir: PathBuf) {
+        *self.data_dir.lock().unwrap() = Some(dir);
+        self.load_from_disk();
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        self.data_dir.lock().unwrap().as_ref().map(|d| d.join(COUNTS_FILE))
+    }
+
+    fn load_from_disk(&self) {
+        if let Some(path) = self.file_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => {
+                        if let Ok(daily) = serde_json::from_str(&json) {
+                            *self.daily.lock().unwrap() = daily;
+                        }
+                    }
+                    Err(e) => eprintln!("[trending] Failed to read keyword counts: {e}"),
+                }
+            }
+        }
+    }
+
+    pub fn flush(&self) {
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        let cutoff = (Local::now().date_naive() - chrono::Duration::days(DAYS_KEPT))
+            .format(DATE_FMT)
+            .to_string();
+        let mut daily = self.daily.lock().unwrap();
+        daily.retain(|day, _| *day >= cutoff);
+        if let Some(path) = self.file_path() {
+            match serde_json::to_string(&*daily) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("[trending] Failed to write keyword counts: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[trending] Failed to serialize keyword counts: {e}"),
+            }
+        }
+    }
+
+    /// Count each distinct keyword/entity once per item, on the item's local date.
+    pub fn record(&self, items: &[StoredItem]) {
+        if items.is_empty() {
+            return;
+        }
+        let mut daily = self.daily.lock().unwrap();
+        for item in items {
+            let Some(day) = Local.timestamp_millis_opt(item.timestamp() as i64).single() else {
+                continue;
+            };
+            let counts = daily.entry(day.format(DATE_FMT).to_string()).or_default();
+            for term in extract_terms(&item.item.title, &item.item.summary) {
+                *counts.entry(term).or_insert(0) += 1;
+            }
+        }
+        self.dirty.store(true, Ordering::SeqCst);
+    }
+
+    pub fn trending(&self, start: NaiveDate) -> Vec<TrendingTerm> {
+        let today = Local::now().date_naive();
+        let window_days = ((today - start).num_days() + 1).max(1);
+        let baseline_start = start - chrono::Duration::days(BASELINE_DAYS);
+        let key = |d: NaiveDate| d.format(DATE_FMT).to_string();
+
+        let daily = self.daily.lock().unwrap();
+        let mut current: HashMap<&str, u32> = HashMap::new();
+        for counts in daily.range(key(start)..).map(|(_, c)| c) {
+            for (term, n) in counts {
+                *current.entry(term.as_str()).or_insert(0) += n;
+            }
+        }
+        let mut baseline: HashMap<&str, u32> = HashMap::new();
+        let baseline_days = daily.range(key(baseline_start)..key(start)).count().max(1) as f64;
+        for counts in daily.range(key(baseline_start)..key(start)).map(|(_, c)| c) {
+            for (term, n) in counts {
+                *baseline.entry(term.as_str()).or_insert(0) += n;
+            }
+        }
+
+        let mut terms: Vec<TrendingTerm> = current
+            .into_iter()
+            .filter(|(_, count)| *count >= MIN_COUNT)
+            .map(|(term, count)| {
+                let expected = baseline.get(term).copied().unwrap_or(0) as f64 / baseline_days * window_days as f64;
+                let expected = (expected * 100.0).round() / 100.0;
+                TrendingTerm {
+                    term: term.to_string(),
+                    count,
+                    baseline: expected,
+                    score: ((count as f64 + 1.0) / (expected + 1.0) * 100.0).round() / 100.0,
+                }
+            })
+            .filter(|t| t.score > 1.0)
+            .collect();
+        terms.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.count.cmp(&a.count)));
+        terms.truncate(MAX_RESULTS);
+        terms
+    }
+}
+
+/// Keywords from title + summary, plus capitalized title phrases ("Elon Musk")
+/// kept together as entities.
+fn extract_terms(title: &str, summary: &str) -> HashSet<String> {
+    let mut terms: HashSet<String> = tokenize(&format!("{title} {summary}")).into_iter().collect();
+
+    let mut phrase: Vec<&str> = Vec::new();
+    // Skip the first word: sentence-initial capitals are not a signal
+    for word in title.split_whitespace().skip(1).chain(std::iter::once("")) {
+        let clean = word.trim_matches(|c: char| !c.is_alphanumeric());
+        let is_cap = clean.chars().next().is_some_and(|c| c.is_uppercase())
+            && clean.chars().count() > 1
+            && !is_stopword(&clean.to_lowercase());
+        if is_cap {
+            phrase.push(clean);
+        } else {
+            if phrase.len() >= 2 {
+                terms.insert(phrase.join(" ").to_lowercase());
+            }
+            phrase.clear();
+        }
+    }
+    terms
+}
+
+pub fn start_flush_timer(store: Arc<TrendingStore>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(60));
+        store.flush();
+    });
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Keywords mentioned unusually often in `range` (`today`, `48h`, `7d`, …)
+/// compared with the preceding four weeks.
+#[tauri::command]
+pub fn get_trending(
+    range: String,
+    store: tauri::State<'_, Arc<TrendingStore>>,
+) -> Result<Vec<TrendingTerm>, String> {
+    let since = crate::ingest::range_start_millis(&range)?;
+    let start = Local
+        .timestamp_millis_opt(since as i64)
+        .single()
+        .ok_or("Invalid range start")?
+        .date_naive();
+    Ok(store.trending(start))
+}
@@ -0,0 +1,456 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+// Voice commands. Transcripts are matched against a small fixed grammar
+// ("next article", "read this", "mark all read", ...) and the action is
+// emitted as `voice-command` for the frontend, which knows the current article;
+// stopping speech is handled here as well, since the backend owns the TTS.
+//
+// Transcripts come from the frontend (`voice_command`) or, in builds with the
+// `voice` feature, from local recognition: hold the push-to-talk shortcut, the
+// microphone is recorded through cpal and transcribed by whisper.cpp with a
+// ggml model the user downloads. Nothing leaves the machine.
+
+const SETTINGS_FILE: &str = "voice_settings.json";
+const DEFAULT_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceAction {
+    NextArticle,
+    PreviousArticle,
+    ReadArticle,
+    StopReading,
+    MarkRead,
+    MarkUnread,
+    MarkAllRead,
+    Star,
+    OpenInBrowser,
+    Refresh,
+    ScrollDown,
+    ScrollUp,
+}
+
+/// Phrases per action. The longest phrase found in a transcript wins, so
+/// "mark all as read" isn't taken for "mark as read".
+const GRAMMAR: &[(VoiceAction, &[&str])] = &[
+    (VoiceAction::NextArticle, &["next article", "next item", "next story", "next one", "next"]),
+    (VoiceAction::PreviousArticle, &["previous article", "previous one", "previous", "go back"]),
+    (VoiceAction::ReadArticle, &["read this", "read it", "read article", "read aloud", "start reading"]),
+    (VoiceAction::StopReading, &["stop reading", "stop", "be quiet", "pause"]),
+    (VoiceAction::MarkRead, &["mark read", "mark as read"]),
+    (VoiceAction::MarkUnread, &["mark unread", "mark as unread", "keep unread"]),
+    (VoiceAction::MarkAllRead, &["mark all read", "mark all as read", "mark everything read", "catch up"]),
+    (VoiceAction::Star, &["star this", "star it", "star", "save this", "favorite"]),
+    (VoiceAction::OpenInBrowser, &["open in browser", "open this", "open article", "open it"]),
+    (VoiceAction::Refresh, &["refresh feeds", "refresh", "reload", "check for new"]),
+    (VoiceAction::ScrollDown, &["scroll down", "page down"]),
+    (VoiceAction::ScrollUp, &["scroll up", "page up"]),
+];
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct VoiceSettings {
+    /// Push-to-talk shortcut registered.
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_shortcut")]
+    pub shortcut: String,
+    /// A whisper.cpp ggml model, e.g. `ggml-base.en.bin`.
+    #[serde(default)]
+    pub model_path: Option<String>,
+    /// ISO 639-1 code the commands are spoken in.
+    #[serde(default = "default_language")]
+    pub language: String,
+}
+
+fn default_shortcut() -> String {
+    DEFAULT_SHORTCUT.into()
+}
+
+fn default_language() -> String {
+    "en".into()
+}
+
+impl Default for VoiceSettings {
+    fn default() -> Self {
+        VoiceSettings {
+            enabled: false,
+            shortcut: default_shortcut(),
+            model_path: None,
+            language: default_language(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct VoiceCommand {
+    pub action: VoiceAction,
+    pub transcript: String,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct GrammarEntry {
+    pub action: VoiceAction,
+    pub phrases: Vec<String>,
+}
+
+static SETTINGS: OnceLock<Mutex<VoiceSettings>> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn settings_lock() -> &'static Mutex<VoiceSettings> {
+    SETTINGS.get_or_init(|| Mutex::new(VoiceSettings::default()))
+}
+
+pub fn init(data_dir: PathBuf) {
+    let path = data_dir.join(SETTINGS_FILE);
+    let _ = DATA_DIR.set(data_dir);
+    if let Ok(json) = std::fs::read_to_string(&path) {
+        if let Ok(settings) = serde_json::from_str::<VoiceSettings>(&json) {
+            *settings_lock().lock().unwrap() = settings;
+        }
+    }
+}
+
+fn save_settings(settings: &VoiceSettings) -> Result<(), String> {
+    let Some(dir) = DATA_DIR.get() else { return Ok(()) };
+    let json =
+        serde_json::to_string_pretty(settings).map_err(|e| format!("Failed to serialize voice settings: {e}"))?;
+    std::fs::write(dir.join(SETTINGS_FILE), json).map_err(|e| format!("Failed to save voice settings: {e}"))
+}
+
+// ── Grammar ──────────────────────────────────────────────────────────
+
+/// Lowercase words without punctuation; recognizers add capitals and full stops.
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// The action whose phrase appears, as whole words, in `transcript`.
+pub fn parse(transcript: &str) -> Option<VoiceAction> {
+    let spoken = words(transcript);
+    GRAMMAR
+        .iter()
+        .flat_map(|(action, phrases)| phrases.iter().map(move |p| (*action, words(p))))
+        .filter(|(_, phrase)| spoken.windows(phrase.len()).any(|w| w == phrase.as_slice()))
+        .max_by_key(|(_, phrase)| phrase.len())
+        .map(|(action, _)| action)
+}
+
+/// Match a transcript and act on it; `voice-unrecognized` when nothing matches.
+pub fn dispatch(app: &tauri::AppHandle, transcript: &str) -> Option<VoiceAction> {
+    let transcript = transcript.trim().to_string();
+    let Some(action) = parse(&transcript) else {
+        eprintln!("[voice] No command in \"{transcript}\"");
+        let _ = app.emit("voice-unrecognized", &transcript);
+        return None;
+    };
+    eprintln!("[voice] \"{transcript}\" -> {action:?}");
+    if action == VoiceAction::StopReading {
+        if let Err(e) = crate::tts_stop() {
+            eprintln!("[voice] {e}");
+        }
+    }
+    let _ = app.emit("voice-command", VoiceCommand { action, transcript });
+    Some(action)
+}
+
+// ── Recognition ──────────────────────────────────────────────────────
+
+#[cfg(all(feature = "voice", not(target_os = "android")))]
+mod recognizer {
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+    use tauri::Emitter;
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    use super::{VoiceSettings, GRAMMAR};
+
+    /// whisper.cpp takes 16 kHz mono.
+    const SAMPLE_RATE: u32 = 16_000;
+    /// A held shortcut stops recording after this long.
+    const MAX_RECORDING: Duration = Duration::from_secs(10);
+    /// Shorter presses are taken as accidental.
+    const MIN_RECORDING: Duration = Duration::from_millis(300);
+
+    /// Set while recording; cleared by releasing the shortcut.
+    static LISTENING: AtomicBool = AtomicBool::new(false);
+
+    /// Model path and the context loaded from it.
+    type LoadedModel = (String, Arc<WhisperContext>);
+
+    /// The loaded model, kept between commands (loading takes a second or more).
+    fn model() -> &'static Mutex<Option<LoadedModel>> {
+        static MODEL: OnceLock<Mutex<Option<LoadedModel>>> = OnceLock::new();
+        MODEL.get_or_init(|| Mutex::new(None))
+    }
+
+    fn load_model(path: &str) -> Result<Arc<WhisperContext>, String> {
+        let mut slot = model().lock().unwrap();
+        if let Some((_, ctx)) = slot.as_ref().filter(|(p, _)| p == path) {
+            return Ok(ctx.clone());
+        }
+        let ctx = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .map_err(|e| format!("Failed to load speech model: {e}"))?;
+        let ctx = Arc::new(ctx);
+        *slot = Some((path.to_string(), ctx.clone()));
+        Ok(ctx)
+    }
+
+    /// Record the default microphone until `LISTENING` is cleared, as 16 kHz mono.
+    fn record() -> Result<Vec<f32>, String> {
+        let device = cpal::default_host().default_input_device().ok_or("No microphone found")?;
+        let config = device.default_input_config().map_err(|e| format!("Microphone unavailable: {e}"))?;
+        let channels = config.channels() as usize;
+        let rate = config.sample_rate().0;
+        let format = config.sample_format();
+        let stream_config: cpal::StreamConfig = config.into();
+
+        let captured = Arc::new(Mutex::new(Vec::<f32>::new()));
+        let sink = captured.clone();
+        // Interleaved frames mixed down to mono
+        let push = move |samples: &mut dyn Iterator<Item = f32>| {
+            let samples: Vec<f32> = samples.collect();
+            let mono = samples.chunks(channels).map(|frame| frame.iter().sum::<f32>() / frame.len() as f32);
+            sink.lock().unwrap().extend(mono);
+        };
+        let on_error = |e| eprintln!("[voice] Microphone error: {e}");
+        let stream = match format {
+            cpal::SampleFormat::F32 => device.build_input_stream(
+                &stream_config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| push(&mut data.iter().copied()),
+                on_error,
+                None,
+            ),
+            cpal::SampleFormat::I16 => device.build_input_stream(
+                &stream_config,
+                move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    push(&mut data.iter().map(|s| *s as f32 / i16::MAX as f32))
+                },
+                on_error,
+                None,
+            ),
+            cpal::SampleFormat::U16 => device.build_input_stream(
+                &stream_config,
+                move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                    push(&mut data.iter().map(|s| (*s as f32 - 32768.0) / 32768.0))
+                },
+                on_error,
+                None,
+            ),
+            other => return Err(format!("Unsupported microphone format {other:?}")),
+        }
+        .map_err(|e| format!("Failed to open microphone: {e}"))?;
+        stream.play().map_err(|e| format!("Failed to start recording: {e}"))?;
+
+        let started = Instant::now();
+        while LISTENING.load(Ordering::SeqCst) && started.elapsed() < MAX_RECORDING {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        drop(stream);
+        if started.elapsed() < MIN_RECORDING {
+            return Ok(Vec::new());
+        }
+        let samples = std::mem::take(&mut *captured.lock().unwrap());
+        Ok(resample(&samples, rate))
+    }
+
+    /// Linear interpolation is plenty for speech recognition.
+    fn resample(samples: &[f32], rate: u32) -> Vec<f32> {
+        if rate == SAMPLE_RATE || samples.is_empty() {
+            return samples.to_vec();
+        }
+        let step = rate as f64 / SAMPLE_RATE as f64;
+        let len = (samples.len() as f64 / step) as usize;
+        (0..len)
+            .map(|i| {
+                let pos = i as f64 * step;
+                let (index, frac) = (pos as usize, pos.fract() as f32);
+                let next = samples.get(index + 1).copied().unwrap_or(samples[index]);
+                samples[index] * (1.0 - frac) + next * frac
+            })
+            .collect()
+    }
+
+    fn transcribe(settings: &VoiceSettings, mut samples: Vec<f32>) -> Result<String, String> {
+        let path = settings.model_path.as_deref().ok_or("Choose a speech model in the voice settings")?;
+        let ctx = load_model(path)?;
+        let mut state = ctx.create_state().map_err(|e| format!("Speech recognition failed: {e}"))?;
+        // whisper.cpp rejects less than a second of audio
+        if samples.len() < SAMPLE_RATE as usize {
+            samples.resize(SAMPLE_RATE as usize, 0.0);
+        }
+        // Listing the commands steers recognition towards them
+        let prompt = GRAMMAR.iter().map(|(_, phrases)| phrases[0]).collect::<Vec<_>>().join(", ");
+        let threads = std::thread::available_parallelism().map(|n| n.get().min(4)).unwrap_or(2) as i32;
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(Some(&settings.language));
+        params.set_initial_prompt(&prompt);
+        params.set_n_threads(threads);
+        params.set_no_context(true);
+        params.set_single_segment(true);
+        params.set_no_timestamps(true);
+        params.set_suppress_blank(true);
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        state.full(params, &samples).map_err(|e| format!("Speech recognition failed: {e}"))?;
+        let segments = state.full_n_segments().map_err(|e| format!("Speech recognition failed: {e}"))?;
+        let text: Vec<String> = (0..segments).filter_map(|i| state.full_get_segment_text(i).ok()).collect();
+        Ok(text.join(" ").trim().to_string())
+    }
+
+    /// Start recording on a worker thread; the command is dispatched when `stop` is called.
+    pub fn start(app: &tauri::AppHandle) -> Result<(), String> {
+        if LISTENING.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let settings = super::settings_lock().lock().unwrap().clone();
+        if settings.model_path.is_none() {
+            LISTENING.store(false, Ordering::SeqCst);
+            return Err("Choose a speech model in the voice settings".into());
+        }
+        let app = app.clone();
+        let _ = app.emit("voice-listening", true);
+        std::thread::spawn(move || {
+            let result = record();
+            LISTENING.store(false, Ordering::SeqCst);
+            let _ = app.emit("voice-listening", false);
+            let transcript = result.and_then(|samples| {
+                if samples.is_empty() {
+                    Ok(String::new())
+                } else {
+                    transcribe(&settings, samples)
+                }
+            });
+            match transcript {
+                Ok(t) if t.is_empty() => {}
+                Ok(t) => {
+                    super::dispatch(&app, &t);
+                }
+                Err(e) => {
+                    eprintln!("[voice] {e}");
+                    let _ = app.emit("voice-error", &e);
+                }
+            }
+        });
+        Ok(())
+    }
+
+    pub fn stop() {
+        LISTENING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Register (or drop) the push-to-talk shortcut for the current settings.
+#[cfg(all(feature = "voice", not(target_os = "android")))]
+fn apply_shortcut(app: &tauri::AppHandle, previous: Option<&str>) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+    if let Some(previous) = previous {
+        let _ = app.global_shortcut().unregister(previous);
+    }
+    let settings = settings_lock().lock().unwrap().clone();
+    if !settings.enabled {
+        return Ok(());
+    }
+    app.global_shortcut()
+        .on_shortcut(settings.shortcut.as_str(), |app, _shortcut, event| match event.state {
+            ShortcutState::Pressed => {
+                if let Err(e) = recognizer::start(app) {
+                    let _ = app.emit("voice-error", &e);
+                }
+            }
+            ShortcutState::Released => recognizer::stop(),
+        })
+        .map_err(|e| format!("Failed to register shortcut '{}': {e}", settings.shortcut))?;
+    eprintln!("[voice] Push-to-talk on {}", settings.shortcut);
+    Ok(())
+}
+
+#[cfg(not(all(feature = "voice", not(target_os = "android"))))]
+fn apply_shortcut(_app: &tauri::AppHandle, _previous: Option<&str>) -> Result<(), String> {
+    Ok(())
+}
+
+/// Register the push-to-talk shortcut at startup, when enabled.
+pub fn start(app: &tauri::AppHandle) {
+    if let Err(e) = apply_shortcut(app, None) {
+        eprintln!("[voice] {e}");
+    }
+}
+
+const NOT_BUILT: &str = "This build has no speech recognition (built without the `voice` feature)";
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Whether this build can recognize speech itself.
+#[tauri::command]
+pub fn voice_available() -> bool {
+    cfg!(all(feature = "voice", not(target_os = "android")))
+}
+
+#[tauri::command]
+pub fn get_voice_settings() -> VoiceSettings {
+    settings_lock().lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_voice_settings(settings: VoiceSettings, app: tauri::AppHandle) -> Result<(), String> {
+    if settings.enabled && !voice_available() {
+        return Err(NOT_BUILT.into());
+    }
+    if let Some(path) = settings.model_path.as_deref().filter(|p| !std::path::Path::new(p).is_file()) {
+        return Err(format!("Model file not found: {path}"));
+    }
+    let previous = {
+        let mut current = settings_lock().lock().unwrap();
+        let previous = current.enabled.then(|| current.shortcut.clone());
+        *current = settings.clone();
+        previous
+    };
+    save_settings(&settings)?;
+    apply_shortcut(&app, previous.as_deref())
+}
+
+/// Phrases understood, per action, for a help screen.
+#[tauri::command]
+pub fn get_voice_commands() -> Vec<GrammarEntry> {
+    GRAMMAR
+        .iter()
+        .map(|(action, phrases)| GrammarEntry {
+            action: *action,
+            phrases: phrases.iter().map(|p| p.to_string()).collect(),
+        })
+        .collect()
+}
+
+/// Act on a transcript from elsewhere (e.g. the webview's speech recognition).
+#[tauri::command]
+pub fn voice_command(transcript: String, app: tauri::AppHandle) -> Option<VoiceAction> {
+    dispatch(&app, &transcript)
+}
+
+/// Push-to-talk from the UI: start recording...
+#[tauri::command]
+pub fn voice_start_listening(#[allow(unused)] app: tauri::AppHandle) -> Result<(), String> {
+    #[cfg(all(feature = "voice", not(target_os = "android")))]
+    return recognizer::start(&app);
+    #[cfg(not(all(feature = "voice", not(target_os = "android"))))]
+    Err(NOT_BUILT.into())
+}
+
+/// ...and stop, which transcribes and dispatches what was said.
+#[tauri::command]
+pub fn voice_stop_listening() {
+    #[cfg(all(feature = "voice", not(target_os = "android")))]
+    recognizer::stop();
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Monitor, PhysicalPosition, PhysicalSize};
+
+use crate::{AppState, SavedGeometry};
+
+// The main window's placement across restarts. Size and position are followed
+// as the window moves, kept in `AppState`, and written when the window is
+// destroyed; at launch they are applied during setup. The monitor is
+// remembered by name, so a window whose screen is gone, or whose spot on it
+// no longer exists, is centered on that monitor or the primary one instead.
+// A window that was collapsed to the widget bar comes back collapsed, where
+// the bar was, and expands to the saved geometry.
+
+const STATE_FILE: &str = "window_state.json";
+
+/// Physical pixels, as the window reports them.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WindowState {
+    #[serde(flatten)]
+    pub geometry: SavedGeometry,
+    /// Maximized on top of the geometry above, which is what un-maximizing returns to.
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+    /// The widget bar's own outer geometry while collapsed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<SavedGeometry>,
+}
+
+fn file_path(app: &tauri::AppHandle) -> Option<std::path::PathBuf> {
+    app.path().app_data_dir().ok().map(|d| d.join(STATE_FILE))
+}
+
+fn load(app: &tauri::AppHandle) -> Option<WindowState> {
+    let json = std::fs::read_to_string(file_path(app)?).ok()?;
+    match serde_json::from_str(&json) {
+        Ok(state) => Some(state),
+        Err(e) => {
+            eprintln!("[window_state] Ignoring unreadable window state: {e}");
+            None
+        }
+    }
+}
+
+fn save(app: &tauri::AppHandle, state: &WindowState) {
+    let Some(path) = file_path(app) else { return };
+    match serde_json::to_string_pretty(state) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                eprintln!("[window_state] Failed to write window state: {e}");
+            }
+        }
+        Err(e) => eprintln!("[window_state] Failed to serialize window state: {e}"),
+    }
+}
+
+/// Whether the window's title bar would be on `monitor`, so it can be dragged.
+fn shows_on(monitor: &Monitor, pos: PhysicalPosition<i32>) -> bool {
+    let (x, y) = (pos.x + 40, pos.y + 10);
+    let (origin, size) = (monitor.position(), monitor.size());
+    x >= origin.x && y >= origin.y && x < origin.x + size.width as i32 && y < origin.y + size.height as i32
+}
+
+/// The window's current placement; while maximized, minimized, in reading
+/// mode or collapsed to the widget bar, the geometry to come back to is kept
+/// from `previous`.
+fn current(window: &tauri::WebviewWindow, previous: Option<&WindowState>) -> Option<WindowState> {
+    let monitor = window.current_monitor().ok().flatten().and_then(|m| m.name().cloned());
+    let app_state = window.state::<AppState>();
+    let collapsed = app_state.saved.lock().unwrap().clone();
+    if let Some(geometry) = collapsed {
+        let bar = SavedGeometry { size: window.outer_size().ok()?, pos: window.outer_position().ok()? };
+        return Some(WindowState { geometry, maximized: false, monitor, collapsed: Some(bar) });
+    }
+    if window.is_minimized().unwrap_or(false) || app_state.reading.lock().unwrap().is_some() {
+        return previous.cloned();
+    }
+    let maximized = window.is_maximized().unwrap_or(false);
+    if let Some(previous) = previous.filter(|_| maximized) {
+        return Some(WindowState { maximized, monitor, collapsed: None, ..previous.clone() });
+    }
+    // Maximized with nothing to return to: the maximized size will have to do
+    let size = window.inner_size().ok()?;
+    let pos = window.outer_position().ok()?;
+    Some(WindowState { geometry: SavedGeometry { size, pos }, maximized, monitor, collapsed: None })
+}
+
+/// Place the main window where it was when the app last closed.
+pub fn restore(app: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+    let Some(state) = load(app) else { return };
+    let monitors = app.available_monitors().unwrap_or_default();
+    let named = monitors.iter().find(|m| state.monitor.is_some() && m.name() == state.monitor.as_ref());
+    let SavedGeometry { size, pos } = state.geometry;
+
+    let visible = match named {
+        Some(monitor) => shows_on(monitor, pos),
+        None => monitors.iter().any(|m| shows_on(m, pos)),
+    };
+    let _ = window.set_size(size);
+    if visible {
+        let _ = window.set_position(pos);
+    } else if let Some(monitor) = named.cloned().or_else(|| app.primary_monitor().ok().flatten()) {
+        // The saved spot is off-screen now: keep the size, as far as it fits, centered
+        let (origin, area) = (monitor.position(), monitor.size());
+        let fitted = PhysicalSize::new(size.width.min(area.width), size.height.min(area.height));
+        let _ = window.set_size(fitted);
+        let _ = window.set_position(PhysicalPosition::new(
+            origin.x + (area.width - fitted.width) as i32 / 2,
+            origin.y + (area.height - fitted.height) as i32 / 2,
+        ));
+    }
+    if let Some(bar) = &state.collapsed {
+        restore_collapsed(window, &app.state::<AppState>(), bar, &monitors);
+    } else if state.maximized {
+        let _ = window.maximize();
+    }
+    eprintln!("[window_state] Restored {}x{} at ({}, {})", size.width, size.height, pos.x, pos.y);
+    *app.state::<AppState>().window_state.lock().unwrap() = Some(state);
+}
+
+/// Collapse the just-restored window, which becomes what expanding returns to,
+/// and put the bar back where it was if that spot is still on a screen.
+fn restore_collapsed(
+    window: &tauri::WebviewWindow,
+    app_state: &AppState,
+    bar: &SavedGeometry,
+    monitors: &[Monitor],
+) {
+    if let Err(e) = crate::collapse(window, app_state) {
+        eprintln!("[window_state] Failed to restore the collapsed bar: {e}");
+        return;
+    }
+    if monitors.iter().any(|m| shows_on(m, bar.pos)) {
+        let _ = window.set_size(bar.size);
+        let _ = window.set_position(bar.pos);
+    }
+    eprintln!("[window_state] Restored collapsed at ({}, {})", bar.pos.x, bar.pos.y);
+}
+
+/// Follow the main window's placement and save it when the window goes away.
+pub fn track(app: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+    let app = app.clone();
+    let win = window.clone();
+    window.on_window_event(move |event| match event {
+        tauri::WindowEvent::Moved(_)
+        | tauri::WindowEvent::Resized(_)
+        | tauri::WindowEvent::CloseRequested { .. } => {
+            let app_state = app.state::<AppState>();
+            let mut tracked = app_state.window_state.lock().unwrap();
+            if let Some(state) = current(&win, tracked.as_ref()) {
+                *tracked = Some(state);
+            }
+        }
+        tauri::WindowEvent::Destroyed => {
+            if let Some(state) = app.state::<AppState>().window_state.lock().unwrap().as_ref() {
+                save(&app, state);
+            }
+        }
+        _ => {}
+    });
+}
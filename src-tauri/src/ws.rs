@@ -0,0 +1,248 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures_util::{SinkExt, StreamExt};
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION, SEC_WEBSOCKET_PROTOCOL};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::Connector;
+use url::{Host, Url};
+
+// WebSocket client bridge for realtime services the webview can't reach
+// because of CORS. Each connection runs on the async runtime; received
+// messages go to the webview as `ws-message`, and `ws-closed` follows the end.
+// There is no automatic reconnect: what to resend after reconnecting depends
+// on the service's protocol. Connections are direct, so they are refused
+// while a manual or SOCKS5 proxy is set rather than leak around it; the
+// target goes through the private-network guard like any HTTP request and
+// TLS trusts the added root certificates.
+
+/// Ping idle connections so NAT and proxies along the way keep them open.
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+/// How long `ws_close` waits for the server to acknowledge.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Serialize, Debug)]
+struct WsMessage {
+    connection_id: String,
+    /// Text as is, binary frames base64-encoded.
+    data: String,
+    binary: bool,
+}
+
+#[derive(Clone, Serialize, Debug)]
+struct WsClosed {
+    connection_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+static CONNECTIONS: OnceLock<Mutex<HashMap<String, mpsc::UnboundedSender<Message>>>> = OnceLock::new();
+
+fn connections_lock() -> &'static Mutex<HashMap<String, mpsc::UnboundedSender<Message>>> {
+    CONNECTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Public roots plus the user's trusted certificates.
+fn tls_connector() -> Connector {
+    let mut roots = rustls::RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    for der in crate::tls::root_certificates() {
+        if let Err(e) = roots.add(der.into()) {
+            eprintln!("[ws] Skipping trusted certificate: {e}");
+        }
+    }
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .expect("ring supports the default protocol versions")
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Connector::Rustls(Arc::new(config))
+}
+
+// ── Connection loop ──────────────────────────────────────────────────
+
+type Stream = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+fn record_close(closed: &mut WsClosed, frame: Option<CloseFrame>) {
+    if let Some(frame) = frame {
+        closed.code = Some(frame.code.into());
+        closed.reason = Some(frame.reason.to_string()).filter(|r| !r.is_empty());
+    }
+}
+
+/// Read until the close handshake completes; tungstenite sends the replies.
+async fn drain(incoming: &mut futures_util::stream::SplitStream<Stream>, closed: &mut WsClosed) {
+    while let Some(Ok(message)) = incoming.next().await {
+        if let Message::Close(frame) = message {
+            record_close(closed, frame);
+        }
+    }
+}
+
+async fn run(
+    app: tauri::AppHandle,
+    id: String,
+    stream: Stream,
+    mut outgoing: mpsc::UnboundedReceiver<Message>,
+) {
+    let (mut sink, mut incoming) = stream.split();
+    let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+    let mut closed = WsClosed { connection_id: id.clone(), code: None, reason: None, error: None };
+    loop {
+        tokio::select! {
+            received = incoming.next() => match received {
+                Some(Ok(Message::Text(text))) => {
+                    let data = text.to_string();
+                    let message = WsMessage { connection_id: id.clone(), data, binary: false };
+                    let _ = app.emit("ws-message", message);
+                }
+                Some(Ok(Message::Binary(bytes))) => {
+                    let data = STANDARD.encode(&bytes);
+                    let message = WsMessage { connection_id: id.clone(), data, binary: true };
+                    let _ = app.emit("ws-message", message);
+                }
+                Some(Ok(Message::Close(frame))) => {
+                    record_close(&mut closed, frame);
+                    drain(&mut incoming, &mut closed).await;
+                    break;
+                }
+                // Pings are answered by tungstenite
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    closed.error = Some(format!("Connection error: {e}"));
+                    break;
+                }
+                None => break,
+            },
+            message = outgoing.recv() => {
+                let Some(message) = message else { break };
+                let closing = matches!(message, Message::Close(_));
+                if let Err(e) = sink.send(message).await {
+                    closed.error = Some(format!("Send failed: {e}"));
+                    break;
+                }
+                if closing {
+                    let _ = tokio::time::timeout(CLOSE_TIMEOUT, drain(&mut incoming, &mut closed)).await;
+                    break;
+                }
+            }
+            _ = ping.tick() => {
+                if let Err(e) = sink.send(Message::Ping(Default::default())).await {
+                    closed.error = Some(format!("Ping failed: {e}"));
+                    break;
+                }
+            }
+        }
+    }
+    if let Some(e) = &closed.error {
+        eprintln!("[ws] {id}: {e}");
+    }
+    connections_lock().lock().unwrap().remove(&id);
+    let _ = app.emit("ws-closed", closed);
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// Open a WebSocket. Resolves once the handshake is done, with the connection
+/// id carried by its `ws-message` / `ws-closed` events.
+#[tauri::command]
+pub async fn ws_connect(
+    url: String,
+    headers: Option<HashMap<String, String>>,
+    protocols: Option<Vec<String>>,
+    window: tauri::WebviewWindow,
+    app: tauri::AppHandle,
+) -> Result<String, String> {
+    let parsed = Url::parse(&url).map_err(|e| format!("Invalid URL: {e}"))?;
+    if !matches!(parsed.scheme(), "ws" | "wss") {
+        return Err(format!("Not a WebSocket URL: {url}"));
+    }
+    crate::permissions::require_host(&window, &url).await?;
+    if crate::proxy::is_explicit() {
+        return Err("WebSockets can't go through the configured proxy".into());
+    }
+    crate::private_hosts::check_url(&parsed)?;
+    let host = match parsed.host() {
+        Some(Host::Ipv6(ip)) => ip.to_string(),
+        Some(host) => host.to_string(),
+        None => return Err(format!("URL has no host: {url}")),
+    };
+    let port = parsed.port_or_known_default().ok_or_else(|| format!("URL has no port: {url}"))?;
+    let mut request = parsed.as_str().into_client_request().map_err(|e| format!("Invalid URL: {e}"))?;
+    let request_headers = request.headers_mut();
+    // The per-site headers are keyed on http(s) hosts, which the ws(s) URL shares
+    for (name, value) in &crate::get_headers_for_url(&parsed) {
+        request_headers.insert(name, value.clone());
+    }
+    if let Some(auth) = crate::http_auth::preemptive(&parsed) {
+        request_headers.insert(AUTHORIZATION, auth);
+    }
+    if let Some(protocols) = protocols.filter(|p| !p.is_empty()) {
+        let value = HeaderValue::from_str(&protocols.join(", ")).map_err(|e| format!("Invalid protocol: {e}"))?;
+        request_headers.insert(SEC_WEBSOCKET_PROTOCOL, value);
+    }
+    for (key, value) in headers.unwrap_or_default() {
+        let name = HeaderName::try_from(key.as_str()).map_err(|e| format!("Invalid header name '{key}': {e}"))?;
+        let value =
+            HeaderValue::from_str(&value).map_err(|e| format!("Invalid header value for '{key}': {e}"))?;
+        request_headers.insert(name, value);
+    }
+
+    // Resolved here so the checked addresses are the ones connected to
+    let connect = async {
+        let addrs = crate::private_hosts::lookup(&host, port).await?;
+        let tcp = tokio::net::TcpStream::connect(&addrs[..])
+            .await
+            .map_err(|e| format!("Failed to connect to {url}: {e}"))?;
+        tokio_tungstenite::client_async_tls_with_config(request, tcp, None, Some(tls_connector()))
+            .await
+            .map_err(|e| format!("Failed to connect to {url}: {e}"))
+    };
+    let (stream, _) = tokio::time::timeout(crate::timeouts::for_host(&host), connect)
+        .await
+        .map_err(|_| format!("Timed out connecting to {url}"))??;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let (sender, outgoing) = mpsc::unbounded_channel();
+    connections_lock().lock().unwrap().insert(id.clone(), sender);
+    tauri::async_runtime::spawn(run(app, id.clone(), stream, outgoing));
+    Ok(id)
+}
+
+/// Send a text message, or a binary one from base64 when `binary` is set.
+#[tauri::command]
+pub fn ws_send(id: String, data: String, binary: Option<bool>) -> Result<(), String> {
+    let message = match binary.unwrap_or(false) {
+        true => Message::binary(STANDARD.decode(&data).map_err(|e| format!("Invalid base64: {e}"))?),
+        false => Message::text(data),
+    };
+    let connections = connections_lock().lock().unwrap();
+    let sender = connections.get(&id).ok_or("Connection is closed")?;
+    sender.send(message).map_err(|_| "Connection is closed".to_string())
+}
+
+/// Start the close handshake. Returns false if the connection was already closed;
+/// `ws-closed` follows once the server has answered.
+#[tauri::command]
+pub fn ws_close(id: String, code: Option<u16>, reason: Option<String>) -> bool {
+    let Some(sender) = connections_lock().lock().unwrap().remove(&id) else {
+        return false;
+    };
+    let frame = CloseFrame {
+        code: code.map(CloseCode::from).unwrap_or(CloseCode::Normal),
+        reason: reason.unwrap_or_default().into(),
+    };
+    let _ = sender.send(Message::Close(Some(frame)));
+    true
+}
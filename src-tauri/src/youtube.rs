@@ -0,0 +1,166 @@
+use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, COOKIE, USER_AGENT};
+use scraper::{Html, Selector};
+use std::sync::OnceLock;
+use url::Url;
+
+// YouTube pages to their Atom feeds. Feeds only exist per channel id
+// (`UC...`) or playlist id, but people paste @handles, /c/ and /user/ vanity
+// URLs, videos and shorts. Playlists map directly; everything else is fetched
+// and the channel id read from the page.
+
+const FEED_BASE: &str = "https://www.youtube.com/feeds/videos.xml";
+
+pub struct ResolvedFeed {
+    pub url: String,
+    /// Channel or playlist name, when the page was fetched.
+    pub title: Option<String>,
+}
+
+/// What the input points at, before any fetching.
+enum Target {
+    Channel(String),
+    Playlist(String),
+    /// A page whose HTML names the channel.
+    Page(Url),
+}
+
+fn channel_id_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^UC[0-9A-Za-z_-]{22}$").unwrap())
+}
+
+/// Channel ids in the page's embedded JSON, the channel's own first.
+fn embedded_id_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#""(?:externalId|browseId|channelId)"\s*:\s*"(UC[0-9A-Za-z_-]{22})""#).unwrap()
+    })
+}
+
+pub fn is_youtube_host(host: &str) -> bool {
+    let host = host.trim_start_matches("www.").trim_start_matches("m.");
+    host == "youtube.com" || host == "youtu.be" || host == "music.youtube.com"
+}
+
+fn parse_target(input: &str) -> Result<Target, String> {
+    let input = input.trim();
+    // A bare handle or channel id
+    if input.starts_with('@') && !input.contains('/') {
+        let url = Url::parse(&format!("https://www.youtube.com/{input}")).map_err(|e| e.to_string())?;
+        return Ok(Target::Page(url));
+    }
+    if channel_id_re().is_match(input) {
+        return Ok(Target::Channel(input.to_string()));
+    }
+    let with_scheme = if input.contains("://") { input.to_string() } else { format!("https://{input}") };
+    let url = Url::parse(&with_scheme).map_err(|e| format!("Invalid URL: {e}"))?;
+    if !url.host_str().is_some_and(is_youtube_host) {
+        return Err("Not a YouTube URL".into());
+    }
+    let query = |key: &str| url.query_pairs().find(|(k, _)| k == key).map(|(_, v)| v.into_owned());
+
+    if url.path() == "/feeds/videos.xml" {
+        if let Some(id) = query("channel_id") {
+            return Ok(Target::Channel(id));
+        }
+        if let Some(id) = query("playlist_id") {
+            return Ok(Target::Playlist(id));
+        }
+    }
+    // Playlists, including a video opened from one
+    if let Some(list) = query("list").filter(|l| !l.is_empty()) {
+        // Mixes and "watch later" are generated per viewer and have no feed
+        if !list.starts_with("RD") && list != "WL" && list != "LL" {
+            return Ok(Target::Playlist(list));
+        }
+    }
+    let segments: Vec<&str> =
+        url.path_segments().map(|s| s.filter(|s| !s.is_empty()).collect()).unwrap_or_default();
+    if let ["channel", id, ..] = segments.as_slice() {
+        if channel_id_re().is_match(id) {
+            return Ok(Target::Channel(id.to_string()));
+        }
+    }
+    if segments.is_empty() {
+        return Err("Paste a channel, @handle, video or playlist URL".into());
+    }
+    Ok(Target::Page(url))
+}
+
+fn feed_url(param: &str, id: &str) -> String {
+    let mut url = Url::parse(FEED_BASE).unwrap();
+    url.query_pairs_mut().append_pair(param, id);
+    url.to_string()
+}
+
+/// The channel id and name a YouTube page declares.
+fn channel_from_page(html: &str) -> Option<(String, Option<String>)> {
+    let doc = Html::parse_document(html);
+    let attr = |selector: &str, name: &str| {
+        let selector = Selector::parse(selector).ok()?;
+        doc.select(&selector).find_map(|el| el.value().attr(name).map(str::to_string))
+    };
+    let from_url = |href: String| {
+        let url = Url::parse(&href).ok()?;
+        let id = match url.query_pairs().find(|(k, _)| k == "channel_id") {
+            Some((_, id)) => id.into_owned(),
+            None => url.path().strip_prefix("/channel/")?.to_string(),
+        };
+        channel_id_re().is_match(&id).then_some(id)
+    };
+
+    // Channel pages link their own feed; video pages only name the uploader
+    let canonical = attr(r#"link[rel="canonical"]"#, "href");
+    let id = attr(r#"link[rel="alternate"][type="application/rss+xml"]"#, "href")
+        .and_then(from_url)
+        .or_else(|| attr(r#"meta[itemprop="channelId"]"#, "content"))
+        .filter(|id| channel_id_re().is_match(id))
+        .or_else(|| canonical.clone().and_then(from_url))
+        .or_else(|| embedded_id_re().captures(html).map(|c| c[1].to_string()))?;
+    // On a video page the title is the video's
+    let channel_page = canonical.is_some_and(|c| c.contains("/channel/") || c.contains("/@"));
+    let title = attr(r#"meta[property="og:title"]"#, "content").filter(|t| channel_page && !t.is_empty());
+    Some((id, title))
+}
+
+async fn get_page(url: &Url) -> Result<String, String> {
+    let mut headers = HeaderMap::new();
+    // The per-host defaults are for feeds; pages need a browser
+    headers.insert(USER_AGENT, HeaderValue::from_static(crate::BROWSER_USER_AGENT));
+    headers.insert(ACCEPT, HeaderValue::from_static("text/html,application/xhtml+xml"));
+    // Skips the EU cookie consent interstitial, which has no channel in it
+    headers.insert(COOKIE, HeaderValue::from_static("SOCS=CAI"));
+    let response = crate::send_get(url.as_str(), headers).await?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err("No such YouTube channel or video".into());
+    }
+    if !status.is_success() {
+        return Err(format!("YouTube answered HTTP {}", status.as_u16()));
+    }
+    crate::read_body(url.as_str(), response).await
+}
+
+/// The Atom feed behind a channel, @handle, /c/ or /user/ URL, video, short or playlist.
+pub async fn resolve(input: &str) -> Result<ResolvedFeed, String> {
+    match parse_target(input)? {
+        Target::Channel(id) => Ok(ResolvedFeed { url: feed_url("channel_id", &id), title: None }),
+        Target::Playlist(id) => Ok(ResolvedFeed { url: feed_url("playlist_id", &id), title: None }),
+        Target::Page(url) => {
+            let html = get_page(&url).await?;
+            let (id, title) =
+                channel_from_page(&html).ok_or_else(|| format!("Couldn't find a channel id on {url}"))?;
+            eprintln!("[youtube] {url} is channel {id}");
+            Ok(ResolvedFeed { url: feed_url("channel_id", &id), title })
+        }
+    }
+}
+
+// ── Tauri Commands ───────────────────────────────────────────────────
+
+/// The `feeds/videos.xml` URL for a YouTube channel, @handle, video or playlist URL.
+#[tauri::command]
+pub async fn resolve_youtube_feed(url: String) -> Result<String, String> {
+    resolve(&url).await.map(|feed| feed.url)
+}
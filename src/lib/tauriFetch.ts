@@ -20,10 +20,29 @@ setTimeout(() => {
 
 const PROXY_URL = 'http://localhost:3001/?url=';
 
-export async function fetchViaBackend(url: string): Promise<string> {
+export interface RedirectHop {
+  url: string;
+  status: number;
+}
+
+export interface FetchUrlResponse {
+  body: string;
+  status: number;
+  /** Final URL after redirects. */
+  url: string;
+  redirects: RedirectHop[];
+  /** Set when every redirect was permanent (301/308): the feed's new address. */
+  permanent_url?: string;
+  headers: Record<string, string>;
+  from_cache: boolean;
+}
+
+/** `feed` marks a feed refresh, which is counted in the feed stats. */
+export async function fetchViaBackend(url: string, opts: { feed?: boolean } = {}): Promise<string> {
   if (isTauri()) {
     try {
-      return await invoke<string>('fetch_url', { targetUrl: url });
+      const response = await invoke<FetchUrlResponse>('fetch_url', { targetUrl: url, feed: opts.feed ?? false });
+      return response.body;
     } catch (e) {
       console.error(`[tauriFetch] invoke fetch_url failed for ${url}:`, e);
       throw e;
@@ -37,11 +56,28 @@ export async function fetchViaBackend(url: string): Promise<string> {
   return response.text();
 }
 
+/** A multipart form part. `file` parts carry a token from `pickUploadFile`, never a path. */
+export type FormPart =
+  | { kind: 'text'; name: string; value: string }
+  | { kind: 'base64'; name: string; data: string; filename?: string; content_type?: string }
+  | { kind: 'file'; name: string; token: string; filename?: string; content_type?: string };
+
+export interface UploadFile {
+  token: string;
+  name: string;
+  size: number;
+}
+
+/** Ask the user for a file to attach to a multipart request; null if cancelled. */
+export async function pickUploadFile(): Promise<UploadFile | null> {
+  return invoke<UploadFile | null>('pick_upload_file');
+}
+
 export interface HttpRequestOptions {
   method: string;
   url: string;
   headers?: Record<string, string>;
-  body?: string;
+  body?: string | { multipart: FormPart[] };
 }
 
 export interface HttpResponseData {
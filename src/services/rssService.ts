@@ -441,7 +441,7 @@ export async function fetchFeed(url: string): Promise<RSSChannel> {
   // Blog platforms: normalize to RSS feed endpoints
   resolvedUrl = resolveBlogPlatformRSS(resolvedUrl);
   resolvedUrl = await resolveYouTubeRSS(resolvedUrl);
-  const text = await fetchViaBackend(resolvedUrl);
+  const text = await fetchViaBackend(resolvedUrl, { feed: true });
 
   if (!text || text.trim().length === 0) {
     throw new Error('Empty response from feed URL');